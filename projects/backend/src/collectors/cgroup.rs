@@ -0,0 +1,254 @@
+//! Cgroup attribution: the systemd unit, slice and container a process runs in
+//!
+//! Container runtimes name the cgroups they create after the container ID, so the
+//! container can be told from /proc/<pid>/cgroup alone, without asking the runtime.
+
+use crate::collectors::{ProcessCollector, SocketCollector};
+use crate::models::{ContainerInfo, ContainerRuntime};
+
+/// Scope prefixes used by runtimes with the systemd cgroup driver
+/// ("docker-<id>.scope", "libpod-<id>.scope", ...)
+const SCOPE_PREFIXES: [(&str, ContainerRuntime); 4] = [
+    ("docker-", ContainerRuntime::Docker),
+    ("libpod-", ContainerRuntime::Podman),
+    ("cri-containerd-", ContainerRuntime::Containerd),
+    ("crio-", ContainerRuntime::CriO),
+];
+
+/// Where a process sits in the cgroup hierarchy
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CgroupAttribution {
+    pub path: Option<String>,
+    pub systemd_unit: Option<String>,
+    pub systemd_slice: Option<String>,
+    pub container: Option<ContainerInfo>,
+}
+
+/// Collector for cgroup membership of processes
+pub struct CgroupCollector;
+
+impl CgroupCollector {
+    /// Read and classify /proc/<pid>/cgroup. Empty when it can't be read or on
+    /// platforms without cgroups.
+    pub fn get_attribution(pid: u32) -> CgroupAttribution {
+        #[cfg(target_os = "linux")]
+        {
+            std::fs::read_to_string(format!("/proc/{}/cgroup", pid))
+                .map(|content| Self::attribute(&content))
+                .unwrap_or_default()
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = pid;
+            CgroupAttribution::default()
+        }
+    }
+
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    pub(crate) fn attribute(content: &str) -> CgroupAttribution {
+        let path = ProcessCollector::cgroup_path(content);
+
+        // On hybrid hierarchies the unified path is "/" and only the v1
+        // controllers show the container
+        let container = Self::container_from_path(&path).or_else(|| {
+            content
+                .lines()
+                .filter_map(|line| line.splitn(3, ':').nth(2))
+                .find_map(Self::container_from_path)
+        });
+
+        CgroupAttribution {
+            systemd_unit: SocketCollector::unit_from_cgroup(content),
+            systemd_slice: Self::slice_from_path(&path),
+            container,
+            path: (!path.is_empty()).then_some(path),
+        }
+    }
+
+    /// Innermost slice of a cgroup path ("user-1000.slice", "system.slice")
+    fn slice_from_path(path: &str) -> Option<String> {
+        path.rsplit('/')
+            .find(|segment| segment.ends_with(".slice"))
+            .map(|slice| slice.to_string())
+    }
+
+    /// Detect a container from the innermost cgroup named after one, e.g.
+    /// `/system.slice/docker-<id>.scope`, `/docker/<id>`,
+    /// `/kubepods.slice/.../kubepods-besteffort-pod<uid>.slice/cri-containerd-<id>.scope`
+    /// or `/kubepods/burstable/pod<uid>/<id>`
+    fn container_from_path(path: &str) -> Option<ContainerInfo> {
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        let in_kubepods = segments.iter().any(|s| s.starts_with("kubepods"));
+        let pod_uid = segments
+            .iter()
+            .filter(|_| in_kubepods)
+            .find_map(|segment| Self::pod_uid(segment));
+
+        segments
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(index, segment)| {
+                let name = segment.strip_suffix(".scope").unwrap_or(segment);
+                let prefixed = SCOPE_PREFIXES.iter().find_map(|(prefix, runtime)| {
+                    name.strip_prefix(prefix)
+                        .filter(|id| Self::is_container_id(id))
+                        .map(|id| (id, *runtime))
+                });
+
+                // cgroupfs driver: the ID alone, under a runtime-specific parent
+                prefixed.or_else(|| {
+                    if !Self::is_container_id(name) {
+                        return None;
+                    }
+                    let parent = index.checked_sub(1).map(|i| segments[i]);
+                    let runtime = match parent {
+                        Some("docker") => ContainerRuntime::Docker,
+                        _ if in_kubepods => ContainerRuntime::Kubernetes,
+                        // containerd namespaces
+                        Some("default") | Some("k8s.io") => ContainerRuntime::Containerd,
+                        _ => return None,
+                    };
+                    Some((name, runtime))
+                })
+            })
+            .map(|(id, runtime)| ContainerInfo {
+                id: id.to_string(),
+                runtime,
+                pod_uid: pod_uid.clone(),
+            })
+    }
+
+    fn is_container_id(id: &str) -> bool {
+        id.len() == 64 && id.bytes().all(|b| b.is_ascii_hexdigit())
+    }
+
+    /// Pod UID from "pod<uid>" (cgroupfs) or "kubepods-burstable-pod<uid>.slice",
+    /// where systemd escapes the dashes of the UID as underscores
+    fn pod_uid(segment: &str) -> Option<String> {
+        let name = segment.strip_suffix(".slice").unwrap_or(segment);
+        let uid = match name.strip_prefix("pod") {
+            Some(uid) => uid.to_string(),
+            None => name.rsplit_once("-pod")?.1.replace('_', "-"),
+        };
+        (!uid.is_empty()).then_some(uid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ID: &str = "3f4e5d6c7b8a99887766554433221100ffeeddccbbaa00112233445566778899";
+
+    fn container(content: &str) -> Option<(ContainerRuntime, Option<String>)> {
+        CgroupCollector::attribute(content)
+            .container
+            .inspect(|c| assert_eq!(c.id, ID))
+            .map(|c| (c.runtime, c.pod_uid))
+    }
+
+    #[test]
+    fn test_systemd_unit_and_slice() {
+        let attribution = CgroupCollector::attribute("0::/system.slice/nginx.service\n");
+        assert_eq!(
+            attribution,
+            CgroupAttribution {
+                path: Some("/system.slice/nginx.service".to_string()),
+                systemd_unit: Some("nginx.service".to_string()),
+                systemd_slice: Some("system.slice".to_string()),
+                container: None,
+            }
+        );
+
+        let attribution = CgroupCollector::attribute(
+            "0::/user.slice/user-1000.slice/user@1000.service/app.slice/app-firefox-1234.scope\n",
+        );
+        assert_eq!(attribution.systemd_slice.as_deref(), Some("app.slice"));
+        assert_eq!(
+            attribution.systemd_unit.as_deref(),
+            Some("app-firefox-1234.scope")
+        );
+
+        assert_eq!(CgroupCollector::attribute(""), CgroupAttribution::default());
+    }
+
+    #[test]
+    fn test_docker_and_podman() {
+        assert_eq!(
+            container(&format!("0::/system.slice/docker-{}.scope\n", ID)),
+            Some((ContainerRuntime::Docker, None))
+        );
+        assert_eq!(
+            container(&format!("0::/docker/{}\n", ID)),
+            Some((ContainerRuntime::Docker, None))
+        );
+        // cgroup v1 with the unified hierarchy mounted but unused
+        assert_eq!(
+            container(&format!(
+                "12:memory:/docker/{id}\n1:name=systemd:/docker/{id}\n0::/\n",
+                id = ID
+            )),
+            Some((ContainerRuntime::Docker, None))
+        );
+        // Rootless podman puts the payload in a "container" child group
+        assert_eq!(
+            container(&format!(
+                "0::/user.slice/user-1000.slice/user@1000.service/user.slice/libpod-{}.scope/container\n",
+                ID
+            )),
+            Some((ContainerRuntime::Podman, None))
+        );
+        // conmon monitors a container but is not part of it
+        assert_eq!(
+            CgroupCollector::attribute(&format!("0::/machine.slice/libpod-conmon-{}.scope\n", ID))
+                .container,
+            None
+        );
+    }
+
+    #[test]
+    fn test_containerd_and_kubepods() {
+        assert_eq!(
+            container(&format!("0::/default/{}\n", ID)),
+            Some((ContainerRuntime::Containerd, None))
+        );
+        assert_eq!(
+            container(&format!(
+                "0::/kubepods.slice/kubepods-burstable.slice/kubepods-burstable-pod7c1e2a4b_9d3f_4e5a_8b6c_1d2e3f4a5b6c.slice/cri-containerd-{}.scope\n",
+                ID
+            )),
+            Some((
+                ContainerRuntime::Containerd,
+                Some("7c1e2a4b-9d3f-4e5a-8b6c-1d2e3f4a5b6c".to_string())
+            ))
+        );
+        assert_eq!(
+            container(&format!(
+                "0::/kubepods.slice/kubepods-pod11111111_2222_3333_4444_555555555555.slice/crio-{}.scope\n",
+                ID
+            )),
+            Some((
+                ContainerRuntime::CriO,
+                Some("11111111-2222-3333-4444-555555555555".to_string())
+            ))
+        );
+        assert_eq!(
+            container(&format!(
+                "0::/kubepods/besteffort/pod11111111-2222-3333-4444-555555555555/{}\n",
+                ID
+            )),
+            Some((
+                ContainerRuntime::Kubernetes,
+                Some("11111111-2222-3333-4444-555555555555".to_string())
+            ))
+        );
+
+        // A bare ID anywhere else is not enough to call it a container
+        assert_eq!(
+            CgroupCollector::attribute(&format!("0::/system.slice/{}\n", ID)).container,
+            None
+        );
+    }
+}
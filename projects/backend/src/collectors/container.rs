@@ -0,0 +1,760 @@
+//! Container inventory collector
+//!
+//! Speaks the Docker Engine HTTP API over the engine's local unix socket. Podman
+//! serves the same API, so both are covered by one client. Requests are plain
+//! HTTP/1.1 with `Connection: close`, which keeps the client to a few lines instead
+//! of pulling in an HTTP stack that can talk to unix sockets.
+
+use crate::models::{
+    ContainerEngine, ContainerError, ContainerImage, ContainerInventory, ContainerNetwork,
+    ContainerPort, ContainerStats, ContainerSummary, ContainerVolume,
+};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Sockets tried when DOCKER_HOST and CONTAINER_HOST don't name one
+#[cfg(unix)]
+const DEFAULT_SOCKETS: [&str; 3] = [
+    "/var/run/docker.sock",
+    "/run/podman/podman.sock",
+    "/run/docker.sock",
+];
+/// Covers the stats endpoint, which samples CPU usage over about a second
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+struct RawVersion {
+    version: String,
+    api_version: String,
+    os: Option<String>,
+    arch: Option<String>,
+    platform: RawPlatform,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+struct RawPlatform {
+    name: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+struct RawContainer {
+    id: String,
+    names: Vec<String>,
+    image: String,
+    #[serde(rename = "ImageID")]
+    image_id: String,
+    command: String,
+    created: i64,
+    state: String,
+    status: String,
+    ports: Vec<RawPort>,
+    labels: Option<BTreeMap<String, String>>,
+    network_settings: Option<RawContainerNetworks>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+struct RawPort {
+    #[serde(rename = "IP")]
+    ip: Option<String>,
+    private_port: u16,
+    public_port: Option<u16>,
+    #[serde(rename = "Type")]
+    protocol: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+struct RawContainerNetworks {
+    networks: Option<HashMap<String, serde_json::Value>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+struct RawImage {
+    id: String,
+    repo_tags: Option<Vec<String>>,
+    created: i64,
+    size: u64,
+    /// -1 when the engine didn't count
+    containers: i64,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+struct RawVolumeList {
+    volumes: Option<Vec<RawVolume>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+struct RawVolume {
+    name: String,
+    driver: String,
+    mountpoint: String,
+    created_at: Option<String>,
+    scope: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+struct RawNetwork {
+    id: String,
+    name: String,
+    driver: String,
+    scope: String,
+    internal: bool,
+    #[serde(rename = "IPAM")]
+    ipam: Option<RawIpam>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+struct RawIpam {
+    config: Option<Vec<RawIpamConfig>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+struct RawIpamConfig {
+    subnet: Option<String>,
+}
+
+/// Stats use snake_case field names, unlike the rest of the API
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct RawStats {
+    cpu_stats: RawCpuStats,
+    precpu_stats: RawCpuStats,
+    memory_stats: RawMemoryStats,
+    networks: Option<HashMap<String, RawNetworkStats>>,
+    blkio_stats: RawBlkioStats,
+    pids_stats: RawPidsStats,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct RawCpuStats {
+    cpu_usage: RawCpuUsage,
+    system_cpu_usage: u64,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct RawCpuUsage {
+    total_usage: u64,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct RawMemoryStats {
+    usage: u64,
+    limit: u64,
+    stats: HashMap<String, u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct RawNetworkStats {
+    rx_bytes: u64,
+    tx_bytes: u64,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct RawBlkioStats {
+    io_service_bytes_recursive: Option<Vec<RawBlkioEntry>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct RawBlkioEntry {
+    op: String,
+    value: u64,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct RawPidsStats {
+    current: u64,
+}
+
+/// Collector for containers, images, volumes and networks of the local engine
+pub struct ContainerCollector;
+
+impl ContainerCollector {
+    /// Get the engine version and all containers, images, volumes and networks
+    pub fn get_inventory() -> Result<ContainerInventory, ContainerError> {
+        Self::inventory_at(&Self::find_socket()?)
+    }
+
+    /// Get live resource usage of one container, by ID or name
+    pub fn get_stats(id: &str) -> Result<ContainerStats, ContainerError> {
+        Self::stats_at(&Self::find_socket()?, id)
+    }
+
+    /// The engine socket from DOCKER_HOST or CONTAINER_HOST, or the first default
+    /// location that exists
+    fn find_socket() -> Result<PathBuf, ContainerError> {
+        #[cfg(unix)]
+        {
+            let from_env = ["DOCKER_HOST", "CONTAINER_HOST"]
+                .iter()
+                .filter_map(|var| std::env::var(var).ok())
+                .find_map(|host| host.strip_prefix("unix://").map(PathBuf::from));
+            if let Some(socket) = from_env {
+                return Ok(socket);
+            }
+
+            // Rootless Docker and Podman listen in the user's runtime directory
+            let runtime_dir = std::env::var("XDG_RUNTIME_DIR").ok().map(PathBuf::from);
+            let user_sockets = runtime_dir
+                .iter()
+                .flat_map(|dir| [dir.join("docker.sock"), dir.join("podman/podman.sock")]);
+
+            DEFAULT_SOCKETS
+                .iter()
+                .map(PathBuf::from)
+                .chain(user_sockets)
+                .find(|socket| socket.exists())
+                .ok_or_else(|| ContainerError::NotRunning {
+                    message: "No Docker or Podman socket found".to_string(),
+                })
+        }
+
+        #[cfg(not(unix))]
+        {
+            Err(ContainerError::Unsupported)
+        }
+    }
+
+    pub(crate) fn inventory_at(socket: &Path) -> Result<ContainerInventory, ContainerError> {
+        let version: RawVersion = Self::get(socket, "/version")?;
+        let containers: Vec<RawContainer> = Self::get(socket, "/containers/json?all=1")?;
+        let images: Vec<RawImage> = Self::get(socket, "/images/json")?;
+        let volumes: RawVolumeList = Self::get(socket, "/volumes")?;
+        let networks: Vec<RawNetwork> = Self::get(socket, "/networks")?;
+
+        Ok(ContainerInventory {
+            engine: ContainerEngine {
+                socket_path: socket.to_string_lossy().to_string(),
+                name: version.platform.name,
+                version: version.version,
+                api_version: version.api_version,
+                os: version.os,
+                arch: version.arch,
+            },
+            containers: containers.into_iter().map(Self::container).collect(),
+            images: images.into_iter().map(Self::image).collect(),
+            volumes: volumes
+                .volumes
+                .unwrap_or_default()
+                .into_iter()
+                .map(|volume| ContainerVolume {
+                    name: volume.name,
+                    driver: volume.driver,
+                    mountpoint: volume.mountpoint,
+                    created_at: volume.created_at,
+                    scope: volume.scope,
+                })
+                .collect(),
+            networks: networks.into_iter().map(Self::network).collect(),
+        })
+    }
+
+    pub(crate) fn stats_at(socket: &Path, id: &str) -> Result<ContainerStats, ContainerError> {
+        // IDs and names are [a-zA-Z0-9][a-zA-Z0-9_.-]*; anything else would change the path
+        let valid = !id.is_empty()
+            && id
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'_' | b'.' | b'-'));
+        if !valid {
+            return Err(ContainerError::InvalidArgument {
+                message: format!("Invalid container ID or name: {}", id),
+            });
+        }
+
+        let raw: RawStats = Self::get(socket, &format!("/containers/{}/stats?stream=false", id))?;
+        Ok(Self::stats(id, raw))
+    }
+
+    fn container(raw: RawContainer) -> ContainerSummary {
+        let mut networks: Vec<String> = raw
+            .network_settings
+            .and_then(|settings| settings.networks)
+            .map(|networks| networks.into_keys().collect())
+            .unwrap_or_default();
+        networks.sort();
+
+        ContainerSummary {
+            id: raw.id,
+            names: raw
+                .names
+                .iter()
+                .map(|name| name.trim_start_matches('/').to_string())
+                .collect(),
+            image: raw.image,
+            image_id: raw.image_id,
+            command: raw.command,
+            created: raw.created,
+            state: raw.state,
+            status: raw.status,
+            ports: raw
+                .ports
+                .into_iter()
+                .map(|port| ContainerPort {
+                    ip: port.ip.filter(|ip| !ip.is_empty()),
+                    private_port: port.private_port,
+                    public_port: port.public_port.filter(|&p| p != 0),
+                    protocol: port.protocol,
+                })
+                .collect(),
+            labels: raw.labels.unwrap_or_default(),
+            networks,
+        }
+    }
+
+    fn image(raw: RawImage) -> ContainerImage {
+        ContainerImage {
+            id: raw.id,
+            repo_tags: raw
+                .repo_tags
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|tag| tag != "<none>:<none>")
+                .collect(),
+            created: raw.created,
+            size_bytes: raw.size,
+            containers: u64::try_from(raw.containers).ok(),
+        }
+    }
+
+    fn network(raw: RawNetwork) -> ContainerNetwork {
+        ContainerNetwork {
+            id: raw.id,
+            name: raw.name,
+            driver: raw.driver,
+            scope: raw.scope,
+            subnets: raw
+                .ipam
+                .and_then(|ipam| ipam.config)
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|config| config.subnet)
+                .collect(),
+            internal: raw.internal,
+        }
+    }
+
+    /// Turn raw counters into usage. Unlike `docker stats`, CPU is not multiplied by
+    /// the number of online CPUs, so it is a share of the whole machine (0-100).
+    fn stats(id: &str, raw: RawStats) -> ContainerStats {
+        let cpu_delta = raw
+            .cpu_stats
+            .cpu_usage
+            .total_usage
+            .saturating_sub(raw.precpu_stats.cpu_usage.total_usage);
+        let system_delta = raw
+            .cpu_stats
+            .system_cpu_usage
+            .saturating_sub(raw.precpu_stats.system_cpu_usage);
+        let cpu_usage = if system_delta > 0 {
+            (cpu_delta as f64 / system_delta as f64 * 100.0) as f32
+        } else {
+            0.0
+        };
+
+        // Page cache can be reclaimed, so it doesn't count as used (cgroup v2, then v1)
+        let memory = &raw.memory_stats;
+        let cache = memory
+            .stats
+            .get("inactive_file")
+            .or_else(|| memory.stats.get("total_inactive_file"))
+            .or_else(|| memory.stats.get("cache"))
+            .copied()
+            .unwrap_or(0);
+
+        let networks = raw.networks.unwrap_or_default();
+        let blkio = raw
+            .blkio_stats
+            .io_service_bytes_recursive
+            .unwrap_or_default();
+        let block_bytes = |op: &str| {
+            blkio
+                .iter()
+                .filter(|entry| entry.op.eq_ignore_ascii_case(op))
+                .map(|entry| entry.value)
+                .sum()
+        };
+
+        ContainerStats {
+            id: id.to_string(),
+            cpu_usage,
+            memory_usage_bytes: memory.usage.saturating_sub(cache),
+            memory_limit_bytes: memory.limit,
+            network_rx_bytes: networks.values().map(|n| n.rx_bytes).sum(),
+            network_tx_bytes: networks.values().map(|n| n.tx_bytes).sum(),
+            block_read_bytes: block_bytes("read"),
+            block_write_bytes: block_bytes("write"),
+            pids: raw.pids_stats.current,
+        }
+    }
+
+    /// GET a JSON document from the engine
+    fn get<T: DeserializeOwned>(socket: &Path, path: &str) -> Result<T, ContainerError> {
+        let (status, body) = Self::request(socket, path)?;
+        if !(200..300).contains(&status) {
+            // Errors carry {"message": "..."}
+            let message = serde_json::from_slice::<serde_json::Value>(&body)
+                .ok()
+                .and_then(|v| v.get("message")?.as_str().map(str::to_string))
+                .unwrap_or_else(|| String::from_utf8_lossy(&body).trim().to_string());
+            return Err(ContainerError::Api { status, message });
+        }
+
+        serde_json::from_slice(&body).map_err(|e| ContainerError::InvalidResponse {
+            message: format!("{}: {}", path, e),
+        })
+    }
+
+    #[cfg(unix)]
+    fn request(socket: &Path, path: &str) -> Result<(u16, Vec<u8>), ContainerError> {
+        use std::io::{ErrorKind, Read, Write};
+        use std::os::unix::net::UnixStream;
+
+        let socket_name = socket.to_string_lossy().to_string();
+        let mut stream = UnixStream::connect(socket).map_err(|e| match e.kind() {
+            ErrorKind::PermissionDenied => ContainerError::PermissionDenied {
+                socket: socket_name.clone(),
+            },
+            _ => ContainerError::NotRunning {
+                message: format!("{}: {}", socket_name, e),
+            },
+        })?;
+        let io_error = |e: std::io::Error| ContainerError::InvalidResponse {
+            message: format!("{}: {}", socket_name, e),
+        };
+
+        stream
+            .set_read_timeout(Some(REQUEST_TIMEOUT))
+            .map_err(io_error)?;
+        stream
+            .set_write_timeout(Some(REQUEST_TIMEOUT))
+            .map_err(io_error)?;
+        write!(
+            stream,
+            "GET {} HTTP/1.1\r\nHost: localhost\r\nUser-Agent: Syslens\r\nAccept: application/json\r\nConnection: close\r\n\r\n",
+            path
+        )
+        .map_err(io_error)?;
+
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response).map_err(io_error)?;
+        Self::parse_response(&response)
+    }
+
+    #[cfg(not(unix))]
+    fn request(socket: &Path, path: &str) -> Result<(u16, Vec<u8>), ContainerError> {
+        let _ = (socket, path);
+        Err(ContainerError::Unsupported)
+    }
+
+    /// Split an HTTP/1.1 response into its status code and body
+    fn parse_response(response: &[u8]) -> Result<(u16, Vec<u8>), ContainerError> {
+        let invalid = |message: &str| ContainerError::InvalidResponse {
+            message: message.to_string(),
+        };
+
+        let header_end = response
+            .windows(4)
+            .position(|w| w == b"\r\n\r\n")
+            .ok_or_else(|| invalid("Incomplete HTTP response"))?;
+        let head = String::from_utf8_lossy(&response[..header_end]);
+        let body = &response[header_end + 4..];
+
+        let mut lines = head.split("\r\n");
+        let status = lines
+            .next()
+            .and_then(|line| line.split_whitespace().nth(1))
+            .and_then(|code| code.parse::<u16>().ok())
+            .ok_or_else(|| invalid("Malformed HTTP status line"))?;
+
+        let header = |name: &str| {
+            head.split("\r\n").skip(1).find_map(|line| {
+                let (key, value) = line.split_once(':')?;
+                key.trim()
+                    .eq_ignore_ascii_case(name)
+                    .then(|| value.trim().to_string())
+            })
+        };
+
+        let body = if header("Transfer-Encoding").is_some_and(|v| v.eq_ignore_ascii_case("chunked"))
+        {
+            Self::decode_chunked(body).ok_or_else(|| invalid("Malformed chunked body"))?
+        } else if let Some(length) = header("Content-Length").and_then(|v| v.parse::<usize>().ok())
+        {
+            body.get(..length)
+                .ok_or_else(|| invalid("Truncated HTTP body"))?
+                .to_vec()
+        } else {
+            body.to_vec()
+        };
+
+        Ok((status, body))
+    }
+
+    fn decode_chunked(mut data: &[u8]) -> Option<Vec<u8>> {
+        let mut body = Vec::new();
+        loop {
+            let line_end = data.windows(2).position(|w| w == b"\r\n")?;
+            let size_field = std::str::from_utf8(&data[..line_end]).ok()?;
+            // Chunk extensions follow a ';'
+            let size_hex = size_field.split(';').next()?.trim();
+            let size = usize::from_str_radix(size_hex, 16).ok()?;
+            data = &data[line_end + 2..];
+            if size == 0 {
+                return Some(body);
+            }
+            body.extend_from_slice(data.get(..size)?);
+            data = data.get(size + 2..)?;
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::os::unix::net::UnixListener;
+
+    /// Serve canned responses keyed by request path until the test ends
+    fn mock_engine(name: &str, routes: Vec<(&'static str, u16, String)>) -> PathBuf {
+        let socket = std::env::temp_dir().join(format!(
+            "syslens_engine_{}_{}.sock",
+            name,
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&socket);
+        let listener = UnixListener::bind(&socket).unwrap();
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { break };
+                let mut request = Vec::new();
+                let mut buf = [0u8; 1024];
+                while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                    match stream.read(&mut buf) {
+                        Ok(0) | Err(_) => break,
+                        Ok(n) => request.extend_from_slice(&buf[..n]),
+                    }
+                }
+                let request = String::from_utf8_lossy(&request);
+                let path = request.split_whitespace().nth(1).unwrap_or_default();
+
+                let response = match routes.iter().find(|(route, _, _)| *route == path) {
+                    // Send successful bodies chunked, as the engine does for larger documents
+                    Some((_, 200, body)) => {
+                        let (first, second) = body.split_at(body.len() / 2);
+                        format!(
+                            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nTransfer-Encoding: chunked\r\n\r\n{:x}\r\n{}\r\n{:x};ext=1\r\n{}\r\n0\r\n\r\n",
+                            first.len(),
+                            first,
+                            second.len(),
+                            second
+                        )
+                    }
+                    Some((_, status, body)) => format!(
+                        "HTTP/1.1 {} Error\r\nContent-Length: {}\r\n\r\n{}",
+                        status,
+                        body.len(),
+                        body
+                    ),
+                    None => {
+                        let body = r#"{"message":"page not found"}"#;
+                        format!(
+                            "HTTP/1.1 404 Not Found\r\nContent-Length: {}\r\n\r\n{}",
+                            body.len(),
+                            body
+                        )
+                    }
+                };
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+
+        socket
+    }
+
+    const CONTAINER_ID: &str = "4f66ad9a0b2e6e8b3c1d5f7a9b0c2d4e6f8a0b1c3d5e7f9a1b3c5d7e9f0a2b4c";
+
+    #[test]
+    fn test_inventory_from_mock_engine() {
+        let containers = format!(
+            r#"[{{"Id":"{}","Names":["/web"],"Image":"nginx:1.27","ImageID":"sha256:aa","Command":"nginx -g 'daemon off;'","Created":1718000000,"State":"running","Status":"Up 2 hours","Ports":[{{"IP":"0.0.0.0","PrivatePort":80,"PublicPort":8080,"Type":"tcp"}},{{"PrivatePort":443,"Type":"tcp"}}],"Labels":{{"com.docker.compose.project":"site"}},"NetworkSettings":{{"Networks":{{"site_default":{{}},"bridge":{{}}}}}}}}]"#,
+            CONTAINER_ID
+        );
+        let socket = mock_engine(
+            "inventory",
+            vec![
+                (
+                    "/version",
+                    200,
+                    r#"{"Platform":{"Name":"Podman Engine"},"Version":"5.2.2","ApiVersion":"1.41","Os":"linux","Arch":"amd64"}"#.to_string(),
+                ),
+                ("/containers/json?all=1", 200, containers),
+                (
+                    "/images/json",
+                    200,
+                    r#"[{"Id":"sha256:aa","RepoTags":["nginx:1.27"],"Created":1717000000,"Size":187000000,"Containers":1},{"Id":"sha256:bb","RepoTags":null,"Created":1,"Size":5,"Containers":-1}]"#.to_string(),
+                ),
+                (
+                    "/volumes",
+                    200,
+                    r#"{"Volumes":[{"Name":"site_data","Driver":"local","Mountpoint":"/var/lib/docker/volumes/site_data/_data","CreatedAt":"2024-06-10T08:00:00Z","Scope":"local"}],"Warnings":null}"#.to_string(),
+                ),
+                (
+                    "/networks",
+                    200,
+                    r#"[{"Name":"bridge","Id":"n1","Scope":"local","Driver":"bridge","Internal":false,"IPAM":{"Config":[{"Subnet":"172.17.0.0/16","Gateway":"172.17.0.1"}]}},{"Name":"none","Id":"n2","Scope":"local","Driver":"null","IPAM":{"Config":null}}]"#.to_string(),
+                ),
+            ],
+        );
+
+        let inventory = ContainerCollector::inventory_at(&socket).unwrap();
+        assert_eq!(inventory.engine.name, "Podman Engine");
+        assert_eq!(inventory.engine.version, "5.2.2");
+        assert_eq!(inventory.engine.api_version, "1.41");
+
+        let web = &inventory.containers[0];
+        assert_eq!(web.id, CONTAINER_ID);
+        assert_eq!(web.names, vec!["web"]);
+        assert_eq!(web.command, "nginx -g 'daemon off;'");
+        assert_eq!(web.state, "running");
+        assert_eq!(web.networks, vec!["bridge", "site_default"]);
+        assert_eq!(
+            web.ports,
+            vec![
+                ContainerPort {
+                    ip: Some("0.0.0.0".to_string()),
+                    private_port: 80,
+                    public_port: Some(8080),
+                    protocol: "tcp".to_string(),
+                },
+                ContainerPort {
+                    ip: None,
+                    private_port: 443,
+                    public_port: None,
+                    protocol: "tcp".to_string(),
+                },
+            ]
+        );
+        assert_eq!(web.labels["com.docker.compose.project"], "site");
+
+        assert_eq!(inventory.images.len(), 2);
+        assert_eq!(inventory.images[0].repo_tags, vec!["nginx:1.27"]);
+        assert_eq!(inventory.images[0].containers, Some(1));
+        assert!(inventory.images[1].repo_tags.is_empty());
+        assert_eq!(inventory.images[1].containers, None);
+
+        assert_eq!(inventory.volumes[0].name, "site_data");
+        assert_eq!(inventory.networks[0].subnets, vec!["172.17.0.0/16"]);
+        assert!(inventory.networks[1].subnets.is_empty());
+
+        let _ = std::fs::remove_file(socket);
+    }
+
+    #[test]
+    fn test_stats_from_mock_engine() {
+        let stats = r#"{
+            "cpu_stats":{"cpu_usage":{"total_usage":2500000000},"system_cpu_usage":20000000000,"online_cpus":4},
+            "precpu_stats":{"cpu_usage":{"total_usage":2000000000},"system_cpu_usage":10000000000},
+            "memory_stats":{"usage":104857600,"limit":2147483648,"stats":{"inactive_file":4857600}},
+            "networks":{"eth0":{"rx_bytes":1000,"tx_bytes":200},"eth1":{"rx_bytes":24,"tx_bytes":6}},
+            "blkio_stats":{"io_service_bytes_recursive":[{"major":8,"minor":0,"op":"read","value":4096},{"major":8,"minor":0,"op":"write","value":8192},{"major":8,"minor":16,"op":"Read","value":4096}]},
+            "pids_stats":{"current":7}
+        }"#;
+        let socket = mock_engine(
+            "stats",
+            vec![
+                ("/containers/web/stats?stream=false", 200, stats.to_string()),
+                (
+                    "/containers/gone/stats?stream=false",
+                    404,
+                    r#"{"message":"No such container: gone"}"#.to_string(),
+                ),
+            ],
+        );
+
+        let stats = ContainerCollector::stats_at(&socket, "web").unwrap();
+        assert_eq!(
+            stats,
+            ContainerStats {
+                id: "web".to_string(),
+                cpu_usage: 5.0,
+                memory_usage_bytes: 100_000_000,
+                memory_limit_bytes: 2_147_483_648,
+                network_rx_bytes: 1024,
+                network_tx_bytes: 206,
+                block_read_bytes: 8192,
+                block_write_bytes: 8192,
+                pids: 7,
+            }
+        );
+
+        assert_eq!(
+            ContainerCollector::stats_at(&socket, "gone"),
+            Err(ContainerError::Api {
+                status: 404,
+                message: "No such container: gone".to_string(),
+            })
+        );
+        assert!(matches!(
+            ContainerCollector::stats_at(&socket, "../version"),
+            Err(ContainerError::InvalidArgument { .. })
+        ));
+
+        let _ = std::fs::remove_file(socket);
+    }
+
+    #[test]
+    fn test_no_daemon() {
+        let missing = std::env::temp_dir().join("syslens_no_engine.sock");
+        assert!(matches!(
+            ContainerCollector::inventory_at(&missing),
+            Err(ContainerError::NotRunning { .. })
+        ));
+
+        // A stale socket file nobody listens on
+        let stale =
+            std::env::temp_dir().join(format!("syslens_stale_engine_{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&stale);
+        drop(UnixListener::bind(&stale).unwrap());
+        assert!(matches!(
+            ContainerCollector::inventory_at(&stale),
+            Err(ContainerError::NotRunning { .. })
+        ));
+        let _ = std::fs::remove_file(stale);
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let (status, body) = ContainerCollector::parse_response(
+            b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\n[]trailing",
+        )
+        .unwrap();
+        assert_eq!(status, 200);
+        assert_eq!(body, b"[]");
+
+        assert!(ContainerCollector::parse_response(b"HTTP/1.1 200 OK\r\n").is_err());
+        assert!(ContainerCollector::parse_response(
+            b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\nzz\r\n"
+        )
+        .is_err());
+    }
+}
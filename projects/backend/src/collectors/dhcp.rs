@@ -0,0 +1,454 @@
+//! DHCP lease reader (Linux)
+//!
+//! Reads current IPv4 leases from the state and lease files of NetworkManager,
+//! systemd-networkd, dhclient and dhcpcd. Whichever client manages an interface,
+//! the lease with the latest expiry wins.
+
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use std::collections::HashMap;
+use std::net::Ipv4Addr;
+use std::path::Path;
+
+const NETWORKMANAGER_DEVICES_DIR: &str = "/run/NetworkManager/devices";
+const NETWORKD_LEASES_DIR: &str = "/run/systemd/netif/leases";
+const DHCLIENT_LEASE_DIRS: &[&str] = &[
+    "/var/lib/dhcp",
+    "/var/lib/dhclient",
+    "/var/lib/NetworkManager",
+];
+const DHCPCD_LEASE_DIRS: &[&str] = &["/var/lib/dhcpcd", "/var/db/dhcpcd"];
+
+// BOOTP message layout (RFC 2131) and DHCP options (RFC 2132)
+const BOOTP_YIADDR: usize = 16;
+const BOOTP_OPTIONS: usize = 240;
+const DHCP_MAGIC_COOKIE: [u8; 4] = [99, 130, 83, 99];
+const OPTION_PAD: u8 = 0;
+const OPTION_SUBNET_MASK: u8 = 1;
+const OPTION_ROUTER: u8 = 3;
+const OPTION_LEASE_TIME: u8 = 51;
+const OPTION_SERVER_ID: u8 = 54;
+const OPTION_END: u8 = 255;
+
+/// An IPv4 DHCP lease
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct DhcpLease {
+    pub interface: String,
+    pub address: Option<String>,
+    pub subnet_mask: Option<String>,
+    pub router: Option<String>,
+    pub server: Option<String>,
+    pub obtained: Option<DateTime<Utc>>,
+    pub expires: Option<DateTime<Utc>>,
+}
+
+/// Read the current lease of every interface, keyed by interface name
+pub(crate) fn read_leases() -> HashMap<String, DhcpLease> {
+    let mut leases = Vec::new();
+
+    // NetworkManager and networkd name their files after the interface index
+    for (interface, path, _) in files_by_interface_index(NETWORKMANAGER_DEVICES_DIR) {
+        if let Ok(content) = std::fs::read_to_string(path) {
+            leases.extend(parse_networkmanager_device(&content, &interface));
+        }
+    }
+    for (interface, path, modified) in files_by_interface_index(NETWORKD_LEASES_DIR) {
+        if let Ok(content) = std::fs::read_to_string(path) {
+            leases.extend(parse_networkd_lease(&content, &interface, modified));
+        }
+    }
+
+    for dir in DHCLIENT_LEASE_DIRS {
+        for (path, _) in list_files(dir) {
+            let name = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or_default();
+            let is_dhclient = name.starts_with("dhclient") || name.ends_with(".leases");
+            if !is_dhclient || name.contains("dhclient6") {
+                continue;
+            }
+            if let Ok(content) = std::fs::read_to_string(&path) {
+                leases.extend(parse_dhclient_leases(&content));
+            }
+        }
+    }
+
+    for dir in DHCPCD_LEASE_DIRS {
+        for (path, modified) in list_files(dir) {
+            let Some(stem) = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .and_then(|n| n.strip_suffix(".lease"))
+            else {
+                continue;
+            };
+            if let Ok(data) = std::fs::read(&path) {
+                let interface = dhcpcd_interface(stem);
+                leases.extend(parse_dhcpcd_lease(&data, &interface, modified));
+            }
+        }
+    }
+
+    let mut current: HashMap<String, DhcpLease> = HashMap::new();
+    for lease in leases {
+        let newer = current
+            .get(&lease.interface)
+            .is_none_or(|existing| lease.expires > existing.expires);
+        if newer {
+            current.insert(lease.interface.clone(), lease);
+        }
+    }
+    current
+}
+
+/// Files in a directory along with their modification time
+fn list_files(dir: &str) -> Vec<(std::path::PathBuf, Option<DateTime<Utc>>)> {
+    std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|e| e.file_type().map(|t| t.is_file()).unwrap_or(false))
+                .map(|e| {
+                    let modified = e
+                        .metadata()
+                        .and_then(|m| m.modified())
+                        .ok()
+                        .map(DateTime::<Utc>::from);
+                    (e.path(), modified)
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Files named after an interface index, with the interface name resolved
+fn files_by_interface_index(dir: &str) -> Vec<(String, std::path::PathBuf, Option<DateTime<Utc>>)> {
+    list_files(dir)
+        .into_iter()
+        .filter_map(|(path, modified)| {
+            let index = path.file_name()?.to_str()?.parse().ok()?;
+            Some((interface_name(index)?, path, modified))
+        })
+        .collect()
+}
+
+fn interface_name(index: u32) -> Option<String> {
+    let mut buf = [0 as libc::c_char; libc::IF_NAMESIZE];
+    let name = unsafe { libc::if_indextoname(index, buf.as_mut_ptr()) };
+    if name.is_null() {
+        return None;
+    }
+    let name = unsafe { std::ffi::CStr::from_ptr(name) };
+    Some(name.to_string_lossy().to_string())
+}
+
+/// dhcpcd names lease files "<interface>.lease" or, for wireless, "<interface>-<ssid>.lease"
+fn dhcpcd_interface(stem: &str) -> String {
+    if Path::new("/sys/class/net").join(stem).exists() {
+        return stem.to_string();
+    }
+    stem.split('-').next().unwrap_or(stem).to_string()
+}
+
+fn from_timestamp(secs: i64) -> Option<DateTime<Utc>> {
+    DateTime::from_timestamp(secs, 0)
+}
+
+/// Parse the [dhcp4] section of a NetworkManager device state file
+/// (/run/NetworkManager/devices/<ifindex>)
+pub(crate) fn parse_networkmanager_device(content: &str, interface: &str) -> Option<DhcpLease> {
+    let mut options = HashMap::new();
+    let mut in_dhcp4 = false;
+
+    for line in content.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_dhcp4 = line == "[dhcp4]";
+        } else if let Some((key, value)) = line.split_once('=').filter(|_| in_dhcp4) {
+            options.insert(key.trim(), value.trim());
+        }
+    }
+
+    let address = options.get("ip_address")?;
+    let expires = options
+        .get("expiry")
+        .and_then(|e| e.parse().ok())
+        .and_then(from_timestamp);
+    let lease_time = options
+        .get("dhcp_lease_time")
+        .and_then(|t| t.parse::<i64>().ok());
+
+    Some(DhcpLease {
+        interface: interface.to_string(),
+        address: Some(address.to_string()),
+        subnet_mask: options.get("subnet_mask").map(|m| m.to_string()),
+        router: options
+            .get("routers")
+            .and_then(|r| r.split_whitespace().next())
+            .map(str::to_string),
+        server: options.get("dhcp_server_identifier").map(|s| s.to_string()),
+        obtained: expires
+            .zip(lease_time)
+            .map(|(expires, secs)| expires - Duration::seconds(secs)),
+        expires,
+    })
+}
+
+/// Parse a systemd-networkd lease file (/run/systemd/netif/leases/<ifindex>). The file
+/// does not record when the lease was obtained, so its modification time stands in.
+pub(crate) fn parse_networkd_lease(
+    content: &str,
+    interface: &str,
+    modified: Option<DateTime<Utc>>,
+) -> Option<DhcpLease> {
+    let values: HashMap<&str, &str> = content
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .collect();
+
+    let lifetime = values
+        .get("LIFETIME")
+        .and_then(|l| l.parse::<i64>().ok())
+        .map(Duration::seconds)
+        .or_else(|| {
+            values
+                .get("LIFETIME_USEC")
+                .and_then(|l| l.parse::<i64>().ok())
+                .map(Duration::microseconds)
+        });
+
+    Some(DhcpLease {
+        interface: interface.to_string(),
+        address: Some(values.get("ADDRESS")?.to_string()),
+        subnet_mask: values.get("NETMASK").map(|m| m.to_string()),
+        router: values
+            .get("ROUTER")
+            .and_then(|r| r.split_whitespace().next())
+            .map(str::to_string),
+        server: values.get("SERVER_ADDRESS").map(|s| s.to_string()),
+        obtained: modified,
+        expires: modified
+            .zip(lifetime)
+            .map(|(obtained, lifetime)| obtained + lifetime),
+    })
+}
+
+/// Parse a dhclient lease database. Leases are appended as they are renewed, so the
+/// last block for an interface is the current one.
+pub(crate) fn parse_dhclient_leases(content: &str) -> Vec<DhcpLease> {
+    let mut leases: Vec<DhcpLease> = Vec::new();
+    let mut current: Option<(DhcpLease, Option<i64>)> = None;
+
+    for line in content.lines() {
+        // Statements end with ';', trailing comments start with '#'
+        let line = line.split('#').next().unwrap_or_default().trim();
+
+        if line.starts_with("lease") && line.ends_with('{') {
+            current = Some((DhcpLease::default(), None));
+            continue;
+        }
+        let Some((lease, lease_time)) = current.as_mut() else {
+            continue;
+        };
+        if line == "}" {
+            let (mut lease, lease_time) = current.take().unwrap();
+            lease.obtained = lease
+                .expires
+                .zip(lease_time)
+                .map(|(expires, secs)| expires - Duration::seconds(secs));
+            if !lease.interface.is_empty() {
+                leases.retain(|l| l.interface != lease.interface);
+                leases.push(lease);
+            }
+            continue;
+        }
+
+        let statement = line.trim_end_matches(';');
+        let mut words = statement.split_whitespace();
+        match words.next() {
+            Some("interface") => {
+                lease.interface = words
+                    .next()
+                    .unwrap_or_default()
+                    .trim_matches('"')
+                    .to_string()
+            }
+            Some("fixed-address") => lease.address = words.next().map(str::to_string),
+            Some("expire") => lease.expires = parse_dhclient_time(words.collect::<Vec<_>>()),
+            Some("option") => {
+                // Lists are comma separated; keep the first entry
+                let value = words
+                    .nth(1)
+                    .and_then(|v| v.split(',').next())
+                    .map(str::to_string);
+                match statement.split_whitespace().nth(1) {
+                    Some("subnet-mask") => lease.subnet_mask = value,
+                    Some("routers") => lease.router = value,
+                    Some("dhcp-server-identifier") => lease.server = value,
+                    Some("dhcp-lease-time") => *lease_time = value.and_then(|v| v.parse().ok()),
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+
+    leases
+}
+
+/// Parse a dhclient time: "<weekday> YYYY/MM/DD HH:MM:SS" in UTC, "epoch <secs>" or "never"
+fn parse_dhclient_time(words: Vec<&str>) -> Option<DateTime<Utc>> {
+    match words.as_slice() {
+        ["epoch", secs] => from_timestamp(secs.parse().ok()?),
+        [_weekday, date, time] => {
+            NaiveDateTime::parse_from_str(&format!("{} {}", date, time), "%Y/%m/%d %H:%M:%S")
+                .ok()
+                .map(|t| t.and_utc())
+        }
+        _ => None,
+    }
+}
+
+/// Parse a dhcpcd lease file, which holds the raw DHCPACK message. dhcpcd rewrites the
+/// file when it binds, so its modification time is when the lease was obtained.
+pub(crate) fn parse_dhcpcd_lease(
+    data: &[u8],
+    interface: &str,
+    modified: Option<DateTime<Utc>>,
+) -> Option<DhcpLease> {
+    let address =
+        Ipv4Addr::from(<[u8; 4]>::try_from(data.get(BOOTP_YIADDR..BOOTP_YIADDR + 4)?).ok()?);
+    if data.get(BOOTP_OPTIONS - 4..BOOTP_OPTIONS)? != DHCP_MAGIC_COOKIE {
+        return None;
+    }
+
+    let mut lease = DhcpLease {
+        interface: interface.to_string(),
+        address: Some(address.to_string()),
+        obtained: modified,
+        ..Default::default()
+    };
+    let ipv4 = |value: &[u8]| {
+        <[u8; 4]>::try_from(value.get(..4)?)
+            .ok()
+            .map(|octets| Ipv4Addr::from(octets).to_string())
+    };
+
+    let mut options = &data[BOOTP_OPTIONS..];
+    while let Some((&code, rest)) = options.split_first() {
+        match code {
+            OPTION_END => break,
+            OPTION_PAD => {
+                options = rest;
+                continue;
+            }
+            _ => {}
+        }
+        let (&len, rest) = rest.split_first()?;
+        let value = rest.get(..len as usize)?;
+        options = &rest[len as usize..];
+
+        match code {
+            OPTION_SUBNET_MASK => lease.subnet_mask = ipv4(value),
+            OPTION_ROUTER => lease.router = ipv4(value),
+            OPTION_SERVER_ID => lease.server = ipv4(value),
+            OPTION_LEASE_TIME => {
+                let secs = u32::from_be_bytes(value.try_into().ok()?);
+                // 0xffffffff is an infinite lease
+                if secs != u32::MAX {
+                    lease.expires = modified.map(|m| m + Duration::seconds(secs as i64));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Some(lease)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_parse_networkmanager_device() {
+        let content = include_str!("../../tests/fixtures/dhcp/networkmanager-device");
+        let lease = parse_networkmanager_device(content, "enp3s0").unwrap();
+
+        assert_eq!(lease.interface, "enp3s0");
+        assert_eq!(lease.address.as_deref(), Some("192.168.1.50"));
+        assert_eq!(lease.subnet_mask.as_deref(), Some("255.255.255.0"));
+        assert_eq!(lease.router.as_deref(), Some("192.168.1.1"));
+        assert_eq!(lease.server.as_deref(), Some("192.168.1.1"));
+        assert_eq!(lease.expires, Some(time("2023-11-15T22:13:20Z")));
+        assert_eq!(lease.obtained, Some(time("2023-11-14T22:13:20Z")));
+
+        // Devices configured statically have no [dhcp4] section
+        assert_eq!(
+            parse_networkmanager_device("[device]\nmanaged=true\n", "enp3s0"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_networkd_lease() {
+        let content = include_str!("../../tests/fixtures/dhcp/networkd-lease");
+        let modified = time("2023-11-14T08:00:00Z");
+        let lease = parse_networkd_lease(content, "ens3", Some(modified)).unwrap();
+
+        assert_eq!(lease.address.as_deref(), Some("10.0.2.15"));
+        assert_eq!(lease.subnet_mask.as_deref(), Some("255.255.255.0"));
+        assert_eq!(lease.router.as_deref(), Some("10.0.2.2"));
+        assert_eq!(lease.server.as_deref(), Some("10.0.2.2"));
+        assert_eq!(lease.obtained, Some(modified));
+        assert_eq!(lease.expires, Some(time("2023-11-15T08:00:00Z")));
+    }
+
+    #[test]
+    fn test_parse_dhclient_leases() {
+        let content = include_str!("../../tests/fixtures/dhcp/dhclient.leases");
+        let leases = parse_dhclient_leases(content);
+        assert_eq!(leases.len(), 2);
+
+        // The renewed eth0 lease replaces the earlier one
+        let eth0 = leases.iter().find(|l| l.interface == "eth0").unwrap();
+        assert_eq!(eth0.address.as_deref(), Some("192.168.1.50"));
+        assert_eq!(eth0.server.as_deref(), Some("192.168.1.254"));
+        assert_eq!(eth0.router.as_deref(), Some("192.168.1.1"));
+        assert_eq!(eth0.expires, Some(time("2023-11-14T10:50:00Z")));
+        assert_eq!(eth0.obtained, Some(time("2023-11-14T09:50:00Z")));
+
+        let wlan0 = leases.iter().find(|l| l.interface == "wlan0").unwrap();
+        assert_eq!(wlan0.subnet_mask.as_deref(), Some("255.255.252.0"));
+        assert_eq!(wlan0.expires, Some(time("2023-11-15T00:13:20Z")));
+        assert_eq!(wlan0.obtained, Some(time("2023-11-14T22:13:20Z")));
+    }
+
+    #[test]
+    fn test_parse_dhcpcd_lease() {
+        let data = include_bytes!("../../tests/fixtures/dhcp/dhcpcd-eth0.lease");
+        let modified = time("2023-11-14T12:00:00Z");
+        let lease = parse_dhcpcd_lease(data, "eth0", Some(modified)).unwrap();
+
+        assert_eq!(lease.address.as_deref(), Some("192.168.0.77"));
+        assert_eq!(lease.subnet_mask.as_deref(), Some("255.255.255.0"));
+        assert_eq!(lease.router.as_deref(), Some("192.168.0.1"));
+        assert_eq!(lease.server.as_deref(), Some("192.168.0.1"));
+        assert_eq!(lease.expires, Some(time("2023-11-15T00:00:00Z")));
+
+        // Truncated or non-DHCP data is rejected
+        assert_eq!(parse_dhcpcd_lease(&data[..100], "eth0", None), None);
+        assert_eq!(parse_dhcpcd_lease(&[0u8; 300], "eth0", None), None);
+    }
+
+    #[test]
+    fn test_read_leases() {
+        for (interface, lease) in read_leases() {
+            assert_eq!(interface, lease.interface);
+        }
+    }
+}
@@ -1085,12 +1085,90 @@ impl HardwareCollector {
             Self::get_usb_devices_windows()
         }
 
-        #[cfg(not(target_os = "windows"))]
+        #[cfg(target_os = "linux")]
+        {
+            let mut names: Vec<String> = std::fs::read_dir("/sys/bus/usb/devices")
+                .map(|entries| {
+                    entries
+                        .flatten()
+                        .map(|e| e.file_name().to_string_lossy().to_string())
+                        // "1-2.3" is a device; "1-2.3:1.0" is one of its interfaces
+                        // and "usb1" the root hub of a controller
+                        .filter(|name| !name.contains(':') && !name.starts_with("usb"))
+                        .collect()
+                })
+                .unwrap_or_default();
+            names.sort();
+
+            names
+                .iter()
+                .filter_map(|name| {
+                    Self::read_linux_usb_device(
+                        &std::path::Path::new("/sys/bus/usb/devices").join(name),
+                    )
+                })
+                .collect()
+        }
+
+        #[cfg(not(any(target_os = "windows", target_os = "linux")))]
         {
             Vec::new()
         }
     }
 
+    /// Read a USB device from its sysfs directory (/sys/bus/usb/devices/<port>)
+    #[cfg(target_os = "linux")]
+    pub(crate) fn read_linux_usb_device(sys_path: &std::path::Path) -> Option<UsbDevice> {
+        use crate::hwids::UsbIdDatabase;
+
+        let read = |file: &str| {
+            std::fs::read_to_string(sys_path.join(file))
+                .map(|s| s.trim().to_string())
+                .ok()
+                .filter(|s| !s.is_empty())
+        };
+
+        let vid = read("idVendor")?;
+        let pid = read("idProduct")?;
+        let vid_num = u16::from_str_radix(&vid, 16).unwrap_or(0);
+        let pid_num = u16::from_str_radix(&pid, 16).unwrap_or(0);
+        let (vendor_name, product_name) = UsbIdDatabase::global().lookup(vid_num, pid_num);
+
+        // Descriptor strings first, the USB ID database as a fallback
+        let name = read("product")
+            .or_else(|| product_name.map(|s| s.to_string()))
+            .unwrap_or_else(|| format!("USB Device {:04X}:{:04X}", vid_num, pid_num));
+        let manufacturer = read("manufacturer").or_else(|| vendor_name.map(|s| s.to_string()));
+
+        // bmAttributes bit 6 is set for self-powered devices
+        let attributes = read("bmAttributes")
+            .and_then(|a| u8::from_str_radix(&a, 16).ok())
+            .unwrap_or(0);
+
+        Some(UsbDevice {
+            name,
+            manufacturer,
+            vid: vid.to_uppercase(),
+            pid: pid.to_uppercase(),
+            port: sys_path.file_name()?.to_string_lossy().to_string(),
+            speed: Self::usb_speed_from_mbps(read("speed").as_deref().unwrap_or_default()),
+            is_bus_powered: attributes & 0x40 == 0,
+        })
+    }
+
+    /// Map the sysfs "speed" attribute (Mbit/s) to a USB speed class
+    #[cfg(target_os = "linux")]
+    fn usb_speed_from_mbps(speed: &str) -> UsbSpeed {
+        match speed {
+            "1.5" => UsbSpeed::Low,
+            "12" => UsbSpeed::Full,
+            "480" => UsbSpeed::High,
+            "5000" => UsbSpeed::Super,
+            "10000" | "20000" => UsbSpeed::SuperPlus,
+            _ => UsbSpeed::Unknown,
+        }
+    }
+
     #[cfg(target_os = "windows")]
     fn get_usb_devices_windows() -> Vec<UsbDevice> {
         use crate::hwids::UsbIdDatabase;
@@ -1227,7 +1305,7 @@ impl HardwareCollector {
     /// Parse EDID data to extract monitor information.
     /// EDID (Extended Display Identification Data) is a 128-byte block that contains
     /// the actual monitor name, manufacturer, and specifications.
-    #[cfg(target_os = "windows")]
+    #[cfg(any(target_os = "windows", target_os = "linux"))]
     fn parse_edid(edid: &[u8]) -> Option<(String, String)> {
         // EDID must be at least 128 bytes
         if edid.len() < 128 {
@@ -1699,9 +1777,101 @@ impl HardwareCollector {
         monitors
     }
 
-    #[cfg(not(target_os = "windows"))]
+    #[cfg(target_os = "linux")]
     pub fn get_monitors() -> Vec<Monitor> {
-        // Linux/macOS implementation would go here
+        let mut connectors: Vec<std::path::PathBuf> = std::fs::read_dir("/sys/class/drm")
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|e| e.path())
+                    // Connectors are named "<card>-<connector>" (e.g. "card0-HDMI-A-1")
+                    .filter(|path| path.join("status").exists())
+                    .collect()
+            })
+            .unwrap_or_default();
+        connectors.sort();
+
+        connectors
+            .iter()
+            .filter_map(|path| Self::read_linux_monitor(path))
+            .collect()
+    }
+
+    /// Read the monitor attached to a DRM connector (/sys/class/drm/card0-HDMI-A-1)
+    #[cfg(target_os = "linux")]
+    pub(crate) fn read_linux_monitor(connector_path: &std::path::Path) -> Option<Monitor> {
+        let status = std::fs::read_to_string(connector_path.join("status")).ok()?;
+        if status.trim() != "connected" {
+            return None;
+        }
+
+        let id = connector_path.file_name()?.to_string_lossy().to_string();
+        let connection = id
+            .split_once('-')
+            .map(|(_, c)| c)
+            .unwrap_or(&id)
+            .to_string();
+        let edid = std::fs::read(connector_path.join("edid")).unwrap_or_default();
+        let (manufacturer, name) = Self::parse_edid(&edid)
+            .map(|(manufacturer, name)| (Some(manufacturer), name))
+            .unwrap_or_else(|| (None, connection.clone()));
+        let timing = Self::parse_edid_timing(&edid);
+
+        // The kernel lists modes best first
+        let resolution = std::fs::read_to_string(connector_path.join("modes"))
+            .ok()
+            .and_then(|modes| modes.lines().next().map(str::to_string))
+            .or_else(|| timing.map(|(width, height, _)| format!("{}x{}", width, height)))
+            .unwrap_or_else(|| "Unknown".to_string());
+
+        Some(Monitor {
+            id,
+            name,
+            manufacturer,
+            resolution,
+            size_inches: Self::parse_edid_size_inches(&edid),
+            connection,
+            hdr_support: false,
+            refresh_rate_hz: timing.map(|(_, _, refresh)| refresh).unwrap_or(0),
+        })
+    }
+
+    /// Screen diagonal from the EDID maximum image size (bytes 21-22, in centimetres)
+    #[cfg(target_os = "linux")]
+    fn parse_edid_size_inches(edid: &[u8]) -> Option<f32> {
+        let (width_cm, height_cm) = (*edid.get(21)? as f32, *edid.get(22)? as f32);
+        if width_cm == 0.0 || height_cm == 0.0 {
+            return None;
+        }
+        let diagonal = (width_cm * width_cm + height_cm * height_cm).sqrt() / 2.54;
+        Some((diagonal * 10.0).round() / 10.0)
+    }
+
+    /// Preferred mode from the first detailed timing descriptor: (width, height, refresh Hz)
+    #[cfg(target_os = "linux")]
+    fn parse_edid_timing(edid: &[u8]) -> Option<(u32, u32, u32)> {
+        let dtd = edid.get(54..72)?;
+        let pixel_clock_hz = u16::from_le_bytes([dtd[0], dtd[1]]) as u64 * 10_000;
+        if pixel_clock_hz == 0 {
+            return None;
+        }
+
+        let h_active = dtd[2] as u32 | ((dtd[4] as u32 & 0xF0) << 4);
+        let h_blank = dtd[3] as u32 | ((dtd[4] as u32 & 0x0F) << 8);
+        let v_active = dtd[5] as u32 | ((dtd[7] as u32 & 0xF0) << 4);
+        let v_blank = dtd[6] as u32 | ((dtd[7] as u32 & 0x0F) << 8);
+        let total = ((h_active + h_blank) * (v_active + v_blank)) as u64;
+        if total == 0 {
+            return None;
+        }
+
+        let refresh = (pixel_clock_hz as f64 / total as f64).round() as u32;
+        Some((h_active, v_active, refresh))
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "linux")))]
+    pub fn get_monitors() -> Vec<Monitor> {
+        // macOS implementation would go here
         Vec::new()
     }
 
@@ -1974,7 +2144,7 @@ mod tests {
         assert!(HardwareCollector::get_motherboard_support_url("Unknown", "").is_none());
     }
 
-    #[cfg(target_os = "windows")]
+    #[cfg(any(target_os = "windows", target_os = "linux"))]
     #[test]
     fn test_parse_edid_valid() {
         // Valid EDID header + manufacturer "DEL" (Dell) + monitor name
@@ -2007,7 +2177,7 @@ mod tests {
         assert_eq!(name, "DELL S2722DGM");
     }
 
-    #[cfg(target_os = "windows")]
+    #[cfg(any(target_os = "windows", target_os = "linux"))]
     #[test]
     fn test_parse_edid_invalid_header() {
         // Invalid EDID header
@@ -2016,7 +2186,7 @@ mod tests {
         assert!(result.is_none());
     }
 
-    #[cfg(target_os = "windows")]
+    #[cfg(any(target_os = "windows", target_os = "linux"))]
     #[test]
     fn test_parse_edid_too_short() {
         // EDID too short (less than 128 bytes)
@@ -2024,4 +2194,55 @@ mod tests {
         let result = HardwareCollector::parse_edid(&edid);
         assert!(result.is_none());
     }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_edid_timing_and_size() {
+        let mut edid = vec![0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00];
+        edid.resize(128, 0x00);
+        // 60 x 34 cm image size
+        edid[21] = 60;
+        edid[22] = 34;
+        // 1920x1080 at 148.5 MHz with 280/45 blanking lines = 60 Hz
+        edid[54..62].copy_from_slice(&[0x02, 0x3A, 0x80, 0x18, 0x71, 0x38, 0x2D, 0x40]);
+
+        assert_eq!(
+            HardwareCollector::parse_edid_timing(&edid),
+            Some((1920, 1080, 60))
+        );
+        assert_eq!(HardwareCollector::parse_edid_size_inches(&edid), Some(27.2));
+
+        // Projectors report no size, and a zero pixel clock marks a non-timing descriptor
+        edid[21] = 0;
+        edid[54] = 0;
+        edid[55] = 0;
+        assert_eq!(HardwareCollector::parse_edid_size_inches(&edid), None);
+        assert_eq!(HardwareCollector::parse_edid_timing(&edid), None);
+        assert_eq!(HardwareCollector::parse_edid_timing(&edid[..60]), None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_usb_speed_from_mbps() {
+        assert!(matches!(
+            HardwareCollector::usb_speed_from_mbps("1.5"),
+            UsbSpeed::Low
+        ));
+        assert!(matches!(
+            HardwareCollector::usb_speed_from_mbps("480"),
+            UsbSpeed::High
+        ));
+        assert!(matches!(
+            HardwareCollector::usb_speed_from_mbps("5000"),
+            UsbSpeed::Super
+        ));
+        assert!(matches!(
+            HardwareCollector::usb_speed_from_mbps("20000"),
+            UsbSpeed::SuperPlus
+        ));
+        assert!(matches!(
+            HardwareCollector::usb_speed_from_mbps(""),
+            UsbSpeed::Unknown
+        ));
+    }
 }
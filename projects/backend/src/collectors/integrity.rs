@@ -0,0 +1,946 @@
+//! Executable integrity collector
+//!
+//! Hashes binaries and checks them against the checksums recorded by the package
+//! manager that installed them: dpkg's md5sums, pacman's mtree files and the rpm
+//! database. The rpm database is SQLite or Berkeley DB depending on the distro, so it
+//! is queried through `rpm` rather than read directly.
+
+use crate::models::{
+    BinaryIntegrity, DigestAlgorithm, IntegrityError, IntegrityStatus, PackageManager, PackageOwner,
+};
+use md5::Md5;
+use sha2::{Digest, Sha224, Sha256, Sha384, Sha512};
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+use std::process::Command;
+
+const DPKG_INFO_DIR: &str = "/var/lib/dpkg/info";
+const DPKG_STATUS: &str = "/var/lib/dpkg/status";
+const PACMAN_LOCAL_DIR: &str = "/var/lib/pacman/local";
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+const RPM_DB_DIRS: [&str; 2] = ["/var/lib/rpm", "/usr/lib/sysimage/rpm"];
+/// Package header, then one line per file of every package owning the path
+const RPM_QUERY_FORMAT: &str =
+    "P\t%{NAME}\t%{EVR}\t%{FILEDIGESTALGO}\n[F\t%{FILENAMES}\t%{FILEDIGESTS}\n]";
+/// Top-level directories that merged /usr turns into symlinks into /usr
+const MERGED_DIRS: [&str; 6] = ["/bin/", "/sbin/", "/lib/", "/lib32/", "/lib64/", "/libx32/"];
+
+/// What a package database records about a file
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct PackageRecord {
+    pub owner: PackageOwner,
+    pub digest: Option<(DigestAlgorithm, String)>,
+}
+
+/// What systemd reports about a service unit
+#[derive(Debug, Clone, PartialEq)]
+struct SystemdUnit {
+    /// None when the unit has no running main process
+    main_pid: Option<u32>,
+    exec_path: Option<String>,
+}
+
+/// Collector for executable hashes and package ownership
+pub struct IntegrityCollector;
+
+impl IntegrityCollector {
+    /// Hash a file and check it against its owning package
+    pub fn verify_file(path: &str) -> Result<BinaryIntegrity, IntegrityError> {
+        Self::verify(Path::new(path), path, false)
+    }
+
+    /// Check the executable a process runs. On Linux the image is read through
+    /// /proc/<pid>/exe, so a binary deleted or replaced since the process started is
+    /// still the one hashed.
+    pub fn verify_process(
+        pid: u32,
+        exe_path: Option<&str>,
+    ) -> Result<BinaryIntegrity, IntegrityError> {
+        #[cfg(target_os = "linux")]
+        {
+            let image = std::path::PathBuf::from(format!("/proc/{}/exe", pid));
+            if let Ok(target) = std::fs::read_link(&image) {
+                let target = target.to_string_lossy().to_string();
+                let (path, deleted) = match target.strip_suffix(" (deleted)") {
+                    Some(path) => (path, true),
+                    None => (target.as_str(), false),
+                };
+                // The image of another user's process may be off limits while the
+                // file itself is readable
+                return match Self::verify(&image, path, deleted) {
+                    Err(IntegrityError::PermissionDenied { .. }) if !deleted => {
+                        Self::verify_file(path)
+                    }
+                    result => result,
+                };
+            }
+        }
+
+        let path = exe_path.ok_or_else(|| IntegrityError::NoExecutable {
+            target: format!("process {}", pid),
+        })?;
+        Self::verify_file(path)
+    }
+
+    /// Check the executable of a service. On Linux the service is a systemd unit, and
+    /// its main process is checked while it runs; elsewhere the executable comes from
+    /// the service's command line.
+    pub fn verify_service(
+        name: &str,
+        binary_path: Option<&str>,
+    ) -> Result<BinaryIntegrity, IntegrityError> {
+        #[cfg(target_os = "linux")]
+        if let Some(unit) = Self::systemd_unit(name) {
+            return Self::verify_systemd_unit(name, unit);
+        }
+
+        let path = binary_path
+            .and_then(Self::executable_from_command_line)
+            .ok_or_else(|| IntegrityError::NoExecutable {
+                target: format!("service {}", name),
+            })?;
+        Self::verify_file(&path)
+    }
+
+    /// Check a unit's running main process, or the program its ExecStart= names when it
+    /// is stopped
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    fn verify_systemd_unit(
+        name: &str,
+        unit: SystemdUnit,
+    ) -> Result<BinaryIntegrity, IntegrityError> {
+        match (unit.main_pid, unit.exec_path) {
+            (Some(pid), exec_path) => Self::verify_process(pid, exec_path.as_deref()),
+            (None, Some(path)) => Self::verify_file(&path),
+            (None, None) => Err(IntegrityError::NoExecutable {
+                target: format!("service {}", name),
+            }),
+        }
+    }
+
+    /// Main process and program of a systemd unit. None when systemctl can't be run.
+    #[cfg(target_os = "linux")]
+    fn systemd_unit(name: &str) -> Option<SystemdUnit> {
+        let output = Command::new("systemctl")
+            .args(["show", "--property=MainPID,ExecStart", "--", name])
+            .output()
+            .ok()?;
+        // Unknown units still succeed, with MainPID=0 and no ExecStart
+        if !output.status.success() {
+            return None;
+        }
+        Some(Self::parse_systemctl_show(&String::from_utf8_lossy(
+            &output.stdout,
+        )))
+    }
+
+    /// Parse `systemctl show --property=MainPID,ExecStart` output. ExecStart lists every
+    /// command as "{ path=/usr/sbin/sshd ; argv[]=/usr/sbin/sshd -D ; ... }"; the first
+    /// one starts the main process.
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    fn parse_systemctl_show(output: &str) -> SystemdUnit {
+        let property = |key: &str| {
+            output
+                .lines()
+                .find_map(|line| line.strip_prefix(key)?.strip_prefix('='))
+        };
+        let main_pid = property("MainPID")
+            .and_then(|pid| pid.trim().parse().ok())
+            .filter(|pid| *pid != 0);
+        let exec_path = property("ExecStart").and_then(|commands| {
+            commands
+                .split(" ; ")
+                .find_map(|field| field.trim_start_matches("{ ").strip_prefix("path="))
+                .filter(|path| !path.is_empty())
+                .map(str::to_string)
+        });
+        SystemdUnit {
+            main_pid,
+            exec_path,
+        }
+    }
+
+    fn verify(image: &Path, path: &str, deleted: bool) -> Result<BinaryIntegrity, IntegrityError> {
+        let managers = Self::package_managers();
+        let record = Self::find_record(&managers, path);
+        Self::check(image, path, deleted, !managers.is_empty(), record)
+    }
+
+    /// Hash `image` and compare it with what the package database recorded for `path`.
+    /// A `deleted` image is never compared, since the record is of its replacement.
+    pub(crate) fn check(
+        image: &Path,
+        path: &str,
+        deleted: bool,
+        has_database: bool,
+        record: Option<PackageRecord>,
+    ) -> Result<BinaryIntegrity, IntegrityError> {
+        let io_error = |e| Self::io_error(path, e);
+        let (sha256, size_bytes) = Self::file_digest(image, DigestAlgorithm::Sha256)
+            .map_err(io_error)?
+            .unwrap_or_default();
+
+        let (status, expected_digest, digest_algorithm) = match &record {
+            None if has_database => (IntegrityStatus::Unowned, None, None),
+            None => (IntegrityStatus::Unknown, None, None),
+            // The database describes whatever is at the path now, typically the
+            // upgraded binary
+            Some(_) if deleted => (IntegrityStatus::Replaced, None, None),
+            Some(PackageRecord {
+                digest: Some((algorithm, expected)),
+                ..
+            }) => {
+                let actual = if *algorithm == DigestAlgorithm::Sha256 {
+                    Some(sha256.clone())
+                } else {
+                    Self::file_digest(image, *algorithm)
+                        .map_err(io_error)?
+                        .map(|(digest, _)| digest)
+                };
+                let status = match actual {
+                    Some(actual) if actual.eq_ignore_ascii_case(expected) => {
+                        IntegrityStatus::Verified
+                    }
+                    Some(_) => IntegrityStatus::Modified,
+                    None => IntegrityStatus::Unverified,
+                };
+                (status, Some(expected.to_lowercase()), Some(*algorithm))
+            }
+            Some(_) => (IntegrityStatus::Unverified, None, None),
+        };
+
+        Ok(BinaryIntegrity {
+            path: path.to_string(),
+            deleted,
+            size_bytes,
+            sha256,
+            package: record.map(|record| record.owner),
+            expected_digest,
+            digest_algorithm,
+            status,
+        })
+    }
+
+    /// Package databases present on this system. On Windows, Authenticode signer
+    /// information would be the counterpart; until then binaries there are Unknown.
+    fn package_managers() -> Vec<PackageManager> {
+        #[cfg(target_os = "linux")]
+        {
+            let mut managers = Vec::new();
+            if Path::new(DPKG_INFO_DIR).is_dir() {
+                managers.push(PackageManager::Dpkg);
+            }
+            if Path::new(PACMAN_LOCAL_DIR).is_dir() {
+                managers.push(PackageManager::Pacman);
+            }
+            if RPM_DB_DIRS.iter().any(|dir| Path::new(dir).is_dir()) {
+                managers.push(PackageManager::Rpm);
+            }
+            managers
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            Vec::new()
+        }
+    }
+
+    fn find_record(managers: &[PackageManager], path: &str) -> Option<PackageRecord> {
+        let aliases = Self::path_aliases(path);
+        managers.iter().find_map(|manager| match manager {
+            PackageManager::Dpkg => {
+                Self::dpkg_record(Path::new(DPKG_INFO_DIR), Path::new(DPKG_STATUS), &aliases)
+            }
+            PackageManager::Pacman => Self::pacman_record(Path::new(PACMAN_LOCAL_DIR), &aliases),
+            PackageManager::Rpm => Self::rpm_record(&aliases),
+        })
+    }
+
+    /// Paths a file may be recorded under. With merged /usr, /bin is a symlink to
+    /// /usr/bin and packages may list either.
+    fn path_aliases(path: &str) -> Vec<String> {
+        let mut aliases = vec![path.to_string()];
+        let merged = |p: &str| MERGED_DIRS.iter().any(|dir| p.starts_with(dir));
+        match path.strip_prefix("/usr") {
+            Some(rest) if merged(rest) => aliases.push(rest.to_string()),
+            _ if merged(path) => aliases.push(format!("/usr{}", path)),
+            _ => {}
+        }
+        aliases
+    }
+
+    /// Find the owner in dpkg's per-package file lists (info/<package>.list) and the
+    /// checksum in info/<package>.md5sums
+    fn dpkg_record(info_dir: &Path, status: &Path, aliases: &[String]) -> Option<PackageRecord> {
+        for entry in std::fs::read_dir(info_dir).ok()?.flatten() {
+            let list = entry.path();
+            if list.extension().is_none_or(|ext| ext != "list") {
+                continue;
+            }
+            let Ok(content) = std::fs::read_to_string(&list) else {
+                continue;
+            };
+            let Some(recorded) = content
+                .lines()
+                .find(|line| aliases.iter().any(|a| a == line))
+            else {
+                continue;
+            };
+
+            // "libc6:amd64" for multi-arch packages
+            let package = list.file_stem()?.to_string_lossy().to_string();
+            let (name, arch) = match package.split_once(':') {
+                Some((name, arch)) => (name, Some(arch)),
+                None => (package.as_str(), None),
+            };
+            let version = std::fs::read_to_string(status)
+                .ok()
+                .and_then(|status| Self::dpkg_version(&status, name, arch));
+
+            // Paths in md5sums are relative to /
+            let relative = recorded.trim_start_matches('/');
+            let digest = std::fs::read_to_string(info_dir.join(format!("{}.md5sums", package)))
+                .ok()
+                .and_then(|sums| {
+                    sums.lines().find_map(|line| {
+                        let (digest, file) = line.split_once("  ")?;
+                        (file == relative).then(|| (DigestAlgorithm::Md5, digest.to_string()))
+                    })
+                });
+
+            return Some(PackageRecord {
+                owner: PackageOwner {
+                    manager: PackageManager::Dpkg,
+                    name: package.clone(),
+                    version,
+                },
+                digest,
+            });
+        }
+        None
+    }
+
+    /// Version of an installed package from dpkg's status file
+    fn dpkg_version(status: &str, name: &str, arch: Option<&str>) -> Option<String> {
+        status.split("\n\n").find_map(|stanza| {
+            let field = |key: &str| {
+                stanza
+                    .lines()
+                    .find_map(|line| line.strip_prefix(key)?.strip_prefix(": "))
+            };
+            let matches = field("Package") == Some(name)
+                && arch.is_none_or(|arch| field("Architecture") == Some(arch));
+            matches
+                .then(|| field("Version"))
+                .flatten()
+                .map(str::to_string)
+        })
+    }
+
+    /// Find the owner in pacman's local database (local/<name>-<version>/files) and
+    /// the checksum in the package's gzip-compressed mtree
+    fn pacman_record(local_dir: &Path, aliases: &[String]) -> Option<PackageRecord> {
+        // Paths in files and mtree are relative to /
+        let relative: Vec<&str> = aliases.iter().map(|a| a.trim_start_matches('/')).collect();
+
+        for entry in std::fs::read_dir(local_dir).ok()?.flatten() {
+            let dir = entry.path();
+            let Ok(files) = std::fs::read_to_string(dir.join("files")) else {
+                continue;
+            };
+            let Some(recorded) =
+                Self::pacman_section(&files, "FILES").find(|line| relative.contains(line))
+            else {
+                continue;
+            };
+
+            let desc = std::fs::read_to_string(dir.join("desc")).unwrap_or_default();
+            let name = Self::pacman_section(&desc, "NAME")
+                .next()
+                .map(str::to_string)
+                .unwrap_or_else(|| entry.file_name().to_string_lossy().to_string());
+            let version = Self::pacman_section(&desc, "VERSION")
+                .next()
+                .map(str::to_string);
+
+            let digest = File::open(dir.join("mtree")).ok().and_then(|file| {
+                let mut mtree = String::new();
+                flate2::read::GzDecoder::new(file)
+                    .read_to_string(&mut mtree)
+                    .ok()?;
+                Self::mtree_digest(&mtree, recorded)
+            });
+
+            return Some(PackageRecord {
+                owner: PackageOwner {
+                    manager: PackageManager::Pacman,
+                    name,
+                    version,
+                },
+                digest,
+            });
+        }
+        None
+    }
+
+    /// Lines of a "%SECTION%" block in pacman's desc and files databases
+    fn pacman_section<'a>(content: &'a str, section: &str) -> impl Iterator<Item = &'a str> {
+        let header = format!("%{}%", section);
+        content
+            .lines()
+            .skip_while(move |line| *line != header)
+            .skip(1)
+            .take_while(|line| !line.is_empty())
+    }
+
+    /// Checksum of a file from an mtree spec ("./usr/bin/ls time=... sha256digest=...")
+    fn mtree_digest(mtree: &str, relative: &str) -> Option<(DigestAlgorithm, String)> {
+        let keywords: Vec<&str> = mtree.lines().find_map(|line| {
+            let mut fields = line.split_whitespace();
+            let name = Self::mtree_unescape(fields.next()?);
+            (name.strip_prefix("./") == Some(relative)).then(|| fields.collect())
+        })?;
+        let keyword = |key: &str| {
+            keywords
+                .iter()
+                .find_map(|field| field.strip_prefix(key)?.strip_prefix('='))
+                .map(str::to_string)
+        };
+
+        keyword("sha256digest")
+            .map(|digest| (DigestAlgorithm::Sha256, digest))
+            .or_else(|| keyword("md5digest").map(|digest| (DigestAlgorithm::Md5, digest)))
+    }
+
+    /// mtree escapes spaces and other special characters as "\ooo" octal
+    fn mtree_unescape(name: &str) -> String {
+        let bytes = name.as_bytes();
+        let mut out = Vec::with_capacity(bytes.len());
+        let mut i = 0;
+        while i < bytes.len() {
+            let octal = bytes
+                .get(i + 1..i + 4)
+                .filter(|_| bytes[i] == b'\\')
+                .and_then(|digits| std::str::from_utf8(digits).ok())
+                .and_then(|digits| u8::from_str_radix(digits, 8).ok());
+            match octal {
+                Some(byte) => {
+                    out.push(byte);
+                    i += 4;
+                }
+                None => {
+                    out.push(bytes[i]);
+                    i += 1;
+                }
+            }
+        }
+        String::from_utf8_lossy(&out).to_string()
+    }
+
+    fn rpm_record(aliases: &[String]) -> Option<PackageRecord> {
+        aliases.iter().find_map(|path| {
+            let output = Command::new("rpm")
+                .args(["-qf", "--queryformat", RPM_QUERY_FORMAT, path])
+                .output()
+                .ok()?;
+            // Fails with "file ... is not owned by any package"
+            if !output.status.success() {
+                return None;
+            }
+            Self::parse_rpm_query(&String::from_utf8_lossy(&output.stdout), path)
+        })
+    }
+
+    /// Parse the output of `rpm -qf --queryformat RPM_QUERY_FORMAT <path>`
+    fn parse_rpm_query(output: &str, path: &str) -> Option<PackageRecord> {
+        let mut package: Option<(PackageOwner, Option<DigestAlgorithm>)> = None;
+
+        for line in output.lines() {
+            match line.split('\t').collect::<Vec<_>>().as_slice() {
+                ["P", name, evr, algorithm] => {
+                    package = Some((
+                        PackageOwner {
+                            manager: PackageManager::Rpm,
+                            name: name.to_string(),
+                            version: Some(evr.to_string()),
+                        },
+                        Self::rpm_algorithm(algorithm),
+                    ));
+                }
+                ["F", file, digest] if *file == path => {
+                    let (owner, algorithm) = package?;
+                    // Directories, symlinks and ghost files have no digest
+                    let digest = algorithm
+                        .filter(|_| !digest.is_empty())
+                        .map(|algorithm| (algorithm, digest.to_string()));
+                    return Some(PackageRecord { owner, digest });
+                }
+                _ => {}
+            }
+        }
+
+        package.map(|(owner, _)| PackageRecord {
+            owner,
+            digest: None,
+        })
+    }
+
+    /// Map rpm's PGP hash algorithm IDs. Packages without the tag predate it and
+    /// use MD5.
+    fn rpm_algorithm(id: &str) -> Option<DigestAlgorithm> {
+        match id {
+            "" | "(none)" | "1" => Some(DigestAlgorithm::Md5),
+            "2" => Some(DigestAlgorithm::Sha1),
+            "8" => Some(DigestAlgorithm::Sha256),
+            "9" => Some(DigestAlgorithm::Sha384),
+            "10" => Some(DigestAlgorithm::Sha512),
+            "11" => Some(DigestAlgorithm::Sha224),
+            _ => None,
+        }
+    }
+
+    /// The executable of a service command line:
+    /// `"C:\Program Files\App\svc.exe" -run`, `C:\Windows\system32\svchost.exe -k netsvcs`
+    /// or `/usr/sbin/sshd -D`
+    pub(crate) fn executable_from_command_line(command: &str) -> Option<String> {
+        let command = command.trim();
+        if let Some(quoted) = command.strip_prefix('"') {
+            return quoted
+                .split('"')
+                .next()
+                .filter(|path| !path.is_empty())
+                .map(str::to_string);
+        }
+
+        // Unquoted Windows paths may contain spaces, so cut after the extension
+        if let Some(end) = command.to_ascii_lowercase().find(".exe") {
+            return Some(command[..end + 4].to_string());
+        }
+        command.split_whitespace().next().map(str::to_string)
+    }
+
+    /// Lowercase hex digest and size of a file. None for algorithms we can't compute.
+    fn file_digest(path: &Path, algorithm: DigestAlgorithm) -> io::Result<Option<(String, u64)>> {
+        let digest = match algorithm {
+            // dpkg's md5sums and older rpm packages. A match only says the file is
+            // what the package shipped; it is no defence against tampering that also
+            // rewrites the package database.
+            DigestAlgorithm::Md5 => Self::hex_digest::<Md5>(path)?,
+            DigestAlgorithm::Sha1 => return Ok(None),
+            DigestAlgorithm::Sha224 => Self::hex_digest::<Sha224>(path)?,
+            DigestAlgorithm::Sha256 => Self::hex_digest::<Sha256>(path)?,
+            DigestAlgorithm::Sha384 => Self::hex_digest::<Sha384>(path)?,
+            DigestAlgorithm::Sha512 => Self::hex_digest::<Sha512>(path)?,
+        };
+        Ok(Some(digest))
+    }
+
+    fn hex_digest<D: Digest>(path: &Path) -> io::Result<(String, u64)> {
+        let mut hasher = D::new();
+        let size = Self::read_chunks(path, |chunk| hasher.update(chunk))?;
+        let hex = hasher
+            .finalize()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+        Ok((hex, size))
+    }
+
+    fn read_chunks(path: &Path, mut consume: impl FnMut(&[u8])) -> io::Result<u64> {
+        let mut file = File::open(path)?;
+        let mut buffer = vec![0u8; 64 * 1024];
+        let mut size = 0u64;
+        loop {
+            let read = file.read(&mut buffer)?;
+            if read == 0 {
+                return Ok(size);
+            }
+            consume(&buffer[..read]);
+            size += read as u64;
+        }
+    }
+
+    fn io_error(path: &str, e: io::Error) -> IntegrityError {
+        match e.kind() {
+            io::ErrorKind::NotFound => IntegrityError::NotFound {
+                path: path.to_string(),
+            },
+            io::ErrorKind::PermissionDenied => IntegrityError::PermissionDenied {
+                path: path.to_string(),
+            },
+            _ => IntegrityError::Io {
+                path: path.to_string(),
+                message: e.to_string(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("syslens_integrity_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_check_against_record() {
+        let dir = temp_dir("check");
+        let file = dir.join("tool");
+        std::fs::write(&file, b"abc").unwrap();
+        let sha256 = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+        let owner = PackageOwner {
+            manager: PackageManager::Dpkg,
+            name: "tool".to_string(),
+            version: Some("1.0-1".to_string()),
+        };
+        let record = |algorithm, digest: &str| PackageRecord {
+            owner: owner.clone(),
+            digest: Some((algorithm, digest.to_string())),
+        };
+        let check = |has_database, record| {
+            IntegrityCollector::check(&file, "/usr/bin/tool", false, has_database, record).unwrap()
+        };
+
+        let verified = check(
+            true,
+            Some(record(
+                DigestAlgorithm::Md5,
+                "900150983CD24FB0D6963F7D28E17F72",
+            )),
+        );
+        assert_eq!(verified.status, IntegrityStatus::Verified);
+        assert_eq!(verified.sha256, sha256);
+        assert_eq!(verified.size_bytes, 3);
+        assert_eq!(verified.package, Some(owner.clone()));
+        assert_eq!(
+            verified.expected_digest.as_deref(),
+            Some("900150983cd24fb0d6963f7d28e17f72")
+        );
+
+        assert_eq!(
+            check(true, Some(record(DigestAlgorithm::Sha256, sha256))).status,
+            IntegrityStatus::Verified
+        );
+        assert_eq!(
+            check(true, Some(record(DigestAlgorithm::Sha256, &"0".repeat(64)))).status,
+            IntegrityStatus::Modified
+        );
+        assert_eq!(
+            check(true, Some(record(DigestAlgorithm::Sha1, &"0".repeat(40)))).status,
+            IntegrityStatus::Unverified
+        );
+        assert_eq!(
+            check(
+                true,
+                Some(PackageRecord {
+                    owner: owner.clone(),
+                    digest: None
+                })
+            )
+            .status,
+            IntegrityStatus::Unverified
+        );
+        assert_eq!(check(true, None).status, IntegrityStatus::Unowned);
+
+        // A deleted image isn't the file the package database describes
+        let replaced = IntegrityCollector::check(
+            &file,
+            "/usr/bin/tool",
+            true,
+            true,
+            Some(record(DigestAlgorithm::Sha256, &"0".repeat(64))),
+        )
+        .unwrap();
+        assert_eq!(replaced.status, IntegrityStatus::Replaced);
+        assert!(replaced.deleted);
+        assert_eq!(replaced.package, Some(owner.clone()));
+        assert_eq!(replaced.expected_digest, None);
+        assert_eq!(check(false, None).status, IntegrityStatus::Unknown);
+
+        assert_eq!(
+            IntegrityCollector::check(&dir.join("missing"), "/missing", false, true, None)
+                .unwrap_err(),
+            IntegrityError::NotFound {
+                path: "/missing".to_string()
+            }
+        );
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_dpkg_record() {
+        let dir = temp_dir("dpkg");
+        let info = dir.join("info");
+        std::fs::create_dir_all(&info).unwrap();
+        std::fs::write(
+            info.join("coreutils.list"),
+            "/.\n/usr\n/usr/bin\n/usr/bin/ls\n/usr/bin/cat\n",
+        )
+        .unwrap();
+        std::fs::write(
+            info.join("coreutils.md5sums"),
+            "0123456789abcdef0123456789abcdef  usr/bin/cat\nfedcba9876543210fedcba9876543210  usr/bin/ls\n",
+        )
+        .unwrap();
+        std::fs::write(
+            info.join("libc6:amd64.list"),
+            "/lib/x86_64-linux-gnu/libc.so.6\n",
+        )
+        .unwrap();
+        std::fs::write(info.join("libc6:amd64.conffiles"), "/usr/bin/ls\n").unwrap();
+        let status = dir.join("status");
+        std::fs::write(
+            &status,
+            "Package: coreutils\nStatus: install ok installed\nArchitecture: amd64\nVersion: 9.4-3\n\n\
+             Package: libc6\nArchitecture: i386\nVersion: 2.38-1\n\n\
+             Package: libc6\nArchitecture: amd64\nVersion: 2.39-0ubuntu8\n",
+        )
+        .unwrap();
+
+        // Merged /usr: the running binary is /bin/ls, the package lists /usr/bin/ls
+        let record = IntegrityCollector::dpkg_record(
+            &info,
+            &status,
+            &IntegrityCollector::path_aliases("/bin/ls"),
+        )
+        .unwrap();
+        assert_eq!(
+            record,
+            PackageRecord {
+                owner: PackageOwner {
+                    manager: PackageManager::Dpkg,
+                    name: "coreutils".to_string(),
+                    version: Some("9.4-3".to_string()),
+                },
+                digest: Some((
+                    DigestAlgorithm::Md5,
+                    "fedcba9876543210fedcba9876543210".to_string()
+                )),
+            }
+        );
+
+        let record = IntegrityCollector::dpkg_record(
+            &info,
+            &status,
+            &IntegrityCollector::path_aliases("/usr/lib/x86_64-linux-gnu/libc.so.6"),
+        )
+        .unwrap();
+        assert_eq!(record.owner.name, "libc6:amd64");
+        assert_eq!(record.owner.version.as_deref(), Some("2.39-0ubuntu8"));
+        assert_eq!(record.digest, None);
+
+        assert_eq!(
+            IntegrityCollector::dpkg_record(&info, &status, &["/opt/app/bin/app".to_string()]),
+            None
+        );
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_pacman_record() {
+        let dir = temp_dir("pacman");
+        let package = dir.join("openssh-9.8p1-1");
+        std::fs::create_dir_all(&package).unwrap();
+        std::fs::write(
+            package.join("desc"),
+            "%NAME%\nopenssh\n\n%VERSION%\n9.8p1-1\n\n%DESC%\nSSH protocol implementation\n",
+        )
+        .unwrap();
+        std::fs::write(
+            package.join("files"),
+            "%FILES%\nusr/\nusr/bin/\nusr/bin/sshd\nusr/share/doc/open ssh/README\n\n%BACKUP%\netc/ssh/sshd_config\tabc\n",
+        )
+        .unwrap();
+        let mtree = "#mtree\n/set type=file uid=0 gid=0 mode=644\n\
+                     ./usr/bin/sshd time=1719000000.0 mode=755 size=917504 md5digest=00112233445566778899aabbccddeeff sha256digest=aabbccddeeff00112233445566778899aabbccddeeff00112233445566778899\n\
+                     ./usr/share/doc/open\\040ssh/README time=1719000000.0 size=10 md5digest=ffeeddccbbaa99887766554433221100\n";
+        let mut encoder = flate2::write::GzEncoder::new(
+            File::create(package.join("mtree")).unwrap(),
+            flate2::Compression::default(),
+        );
+        encoder.write_all(mtree.as_bytes()).unwrap();
+        encoder.finish().unwrap();
+
+        let record = IntegrityCollector::pacman_record(
+            &dir,
+            &IntegrityCollector::path_aliases("/usr/bin/sshd"),
+        )
+        .unwrap();
+        assert_eq!(
+            record,
+            PackageRecord {
+                owner: PackageOwner {
+                    manager: PackageManager::Pacman,
+                    name: "openssh".to_string(),
+                    version: Some("9.8p1-1".to_string()),
+                },
+                digest: Some((
+                    DigestAlgorithm::Sha256,
+                    "aabbccddeeff00112233445566778899aabbccddeeff00112233445566778899".to_string()
+                )),
+            }
+        );
+
+        let record = IntegrityCollector::pacman_record(
+            &dir,
+            &["/usr/share/doc/open ssh/README".to_string()],
+        )
+        .unwrap();
+        assert_eq!(
+            record.digest,
+            Some((
+                DigestAlgorithm::Md5,
+                "ffeeddccbbaa99887766554433221100".to_string()
+            ))
+        );
+
+        // Backup entries are not files of the package
+        assert_eq!(
+            IntegrityCollector::pacman_record(&dir, &["/etc/ssh/sshd_config\tabc".to_string()]),
+            None
+        );
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_parse_rpm_query() {
+        let output = "P\tbash\t5.2.26-3.fc40\t8\n\
+                      F\t/usr/bin/bash\t0f1e2d3c4b5a69788796a5b4c3d2e1f00f1e2d3c4b5a69788796a5b4c3d2e1f0\n\
+                      F\t/usr/bin/sh\t\n";
+        assert_eq!(
+            IntegrityCollector::parse_rpm_query(output, "/usr/bin/bash"),
+            Some(PackageRecord {
+                owner: PackageOwner {
+                    manager: PackageManager::Rpm,
+                    name: "bash".to_string(),
+                    version: Some("5.2.26-3.fc40".to_string()),
+                },
+                digest: Some((
+                    DigestAlgorithm::Sha256,
+                    "0f1e2d3c4b5a69788796a5b4c3d2e1f00f1e2d3c4b5a69788796a5b4c3d2e1f0".to_string()
+                )),
+            })
+        );
+        // A symlink has no digest
+        assert_eq!(
+            IntegrityCollector::parse_rpm_query(output, "/usr/bin/sh").and_then(|r| r.digest),
+            None
+        );
+
+        // Old packages without FILEDIGESTALGO use MD5
+        let output =
+            "P\tlegacy\t1.0-1\t(none)\nF\t/usr/bin/legacy\t0123456789abcdef0123456789abcdef\n";
+        assert_eq!(
+            IntegrityCollector::parse_rpm_query(output, "/usr/bin/legacy")
+                .and_then(|r| r.digest)
+                .map(|(algorithm, _)| algorithm),
+            Some(DigestAlgorithm::Md5)
+        );
+    }
+
+    #[test]
+    fn test_executable_from_command_line() {
+        let exe = IntegrityCollector::executable_from_command_line;
+        assert_eq!(
+            exe(r#""C:\Program Files\Vendor\Agent Service.exe" --service"#).as_deref(),
+            Some(r"C:\Program Files\Vendor\Agent Service.exe")
+        );
+        assert_eq!(
+            exe(r"C:\Windows\system32\svchost.exe -k netsvcs -p").as_deref(),
+            Some(r"C:\Windows\system32\svchost.exe")
+        );
+        assert_eq!(
+            exe(r"C:\Program Files\App\App.EXE /run").as_deref(),
+            Some(r"C:\Program Files\App\App.EXE")
+        );
+        assert_eq!(exe("/usr/sbin/sshd -D").as_deref(), Some("/usr/sbin/sshd"));
+        assert_eq!(exe("  "), None);
+    }
+
+    #[test]
+    fn test_parse_systemctl_show() {
+        let output = "MainPID=812\n\
+                      ExecStart={ path=/usr/sbin/sshd ; argv[]=/usr/sbin/sshd -D $SSHD_OPTS ; ignore_errors=no ; start_time=[n/a] ; stop_time=[n/a] ; pid=0 ; code=(null) ; status=0/0 }\n";
+        assert_eq!(
+            IntegrityCollector::parse_systemctl_show(output),
+            SystemdUnit {
+                main_pid: Some(812),
+                exec_path: Some("/usr/sbin/sshd".to_string()),
+            }
+        );
+
+        // A stopped oneshot with two commands, and an unknown unit
+        let output = "ExecStart={ path=/usr/bin/true ; argv[]=/usr/bin/true ; ignore_errors=no } { path=/usr/bin/false ; argv[]=/usr/bin/false ; ignore_errors=yes }\nMainPID=0\n";
+        assert_eq!(
+            IntegrityCollector::parse_systemctl_show(output),
+            SystemdUnit {
+                main_pid: None,
+                exec_path: Some("/usr/bin/true".to_string()),
+            }
+        );
+        assert_eq!(
+            IntegrityCollector::parse_systemctl_show("MainPID=0\nExecStart=\n"),
+            SystemdUnit {
+                main_pid: None,
+                exec_path: None,
+            }
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_verify_systemd_unit() {
+        let exe = std::env::current_exe().unwrap();
+        let exe = exe.to_string_lossy();
+
+        // A running unit is checked through its main process
+        let running = SystemdUnit {
+            main_pid: Some(std::process::id()),
+            exec_path: Some("/nonexistent/daemon".to_string()),
+        };
+        let integrity = IntegrityCollector::verify_systemd_unit("test.service", running).unwrap();
+        assert_eq!(integrity.path, exe);
+
+        let stopped = SystemdUnit {
+            main_pid: None,
+            exec_path: Some(exe.to_string()),
+        };
+        let integrity = IntegrityCollector::verify_systemd_unit("test.service", stopped).unwrap();
+        assert_eq!(integrity.path, exe);
+
+        let unknown = SystemdUnit {
+            main_pid: None,
+            exec_path: None,
+        };
+        assert_eq!(
+            IntegrityCollector::verify_systemd_unit("missing.service", unknown).unwrap_err(),
+            IntegrityError::NoExecutable {
+                target: "service missing.service".to_string()
+            }
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_verify_own_process() {
+        let exe = std::env::current_exe().unwrap();
+        let integrity = IntegrityCollector::verify_process(std::process::id(), None).unwrap();
+        assert_eq!(integrity.path, exe.to_string_lossy());
+        assert!(!integrity.deleted);
+        assert_eq!(
+            Some((integrity.sha256, integrity.size_bytes)),
+            IntegrityCollector::file_digest(&exe, DigestAlgorithm::Sha256).unwrap()
+        );
+    }
+}
@@ -0,0 +1,281 @@
+//! Network link table (Linux)
+//!
+//! Dumps interfaces over rtnetlink, including the driver-level link kind
+//! (wireguard, tun, bridge, vlan, ...) that sysinfo and /sys do not expose directly.
+//! Other network namespaces are dumped from a helper thread that joins them.
+
+use crate::collectors::netlink::{push_attribute, Attributes, NetlinkSocket, NLM_F_DUMP};
+use crate::collectors::network::format_mac_address;
+use std::collections::HashMap;
+use std::os::fd::AsRawFd;
+use std::path::{Path, PathBuf};
+
+// rtnetlink link messages (linux/rtnetlink.h, linux/if_link.h)
+const RTM_NEWLINK: u16 = 16;
+const RTM_GETLINK: u16 = 18;
+const IFINFOMSG_LEN: usize = 16;
+const IFLA_ADDRESS: u16 = 1;
+const IFLA_IFNAME: u16 = 3;
+const IFLA_MTU: u16 = 4;
+const IFLA_LINK: u16 = 5;
+const IFLA_MASTER: u16 = 10;
+const IFLA_LINKINFO: u16 = 18;
+const IFLA_LINK_NETNSID: u16 = 37;
+const IFLA_INFO_KIND: u16 = 1;
+const IFLA_INFO_DATA: u16 = 2;
+const IFLA_VLAN_ID: u16 = 1;
+const IFLA_BOND_MODE: u16 = 1;
+const IFF_UP: u32 = 0x1;
+const IFF_LOOPBACK: u32 = 0x8;
+const IFF_LOWER_UP: u32 = 0x10000;
+
+// Namespace ids (linux/net_namespace.h)
+const RTM_NEWNSID: u16 = 88;
+const RTM_GETNSID: u16 = 90;
+const RTGENMSG_LEN: usize = 4;
+const NETNSA_NSID: u16 = 1;
+const NETNSA_FD: u16 = 3;
+
+/// An interface as reported by RTM_GETLINK
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct LinkInfo {
+    pub index: u32,
+    pub name: String,
+    /// Link kind from IFLA_INFO_KIND; None for physical devices
+    pub kind: Option<String>,
+    pub is_up: bool,
+    /// The physical layer reports carrier (cable plugged in, associated, ...)
+    pub has_carrier: bool,
+    pub is_loopback: bool,
+    pub mac_address: Option<String>,
+    pub mtu: Option<u32>,
+    /// Index of the bridge, bond or VRF this interface is enslaved to
+    pub master: Option<u32>,
+    /// Lower device (VLAN/macvlan parent) or veth peer index
+    pub link: Option<u32>,
+    /// Namespace id of `link` when it lives in another network namespace
+    pub link_netnsid: Option<i32>,
+    pub vlan_id: Option<u16>,
+    pub bond_mode: Option<u8>,
+}
+
+/// Dump every interface in the current network namespace
+pub(crate) fn dump_links() -> std::io::Result<Vec<LinkInfo>> {
+    let mut socket = NetlinkSocket::open(libc::NETLINK_ROUTE)?;
+
+    // struct ifinfomsg with only the family set
+    let mut request = [0u8; IFINFOMSG_LEN];
+    request[0] = libc::AF_UNSPEC as u8;
+
+    let replies = socket.request(RTM_GETLINK, NLM_F_DUMP, &request)?;
+    Ok(replies
+        .iter()
+        .filter(|m| m.msg_type == RTM_NEWLINK)
+        .filter_map(|m| parse_link_message(&m.payload))
+        .collect())
+}
+
+/// Parse an RTM_NEWLINK payload (struct ifinfomsg followed by IFLA_* attributes)
+pub(crate) fn parse_link_message(payload: &[u8]) -> Option<LinkInfo> {
+    let header = payload.get(..IFINFOMSG_LEN)?;
+    let index = i32::from_ne_bytes([header[4], header[5], header[6], header[7]]) as u32;
+    let flags = u32::from_ne_bytes([header[8], header[9], header[10], header[11]]);
+
+    let attrs = Attributes::parse(&payload[IFINFOMSG_LEN..]);
+    let link_info = attrs.get_nested(IFLA_LINKINFO);
+    let kind = link_info
+        .as_ref()
+        .and_then(|info| info.get_string(IFLA_INFO_KIND));
+    let info_data = link_info.and_then(|info| info.get_nested(IFLA_INFO_DATA));
+
+    Some(LinkInfo {
+        index,
+        name: attrs.get_string(IFLA_IFNAME)?,
+        is_up: flags & IFF_UP != 0,
+        has_carrier: flags & IFF_LOWER_UP != 0,
+        is_loopback: flags & IFF_LOOPBACK != 0,
+        mac_address: attrs
+            .get(IFLA_ADDRESS)
+            .filter(|a| a.len() == 6 && a.iter().any(|&b| b != 0))
+            .map(format_mac_address),
+        mtu: attrs.get_u32(IFLA_MTU),
+        master: attrs.get_u32(IFLA_MASTER).filter(|&i| i != 0),
+        link: attrs.get_u32(IFLA_LINK).filter(|&i| i != 0),
+        link_netnsid: attrs.get_u32(IFLA_LINK_NETNSID).map(|id| id as i32),
+        vlan_id: info_data
+            .as_ref()
+            .filter(|_| kind.as_deref() == Some("vlan"))
+            .and_then(|data| data.get_u16(IFLA_VLAN_ID)),
+        bond_mode: info_data
+            .as_ref()
+            .filter(|_| kind.as_deref() == Some("bond"))
+            .and_then(|data| data.get_u8(IFLA_BOND_MODE)),
+        kind,
+    })
+}
+
+/// Dump the interfaces of the network namespace behind `namespace` (a /proc/<pid>/ns/net
+/// or /run/netns/<name> file), along with the ids that namespace assigned to each of
+/// `peers`. Joining a namespace requires CAP_SYS_ADMIN.
+pub(crate) fn dump_links_in(
+    namespace: &Path,
+    peers: &[(u64, PathBuf)],
+) -> std::io::Result<(Vec<LinkInfo>, HashMap<i32, u64>)> {
+    let namespace = std::fs::File::open(namespace)?;
+    let peers = peers.to_vec();
+
+    // setns only affects the calling thread, so the rest of the process stays put
+    std::thread::spawn(move || {
+        if unsafe { libc::setns(namespace.as_raw_fd(), libc::CLONE_NEWNET) } != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok((dump_links()?, namespace_ids(&peers)))
+    })
+    .join()
+    .map_err(|_| std::io::Error::other("namespace thread panicked"))?
+}
+
+/// Map the ids the current network namespace uses for `namespaces` (as referenced by
+/// IFLA_LINK_NETNSID) to their inodes. Namespaces without an assigned id are skipped.
+pub(crate) fn namespace_ids(namespaces: &[(u64, PathBuf)]) -> HashMap<i32, u64> {
+    let Ok(mut socket) = NetlinkSocket::open(libc::NETLINK_ROUTE) else {
+        return HashMap::new();
+    };
+
+    namespaces
+        .iter()
+        .filter_map(|(inode, path)| {
+            let file = std::fs::File::open(path).ok()?;
+
+            // struct rtgenmsg (padded) followed by NETNSA_FD
+            let mut request = vec![0u8; RTGENMSG_LEN];
+            request[0] = libc::AF_UNSPEC as u8;
+            push_attribute(
+                &mut request,
+                NETNSA_FD,
+                &(file.as_raw_fd() as u32).to_ne_bytes(),
+            );
+
+            let replies = socket.request(RTM_GETNSID, 0, &request).ok()?;
+            let nsid = replies
+                .iter()
+                .filter(|m| m.msg_type == RTM_NEWNSID)
+                .find_map(|m| {
+                    Attributes::parse(m.payload.get(RTGENMSG_LEN..)?).get_u32(NETNSA_NSID)
+                })? as i32;
+            (nsid >= 0).then_some((nsid, *inode))
+        })
+        .collect()
+}
+
+/// Bonding mode name for an IFLA_BOND_MODE value
+pub(crate) fn bond_mode_name(mode: u8) -> Option<&'static str> {
+    match mode {
+        0 => Some("balance-rr"),
+        1 => Some("active-backup"),
+        2 => Some("balance-xor"),
+        3 => Some("broadcast"),
+        4 => Some("802.3ad"),
+        5 => Some("balance-tlb"),
+        6 => Some("balance-alb"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_link_message() {
+        // ifinfomsg for ifindex 5 (IFF_UP) with IFLA_IFNAME "wg0" and IFLA_LINKINFO/KIND "wireguard"
+        let mut payload = vec![0u8; IFINFOMSG_LEN];
+        payload[2..4].copy_from_slice(&65534u16.to_ne_bytes());
+        payload[4..8].copy_from_slice(&5i32.to_ne_bytes());
+        payload[8..12].copy_from_slice(&(IFF_UP | 0x1000).to_ne_bytes());
+        push_attribute(&mut payload, IFLA_IFNAME, b"wg0\0");
+        let mut link_info = Vec::new();
+        push_attribute(&mut link_info, IFLA_INFO_KIND, b"wireguard\0");
+        push_attribute(&mut payload, IFLA_LINKINFO, &link_info);
+
+        let link = parse_link_message(&payload).unwrap();
+        assert_eq!(link.index, 5);
+        assert_eq!(link.name, "wg0");
+        assert_eq!(link.kind.as_deref(), Some("wireguard"));
+        assert!(link.is_up);
+        assert!(!link.is_loopback);
+        assert_eq!(link.master, None);
+    }
+
+    #[test]
+    fn test_parse_vlan_and_veth_links() {
+        let header = |index: i32| {
+            let mut payload = vec![0u8; IFINFOMSG_LEN];
+            payload[4..8].copy_from_slice(&index.to_ne_bytes());
+            payload
+        };
+        let link_info = |kind: &[u8], data: &[u8]| {
+            let mut info = Vec::new();
+            push_attribute(&mut info, IFLA_INFO_KIND, kind);
+            push_attribute(&mut info, IFLA_INFO_DATA, data);
+            info
+        };
+
+        // eth0.100 on top of ifindex 2, enslaved to bridge ifindex 7
+        let mut vlan_data = Vec::new();
+        push_attribute(&mut vlan_data, IFLA_VLAN_ID, &100u16.to_ne_bytes());
+        let mut payload = header(8);
+        push_attribute(&mut payload, IFLA_IFNAME, b"eth0.100\0");
+        push_attribute(
+            &mut payload,
+            IFLA_ADDRESS,
+            &[0x52, 0x54, 0x00, 0x12, 0x34, 0x56],
+        );
+        push_attribute(&mut payload, IFLA_MTU, &1500u32.to_ne_bytes());
+        push_attribute(&mut payload, IFLA_LINK, &2u32.to_ne_bytes());
+        push_attribute(&mut payload, IFLA_MASTER, &7u32.to_ne_bytes());
+        push_attribute(
+            &mut payload,
+            IFLA_LINKINFO,
+            &link_info(b"vlan\0", &vlan_data),
+        );
+
+        let vlan = parse_link_message(&payload).unwrap();
+        assert_eq!(vlan.vlan_id, Some(100));
+        assert_eq!(vlan.link, Some(2));
+        assert_eq!(vlan.master, Some(7));
+        assert_eq!(vlan.mtu, Some(1500));
+        assert_eq!(vlan.mac_address.as_deref(), Some("52:54:00:12:34:56"));
+
+        // veth whose peer (ifindex 4) lives in the namespace with id 0
+        let mut payload = header(9);
+        push_attribute(&mut payload, IFLA_IFNAME, b"veth1a2b\0");
+        push_attribute(&mut payload, IFLA_LINK, &4u32.to_ne_bytes());
+        push_attribute(&mut payload, IFLA_LINK_NETNSID, &0u32.to_ne_bytes());
+        push_attribute(&mut payload, IFLA_LINKINFO, &link_info(b"veth\0", &[]));
+
+        let veth = parse_link_message(&payload).unwrap();
+        assert_eq!(veth.kind.as_deref(), Some("veth"));
+        assert_eq!((veth.link, veth.link_netnsid), (Some(4), Some(0)));
+        assert_eq!(veth.vlan_id, None);
+        assert_eq!(veth.mac_address, None);
+    }
+
+    #[test]
+    fn test_dump_links() {
+        // Every namespace has a loopback device
+        if let Ok(links) = dump_links() {
+            assert!(links
+                .iter()
+                .any(|l| l.name == "lo" && l.kind.is_none() && l.is_loopback));
+        }
+    }
+
+    #[test]
+    fn test_dump_links_in_current_namespace() {
+        // Re-entering our own namespace is allowed with CAP_SYS_ADMIN; skip otherwise
+        if let Ok((links, _)) = dump_links_in(Path::new("/proc/self/ns/net"), &[]) {
+            assert!(links.iter().any(|l| l.is_loopback));
+        }
+    }
+}
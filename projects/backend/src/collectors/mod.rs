@@ -2,16 +2,40 @@
 //!
 //! Each collector module provides functions to gather specific types of system data.
 
+pub mod cgroup;
+pub mod container;
+#[cfg(target_os = "linux")]
+pub(crate) mod dhcp;
 pub mod hardware;
+pub mod integrity;
+#[cfg(target_os = "linux")]
+pub(crate) mod links;
+pub mod neighbors;
+#[cfg(target_os = "linux")]
+pub(crate) mod netlink;
 pub mod network;
+pub mod network_topology;
 pub mod process;
 pub mod service;
+pub mod sockets;
 pub mod storage;
+pub mod storage_topology;
 pub mod system;
+pub mod vpn;
+pub mod wireless;
 
+pub use cgroup::CgroupCollector;
+pub use container::ContainerCollector;
 pub use hardware::HardwareCollector;
+pub use integrity::IntegrityCollector;
+pub use neighbors::NeighborCollector;
 pub use network::NetworkCollector;
+pub use network_topology::NetworkTopologyCollector;
 pub use process::ProcessCollector;
 pub use service::ServiceCollector;
+pub use sockets::SocketCollector;
 pub use storage::StorageCollector;
+pub use storage_topology::StorageTopologyCollector;
 pub use system::SystemCollector;
+pub use vpn::VpnCollector;
+pub use wireless::WirelessCollector;
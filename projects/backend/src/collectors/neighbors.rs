@@ -0,0 +1,273 @@
+//! Neighbor (ARP/NDP) table collector
+//!
+//! Reads the kernel neighbor cache and resolves each MAC address to a hardware vendor.
+//! IPv4 entries come from /proc/net/arp; IPv6 entries are dumped over rtnetlink.
+
+use crate::hwids::OuiDatabase;
+use crate::models::NeighborEntry;
+
+#[cfg(target_os = "linux")]
+use crate::collectors::netlink::{Attributes, NetlinkSocket, NLM_F_DUMP};
+#[cfg(target_os = "linux")]
+use crate::collectors::network::format_mac_address;
+#[cfg(target_os = "linux")]
+use crate::models::NeighborState;
+#[cfg(target_os = "linux")]
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+// rtnetlink neighbor messages (linux/neighbour.h)
+#[cfg(target_os = "linux")]
+const RTM_NEWNEIGH: u16 = 28;
+#[cfg(target_os = "linux")]
+const RTM_GETNEIGH: u16 = 30;
+#[cfg(target_os = "linux")]
+const NDMSG_LEN: usize = 12;
+#[cfg(target_os = "linux")]
+const NDA_DST: u16 = 1;
+#[cfg(target_os = "linux")]
+const NDA_LLADDR: u16 = 2;
+#[cfg(target_os = "linux")]
+const NTF_ROUTER: u8 = 0x80;
+
+// ARP flags (linux/if_arp.h)
+#[cfg(target_os = "linux")]
+const ATF_COM: u32 = 0x02;
+#[cfg(target_os = "linux")]
+const ATF_PERM: u32 = 0x04;
+
+/// Collector for the neighbor cache
+pub struct NeighborCollector;
+
+impl NeighborCollector {
+    /// Get all IPv4 and IPv6 neighbor entries
+    pub fn get_neighbors() -> Vec<NeighborEntry> {
+        #[cfg(target_os = "linux")]
+        let mut neighbors = {
+            let mut neighbors = std::fs::read_to_string("/proc/net/arp")
+                .map(|content| Self::parse_proc_net_arp(&content))
+                .unwrap_or_default();
+
+            match Self::get_ipv6_neighbors() {
+                Ok(ipv6) => neighbors.extend(ipv6),
+                Err(e) => log::warn!("Failed to dump IPv6 neighbors: {}", e),
+            }
+            neighbors
+        };
+
+        #[cfg(not(target_os = "linux"))]
+        let mut neighbors: Vec<NeighborEntry> = Vec::new();
+
+        let oui = OuiDatabase::global();
+        for neighbor in &mut neighbors {
+            Self::resolve_vendor(neighbor, oui);
+        }
+
+        neighbors
+    }
+
+    /// Fill in the vendor for a neighbor's MAC address.
+    /// Locally administered addresses are not registered with the IEEE, so they get no vendor.
+    fn resolve_vendor(neighbor: &mut NeighborEntry, oui: &OuiDatabase) {
+        let Some(mac) = neighbor.mac_address.as_deref() else {
+            return;
+        };
+
+        let first_octet = mac
+            .get(..2)
+            .and_then(|b| u8::from_str_radix(b, 16).ok())
+            .unwrap_or(0);
+        neighbor.is_locally_administered = first_octet & 0x02 != 0;
+
+        if !neighbor.is_locally_administered {
+            neighbor.vendor = oui.lookup_mac(mac).map(|v| v.to_string());
+        }
+    }
+
+    /// Parse /proc/net/arp. It only distinguishes resolved, permanent and unresolved
+    /// entries, so resolved entries are reported as reachable.
+    #[cfg(target_os = "linux")]
+    fn parse_proc_net_arp(content: &str) -> Vec<NeighborEntry> {
+        content
+            .lines()
+            .skip(1)
+            .filter_map(|line| {
+                let fields: Vec<&str> = line.split_whitespace().collect();
+                if fields.len() < 6 {
+                    return None;
+                }
+
+                let flags = u32::from_str_radix(fields[2].trim_start_matches("0x"), 16).ok()?;
+                let state = if flags & ATF_PERM != 0 {
+                    NeighborState::Permanent
+                } else if flags & ATF_COM != 0 {
+                    NeighborState::Reachable
+                } else {
+                    NeighborState::Incomplete
+                };
+                let mac_address = Some(fields[3].to_uppercase())
+                    .filter(|_| state != NeighborState::Incomplete)
+                    .filter(|mac| mac != "00:00:00:00:00:00");
+
+                Some(NeighborEntry {
+                    ip_address: fields[0].to_string(),
+                    mac_address,
+                    interface: fields[5].to_string(),
+                    state,
+                    vendor: None,
+                    is_locally_administered: false,
+                    is_router: false,
+                })
+            })
+            .collect()
+    }
+
+    #[cfg(target_os = "linux")]
+    fn get_ipv6_neighbors() -> std::io::Result<Vec<NeighborEntry>> {
+        let mut socket = NetlinkSocket::open(libc::NETLINK_ROUTE)?;
+
+        // struct ndmsg with only the family set
+        let mut request = [0u8; NDMSG_LEN];
+        request[0] = libc::AF_INET6 as u8;
+
+        let replies = socket.request(RTM_GETNEIGH, NLM_F_DUMP, &request)?;
+        Ok(replies
+            .iter()
+            .filter(|m| m.msg_type == RTM_NEWNEIGH)
+            .filter_map(|m| Self::parse_neighbor_message(&m.payload, Self::interface_name))
+            .collect())
+    }
+
+    /// Parse an RTM_NEWNEIGH payload (struct ndmsg followed by NDA_* attributes)
+    #[cfg(target_os = "linux")]
+    fn parse_neighbor_message(
+        payload: &[u8],
+        interface_name: impl Fn(u32) -> String,
+    ) -> Option<NeighborEntry> {
+        let header = payload.get(..NDMSG_LEN)?;
+        let family = header[0] as i32;
+        let ifindex = i32::from_ne_bytes([header[4], header[5], header[6], header[7]]) as u32;
+        let state = u16::from_ne_bytes([header[8], header[9]]);
+        let flags = header[10];
+
+        let attrs = Attributes::parse(&payload[NDMSG_LEN..]);
+        let dst = attrs.get(NDA_DST)?;
+        let ip_address = match family {
+            libc::AF_INET6 => Ipv6Addr::from(<[u8; 16]>::try_from(dst).ok()?).to_string(),
+            libc::AF_INET => Ipv4Addr::from(<[u8; 4]>::try_from(dst).ok()?).to_string(),
+            _ => return None,
+        };
+
+        Some(NeighborEntry {
+            ip_address,
+            mac_address: attrs
+                .get(NDA_LLADDR)
+                .filter(|mac| !mac.is_empty())
+                .map(format_mac_address),
+            interface: interface_name(ifindex),
+            state: Self::nud_state(state),
+            vendor: None,
+            is_locally_administered: false,
+            is_router: flags & NTF_ROUTER != 0,
+        })
+    }
+
+    /// Map NUD_* state bits (linux/neighbour.h) to a neighbor state
+    #[cfg(target_os = "linux")]
+    fn nud_state(state: u16) -> NeighborState {
+        match state {
+            0x01 => NeighborState::Incomplete,
+            0x02 => NeighborState::Reachable,
+            0x04 => NeighborState::Stale,
+            0x08 => NeighborState::Delay,
+            0x10 => NeighborState::Probe,
+            0x20 => NeighborState::Failed,
+            0x40 => NeighborState::NoArp,
+            0x80 => NeighborState::Permanent,
+            _ => NeighborState::Unknown,
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn interface_name(ifindex: u32) -> String {
+        let mut buf = [0 as libc::c_char; libc::IF_NAMESIZE];
+        let name = unsafe { libc::if_indextoname(ifindex, buf.as_mut_ptr()) };
+        if name.is_null() {
+            return ifindex.to_string();
+        }
+        unsafe { std::ffi::CStr::from_ptr(name) }
+            .to_string_lossy()
+            .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_neighbors() {
+        // The neighbor cache may be empty in a test environment
+        for neighbor in NeighborCollector::get_neighbors() {
+            assert!(!neighbor.ip_address.is_empty());
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_proc_net_arp() {
+        let content = "\
+IP address       HW type     Flags       HW address            Mask     Device
+192.168.1.1      0x1         0x2         00:50:56:c0:00:08     *        eth0
+192.168.1.23     0x1         0x0         00:00:00:00:00:00     *        eth0
+192.168.1.50     0x1         0x6         da:a1:19:00:11:22     *        wlan0
+";
+        let mut neighbors = NeighborCollector::parse_proc_net_arp(content);
+        assert_eq!(neighbors.len(), 3);
+
+        let mut oui = OuiDatabase::new();
+        oui.add_vendor(0x005056, "VMware, Inc.");
+        oui.add_vendor(0xDAA119, "Should Not Match");
+        for neighbor in &mut neighbors {
+            NeighborCollector::resolve_vendor(neighbor, &oui);
+        }
+
+        assert_eq!(neighbors[0].state, NeighborState::Reachable);
+        assert_eq!(
+            neighbors[0].mac_address.as_deref(),
+            Some("00:50:56:C0:00:08")
+        );
+        assert_eq!(neighbors[0].vendor.as_deref(), Some("VMware, Inc."));
+
+        assert_eq!(neighbors[1].state, NeighborState::Incomplete);
+        assert_eq!(neighbors[1].mac_address, None);
+
+        // Randomized MACs have the locally administered bit set and no registered vendor
+        assert_eq!(neighbors[2].state, NeighborState::Permanent);
+        assert!(neighbors[2].is_locally_administered);
+        assert_eq!(neighbors[2].vendor, None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_recorded_neighbor_message() {
+        // RTM_NEWNEIGH payload: fe80::1 on ifindex 2, NUD_STALE, NTF_ROUTER
+        let payload: Vec<u8> = [
+            "0a000000020000000400800114000100fe800000000000000000000000000001",
+            "0a0002003c0754aabbcc0000080004000100000014000300b00400002c010000",
+            "c8af000002000000",
+        ]
+        .concat()
+        .as_bytes()
+        .chunks(2)
+        .map(|pair| u8::from_str_radix(std::str::from_utf8(pair).unwrap(), 16).unwrap())
+        .collect();
+
+        let entry =
+            NeighborCollector::parse_neighbor_message(&payload, |i| format!("if{}", i)).unwrap();
+        assert_eq!(entry.ip_address, "fe80::1");
+        assert_eq!(entry.mac_address.as_deref(), Some("3C:07:54:AA:BB:CC"));
+        assert_eq!(entry.interface, "if2");
+        assert_eq!(entry.state, NeighborState::Stale);
+        assert!(entry.is_router);
+    }
+}
@@ -0,0 +1,451 @@
+//! Minimal netlink socket and message helpers (Linux)
+//!
+//! Shared by the collectors that query the kernel over netlink rather than parsing /proc text.
+
+use std::io;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+use std::time::Duration;
+
+pub(crate) const NLMSG_ERROR: u16 = 2;
+pub(crate) const NLMSG_DONE: u16 = 3;
+
+pub(crate) const NLM_F_REQUEST: u16 = 0x1;
+pub(crate) const NLM_F_MULTI: u16 = 0x2;
+pub(crate) const NLM_F_ACK: u16 = 0x4;
+pub(crate) const NLM_F_DUMP: u16 = 0x300;
+
+const NLMSG_HDRLEN: usize = 16;
+const NLA_HDRLEN: usize = 4;
+/// Strips NLA_F_NESTED and NLA_F_NET_BYTEORDER from attribute types
+const NLA_TYPE_MASK: u16 = 0x3fff;
+
+// Generic netlink controller
+const GENL_HDRLEN: usize = 4;
+const GENL_ID_CTRL: u16 = 0x10;
+const CTRL_CMD_GETFAMILY: u8 = 3;
+const CTRL_ATTR_FAMILY_ID: u16 = 1;
+const CTRL_ATTR_FAMILY_NAME: u16 = 2;
+
+/// How long to wait for a kernel reply before giving up
+const RECV_TIMEOUT: Duration = Duration::from_secs(2);
+const RECV_BUFFER_SIZE: usize = 64 * 1024;
+/// Kernel-side queue for multicast subscriptions, so bursts are not dropped
+const SUBSCRIBE_SOCKET_BUFFER: libc::c_int = 1024 * 1024;
+
+fn align(len: usize) -> usize {
+    (len + 3) & !3
+}
+
+/// A single netlink message with its header fields split out
+#[derive(Debug, Clone)]
+pub(crate) struct NetlinkMessage {
+    pub msg_type: u16,
+    pub flags: u16,
+    pub seq: u32,
+    pub payload: Vec<u8>,
+}
+
+impl NetlinkMessage {
+    /// Payload of a generic netlink message with the genlmsghdr removed
+    pub fn genl_payload(&self) -> &[u8] {
+        self.payload.get(GENL_HDRLEN..).unwrap_or(&[])
+    }
+
+    /// Error code carried by an NLMSG_ERROR message (0 for an acknowledgement)
+    fn error_code(&self) -> i32 {
+        self.payload
+            .get(..4)
+            .map(|b| i32::from_ne_bytes([b[0], b[1], b[2], b[3]]))
+            .unwrap_or(0)
+    }
+}
+
+/// Split a receive buffer into the netlink messages it contains
+pub(crate) fn parse_messages(buf: &[u8]) -> Vec<NetlinkMessage> {
+    let mut messages = Vec::new();
+    let mut offset = 0;
+
+    while offset + NLMSG_HDRLEN <= buf.len() {
+        let header = &buf[offset..offset + NLMSG_HDRLEN];
+        let len = u32::from_ne_bytes([header[0], header[1], header[2], header[3]]) as usize;
+        if len < NLMSG_HDRLEN || offset + len > buf.len() {
+            break;
+        }
+
+        messages.push(NetlinkMessage {
+            msg_type: u16::from_ne_bytes([header[4], header[5]]),
+            flags: u16::from_ne_bytes([header[6], header[7]]),
+            seq: u32::from_ne_bytes([header[8], header[9], header[10], header[11]]),
+            payload: buf[offset + NLMSG_HDRLEN..offset + len].to_vec(),
+        });
+        offset += align(len);
+    }
+
+    messages
+}
+
+/// Append a netlink attribute (header, data and padding) to a buffer
+pub(crate) fn push_attribute(buf: &mut Vec<u8>, kind: u16, data: &[u8]) {
+    let len = NLA_HDRLEN + data.len();
+    buf.extend_from_slice(&(len as u16).to_ne_bytes());
+    buf.extend_from_slice(&kind.to_ne_bytes());
+    buf.extend_from_slice(data);
+    buf.resize(buf.len() + align(len) - len, 0);
+}
+
+/// Build a generic netlink payload: genlmsghdr followed by attributes
+pub(crate) fn genl_message(cmd: u8, attributes: &[u8]) -> Vec<u8> {
+    let mut payload = vec![cmd, 1, 0, 0];
+    payload.extend_from_slice(attributes);
+    payload
+}
+
+/// Parsed netlink attributes in the order they appeared
+pub(crate) struct Attributes<'a> {
+    entries: Vec<(u16, &'a [u8])>,
+}
+
+impl<'a> Attributes<'a> {
+    pub fn parse(data: &'a [u8]) -> Self {
+        let mut entries = Vec::new();
+        let mut offset = 0;
+
+        while offset + NLA_HDRLEN <= data.len() {
+            let len = u16::from_ne_bytes([data[offset], data[offset + 1]]) as usize;
+            let kind = u16::from_ne_bytes([data[offset + 2], data[offset + 3]]) & NLA_TYPE_MASK;
+            if len < NLA_HDRLEN || offset + len > data.len() {
+                break;
+            }
+            entries.push((kind, &data[offset + NLA_HDRLEN..offset + len]));
+            offset += align(len);
+        }
+
+        Self { entries }
+    }
+
+    pub fn get(&self, kind: u16) -> Option<&'a [u8]> {
+        self.entries
+            .iter()
+            .find(|(k, _)| *k == kind)
+            .map(|(_, data)| *data)
+    }
+
+    pub fn get_u8(&self, kind: u16) -> Option<u8> {
+        self.get(kind)?.first().copied()
+    }
+
+    pub fn get_u16(&self, kind: u16) -> Option<u16> {
+        let data = self.get(kind)?.get(..2)?;
+        Some(u16::from_ne_bytes([data[0], data[1]]))
+    }
+
+    pub fn get_u32(&self, kind: u16) -> Option<u32> {
+        let data = self.get(kind)?.get(..4)?;
+        Some(u32::from_ne_bytes([data[0], data[1], data[2], data[3]]))
+    }
+
+    pub fn get_u64(&self, kind: u16) -> Option<u64> {
+        let data = self.get(kind)?.get(..8)?;
+        Some(u64::from_ne_bytes(data.try_into().ok()?))
+    }
+
+    /// NUL-terminated string attribute
+    pub fn get_string(&self, kind: u16) -> Option<String> {
+        let data = self.get(kind)?;
+        let end = data.iter().position(|&b| b == 0).unwrap_or(data.len());
+        Some(String::from_utf8_lossy(&data[..end]).to_string())
+    }
+
+    pub fn get_nested(&self, kind: u16) -> Option<Attributes<'a>> {
+        self.get(kind).map(Attributes::parse)
+    }
+
+    /// All attributes in order, e.g. the indexed elements of a nested array
+    pub fn iter(&self) -> impl Iterator<Item = (u16, &'a [u8])> + '_ {
+        self.entries.iter().copied()
+    }
+}
+
+/// A blocking netlink socket for request/response exchanges with the kernel
+pub(crate) struct NetlinkSocket {
+    fd: OwnedFd,
+    seq: u32,
+}
+
+impl NetlinkSocket {
+    /// Open a netlink socket for the given protocol (e.g. NETLINK_GENERIC)
+    pub fn open(protocol: libc::c_int) -> io::Result<Self> {
+        let fd = unsafe {
+            libc::socket(
+                libc::AF_NETLINK,
+                libc::SOCK_RAW | libc::SOCK_CLOEXEC,
+                protocol,
+            )
+        };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+
+        let timeout = libc::timeval {
+            tv_sec: RECV_TIMEOUT.as_secs() as libc::time_t,
+            tv_usec: 0,
+        };
+        let result = unsafe {
+            libc::setsockopt(
+                fd.as_raw_fd(),
+                libc::SOL_SOCKET,
+                libc::SO_RCVTIMEO,
+                &timeout as *const libc::timeval as *const libc::c_void,
+                std::mem::size_of::<libc::timeval>() as libc::socklen_t,
+            )
+        };
+        if result < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(Self { fd, seq: 0 })
+    }
+
+    /// Open a socket that receives kernel notifications for the given multicast
+    /// groups (e.g. RTMGRP_LINK | RTMGRP_IPV4_IFADDR)
+    pub fn subscribe(protocol: libc::c_int, groups: u32) -> io::Result<Self> {
+        let socket = Self::open(protocol)?;
+
+        let size = SUBSCRIBE_SOCKET_BUFFER;
+        let result = unsafe {
+            libc::setsockopt(
+                socket.fd.as_raw_fd(),
+                libc::SOL_SOCKET,
+                libc::SO_RCVBUF,
+                &size as *const libc::c_int as *const libc::c_void,
+                std::mem::size_of::<libc::c_int>() as libc::socklen_t,
+            )
+        };
+        // The default queue still works; bursts are just more likely to overflow it,
+        // which receive() reports as ENOBUFS
+        if result < 0 {
+            log::warn!(
+                "Failed to enlarge netlink receive buffer: {}",
+                io::Error::last_os_error()
+            );
+        }
+
+        let mut address: libc::sockaddr_nl = unsafe { std::mem::zeroed() };
+        address.nl_family = libc::AF_NETLINK as libc::sa_family_t;
+        address.nl_groups = groups;
+        let result = unsafe {
+            libc::bind(
+                socket.fd.as_raw_fd(),
+                &address as *const libc::sockaddr_nl as *const libc::sockaddr,
+                std::mem::size_of::<libc::sockaddr_nl>() as libc::socklen_t,
+            )
+        };
+        if result < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(socket)
+    }
+
+    /// Wait up to `timeout` for the next batch of notifications on a subscribed socket.
+    /// Returns an empty list when nothing arrived; fails with ENOBUFS when the kernel
+    /// had to drop notifications because the socket queue overflowed.
+    pub fn receive(&mut self, timeout: Duration) -> io::Result<Vec<NetlinkMessage>> {
+        Ok(self
+            .receive_raw(timeout)?
+            .map(|datagram| parse_messages(&datagram))
+            .unwrap_or_default())
+    }
+
+    /// Wait up to `timeout` for the next datagram without interpreting it. Protocols
+    /// such as NETLINK_KOBJECT_UEVENT don't frame their payload in netlink headers.
+    pub fn receive_raw(&mut self, timeout: Duration) -> io::Result<Option<Vec<u8>>> {
+        let mut pollfd = libc::pollfd {
+            fd: self.fd.as_raw_fd(),
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        let ready = unsafe { libc::poll(&mut pollfd, 1, timeout.as_millis() as libc::c_int) };
+        if ready < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        if ready == 0 {
+            return Ok(None);
+        }
+
+        let mut buf = vec![0u8; RECV_BUFFER_SIZE];
+        let received = unsafe {
+            libc::recv(
+                self.fd.as_raw_fd(),
+                buf.as_mut_ptr() as *mut libc::c_void,
+                buf.len(),
+                libc::MSG_DONTWAIT,
+            )
+        };
+        if received < 0 {
+            let error = io::Error::last_os_error();
+            return match error.kind() {
+                io::ErrorKind::WouldBlock => Ok(None),
+                _ => Err(error),
+            };
+        }
+        buf.truncate(received as usize);
+        Ok(Some(buf))
+    }
+
+    /// Send a single message without waiting for a reply; returns its sequence number.
+    /// Used for protocols that answer with notifications, such as the proc connector.
+    pub fn send(&mut self, msg_type: u16, flags: u16, payload: &[u8]) -> io::Result<u32> {
+        self.seq = self.seq.wrapping_add(1);
+        let seq = self.seq;
+
+        let len = NLMSG_HDRLEN + payload.len();
+        let mut message = Vec::with_capacity(align(len));
+        message.extend_from_slice(&(len as u32).to_ne_bytes());
+        message.extend_from_slice(&msg_type.to_ne_bytes());
+        message.extend_from_slice(&flags.to_ne_bytes());
+        message.extend_from_slice(&seq.to_ne_bytes());
+        message.extend_from_slice(&0u32.to_ne_bytes());
+        message.extend_from_slice(payload);
+
+        let sent = unsafe {
+            libc::send(
+                self.fd.as_raw_fd(),
+                message.as_ptr() as *const libc::c_void,
+                message.len(),
+                0,
+            )
+        };
+        if sent < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(seq)
+    }
+
+    /// Send a request and collect every reply message up to NLMSG_DONE (for dumps)
+    /// or the first reply/acknowledgement (for single requests)
+    pub fn request(
+        &mut self,
+        msg_type: u16,
+        flags: u16,
+        payload: &[u8],
+    ) -> io::Result<Vec<NetlinkMessage>> {
+        let seq = self.send(msg_type, flags | NLM_F_REQUEST, payload)?;
+
+        let mut replies = Vec::new();
+        let mut buf = vec![0u8; RECV_BUFFER_SIZE];
+
+        loop {
+            let received = unsafe {
+                libc::recv(
+                    self.fd.as_raw_fd(),
+                    buf.as_mut_ptr() as *mut libc::c_void,
+                    buf.len(),
+                    0,
+                )
+            };
+            if received < 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            let mut finished = false;
+            for message in parse_messages(&buf[..received as usize]) {
+                if message.seq != seq {
+                    continue;
+                }
+                match message.msg_type {
+                    NLMSG_DONE => finished = true,
+                    NLMSG_ERROR => {
+                        let code = message.error_code();
+                        if code != 0 {
+                            return Err(io::Error::from_raw_os_error(-code));
+                        }
+                        finished = true;
+                    }
+                    _ => {
+                        if message.flags & NLM_F_MULTI == 0 && flags & NLM_F_ACK == 0 {
+                            finished = true;
+                        }
+                        replies.push(message);
+                    }
+                }
+            }
+
+            if finished {
+                return Ok(replies);
+            }
+        }
+    }
+
+    /// Look up the numeric id of a generic netlink family (e.g. "nl80211")
+    pub fn resolve_genl_family(&mut self, name: &str) -> io::Result<u16> {
+        let mut family_name = name.as_bytes().to_vec();
+        family_name.push(0);
+        let mut attributes = Vec::new();
+        push_attribute(&mut attributes, CTRL_ATTR_FAMILY_NAME, &family_name);
+
+        let replies = self.request(
+            GENL_ID_CTRL,
+            0,
+            &genl_message(CTRL_CMD_GETFAMILY, &attributes),
+        )?;
+
+        replies
+            .iter()
+            .find_map(|m| Attributes::parse(m.genl_payload()).get_u16(CTRL_ATTR_FAMILY_ID))
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("Generic netlink family '{}' not found", name),
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_attributes_roundtrip() {
+        let mut buf = Vec::new();
+        push_attribute(&mut buf, 3, &7u32.to_ne_bytes());
+        push_attribute(&mut buf, 4, b"wlan0\0");
+        let mut nested = Vec::new();
+        push_attribute(&mut nested, 1, &[0xC4]);
+        push_attribute(&mut buf, 0x8000 | 21, &nested);
+
+        // Attributes are padded to four bytes
+        assert_eq!(buf.len() % 4, 0);
+
+        let attrs = Attributes::parse(&buf);
+        assert_eq!(attrs.get_u32(3), Some(7));
+        assert_eq!(attrs.get_string(4).as_deref(), Some("wlan0"));
+        assert_eq!(attrs.get_nested(21).and_then(|n| n.get_u8(1)), Some(0xC4));
+        assert_eq!(attrs.get(99), None);
+    }
+
+    #[test]
+    fn test_parse_messages() {
+        // Two messages back to back: a 20-byte reply and NLMSG_DONE
+        let mut buf = Vec::new();
+        for (msg_type, payload) in [(0x1c_u16, &[1u8, 2, 3, 4][..]), (NLMSG_DONE, &[0u8; 4][..])] {
+            buf.extend_from_slice(&((NLMSG_HDRLEN + payload.len()) as u32).to_ne_bytes());
+            buf.extend_from_slice(&msg_type.to_ne_bytes());
+            buf.extend_from_slice(&NLM_F_MULTI.to_ne_bytes());
+            buf.extend_from_slice(&9u32.to_ne_bytes());
+            buf.extend_from_slice(&0u32.to_ne_bytes());
+            buf.extend_from_slice(payload);
+        }
+        // A truncated trailing header is ignored
+        buf.extend_from_slice(&[0xff; 6]);
+
+        let messages = parse_messages(&buf);
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].msg_type, 0x1c);
+        assert_eq!(messages[0].seq, 9);
+        assert_eq!(messages[0].payload, vec![1, 2, 3, 4]);
+        assert_eq!(messages[1].msg_type, NLMSG_DONE);
+    }
+}
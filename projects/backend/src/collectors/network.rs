@@ -1,5 +1,9 @@
 //! Network information collector
 
+#[cfg(target_os = "linux")]
+use crate::collectors::dhcp::{self, DhcpLease};
+#[cfg(not(target_os = "windows"))]
+use crate::collectors::{VpnCollector, WirelessCollector};
 use crate::models::{
     AdapterStats, AdapterStatus, AdapterType, ConnectionState, DnsConfig, Ipv4Config, Ipv6Config,
     NetworkAdapter, NetworkConnection, Route, RouteType,
@@ -8,6 +12,13 @@ use std::collections::HashMap;
 use std::time::{SystemTime, UNIX_EPOCH};
 use sysinfo::Networks;
 
+#[cfg(any(target_os = "linux", target_os = "windows"))]
+use chrono::Utc;
+#[cfg(target_os = "windows")]
+use chrono::{DateTime, NaiveDateTime};
+#[cfg(not(target_os = "windows"))]
+use std::net::IpAddr;
+
 #[cfg(target_os = "windows")]
 use windows::Win32::NetworkManagement::IpHelper::{
     GetAdaptersAddresses, GetIpForwardTable, GAA_FLAG_INCLUDE_GATEWAYS, GAA_FLAG_INCLUDE_PREFIX,
@@ -62,6 +73,17 @@ struct Win32NetworkAdapterConfiguration {
     mtu: Option<u32>,
 }
 
+/// Cumulative traffic counters for one interface
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct InterfaceCounters {
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+    pub packets_sent: u64,
+    pub packets_received: u64,
+    pub errors: u64,
+    pub drops: u64,
+}
+
 /// Collector for network-related information
 pub struct NetworkCollector;
 
@@ -290,6 +312,11 @@ impl NetworkCollector {
                             (false, None, None, None)
                         };
 
+                    let lease_expires_in_secs = lease_expires
+                        .as_deref()
+                        .and_then(Self::parse_wmi_datetime)
+                        .map(|expires| (expires - Utc::now()).num_seconds());
+
                     ipv4_config = Some(Ipv4Config {
                         address: ip_address,
                         subnet_mask,
@@ -298,6 +325,7 @@ impl NetworkCollector {
                         dhcp_server,
                         lease_obtained,
                         lease_expires,
+                        lease_expires_in_secs,
                     });
                 } else if sockaddr.sa_family == AF_INET6 {
                     let sockaddr_in6 = &*(addr.Address.lpSockaddr as *const SOCKADDR_IN6);
@@ -345,7 +373,14 @@ impl NetworkCollector {
         (ipv4_config, ipv6_config)
     }
 
+    /// Parse a WMI CIM_DATETIME ("20240115083012.000000-300", offset in minutes)
     #[cfg(target_os = "windows")]
+    fn parse_wmi_datetime(value: &str) -> Option<DateTime<Utc>> {
+        let local = NaiveDateTime::parse_from_str(value.get(..14)?, "%Y%m%d%H%M%S").ok()?;
+        let offset_minutes: i64 = value.get(21..)?.parse().ok()?;
+        Some((local - chrono::Duration::minutes(offset_minutes)).and_utc())
+    }
+
     fn prefix_to_subnet_mask(prefix: u8) -> String {
         let mask: u32 = if prefix == 0 {
             0
@@ -480,13 +515,23 @@ impl NetworkCollector {
     fn get_unix_adapters() -> Vec<NetworkAdapter> {
         let networks = Networks::new_with_refreshed_list();
         let mut adapters = Vec::new();
+        let dns_config = Self::read_resolv_conf();
+        let tunnel_kinds = VpnCollector::tunnel_kinds();
+        #[cfg(target_os = "linux")]
+        let leases = dhcp::read_leases();
 
         for (name, data) in networks.iter() {
             let adapter = NetworkAdapter {
                 id: name.clone(),
                 name: name.clone(),
                 description: name.clone(),
-                adapter_type: Self::detect_adapter_type(name),
+                adapter_type: if WirelessCollector::is_wireless(name) {
+                    AdapterType::WiFi
+                } else if tunnel_kinds.contains_key(name) {
+                    AdapterType::Tunnel
+                } else {
+                    Self::detect_adapter_type(name)
+                },
                 mac_address: data.mac_address().to_string(),
                 status: if data.total_received() > 0 || data.total_transmitted() > 0 {
                     AdapterStatus::Up
@@ -495,21 +540,97 @@ impl NetworkCollector {
                 },
                 speed_mbps: None,
                 mtu: 1500,
-                ipv4_config: None,
+                ipv4_config: data
+                    .ip_networks()
+                    .iter()
+                    .find_map(|network| match network.addr {
+                        IpAddr::V4(address) => Some(Ipv4Config {
+                            address: address.to_string(),
+                            subnet_mask: Self::prefix_to_subnet_mask(network.prefix),
+                            default_gateway: None,
+                            dhcp_enabled: false,
+                            dhcp_server: None,
+                            lease_obtained: None,
+                            lease_expires: None,
+                            lease_expires_in_secs: None,
+                        }),
+                        IpAddr::V6(_) => None,
+                    }),
                 ipv6_config: None,
-                dns_config: DnsConfig::default(),
+                // Resolvers are system-wide here; the loopback adapter does not use them
+                dns_config: if name == "lo" {
+                    DnsConfig::default()
+                } else {
+                    dns_config.clone()
+                },
             };
+
+            #[cfg(target_os = "linux")]
+            let adapter = Self::apply_dhcp_lease(adapter, leases.get(name));
+
             adapters.push(adapter);
         }
 
         adapters
     }
 
+    /// Fill in DHCP details when the interface's address came from its lease
+    #[cfg(target_os = "linux")]
+    fn apply_dhcp_lease(mut adapter: NetworkAdapter, lease: Option<&DhcpLease>) -> NetworkAdapter {
+        let (Some(config), Some(lease)) = (adapter.ipv4_config.as_mut(), lease) else {
+            return adapter;
+        };
+        if lease.address.as_ref().is_some_and(|a| *a != config.address) {
+            return adapter;
+        }
+
+        config.dhcp_enabled = true;
+        config.dhcp_server = lease.server.clone();
+        config.default_gateway = lease.router.clone();
+        config.lease_obtained = lease.obtained.map(|t| t.to_rfc3339());
+        config.lease_expires = lease.expires.map(|t| t.to_rfc3339());
+        config.lease_expires_in_secs = lease
+            .expires
+            .map(|expires| (expires - Utc::now()).num_seconds());
+        adapter
+    }
+
+    /// Read the system resolver configuration. With systemd-resolved the upstream
+    /// servers are listed in its own resolv.conf rather than the 127.0.0.53 stub.
+    #[cfg(not(target_os = "windows"))]
+    fn read_resolv_conf() -> DnsConfig {
+        ["/run/systemd/resolve/resolv.conf", "/etc/resolv.conf"]
+            .iter()
+            .find_map(|path| std::fs::read_to_string(path).ok())
+            .map(|content| Self::parse_resolv_conf(&content))
+            .unwrap_or_default()
+    }
+
+    /// Parse resolv.conf nameserver, domain and search lines
+    #[cfg(not(target_os = "windows"))]
+    fn parse_resolv_conf(content: &str) -> DnsConfig {
+        let mut config = DnsConfig::default();
+
+        for line in content.lines() {
+            let mut fields = line.split_whitespace();
+            match fields.next() {
+                Some("nameserver") => config.servers.extend(fields.next().map(String::from)),
+                Some("domain") => config.suffix = fields.next().map(String::from),
+                // The last search line wins
+                Some("search") => config.search_list = fields.map(String::from).collect(),
+                _ => {}
+            }
+        }
+
+        config
+    }
+
     /// Get statistics for a specific adapter
     pub fn get_adapter_stats(adapter_id: &str) -> Option<AdapterStats> {
         let networks = Networks::new_with_refreshed_list();
+        let counters = Self::get_interface_counters(&networks);
 
-        networks.get(adapter_id).map(|data| {
+        counters.get(adapter_id).map(|c| {
             let timestamp = SystemTime::now()
                 .duration_since(UNIX_EPOCH)
                 .unwrap_or_default()
@@ -517,17 +638,70 @@ impl NetworkCollector {
 
             AdapterStats {
                 adapter_id: adapter_id.to_string(),
-                bytes_sent: data.total_transmitted(),
-                bytes_received: data.total_received(),
-                packets_sent: data.total_packets_transmitted(),
-                packets_received: data.total_packets_received(),
-                errors: data.total_errors_on_received() + data.total_errors_on_transmitted(),
-                discards: 0,
+                bytes_sent: c.bytes_sent,
+                bytes_received: c.bytes_received,
+                packets_sent: c.packets_sent,
+                packets_received: c.packets_received,
+                errors: c.errors,
+                discards: c.drops,
                 timestamp,
             }
         })
     }
 
+    /// Get cumulative traffic counters for every interface.
+    /// On Linux these are read from /sys/class/net/*/statistics, which also carries drop counts.
+    pub fn get_interface_counters(networks: &Networks) -> HashMap<String, InterfaceCounters> {
+        let counters: HashMap<String, InterfaceCounters> = networks
+            .iter()
+            .map(|(name, data)| {
+                let counters = InterfaceCounters {
+                    bytes_sent: data.total_transmitted(),
+                    bytes_received: data.total_received(),
+                    packets_sent: data.total_packets_transmitted(),
+                    packets_received: data.total_packets_received(),
+                    errors: data.total_errors_on_received() + data.total_errors_on_transmitted(),
+                    drops: 0,
+                };
+                (name.clone(), counters)
+            })
+            .collect();
+
+        #[cfg(target_os = "linux")]
+        let counters = counters
+            .into_iter()
+            .map(|(name, c)| {
+                let c = Self::read_sysfs_counters(&name).unwrap_or(c);
+                (name, c)
+            })
+            .collect();
+
+        counters
+    }
+
+    #[cfg(target_os = "linux")]
+    fn read_sysfs_counters(interface: &str) -> Option<InterfaceCounters> {
+        let dir = std::path::Path::new("/sys/class/net")
+            .join(interface)
+            .join("statistics");
+        let read = |name: &str| -> Option<u64> {
+            std::fs::read_to_string(dir.join(name))
+                .ok()?
+                .trim()
+                .parse()
+                .ok()
+        };
+
+        Some(InterfaceCounters {
+            bytes_sent: read("tx_bytes")?,
+            bytes_received: read("rx_bytes")?,
+            packets_sent: read("tx_packets")?,
+            packets_received: read("rx_packets")?,
+            errors: read("rx_errors")? + read("tx_errors")?,
+            drops: read("rx_dropped")? + read("tx_dropped")?,
+        })
+    }
+
     /// Get active network connections
     pub fn get_active_connections() -> Vec<NetworkConnection> {
         #[cfg(target_os = "windows")]
@@ -700,6 +874,13 @@ impl NetworkCollector {
             || name_lower.contains("wlan")
         {
             AdapterType::WiFi
+        } else if name_lower.contains("wireguard")
+            || name_lower.contains("vpn")
+            || name_lower.starts_with("tun")
+            || name_lower.starts_with("tap")
+            || name_lower.starts_with("wg")
+        {
+            AdapterType::Tunnel
         } else if name_lower.contains("ethernet") || name_lower.contains("eth") {
             AdapterType::Ethernet
         } else if name_lower.contains("loopback") || name_lower.contains("lo") {
@@ -717,7 +898,7 @@ impl NetworkCollector {
 
 /// Format MAC address bytes into string
 #[allow(dead_code)]
-fn format_mac_address(bytes: &[u8]) -> String {
+pub(crate) fn format_mac_address(bytes: &[u8]) -> String {
     bytes
         .iter()
         .map(|b| format!("{:02X}", b))
@@ -750,5 +931,76 @@ mod tests {
             NetworkCollector::detect_adapter_type("Loopback"),
             AdapterType::Loopback
         ));
+        assert!(matches!(
+            NetworkCollector::detect_adapter_type("wg0"),
+            AdapterType::Tunnel
+        ));
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    #[test]
+    fn test_parse_resolv_conf() {
+        let content = "\
+# This is /run/systemd/resolve/resolv.conf managed by man:systemd-resolved(8).
+nameserver 192.168.1.1
+nameserver 2001:4860:4860::8888
+domain home.lan
+search home.lan corp.example.com
+options edns0 trust-ad
+";
+        let config = NetworkCollector::parse_resolv_conf(content);
+        assert_eq!(config.servers, vec!["192.168.1.1", "2001:4860:4860::8888"]);
+        assert_eq!(config.suffix.as_deref(), Some("home.lan"));
+        assert_eq!(config.search_list, vec!["home.lan", "corp.example.com"]);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_apply_dhcp_lease() {
+        let adapter = NetworkAdapter {
+            id: "eth0".to_string(),
+            name: "eth0".to_string(),
+            description: "eth0".to_string(),
+            adapter_type: AdapterType::Ethernet,
+            mac_address: "52:54:00:12:34:56".to_string(),
+            status: AdapterStatus::Up,
+            speed_mbps: None,
+            mtu: 1500,
+            ipv4_config: Some(Ipv4Config {
+                address: "192.168.1.50".to_string(),
+                subnet_mask: NetworkCollector::prefix_to_subnet_mask(24),
+                default_gateway: None,
+                dhcp_enabled: false,
+                dhcp_server: None,
+                lease_obtained: None,
+                lease_expires: None,
+                lease_expires_in_secs: None,
+            }),
+            ipv6_config: None,
+            dns_config: DnsConfig::default(),
+        };
+        let expires = Utc::now() + chrono::Duration::minutes(5);
+        let mut lease = DhcpLease {
+            interface: "eth0".to_string(),
+            address: Some("192.168.1.50".to_string()),
+            router: Some("192.168.1.1".to_string()),
+            server: Some("192.168.1.254".to_string()),
+            expires: Some(expires),
+            ..Default::default()
+        };
+
+        let updated = NetworkCollector::apply_dhcp_lease(adapter.clone(), Some(&lease));
+        let config = updated.ipv4_config.unwrap();
+        assert_eq!(config.subnet_mask, "255.255.255.0");
+        assert!(config.dhcp_enabled);
+        assert_eq!(config.dhcp_server.as_deref(), Some("192.168.1.254"));
+        assert_eq!(config.default_gateway.as_deref(), Some("192.168.1.1"));
+        assert_eq!(config.lease_expires, Some(expires.to_rfc3339()));
+        assert!((295..=300).contains(&config.lease_expires_in_secs.unwrap()));
+
+        // A stale lease for a different address does not apply
+        lease.address = Some("192.168.1.77".to_string());
+        let updated = NetworkCollector::apply_dhcp_lease(adapter, Some(&lease));
+        assert!(!updated.ipv4_config.unwrap().dhcp_enabled);
     }
 }
@@ -0,0 +1,586 @@
+//! Network topology collector
+//!
+//! Links interfaces into a graph by bridge and bond membership, VLAN parents and veth
+//! pairs, across every network namespace on the machine (containers, `ip netns`).
+
+use crate::models::{NetworkEdge, NetworkNode, NetworkNodeKind, NetworkRelation, NetworkTopology};
+use std::collections::HashSet;
+
+#[cfg(target_os = "linux")]
+use crate::collectors::links::{self, LinkInfo};
+#[cfg(target_os = "linux")]
+use crate::collectors::VpnCollector;
+#[cfg(target_os = "linux")]
+use crate::models::NetworkNamespace;
+#[cfg(target_os = "linux")]
+use std::collections::{BTreeMap, HashMap};
+#[cfg(target_os = "linux")]
+use std::os::unix::fs::MetadataExt;
+#[cfg(target_os = "linux")]
+use std::path::{Path, PathBuf};
+
+#[cfg(not(target_os = "linux"))]
+use crate::collectors::NetworkCollector;
+#[cfg(not(target_os = "linux"))]
+use crate::models::AdapterType;
+
+/// Interfaces of one network namespace and the ids it uses for the other namespaces
+#[cfg(target_os = "linux")]
+struct NamespaceLinks {
+    inode: u64,
+    is_current: bool,
+    links: Vec<LinkInfo>,
+    namespace_ids: HashMap<i32, u64>,
+}
+
+/// Collector for the network topology graph
+pub struct NetworkTopologyCollector;
+
+impl NetworkTopologyCollector {
+    /// Build the network topology graph
+    pub fn get_topology() -> NetworkTopology {
+        #[cfg(target_os = "linux")]
+        let mut topology = Self::get_topology_linux();
+
+        #[cfg(not(target_os = "linux"))]
+        let mut topology = Self::get_topology_flat();
+
+        // Both ends of a veth pair report each other; keep each link once
+        let mut seen = HashSet::new();
+        topology
+            .edges
+            .retain(|e| seen.insert((e.parent.clone(), e.child.clone())));
+
+        topology
+    }
+
+    /// Without link relationships, list the adapters on their own
+    #[cfg(not(target_os = "linux"))]
+    fn get_topology_flat() -> NetworkTopology {
+        let nodes = NetworkCollector::get_adapters()
+            .into_iter()
+            .map(|adapter| NetworkNode {
+                id: Self::node_id(0, &adapter.name),
+                kind: match adapter.adapter_type {
+                    AdapterType::Ethernet | AdapterType::WiFi => NetworkNodeKind::Physical,
+                    AdapterType::Loopback => NetworkNodeKind::Loopback,
+                    AdapterType::Tunnel => NetworkNodeKind::Tunnel,
+                    AdapterType::Virtual | AdapterType::Unknown => NetworkNodeKind::Virtual,
+                },
+                name: adapter.name,
+                namespace: 0,
+                index: None,
+                link_kind: None,
+                mac_address: Some(adapter.mac_address),
+                mtu: Some(adapter.mtu),
+                is_up: matches!(adapter.status, crate::models::AdapterStatus::Up),
+                vlan_id: None,
+                bond_mode: None,
+            })
+            .collect();
+
+        NetworkTopology {
+            namespaces: Vec::new(),
+            nodes,
+            edges: Vec::new(),
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn get_topology_linux() -> NetworkTopology {
+        let current = Self::namespace_inode(Path::new("/proc/self/ns/net"));
+        let mut namespaces = Self::list_namespaces();
+        let paths: Vec<(u64, PathBuf)> = namespaces
+            .iter()
+            .map(|(inode, (_, path))| (*inode, path.clone()))
+            .collect();
+
+        let mut dumps = Vec::new();
+        let mut name_only = Vec::new();
+        for (inode, (namespace, path)) in namespaces.iter_mut() {
+            namespace.is_current = Some(*inode) == current;
+
+            let dump = if namespace.is_current {
+                links::dump_links()
+                    .or_else(|e| {
+                        log::debug!("rtnetlink link dump failed, using sysfs: {}", e);
+                        Self::links_from_sysfs()
+                    })
+                    .map(|links| (links, links::namespace_ids(&paths)))
+            } else {
+                links::dump_links_in(path, &paths)
+            };
+
+            match dump {
+                Ok((links, namespace_ids)) => dumps.push(NamespaceLinks {
+                    inode: *inode,
+                    is_current: namespace.is_current,
+                    links,
+                    namespace_ids,
+                }),
+                Err(e) => {
+                    log::debug!("Cannot enter network namespace {}: {}", inode, e);
+                    namespace.details_available = false;
+                    // Interface names are still visible through a member process
+                    if let Some(pid) = namespace.pid {
+                        let dev = std::fs::read_to_string(format!("/proc/{}/net/dev", pid))
+                            .unwrap_or_default();
+                        name_only.extend(
+                            Self::parse_net_dev_names(&dev)
+                                .into_iter()
+                                .map(|name| (*inode, name)),
+                        );
+                    }
+                }
+            }
+        }
+
+        let (mut nodes, edges) = Self::build_graph(&dumps);
+        nodes.extend(name_only.into_iter().map(|(inode, name)| NetworkNode {
+            id: Self::node_id(inode, &name),
+            kind: if name == "lo" {
+                NetworkNodeKind::Loopback
+            } else {
+                NetworkNodeKind::Virtual
+            },
+            name,
+            namespace: inode,
+            index: None,
+            link_kind: None,
+            mac_address: None,
+            mtu: None,
+            is_up: false,
+            vlan_id: None,
+            bond_mode: None,
+        }));
+
+        NetworkTopology {
+            namespaces: namespaces.into_values().map(|(n, _)| n).collect(),
+            nodes,
+            edges,
+        }
+    }
+
+    /// Turn per-namespace link dumps into nodes and edges
+    #[cfg(target_os = "linux")]
+    fn build_graph(dumps: &[NamespaceLinks]) -> (Vec<NetworkNode>, Vec<NetworkEdge>) {
+        let mut nodes = Vec::new();
+        let mut ids: HashMap<(u64, u32), (String, NetworkNodeKind)> = HashMap::new();
+
+        for dump in dumps {
+            for link in &dump.links {
+                let id = Self::node_id(dump.inode, &link.name);
+                let kind = Self::node_kind(link, dump.is_current);
+                ids.insert((dump.inode, link.index), (id.clone(), kind.clone()));
+
+                let bond_mode = link
+                    .bond_mode
+                    .and_then(links::bond_mode_name)
+                    .map(str::to_string)
+                    .or_else(|| {
+                        // Fall back to sysfs, which is only visible for our own namespace
+                        dump.is_current
+                            .then(|| {
+                                std::fs::read_to_string(format!(
+                                    "/sys/class/net/{}/bonding/mode",
+                                    link.name
+                                ))
+                                .ok()
+                            })
+                            .flatten()
+                            .and_then(|mode| mode.split_whitespace().next().map(str::to_string))
+                    });
+
+                nodes.push(NetworkNode {
+                    id,
+                    name: link.name.clone(),
+                    namespace: dump.inode,
+                    index: Some(link.index),
+                    kind,
+                    link_kind: link.kind.clone(),
+                    mac_address: link.mac_address.clone(),
+                    mtu: link.mtu,
+                    is_up: link.is_up,
+                    vlan_id: link.vlan_id,
+                    bond_mode,
+                });
+            }
+        }
+
+        let mut edges = Vec::new();
+        for dump in dumps {
+            for link in &dump.links {
+                let id = Self::node_id(dump.inode, &link.name);
+
+                if let Some((master, master_kind)) =
+                    link.master.and_then(|index| ids.get(&(dump.inode, index)))
+                {
+                    let relation = match master_kind {
+                        NetworkNodeKind::Bridge => NetworkRelation::BridgePort,
+                        NetworkNodeKind::Bond => NetworkRelation::BondSlave,
+                        _ => NetworkRelation::Master,
+                    };
+                    edges.push(Self::edge(master, &id, relation));
+                }
+
+                // The lower device or peer may live in another namespace
+                let link_namespace = match link.link_netnsid {
+                    Some(nsid) => dump.namespace_ids.get(&nsid).copied(),
+                    None => Some(dump.inode),
+                };
+                let Some((lower, _)) = link
+                    .link
+                    .zip(link_namespace)
+                    .and_then(|(index, namespace)| ids.get(&(namespace, index)))
+                else {
+                    continue;
+                };
+
+                match link.kind.as_deref() {
+                    Some("veth") => {
+                        let (parent, child) = if *lower < id {
+                            (lower.as_str(), id.as_str())
+                        } else {
+                            (id.as_str(), lower.as_str())
+                        };
+                        edges.push(Self::edge(parent, child, NetworkRelation::VethPeer));
+                    }
+                    Some("vlan") => edges.push(Self::edge(lower, &id, NetworkRelation::VlanParent)),
+                    _ => edges.push(Self::edge(lower, &id, NetworkRelation::LowerDevice)),
+                }
+            }
+        }
+
+        (nodes, edges)
+    }
+
+    #[cfg(target_os = "linux")]
+    fn node_kind(link: &LinkInfo, is_current: bool) -> NetworkNodeKind {
+        if link.is_loopback {
+            return NetworkNodeKind::Loopback;
+        }
+        match link.kind.as_deref() {
+            Some("bridge") | Some("openvswitch") => NetworkNodeKind::Bridge,
+            Some("bond") | Some("team") => NetworkNodeKind::Bond,
+            Some("vlan") => NetworkNodeKind::Vlan,
+            Some("veth") => NetworkNodeKind::Veth,
+            Some("macvlan") | Some("macvtap") | Some("ipvlan") | Some("ipvtap") => {
+                NetworkNodeKind::MacVlan
+            }
+            Some(kind) if VpnCollector::tunnel_kind(&link.name, kind).is_some() => {
+                NetworkNodeKind::Tunnel
+            }
+            Some(_) => NetworkNodeKind::Virtual,
+            // Devices without a link kind are hardware unless sysfs shows no backing device
+            None if is_current
+                && !Path::new("/sys/class/net")
+                    .join(&link.name)
+                    .join("device")
+                    .exists() =>
+            {
+                NetworkNodeKind::Virtual
+            }
+            None => NetworkNodeKind::Physical,
+        }
+    }
+
+    fn node_id(namespace: u64, name: &str) -> String {
+        format!("{}/{}", namespace, name)
+    }
+
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    fn edge(parent: &str, child: &str, relation: NetworkRelation) -> NetworkEdge {
+        NetworkEdge {
+            parent: parent.to_string(),
+            child: child.to_string(),
+            relation,
+        }
+    }
+
+    /// Every network namespace with a member process or an `ip netns` name, keyed by
+    /// inode, along with a path that can be opened to enter it
+    #[cfg(target_os = "linux")]
+    fn list_namespaces() -> BTreeMap<u64, (NetworkNamespace, PathBuf)> {
+        let mut namespaces = BTreeMap::new();
+
+        let mut pids: Vec<u32> = std::fs::read_dir("/proc")
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .filter_map(|e| e.file_name().to_str()?.parse().ok())
+                    .collect()
+            })
+            .unwrap_or_default();
+        pids.sort_unstable();
+
+        for pid in pids {
+            let path = PathBuf::from(format!("/proc/{}/ns/net", pid));
+            let Some(inode) = Self::namespace_inode(&path) else {
+                continue;
+            };
+            namespaces.entry(inode).or_insert_with(|| {
+                let process_name = std::fs::read_to_string(format!("/proc/{}/comm", pid))
+                    .ok()
+                    .map(|comm| comm.trim().to_string());
+                (Self::namespace(inode, None, Some(pid), process_name), path)
+            });
+        }
+
+        // Named namespaces are bind mounts of the namespace file; they can be entered
+        // even when no process runs inside
+        if let Ok(entries) = std::fs::read_dir("/run/netns") {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                let Ok(metadata) = std::fs::metadata(&path) else {
+                    continue;
+                };
+                let name = entry.file_name().to_string_lossy().to_string();
+                let (namespace, ns_path) = namespaces.entry(metadata.ino()).or_insert_with(|| {
+                    (
+                        Self::namespace(metadata.ino(), None, None, None),
+                        path.clone(),
+                    )
+                });
+                namespace.name = Some(name);
+                *ns_path = path;
+            }
+        }
+
+        namespaces
+    }
+
+    #[cfg(target_os = "linux")]
+    fn namespace(
+        inode: u64,
+        name: Option<String>,
+        pid: Option<u32>,
+        process_name: Option<String>,
+    ) -> NetworkNamespace {
+        NetworkNamespace {
+            inode,
+            name,
+            is_current: false,
+            pid,
+            process_name,
+            details_available: true,
+        }
+    }
+
+    /// Inode of a namespace file, from its "net:[4026531840]" link target
+    #[cfg(target_os = "linux")]
+    fn namespace_inode(path: &Path) -> Option<u64> {
+        let target = std::fs::read_link(path).ok()?;
+        Self::parse_namespace_link(&target.to_string_lossy())
+    }
+
+    #[cfg(target_os = "linux")]
+    fn parse_namespace_link(target: &str) -> Option<u64> {
+        target
+            .strip_prefix("net:[")?
+            .strip_suffix(']')?
+            .parse()
+            .ok()
+    }
+
+    /// Interface names from /proc/<pid>/net/dev ("  eth0: 1234 ...")
+    #[cfg(target_os = "linux")]
+    fn parse_net_dev_names(content: &str) -> Vec<String> {
+        content
+            .lines()
+            .skip(2)
+            .filter_map(|line| line.split_once(':'))
+            .map(|(name, _)| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .collect()
+    }
+
+    /// Read the current namespace's interfaces from /sys/class/net when rtnetlink is unavailable
+    #[cfg(target_os = "linux")]
+    fn links_from_sysfs() -> std::io::Result<Vec<LinkInfo>> {
+        let mut links = Vec::new();
+
+        for entry in std::fs::read_dir("/sys/class/net")?.flatten() {
+            let sys_path = entry.path();
+            let read = |file: &str| {
+                std::fs::read_to_string(sys_path.join(file))
+                    .map(|s| s.trim().to_string())
+                    .unwrap_or_default()
+            };
+            let Ok(index) = read("ifindex").parse::<u32>() else {
+                continue;
+            };
+            let flags =
+                u32::from_str_radix(read("flags").trim_start_matches("0x"), 16).unwrap_or(0);
+            let kind = read("uevent")
+                .lines()
+                .find_map(|line| line.strip_prefix("DEVTYPE="))
+                .map(str::to_string);
+
+            links.push(LinkInfo {
+                index,
+                name: entry.file_name().to_string_lossy().to_string(),
+                is_up: flags & libc::IFF_UP as u32 != 0,
+                has_carrier: read("carrier") == "1",
+                is_loopback: flags & libc::IFF_LOOPBACK as u32 != 0,
+                mac_address: Some(read("address").to_uppercase())
+                    .filter(|mac| !mac.is_empty() && mac != "00:00:00:00:00:00"),
+                mtu: read("mtu").parse().ok(),
+                master: std::fs::canonicalize(sys_path.join("master"))
+                    .ok()
+                    .and_then(|master| std::fs::read_to_string(master.join("ifindex")).ok())
+                    .and_then(|index| index.trim().parse().ok()),
+                link: read("iflink").parse().ok().filter(|&link| link != index),
+                link_netnsid: None,
+                vlan_id: None,
+                bond_mode: None,
+                kind,
+            });
+        }
+
+        Ok(links)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_topology() {
+        let topology = NetworkTopologyCollector::get_topology();
+        let ids: HashSet<&str> = topology.nodes.iter().map(|n| n.id.as_str()).collect();
+
+        // Every edge must connect nodes in the graph
+        for edge in &topology.edges {
+            assert!(
+                ids.contains(edge.parent.as_str()) && ids.contains(edge.child.as_str()),
+                "Dangling edge {:?}",
+                edge
+            );
+        }
+
+        #[cfg(target_os = "linux")]
+        assert!(topology.namespaces.iter().any(|n| n.is_current));
+    }
+
+    #[cfg(target_os = "linux")]
+    fn link(index: u32, name: &str, kind: Option<&str>) -> LinkInfo {
+        LinkInfo {
+            index,
+            name: name.to_string(),
+            kind: kind.map(str::to_string),
+            is_up: true,
+            has_carrier: true,
+            is_loopback: false,
+            mac_address: None,
+            mtu: Some(1500),
+            master: None,
+            link: None,
+            link_netnsid: None,
+            vlan_id: None,
+            bond_mode: None,
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_build_graph() {
+        // Host: eth0 + eth1 bonded into bond0, VLAN 10 on bond0 in bridge br0,
+        // and a veth into a container namespace
+        let mut eth0 = link(2, "eth0", None);
+        eth0.master = Some(4);
+        let mut eth1 = link(3, "eth1", None);
+        eth1.master = Some(4);
+        let mut bond0 = link(4, "bond0", Some("bond"));
+        bond0.bond_mode = Some(4);
+        let mut vlan = link(5, "bond0.10", Some("vlan"));
+        vlan.link = Some(4);
+        vlan.vlan_id = Some(10);
+        vlan.master = Some(6);
+        let br0 = link(6, "br0", Some("bridge"));
+        let mut veth_host = link(7, "veth9f3c", Some("veth"));
+        veth_host.master = Some(6);
+        veth_host.link = Some(2);
+        veth_host.link_netnsid = Some(0);
+
+        let mut veth_container = link(2, "eth0", Some("veth"));
+        veth_container.link = Some(7);
+        veth_container.link_netnsid = Some(0);
+
+        let dumps = vec![
+            NamespaceLinks {
+                inode: 100,
+                is_current: false,
+                links: vec![eth0, eth1, bond0, vlan, br0, veth_host],
+                namespace_ids: HashMap::from([(0, 200)]),
+            },
+            NamespaceLinks {
+                inode: 200,
+                is_current: false,
+                links: vec![veth_container],
+                namespace_ids: HashMap::from([(0, 100)]),
+            },
+        ];
+
+        let (nodes, mut edges) = NetworkTopologyCollector::build_graph(&dumps);
+        assert_eq!(nodes.len(), 7);
+        let bond = nodes.iter().find(|n| n.id == "100/bond0").unwrap();
+        assert_eq!(bond.kind, NetworkNodeKind::Bond);
+        assert_eq!(bond.bond_mode.as_deref(), Some("802.3ad"));
+
+        let mut seen = HashSet::new();
+        edges.retain(|e| seen.insert((e.parent.clone(), e.child.clone())));
+        let has = |parent: &str, child: &str, relation: NetworkRelation| {
+            edges.contains(&NetworkEdge {
+                parent: parent.to_string(),
+                child: child.to_string(),
+                relation,
+            })
+        };
+
+        assert!(has("100/bond0", "100/eth0", NetworkRelation::BondSlave));
+        assert!(has("100/bond0", "100/eth1", NetworkRelation::BondSlave));
+        assert!(has(
+            "100/bond0",
+            "100/bond0.10",
+            NetworkRelation::VlanParent
+        ));
+        assert!(has("100/br0", "100/bond0.10", NetworkRelation::BridgePort));
+        assert!(has("100/br0", "100/veth9f3c", NetworkRelation::BridgePort));
+        // Reported from both ends but kept once
+        assert!(has("100/veth9f3c", "200/eth0", NetworkRelation::VethPeer));
+        assert_eq!(edges.len(), 6);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_namespace_files() {
+        assert_eq!(
+            NetworkTopologyCollector::parse_namespace_link("net:[4026531840]"),
+            Some(4026531840)
+        );
+        assert_eq!(
+            NetworkTopologyCollector::parse_namespace_link("pid:[4026531836]"),
+            None
+        );
+
+        let dev = "\
+Inter-|   Receive                            |  Transmit
+ face |bytes    packets errs drop fifo frame compressed multicast|bytes    packets errs drop fifo colls carrier compressed
+    lo:    1234      12    0    0    0     0          0         0     1234      12    0    0    0     0       0          0
+  eth0: 9876543    4321    0    0    0     0          0         0   123456     789    0    0    0     0       0          0
+";
+        assert_eq!(
+            NetworkTopologyCollector::parse_net_dev_names(dev),
+            vec!["lo", "eth0"]
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_links_from_sysfs() {
+        if let Ok(links) = NetworkTopologyCollector::links_from_sysfs() {
+            assert!(links.iter().any(|l| l.is_loopback));
+        }
+    }
+}
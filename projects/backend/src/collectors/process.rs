@@ -1,9 +1,88 @@
 //! Process information collector
 
-use crate::models::process::{ProcessInfo, ProcessSummary};
+use crate::collectors::CgroupCollector;
+use crate::models::process::{
+    ApplicationMemory, ProcessDetails, ProcessGroup, ProcessGroupBy, ProcessInfo, ProcessMemory,
+    ProcessSummary, ProcessTreeNode,
+};
 use crate::services::ICON_CACHE;
+use crate::state::thread_groups;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use sysinfo::{Process, ProcessStatus, System, Users};
 
+#[cfg(target_os = "linux")]
+use crate::collectors::SocketCollector;
+#[cfg(target_os = "linux")]
+use crate::models::process::{
+    EnvironmentVariable, OpenFile, OpenFileKind, ProcessCapabilities, ProcessDetailField,
+    ProcessNamespace, ProcessSocket, ProcessThread, ResourceLimit, SocketFamily,
+};
+#[cfg(target_os = "linux")]
+use crate::models::{ConnectionProtocol, ConnectionState};
+#[cfg(target_os = "linux")]
+use std::io;
+#[cfg(target_os = "linux")]
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+#[cfg(target_os = "linux")]
+use std::path::Path;
+
+/// Capability names by bit number (linux/capability.h)
+#[cfg(target_os = "linux")]
+const CAPABILITY_NAMES: [&str; 41] = [
+    "CAP_CHOWN",
+    "CAP_DAC_OVERRIDE",
+    "CAP_DAC_READ_SEARCH",
+    "CAP_FOWNER",
+    "CAP_FSETID",
+    "CAP_KILL",
+    "CAP_SETGID",
+    "CAP_SETUID",
+    "CAP_SETPCAP",
+    "CAP_LINUX_IMMUTABLE",
+    "CAP_NET_BIND_SERVICE",
+    "CAP_NET_BROADCAST",
+    "CAP_NET_ADMIN",
+    "CAP_NET_RAW",
+    "CAP_IPC_LOCK",
+    "CAP_IPC_OWNER",
+    "CAP_SYS_MODULE",
+    "CAP_SYS_RAWIO",
+    "CAP_SYS_CHROOT",
+    "CAP_SYS_PTRACE",
+    "CAP_SYS_PACCT",
+    "CAP_SYS_ADMIN",
+    "CAP_SYS_BOOT",
+    "CAP_SYS_NICE",
+    "CAP_SYS_RESOURCE",
+    "CAP_SYS_TIME",
+    "CAP_SYS_TTY_CONFIG",
+    "CAP_MKNOD",
+    "CAP_LEASE",
+    "CAP_AUDIT_WRITE",
+    "CAP_AUDIT_CONTROL",
+    "CAP_SETFCAP",
+    "CAP_MAC_OVERRIDE",
+    "CAP_MAC_ADMIN",
+    "CAP_SYSLOG",
+    "CAP_WAKE_ALARM",
+    "CAP_BLOCK_SUSPEND",
+    "CAP_AUDIT_READ",
+    "CAP_PERFMON",
+    "CAP_BPF",
+    "CAP_CHECKPOINT_RESTORE",
+];
+
+/// A socket from the /proc/<pid>/net tables, keyed by inode
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone)]
+struct SocketEntry {
+    family: SocketFamily,
+    protocol: Option<ConnectionProtocol>,
+    local_address: Option<String>,
+    remote_address: Option<String>,
+    state: Option<ConnectionState>,
+}
+
 /// Collector for process information
 pub struct ProcessCollector;
 
@@ -21,8 +100,7 @@ impl ProcessCollector {
         let users = Users::new_with_refreshed_list();
         let cpu_count = sys.cpus().len() as f32;
 
-        sys.processes()
-            .iter()
+        thread_groups(&sys)
             .map(|(pid, process)| Self::process_to_info(*pid, process, &users, cpu_count))
             .collect()
     }
@@ -33,7 +111,7 @@ impl ProcessCollector {
         sys.refresh_cpu_all();
         sys.refresh_processes(sysinfo::ProcessesToUpdate::All);
 
-        let processes: Vec<&Process> = sys.processes().values().collect();
+        let processes: Vec<&Process> = thread_groups(&sys).map(|(_, p)| p).collect();
         let cpu_count = sys.cpus().len() as f32;
 
         let running_count = processes
@@ -61,6 +139,776 @@ impl ProcessCollector {
         }
     }
 
+    /// Get all running processes as a tree
+    pub fn get_process_tree(collapse_by_executable: bool) -> Vec<ProcessTreeNode> {
+        Self::build_process_tree(Self::get_processes(), collapse_by_executable)
+    }
+
+    /// Arrange processes into trees by parent PID, summing resource usage over each
+    /// subtree. Processes whose parent is gone become orphan roots. With
+    /// `collapse_by_executable`, children running the same executable as their parent
+    /// are folded into it and siblings sharing an executable are merged into one node
+    /// (e.g. all renderer processes of a browser).
+    pub fn build_process_tree(
+        processes: Vec<ProcessInfo>,
+        collapse_by_executable: bool,
+    ) -> Vec<ProcessTreeNode> {
+        let mut by_pid: BTreeMap<u32, ProcessInfo> =
+            processes.into_iter().map(|p| (p.pid, p)).collect();
+
+        let mut children: BTreeMap<u32, Vec<u32>> = BTreeMap::new();
+        let mut roots = Vec::new();
+        for process in by_pid.values() {
+            match process.parent_pid {
+                Some(parent) if parent != process.pid && by_pid.contains_key(&parent) => {
+                    children.entry(parent).or_default().push(process.pid)
+                }
+                _ => roots.push(process.pid),
+            }
+        }
+
+        let mut visited = HashSet::new();
+        let mut tree = Vec::new();
+        for pid in roots {
+            let parent_gone = by_pid[&pid]
+                .parent_pid
+                .is_some_and(|parent| parent != 0 && parent != pid);
+            if let Some(mut node) = Self::build_node(
+                pid,
+                &mut by_pid,
+                &children,
+                &mut visited,
+                collapse_by_executable,
+            ) {
+                node.is_orphan = parent_gone;
+                tree.push(node);
+            }
+        }
+
+        // Whatever is left sits on a parent cycle (PID reuse between snapshots); show
+        // each cycle from its lowest PID rather than dropping it
+        while let Some(&pid) = by_pid.keys().next() {
+            if let Some(mut node) = Self::build_node(
+                pid,
+                &mut by_pid,
+                &children,
+                &mut visited,
+                collapse_by_executable,
+            ) {
+                node.is_orphan = true;
+                tree.push(node);
+            }
+        }
+
+        if collapse_by_executable {
+            tree = Self::merge_siblings(tree);
+        }
+        tree.sort_by_key(|node| node.process.pid);
+        tree
+    }
+
+    fn build_node(
+        pid: u32,
+        by_pid: &mut BTreeMap<u32, ProcessInfo>,
+        children: &BTreeMap<u32, Vec<u32>>,
+        visited: &mut HashSet<u32>,
+        collapse_by_executable: bool,
+    ) -> Option<ProcessTreeNode> {
+        if !visited.insert(pid) {
+            return None;
+        }
+        let process = by_pid.remove(&pid)?;
+
+        let child_nodes: Vec<ProcessTreeNode> = children
+            .get(&pid)
+            .into_iter()
+            .flatten()
+            .filter_map(|&child| {
+                Self::build_node(child, by_pid, children, visited, collapse_by_executable)
+            })
+            .collect();
+
+        let is_zombie = process.status == "Zombie";
+        let mut node = ProcessTreeNode {
+            pids: vec![process.pid],
+            is_orphan: false,
+            is_zombie,
+            subtree_cpu_usage: process.cpu_usage,
+            subtree_memory_bytes: process.memory_bytes,
+            subtree_disk_read_bytes: process.disk_read_bytes,
+            subtree_disk_write_bytes: process.disk_write_bytes,
+            subtree_process_count: 1,
+            subtree_zombie_count: is_zombie as usize,
+            children: Vec::new(),
+            process,
+        };
+
+        for child in child_nodes {
+            node.subtree_cpu_usage += child.subtree_cpu_usage;
+            node.subtree_memory_bytes += child.subtree_memory_bytes;
+            node.subtree_disk_read_bytes += child.subtree_disk_read_bytes;
+            node.subtree_disk_write_bytes += child.subtree_disk_write_bytes;
+            node.subtree_process_count += child.subtree_process_count;
+            node.subtree_zombie_count += child.subtree_zombie_count;
+
+            // Totals already include the child, so folding it in only moves its
+            // processes and children up
+            if collapse_by_executable
+                && Self::executable_key(&child.process) == Self::executable_key(&node.process)
+            {
+                node.pids.extend(child.pids);
+                node.children.extend(child.children);
+            } else {
+                node.children.push(child);
+            }
+        }
+
+        if collapse_by_executable {
+            node.children = Self::merge_siblings(std::mem::take(&mut node.children));
+            node.pids.sort_unstable();
+        }
+        Some(node)
+    }
+
+    /// Merge sibling nodes that run the same executable into the one with the lowest PID
+    fn merge_siblings(nodes: Vec<ProcessTreeNode>) -> Vec<ProcessTreeNode> {
+        let mut groups: Vec<ProcessTreeNode> = Vec::new();
+        for node in nodes {
+            let key = Self::executable_key(&node.process);
+            match groups
+                .iter_mut()
+                .find(|group| Self::executable_key(&group.process) == key)
+            {
+                Some(group) => {
+                    group.subtree_cpu_usage += node.subtree_cpu_usage;
+                    group.subtree_memory_bytes += node.subtree_memory_bytes;
+                    group.subtree_disk_read_bytes += node.subtree_disk_read_bytes;
+                    group.subtree_disk_write_bytes += node.subtree_disk_write_bytes;
+                    group.subtree_process_count += node.subtree_process_count;
+                    group.subtree_zombie_count += node.subtree_zombie_count;
+                    group.is_zombie &= node.is_zombie;
+                    group.is_orphan &= node.is_orphan;
+                    group.pids.extend(node.pids);
+                    group.children.extend(node.children);
+                    if node.process.pid < group.process.pid {
+                        group.process = node.process;
+                    }
+                }
+                None => groups.push(node),
+            }
+        }
+
+        for group in &mut groups {
+            group.pids.sort_unstable();
+            // Children of merged siblings may now share executables themselves
+            group.children = Self::merge_siblings(std::mem::take(&mut group.children));
+            group.children.sort_by_key(|child| child.process.pid);
+        }
+        groups
+    }
+
+    /// Executable a process is grouped by; the name when the path is unknown
+    fn executable_key(process: &ProcessInfo) -> &str {
+        process.exe_path.as_deref().unwrap_or(&process.name)
+    }
+
+    /// Total resource usage per container or per systemd unit, largest memory first.
+    /// Processes outside any container or unit form one group with no key, listed last.
+    pub fn group_processes(
+        processes: &[ProcessInfo],
+        group_by: ProcessGroupBy,
+    ) -> Vec<ProcessGroup> {
+        let mut groups: BTreeMap<Option<&str>, ProcessGroup> = BTreeMap::new();
+
+        for process in processes {
+            let key = match group_by {
+                ProcessGroupBy::Container => process.container.as_ref().map(|c| c.id.as_str()),
+                ProcessGroupBy::Unit => process.systemd_unit.as_deref(),
+            };
+            let group = groups.entry(key).or_insert_with(|| ProcessGroup {
+                key: key.map(str::to_string),
+                container: match group_by {
+                    ProcessGroupBy::Container => process.container.clone(),
+                    ProcessGroupBy::Unit => None,
+                },
+                pids: Vec::new(),
+                cpu_usage: 0.0,
+                memory_bytes: 0,
+                disk_read_bytes: 0,
+                disk_write_bytes: 0,
+            });
+            group.pids.push(process.pid);
+            group.cpu_usage += process.cpu_usage;
+            group.memory_bytes += process.memory_bytes;
+            group.disk_read_bytes += process.disk_read_bytes;
+            group.disk_write_bytes += process.disk_write_bytes;
+        }
+
+        let mut groups: Vec<ProcessGroup> = groups.into_values().collect();
+        for group in &mut groups {
+            group.pids.sort_unstable();
+        }
+        groups.sort_by(|a, b| {
+            a.key
+                .is_none()
+                .cmp(&b.key.is_none())
+                .then(b.memory_bytes.cmp(&a.memory_bytes))
+        });
+        groups
+    }
+
+    /// Get PSS, USS, swap and shared/private memory of a process from smaps_rollup
+    pub fn get_process_memory(pid: u32) -> Result<ProcessMemory, String> {
+        #[cfg(target_os = "linux")]
+        {
+            let path = Path::new("/proc")
+                .join(pid.to_string())
+                .join("smaps_rollup");
+            let content = std::fs::read_to_string(path).map_err(|e| match e.kind() {
+                io::ErrorKind::PermissionDenied => {
+                    format!("Permission denied reading memory of process {}", pid)
+                }
+                _ => format!("Process with PID {} not found", pid),
+            })?;
+            Self::parse_smaps_rollup(pid, &content)
+                .ok_or_else(|| format!("Unrecognized smaps_rollup for process {}", pid))
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = pid;
+            Err("Process memory accounting is not supported on this platform".to_string())
+        }
+    }
+
+    /// Group processes by executable and sum their memory. Processes without an
+    /// entry in `memory` only contribute RSS. Sorted by PSS, largest first.
+    pub fn group_memory_by_application(
+        processes: &[ProcessInfo],
+        memory: &HashMap<u32, ProcessMemory>,
+    ) -> Vec<ApplicationMemory> {
+        let mut groups: BTreeMap<&str, ApplicationMemory> = BTreeMap::new();
+
+        for process in processes {
+            let group = groups
+                .entry(Self::executable_key(process))
+                .or_insert_with(|| ApplicationMemory {
+                    name: process.name.clone(),
+                    exe_path: process.exe_path.clone(),
+                    pids: Vec::new(),
+                    rss_bytes: 0,
+                    pss_bytes: 0,
+                    uss_bytes: 0,
+                    shared_bytes: 0,
+                    swap_bytes: 0,
+                    inaccessible_count: 0,
+                });
+            group.pids.push(process.pid);
+
+            match memory.get(&process.pid) {
+                Some(usage) => {
+                    group.rss_bytes += usage.rss_bytes;
+                    group.pss_bytes += usage.pss_bytes;
+                    group.uss_bytes += usage.uss_bytes;
+                    group.shared_bytes += usage.shared_clean_bytes + usage.shared_dirty_bytes;
+                    group.swap_bytes += usage.swap_bytes;
+                }
+                None => {
+                    group.rss_bytes += process.memory_bytes;
+                    group.inaccessible_count += 1;
+                }
+            }
+        }
+
+        let mut applications: Vec<ApplicationMemory> = groups.into_values().collect();
+        for application in &mut applications {
+            application.pids.sort_unstable();
+        }
+        applications.sort_by(|a, b| {
+            b.pss_bytes
+                .cmp(&a.pss_bytes)
+                .then(b.rss_bytes.cmp(&a.rss_bytes))
+        });
+        applications
+    }
+
+    /// Parse the "Field:   123 kB" lines of smaps_rollup
+    #[cfg(target_os = "linux")]
+    fn parse_smaps_rollup(pid: u32, content: &str) -> Option<ProcessMemory> {
+        let fields: HashMap<&str, u64> = content
+            .lines()
+            .filter_map(|line| {
+                let (key, value) = line.split_once(':')?;
+                let kb = value
+                    .trim()
+                    .strip_suffix("kB")?
+                    .trim()
+                    .parse::<u64>()
+                    .ok()?;
+                Some((key.trim(), kb * 1024))
+            })
+            .collect();
+        let field = |name: &str| fields.get(name).copied().unwrap_or(0);
+
+        let rss_bytes = *fields.get("Rss")?;
+        let private_clean_bytes = field("Private_Clean");
+        let private_dirty_bytes = field("Private_Dirty");
+        Some(ProcessMemory {
+            pid,
+            rss_bytes,
+            pss_bytes: field("Pss"),
+            uss_bytes: private_clean_bytes + private_dirty_bytes,
+            shared_clean_bytes: field("Shared_Clean"),
+            shared_dirty_bytes: field("Shared_Dirty"),
+            private_clean_bytes,
+            private_dirty_bytes,
+            swap_bytes: field("Swap"),
+            swap_pss_bytes: field("SwapPss"),
+        })
+    }
+
+    /// Get threads, open files, sockets, environment, limits, cgroup, capabilities
+    /// and namespaces of a single process. Sections that can't be read are marked
+    /// rather than failing the whole request.
+    pub fn get_process_details(pid: u32) -> Result<ProcessDetails, String> {
+        #[cfg(target_os = "linux")]
+        {
+            Self::read_process_details(&Path::new("/proc").join(pid.to_string()), pid)
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = pid;
+            Err("Process details are not supported on this platform".to_string())
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn read_process_details(dir: &Path, pid: u32) -> Result<ProcessDetails, String> {
+        let not_found = || format!("Process with PID {} not found", pid);
+        let stat = std::fs::read_to_string(dir.join("stat")).map_err(|_| not_found())?;
+        let (name, fields) = Self::parse_stat(&stat).ok_or_else(not_found)?;
+        let status = Self::state_name(fields.first().copied().unwrap_or_default());
+
+        let command_line = std::fs::read(dir.join("cmdline")).map(|data| {
+            data.split(|&b| b == 0)
+                .filter(|arg| !arg.is_empty())
+                .map(|arg| String::from_utf8_lossy(arg).to_string())
+                .collect()
+        });
+        let link = |name: &str| {
+            std::fs::read_link(dir.join(name)).map(|target| target.to_string_lossy().to_string())
+        };
+
+        let open_files = Self::read_open_files(dir);
+        let sockets = match &open_files {
+            Ok(files) => Ok(Self::sockets_of(dir, files)),
+            Err(e) => Err(io::Error::from(e.kind())),
+        };
+
+        let cgroup = std::fs::read_to_string(dir.join("cgroup"));
+        let systemd_unit = cgroup
+            .as_ref()
+            .ok()
+            .and_then(|content| SocketCollector::unit_from_cgroup(content));
+
+        let capabilities = std::fs::read_to_string(dir.join("status")).and_then(|content| {
+            Self::parse_capabilities(&content).ok_or_else(|| io::ErrorKind::InvalidData.into())
+        });
+
+        Ok(ProcessDetails {
+            pid,
+            name,
+            status,
+            command_line: Self::detail(command_line),
+            exe_path: Self::detail(link("exe")),
+            cwd: Self::detail(link("cwd")),
+            threads: Self::detail(Self::read_threads(dir)),
+            open_files: Self::detail(open_files),
+            sockets: Self::detail(sockets),
+            environment: Self::detail(
+                std::fs::read(dir.join("environ")).map(|data| Self::parse_environ(&data)),
+            ),
+            limits: Self::detail(
+                std::fs::read_to_string(dir.join("limits"))
+                    .map(|content| Self::parse_limits(&content)),
+            ),
+            cgroup: Self::detail(cgroup.map(|content| Self::cgroup_path(&content))),
+            systemd_unit,
+            capabilities: Self::detail(capabilities),
+            namespaces: Self::detail(Self::read_namespaces(dir)),
+        })
+    }
+
+    /// Turn a read result into a detail field, keeping permission errors visible
+    #[cfg(target_os = "linux")]
+    fn detail<T>(result: io::Result<T>) -> ProcessDetailField<T> {
+        match result {
+            Ok(value) => ProcessDetailField::Available(value),
+            Err(e) if e.kind() == io::ErrorKind::PermissionDenied => {
+                ProcessDetailField::PermissionDenied
+            }
+            Err(_) => ProcessDetailField::Unavailable,
+        }
+    }
+
+    /// Split /proc/<pid>/stat into the command name and the fields after it.
+    /// The name is parenthesized and may itself contain spaces and parentheses.
+    #[cfg(target_os = "linux")]
+    fn parse_stat(content: &str) -> Option<(String, Vec<&str>)> {
+        let start = content.find('(')?;
+        let end = content.rfind(')')?;
+        let name = content.get(start + 1..end)?.to_string();
+        Some((name, content[end + 1..].split_whitespace().collect()))
+    }
+
+    /// Process state letter from /proc/<pid>/stat, named like sysinfo's ProcessStatus
+    #[cfg(target_os = "linux")]
+    fn state_name(state: &str) -> String {
+        match state {
+            "R" => "Run",
+            "S" => "Sleep",
+            "D" => "UninterruptibleDiskSleep",
+            "T" => "Stop",
+            "t" => "Tracing",
+            "Z" => "Zombie",
+            "X" | "x" => "Dead",
+            "I" => "Idle",
+            "K" => "Wakekill",
+            "W" => "Waking",
+            "P" => "Parked",
+            _ => "Unknown",
+        }
+        .to_string()
+    }
+
+    #[cfg(target_os = "linux")]
+    fn read_threads(dir: &Path) -> io::Result<Vec<ProcessThread>> {
+        let ticks_per_sec = match unsafe { libc::sysconf(libc::_SC_CLK_TCK) } {
+            ticks if ticks > 0 => ticks as u64,
+            _ => 100,
+        };
+
+        let mut threads: Vec<ProcessThread> = std::fs::read_dir(dir.join("task"))?
+            .flatten()
+            .filter_map(|entry| {
+                let tid = entry.file_name().to_str()?.parse().ok()?;
+                // Threads that exit while we read are skipped
+                let stat = std::fs::read_to_string(entry.path().join("stat")).ok()?;
+                Self::parse_thread_stat(tid, &stat, ticks_per_sec)
+            })
+            .collect();
+        threads.sort_by_key(|thread| thread.tid);
+        Ok(threads)
+    }
+
+    #[cfg(target_os = "linux")]
+    fn parse_thread_stat(tid: u32, content: &str, ticks_per_sec: u64) -> Option<ProcessThread> {
+        let (name, fields) = Self::parse_stat(content)?;
+        // Field numbers from proc(5), counted from the state field (3)
+        let ticks = |index: usize| -> Option<u64> { fields.get(index)?.parse().ok() };
+
+        Some(ProcessThread {
+            tid,
+            name,
+            state: Self::state_name(fields.first()?),
+            user_time_ms: ticks(11)? * 1000 / ticks_per_sec,
+            system_time_ms: ticks(12)? * 1000 / ticks_per_sec,
+            last_cpu: fields.get(36).and_then(|cpu| cpu.parse().ok()),
+        })
+    }
+
+    #[cfg(target_os = "linux")]
+    fn read_open_files(dir: &Path) -> io::Result<Vec<OpenFile>> {
+        let mut files: Vec<OpenFile> = std::fs::read_dir(dir.join("fd"))?
+            .flatten()
+            .filter_map(|entry| {
+                let fd = entry.file_name().to_str()?.parse().ok()?;
+                // Descriptors closed while we read are skipped
+                let target = std::fs::read_link(entry.path()).ok()?;
+                let target = target.to_string_lossy().to_string();
+                Some(OpenFile {
+                    fd,
+                    kind: Self::open_file_kind(&target),
+                    target,
+                })
+            })
+            .collect();
+        files.sort_by_key(|file| file.fd);
+        Ok(files)
+    }
+
+    #[cfg(target_os = "linux")]
+    fn open_file_kind(target: &str) -> OpenFileKind {
+        if target.starts_with("socket:[") {
+            OpenFileKind::Socket
+        } else if target.starts_with("pipe:[") {
+            OpenFileKind::Pipe
+        } else if target.starts_with("anon_inode:") {
+            OpenFileKind::AnonInode
+        } else if target.ends_with(" (deleted)") {
+            OpenFileKind::Deleted
+        } else if target.starts_with("/dev/") {
+            OpenFileKind::Device
+        } else if target.starts_with('/') {
+            OpenFileKind::File
+        } else {
+            OpenFileKind::Other
+        }
+    }
+
+    /// Resolve socket descriptors against the socket tables of the process's own
+    /// network namespace (/proc/<pid>/net)
+    #[cfg(target_os = "linux")]
+    fn sockets_of(dir: &Path, files: &[OpenFile]) -> Vec<ProcessSocket> {
+        let mut table: HashMap<u64, SocketEntry> = HashMap::new();
+        let inet_tables = [
+            ("tcp", SocketFamily::Ipv4, ConnectionProtocol::TCP),
+            ("tcp6", SocketFamily::Ipv6, ConnectionProtocol::TCP),
+            ("udp", SocketFamily::Ipv4, ConnectionProtocol::UDP),
+            ("udp6", SocketFamily::Ipv6, ConnectionProtocol::UDP),
+        ];
+        for (file, family, protocol) in inet_tables {
+            if let Ok(content) = std::fs::read_to_string(dir.join("net").join(file)) {
+                table.extend(Self::parse_inet_table(&content, family, protocol));
+            }
+        }
+        if let Ok(content) = std::fs::read_to_string(dir.join("net/unix")) {
+            table.extend(Self::parse_unix_table(&content));
+        }
+
+        files
+            .iter()
+            .filter_map(|file| {
+                let inode = SocketCollector::parse_socket_link(&file.target)?;
+                let entry = table.get(&inode).cloned();
+                Some(ProcessSocket {
+                    fd: file.fd,
+                    inode,
+                    family: entry.as_ref().map_or(SocketFamily::Other, |e| e.family),
+                    protocol: entry.as_ref().and_then(|e| e.protocol),
+                    local_address: entry.as_ref().and_then(|e| e.local_address.clone()),
+                    remote_address: entry.as_ref().and_then(|e| e.remote_address.clone()),
+                    state: entry.and_then(|e| e.state),
+                })
+            })
+            .collect()
+    }
+
+    /// Parse /proc/net/{tcp,tcp6,udp,udp6}
+    #[cfg(target_os = "linux")]
+    fn parse_inet_table(
+        content: &str,
+        family: SocketFamily,
+        protocol: ConnectionProtocol,
+    ) -> Vec<(u64, SocketEntry)> {
+        content
+            .lines()
+            .skip(1)
+            .filter_map(|line| {
+                let fields: Vec<&str> = line.split_whitespace().collect();
+                let local = Self::parse_hex_socket_address(fields.get(1)?)?;
+                let remote = Self::parse_hex_socket_address(fields.get(2)?)?;
+                let state = u8::from_str_radix(fields.get(3)?, 16).ok()?;
+                let inode = fields.get(9)?.parse().ok()?;
+
+                // Listening and unconnected sockets have no peer
+                let connected = remote.port() != 0 || !remote.ip().is_unspecified();
+                let state = match protocol {
+                    ConnectionProtocol::TCP => Some(Self::tcp_state(state)),
+                    ConnectionProtocol::UDP => None,
+                };
+
+                Some((
+                    inode,
+                    SocketEntry {
+                        family,
+                        protocol: Some(protocol),
+                        local_address: Some(local.to_string()),
+                        remote_address: connected.then(|| remote.to_string()),
+                        state,
+                    },
+                ))
+            })
+            .collect()
+    }
+
+    /// Parse an "0100007F:0035" address. The address is printed as 32-bit words in
+    /// host byte order, the port in plain hex.
+    #[cfg(target_os = "linux")]
+    fn parse_hex_socket_address(field: &str) -> Option<SocketAddr> {
+        let (address, port) = field.split_once(':')?;
+        let port = u16::from_str_radix(port, 16).ok()?;
+
+        let mut bytes = Vec::with_capacity(16);
+        for index in (0..address.len()).step_by(8) {
+            let word = u32::from_str_radix(address.get(index..index + 8)?, 16).ok()?;
+            bytes.extend_from_slice(&word.to_ne_bytes());
+        }
+        let ip = match bytes.len() {
+            4 => IpAddr::V4(Ipv4Addr::from(<[u8; 4]>::try_from(bytes).ok()?)),
+            16 => IpAddr::V6(Ipv6Addr::from(<[u8; 16]>::try_from(bytes).ok()?)),
+            _ => return None,
+        };
+        Some(SocketAddr::new(ip, port))
+    }
+
+    /// TCP state numbers from include/net/tcp_states.h
+    #[cfg(target_os = "linux")]
+    fn tcp_state(state: u8) -> ConnectionState {
+        match state {
+            1 => ConnectionState::Established,
+            2 => ConnectionState::SynSent,
+            3 => ConnectionState::SynReceived,
+            4 => ConnectionState::FinWait1,
+            5 => ConnectionState::FinWait2,
+            6 => ConnectionState::TimeWait,
+            7 => ConnectionState::Closed,
+            8 => ConnectionState::CloseWait,
+            9 => ConnectionState::LastAck,
+            10 => ConnectionState::Listen,
+            11 => ConnectionState::Closing,
+            _ => ConnectionState::Unknown,
+        }
+    }
+
+    /// Parse /proc/net/unix: "Num RefCount Protocol Flags Type St Inode Path"
+    #[cfg(target_os = "linux")]
+    fn parse_unix_table(content: &str) -> Vec<(u64, SocketEntry)> {
+        content
+            .lines()
+            .skip(1)
+            .filter_map(|line| {
+                let fields: Vec<&str> = line.split_whitespace().collect();
+                let inode = fields.get(6)?.parse().ok()?;
+                Some((
+                    inode,
+                    SocketEntry {
+                        family: SocketFamily::Unix,
+                        protocol: None,
+                        // Unbound sockets have no path; abstract ones start with '@'
+                        local_address: fields.get(7).map(|path| path.to_string()),
+                        remote_address: None,
+                        state: None,
+                    },
+                ))
+            })
+            .collect()
+    }
+
+    #[cfg(target_os = "linux")]
+    fn parse_environ(data: &[u8]) -> Vec<EnvironmentVariable> {
+        data.split(|&b| b == 0)
+            .filter_map(|entry| {
+                let entry = String::from_utf8_lossy(entry);
+                let (name, value) = entry.split_once('=')?;
+                Some(EnvironmentVariable {
+                    name: name.to_string(),
+                    value: value.to_string(),
+                })
+            })
+            .collect()
+    }
+
+    /// Parse /proc/<pid>/limits. The limit name is a fixed 26-character column
+    /// followed by the soft limit, hard limit and an optional unit.
+    #[cfg(target_os = "linux")]
+    fn parse_limits(content: &str) -> Vec<ResourceLimit> {
+        const NAME_WIDTH: usize = 26;
+        let value = |field: &str| field.parse::<u64>().ok();
+
+        content
+            .lines()
+            .skip(1)
+            .filter_map(|line| {
+                let name = line.get(..NAME_WIDTH)?.trim();
+                let mut columns = line[NAME_WIDTH..].split_whitespace();
+                let soft = columns.next()?;
+                let hard = columns.next()?;
+                Some(ResourceLimit {
+                    name: name.to_string(),
+                    soft: value(soft),
+                    hard: value(hard),
+                    unit: columns.next().map(|unit| unit.to_string()),
+                })
+            })
+            .collect()
+    }
+
+    /// Cgroup path from /proc/<pid>/cgroup, preferring the unified hierarchy
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    pub(crate) fn cgroup_path(content: &str) -> String {
+        content
+            .lines()
+            .find_map(|line| line.strip_prefix("0::"))
+            .or_else(|| {
+                content
+                    .lines()
+                    .find_map(|line| line.split_once(":name=systemd:").map(|(_, path)| path))
+            })
+            .or_else(|| {
+                content
+                    .lines()
+                    .next()
+                    .and_then(|line| line.splitn(3, ':').nth(2))
+            })
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    /// Capability sets from the Cap* lines of /proc/<pid>/status
+    #[cfg(target_os = "linux")]
+    fn parse_capabilities(status: &str) -> Option<ProcessCapabilities> {
+        let set = |key: &str| -> Option<Vec<String>> {
+            let mask = status.lines().find_map(|line| {
+                let (name, value) = line.split_once(':')?;
+                if name != key {
+                    return None;
+                }
+                u64::from_str_radix(value.trim(), 16).ok()
+            })?;
+            Some(
+                (0..64)
+                    .filter(|bit| mask & (1 << bit) != 0)
+                    .map(|bit| match CAPABILITY_NAMES.get(bit) {
+                        Some(name) => name.to_string(),
+                        None => format!("CAP_{}", bit),
+                    })
+                    .collect(),
+            )
+        };
+
+        Some(ProcessCapabilities {
+            inheritable: set("CapInh")?,
+            permitted: set("CapPrm")?,
+            effective: set("CapEff")?,
+            bounding: set("CapBnd")?,
+            // Added in Linux 4.3
+            ambient: set("CapAmb").unwrap_or_default(),
+        })
+    }
+
+    #[cfg(target_os = "linux")]
+    fn read_namespaces(dir: &Path) -> io::Result<Vec<ProcessNamespace>> {
+        let mut namespaces = Vec::new();
+        for entry in std::fs::read_dir(dir.join("ns"))?.flatten() {
+            // Links read "net:[4026531840]"
+            let target = std::fs::read_link(entry.path())?;
+            let inode = target
+                .to_string_lossy()
+                .split_once(":[")
+                .and_then(|(_, inode)| inode.strip_suffix(']')?.parse().ok());
+            if let Some(inode) = inode {
+                namespaces.push(ProcessNamespace {
+                    kind: entry.file_name().to_string_lossy().to_string(),
+                    inode,
+                });
+            }
+        }
+        namespaces.sort_by(|a, b| a.kind.cmp(&b.kind));
+        Ok(namespaces)
+    }
+
     fn process_to_info(
         pid: sysinfo::Pid,
         process: &Process,
@@ -78,8 +926,12 @@ impl ProcessCollector {
 
         // Get executable path and icon
         let exe_path = process.exe().map(|p| p.to_string_lossy().to_string());
-        let icon_base64 =
-            ICON_CACHE.get_icon_for_process(&process.name().to_string_lossy(), exe_path.as_deref());
+        let attribution = CgroupCollector::get_attribution(pid.as_u32());
+        let icon_hash = ICON_CACHE
+            .get_icon_hash_for_process(&process.name().to_string_lossy(), exe_path.as_deref());
+        let icon_base64 = icon_hash
+            .as_deref()
+            .and_then(|hash| ICON_CACHE.get_icon_by_hash(hash));
 
         ProcessInfo {
             pid: pid.as_u32(),
@@ -101,6 +953,11 @@ impl ProcessCollector {
             disk_write_bytes: disk_usage.written_bytes,
             exe_path,
             icon_base64,
+            icon_hash,
+            cgroup: attribution.path,
+            systemd_unit: attribution.systemd_unit,
+            systemd_slice: attribution.systemd_slice,
+            container: attribution.container,
         }
     }
 }
@@ -108,6 +965,7 @@ impl ProcessCollector {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::process::{ContainerInfo, ContainerRuntime};
 
     #[test]
     fn test_get_processes() {
@@ -170,6 +1028,11 @@ mod tests {
             disk_write_bytes: 512 * 1024,
             exe_path: Some("C:\\test\\test_process.exe".to_string()),
             icon_base64: None,
+            icon_hash: None,
+            cgroup: None,
+            systemd_unit: None,
+            systemd_slice: None,
+            container: None,
         };
 
         let json = serde_json::to_string(&process_info).unwrap();
@@ -197,4 +1060,490 @@ mod tests {
         assert!(json.contains("\"sleepingCount\":85"));
         assert!(json.contains("\"totalCpuUsage\":25.5"));
     }
+
+    fn tree_process(pid: u32, parent_pid: Option<u32>, exe: &str) -> ProcessInfo {
+        ProcessInfo {
+            pid,
+            parent_pid,
+            name: exe.rsplit('/').next().unwrap_or(exe).to_string(),
+            cpu_usage: 1.0,
+            memory_bytes: 100,
+            virtual_memory_bytes: 1000,
+            status: "Sleep".to_string(),
+            user: None,
+            command: exe.to_string(),
+            start_time: 0,
+            disk_read_bytes: 10,
+            disk_write_bytes: 5,
+            exe_path: Some(exe.to_string()),
+            icon_base64: None,
+            icon_hash: None,
+            cgroup: None,
+            systemd_unit: None,
+            systemd_slice: None,
+            container: None,
+        }
+    }
+
+    #[test]
+    fn test_build_process_tree_aggregates() {
+        let mut zombie = tree_process(5, Some(2), "/usr/bin/make");
+        zombie.status = "Zombie".to_string();
+        zombie.memory_bytes = 0;
+        let processes = vec![
+            tree_process(1, None, "/sbin/init"),
+            tree_process(2, Some(1), "/usr/bin/bash"),
+            tree_process(3, Some(2), "/usr/bin/vim"),
+            tree_process(4, Some(1), "/usr/sbin/sshd"),
+            zombie,
+        ];
+
+        let tree = ProcessCollector::build_process_tree(processes, false);
+        assert_eq!(tree.len(), 1);
+        let init = &tree[0];
+        assert_eq!(init.process.pid, 1);
+        assert!(!init.is_orphan);
+        assert_eq!(init.subtree_process_count, 5);
+        assert_eq!(init.subtree_memory_bytes, 400);
+        assert_eq!(init.subtree_disk_read_bytes, 50);
+        assert_eq!(init.subtree_zombie_count, 1);
+        assert!((init.subtree_cpu_usage - 5.0).abs() < f32::EPSILON);
+
+        let bash = &init.children[0];
+        assert_eq!(bash.process.pid, 2);
+        assert_eq!(bash.subtree_process_count, 3);
+        assert_eq!(
+            bash.children
+                .iter()
+                .map(|c| c.process.pid)
+                .collect::<Vec<_>>(),
+            vec![3, 5]
+        );
+        assert!(bash.children[1].is_zombie);
+    }
+
+    #[test]
+    fn test_build_process_tree_orphans_and_cycles() {
+        let processes = vec![
+            tree_process(1, None, "/sbin/init"),
+            // Parent 40 exited between snapshots
+            tree_process(41, Some(40), "/usr/bin/sleep"),
+            // PID reuse can produce a parent loop
+            tree_process(50, Some(51), "/usr/bin/a"),
+            tree_process(51, Some(50), "/usr/bin/b"),
+        ];
+
+        let tree = ProcessCollector::build_process_tree(processes, false);
+        let roots: Vec<u32> = tree.iter().map(|n| n.process.pid).collect();
+        assert_eq!(roots, vec![1, 41, 50]);
+        assert!(!tree[0].is_orphan);
+        assert!(tree[1].is_orphan);
+        assert!(tree[2].is_orphan);
+        assert_eq!(tree[2].subtree_process_count, 2);
+    }
+
+    #[test]
+    fn test_build_process_tree_collapse_by_executable() {
+        let processes = vec![
+            tree_process(1, None, "/sbin/init"),
+            tree_process(100, Some(1), "/opt/chrome/chrome"),
+            // Zygote and renderers run the same binary
+            tree_process(101, Some(100), "/opt/chrome/chrome"),
+            tree_process(102, Some(101), "/opt/chrome/chrome"),
+            tree_process(103, Some(101), "/opt/chrome/chrome"),
+            tree_process(104, Some(100), "/opt/chrome/chrome_crashpad"),
+            tree_process(200, Some(1), "/usr/bin/bash"),
+            tree_process(201, Some(1), "/usr/bin/bash"),
+            tree_process(202, Some(200), "/usr/bin/python3"),
+            tree_process(203, Some(201), "/usr/bin/python3"),
+        ];
+
+        let tree = ProcessCollector::build_process_tree(processes.clone(), true);
+        assert_eq!(tree.len(), 1);
+        let init = &tree[0];
+        assert_eq!(init.subtree_process_count, 10);
+        assert_eq!(init.children.len(), 2);
+
+        let chrome = &init.children[0];
+        assert_eq!(chrome.pids, vec![100, 101, 102, 103]);
+        assert_eq!(chrome.subtree_process_count, 5);
+        assert_eq!(chrome.children.len(), 1);
+        assert_eq!(chrome.children[0].process.pid, 104);
+
+        // Sibling shells merge, and so do their python children
+        let bash = &init.children[1];
+        assert_eq!(bash.process.pid, 200);
+        assert_eq!(bash.pids, vec![200, 201]);
+        assert_eq!(bash.subtree_memory_bytes, 400);
+        assert_eq!(bash.children.len(), 1);
+        assert_eq!(bash.children[0].pids, vec![202, 203]);
+
+        // Without collapsing every process keeps its own node
+        let tree = ProcessCollector::build_process_tree(processes, false);
+        assert_eq!(tree[0].children.len(), 3);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_process_tree_skips_threads() {
+        fn find(nodes: &[ProcessTreeNode], pid: u32) -> Option<&ProcessTreeNode> {
+            nodes.iter().find_map(|node| {
+                if node.process.pid == pid {
+                    Some(node)
+                } else {
+                    find(&node.children, pid)
+                }
+            })
+        }
+
+        let (tid_tx, tid_rx) = std::sync::mpsc::channel();
+        let (done_tx, done_rx) = std::sync::mpsc::channel::<()>();
+        let thread = std::thread::spawn(move || {
+            let tid = unsafe { libc::syscall(libc::SYS_gettid) } as u32;
+            tid_tx.send(tid).unwrap();
+            let _ = done_rx.recv();
+        });
+        let tid = tid_rx.recv().unwrap();
+
+        let processes = ProcessCollector::get_processes();
+        let tree = ProcessCollector::get_process_tree(false);
+        done_tx.send(()).unwrap();
+        thread.join().unwrap();
+
+        assert!(processes.iter().all(|p| p.pid != tid));
+        assert!(find(&tree, tid).is_none());
+        let own = find(&tree, std::process::id()).expect("own process in tree");
+        assert!(own.children.iter().all(|c| c.process.pid != tid));
+    }
+
+    #[test]
+    fn test_group_processes() {
+        let container = |id: &str| ContainerInfo {
+            id: id.repeat(64),
+            runtime: ContainerRuntime::Docker,
+            pod_uid: None,
+        };
+        let mut processes = vec![
+            tree_process(1, None, "/sbin/init"),
+            tree_process(100, Some(1), "/usr/bin/dockerd"),
+            tree_process(200, Some(1), "/usr/bin/node"),
+            tree_process(201, Some(200), "/usr/bin/node"),
+            tree_process(300, Some(1), "/usr/bin/python3"),
+        ];
+        for process in &mut processes {
+            process.cpu_usage = 1.5;
+            process.memory_bytes = 1000;
+        }
+        processes[0].systemd_unit = Some("init.scope".to_string());
+        processes[1].systemd_unit = Some("docker.service".to_string());
+        for process in &mut processes[2..4] {
+            process.container = Some(container("a"));
+            process.systemd_unit = Some(format!("docker-{}.scope", "a".repeat(64)));
+        }
+        processes[4].container = Some(container("b"));
+        processes[4].memory_bytes = 5000;
+
+        let groups = ProcessCollector::group_processes(&processes, ProcessGroupBy::Container);
+        let summary: Vec<(Option<String>, Vec<u32>, u64)> = groups
+            .iter()
+            .map(|g| (g.key.clone(), g.pids.clone(), g.memory_bytes))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (Some("b".repeat(64)), vec![300], 5000),
+                (Some("a".repeat(64)), vec![200, 201], 2000),
+                (None, vec![1, 100], 2000),
+            ]
+        );
+        assert_eq!(groups[1].cpu_usage, 3.0);
+        assert_eq!(groups[1].container, Some(container("a")));
+        assert_eq!(groups[2].container, None);
+
+        let groups = ProcessCollector::group_processes(&processes, ProcessGroupBy::Unit);
+        assert_eq!(groups.len(), 4);
+        assert_eq!(groups[3].key, None);
+        assert_eq!(groups[3].pids, vec![300]);
+        assert!(groups.iter().all(|g| g.container.is_none()));
+    }
+
+    #[test]
+    fn test_group_memory_by_application() {
+        let mut processes = vec![
+            tree_process(100, Some(1), "/opt/chrome/chrome"),
+            tree_process(101, Some(100), "/opt/chrome/chrome"),
+            tree_process(102, Some(100), "/opt/chrome/chrome"),
+            tree_process(200, Some(1), "/usr/bin/bash"),
+        ];
+        for process in &mut processes {
+            process.memory_bytes = 50 * 1024;
+        }
+        let usage = |pid, pss, private| ProcessMemory {
+            pid,
+            rss_bytes: 100 * 1024,
+            pss_bytes: pss,
+            uss_bytes: private,
+            shared_clean_bytes: 100 * 1024 - private,
+            private_clean_bytes: private,
+            ..Default::default()
+        };
+        let memory: HashMap<u32, ProcessMemory> = [
+            (100, usage(100, 40 * 1024, 20 * 1024)),
+            (101, usage(101, 30 * 1024, 10 * 1024)),
+            (200, usage(200, 90 * 1024, 80 * 1024)),
+        ]
+        .into_iter()
+        .collect();
+
+        let applications = ProcessCollector::group_memory_by_application(&processes, &memory);
+        assert_eq!(applications.len(), 2);
+
+        let bash = &applications[0];
+        assert_eq!(bash.exe_path.as_deref(), Some("/usr/bin/bash"));
+        assert_eq!(bash.pss_bytes, 90 * 1024);
+
+        let chrome = &applications[1];
+        assert_eq!(chrome.pids, vec![100, 101, 102]);
+        assert_eq!(chrome.pss_bytes, 70 * 1024);
+        assert_eq!(chrome.uss_bytes, 30 * 1024);
+        assert_eq!(chrome.shared_bytes, 170 * 1024);
+        // The unreadable process still counts towards RSS
+        assert_eq!(chrome.rss_bytes, 250 * 1024);
+        assert_eq!(chrome.inaccessible_count, 1);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_smaps_rollup() {
+        let content = "\
+56503ea86000-7fffe6572000 ---p 00000000 00:00 0                          [rollup]
+Rss:                1444 kB
+Pss:                 482 kB
+Pss_Dirty:           104 kB
+Pss_Anon:            104 kB
+Shared_Clean:       1300 kB
+Shared_Dirty:          0 kB
+Private_Clean:        40 kB
+Private_Dirty:       104 kB
+Swap:                 16 kB
+SwapPss:               8 kB
+Locked:                0 kB
+";
+        let memory = ProcessCollector::parse_smaps_rollup(42, content).unwrap();
+        assert_eq!(
+            memory,
+            ProcessMemory {
+                pid: 42,
+                rss_bytes: 1444 * 1024,
+                pss_bytes: 482 * 1024,
+                uss_bytes: 144 * 1024,
+                shared_clean_bytes: 1300 * 1024,
+                shared_dirty_bytes: 0,
+                private_clean_bytes: 40 * 1024,
+                private_dirty_bytes: 104 * 1024,
+                swap_bytes: 16 * 1024,
+                swap_pss_bytes: 8 * 1024,
+            }
+        );
+
+        assert!(ProcessCollector::parse_smaps_rollup(42, "").is_none());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_get_process_memory_self() {
+        let memory = ProcessCollector::get_process_memory(std::process::id()).unwrap();
+        assert!(memory.rss_bytes > 0);
+        assert!(memory.pss_bytes <= memory.rss_bytes);
+        assert!(memory.uss_bytes <= memory.pss_bytes);
+
+        assert!(ProcessCollector::get_process_memory(u32::MAX).is_err());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_get_process_details_self() {
+        let details = ProcessCollector::get_process_details(std::process::id()).unwrap();
+        assert_eq!(details.pid, std::process::id());
+        assert!(!details.name.is_empty());
+
+        // Our own process is always readable
+        let threads = details.threads.value().expect("threads should be readable");
+        assert!(threads.iter().any(|t| t.tid == std::process::id()));
+        assert!(details.cwd.value().is_some());
+        assert!(details
+            .open_files
+            .value()
+            .is_some_and(|files| !files.is_empty()));
+        assert!(details
+            .limits
+            .value()
+            .is_some_and(|limits| limits.iter().any(|l| l.name == "Max open files")));
+        assert!(details
+            .namespaces
+            .value()
+            .is_some_and(|ns| ns.iter().any(|n| n.kind == "net" && n.inode > 0)));
+        assert!(details.capabilities.value().is_some());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_get_process_details_missing() {
+        let result = ProcessCollector::get_process_details(u32::MAX);
+        assert!(result.is_err());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_detail_marks_permission_denied() {
+        use crate::models::process::ProcessDetailField;
+
+        let denied: ProcessDetailField<String> = ProcessCollector::detail(Err(
+            std::io::Error::from(std::io::ErrorKind::PermissionDenied),
+        ));
+        assert!(matches!(denied, ProcessDetailField::PermissionDenied));
+        let gone: ProcessDetailField<String> =
+            ProcessCollector::detail(Err(std::io::Error::from(std::io::ErrorKind::NotFound)));
+        assert!(matches!(gone, ProcessDetailField::Unavailable));
+
+        let json = serde_json::to_string(&denied).unwrap();
+        assert_eq!(json, r#"{"status":"permissionDenied"}"#);
+        let json = serde_json::to_string(&ProcessDetailField::Available(42)).unwrap();
+        assert_eq!(json, r#"{"status":"available","value":42}"#);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_thread_stat() {
+        // A thread name containing spaces and parentheses
+        let stat = "4242 (Web Content (x)) S 4200 4200 4200 0 -1 4194560 12000 0 0 0 \
+                    250 75 0 0 20 0 31 0 123456 3000000000 50000 18446744073709551615 \
+                    1 1 0 0 0 0 0 16781312 83192 0 0 0 -1 3 0 0 0 0 0";
+        let thread = ProcessCollector::parse_thread_stat(4242, stat, 100).unwrap();
+        assert_eq!(thread.name, "Web Content (x)");
+        assert_eq!(thread.state, "Sleep");
+        assert_eq!(thread.user_time_ms, 2500);
+        assert_eq!(thread.system_time_ms, 750);
+        assert_eq!(thread.last_cpu, Some(3));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_open_file_kind() {
+        use crate::models::process::OpenFileKind;
+
+        let kind = ProcessCollector::open_file_kind;
+        assert_eq!(kind("socket:[12345]"), OpenFileKind::Socket);
+        assert_eq!(kind("pipe:[6789]"), OpenFileKind::Pipe);
+        assert_eq!(kind("anon_inode:[eventfd]"), OpenFileKind::AnonInode);
+        assert_eq!(kind("/dev/pts/0"), OpenFileKind::Device);
+        assert_eq!(kind("/home/user/.cache/x.db"), OpenFileKind::File);
+        assert_eq!(kind("/tmp/scratch (deleted)"), OpenFileKind::Deleted);
+        assert_eq!(kind("net:[4026531840]"), OpenFileKind::Other);
+    }
+
+    #[cfg(all(target_os = "linux", target_endian = "little"))]
+    #[test]
+    fn test_parse_inet_table() {
+        use crate::models::process::SocketFamily;
+        use crate::models::{ConnectionProtocol, ConnectionState};
+
+        let tcp = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode\n\
+                   \x20  0: 3500007F:0035 00000000:0000 0A 00000000:00000000 00:00000000 00000000   991        0 21290 1 0000000000000000 100 0 0 10 5\n\
+                   \x20  1: 0F02000A:C5A4 5DB8D822:01BB 01 00000000:00000000 02:000A7D50 00000000  1000        0 88412 2 0000000000000000 20 4 30 10 -1\n";
+        let entries =
+            ProcessCollector::parse_inet_table(tcp, SocketFamily::Ipv4, ConnectionProtocol::TCP);
+        assert_eq!(entries.len(), 2);
+
+        let (inode, listener) = &entries[0];
+        assert_eq!(*inode, 21290);
+        assert_eq!(listener.local_address.as_deref(), Some("127.0.0.53:53"));
+        assert!(listener.remote_address.is_none());
+        assert!(matches!(listener.state, Some(ConnectionState::Listen)));
+
+        let (inode, connection) = &entries[1];
+        assert_eq!(*inode, 88412);
+        assert_eq!(connection.local_address.as_deref(), Some("10.0.2.15:50596"));
+        assert_eq!(
+            connection.remote_address.as_deref(),
+            Some("34.216.184.93:443")
+        );
+        assert!(matches!(
+            connection.state,
+            Some(ConnectionState::Established)
+        ));
+
+        let address =
+            ProcessCollector::parse_hex_socket_address("00000000000000000000000001000000:1F90")
+                .unwrap();
+        assert_eq!(address.to_string(), "[::1]:8080");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_unix_table() {
+        let unix = "Num       RefCount Protocol Flags    Type St Inode Path\n\
+                    0000000000000000: 00000002 00000000 00010000 0001 01 23456 /run/systemd/notify\n\
+                    0000000000000000: 00000003 00000000 00000000 0001 03 23457\n";
+        let entries = ProcessCollector::parse_unix_table(unix);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].0, 23456);
+        assert_eq!(
+            entries[0].1.local_address.as_deref(),
+            Some("/run/systemd/notify")
+        );
+        assert!(entries[1].1.local_address.is_none());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_limits() {
+        let limits = "Limit                     Soft Limit           Hard Limit           Units     \n\
+                      Max cpu time              unlimited            unlimited            seconds   \n\
+                      Max open files            1024                 524288               files     \n\
+                      Max realtime priority     0                    0                    \n";
+        let limits = ProcessCollector::parse_limits(limits);
+        assert_eq!(limits.len(), 3);
+        assert_eq!(limits[0].name, "Max cpu time");
+        assert_eq!(limits[0].soft, None);
+        assert_eq!(limits[0].unit.as_deref(), Some("seconds"));
+        assert_eq!(limits[1].soft, Some(1024));
+        assert_eq!(limits[1].hard, Some(524288));
+        assert_eq!(limits[2].name, "Max realtime priority");
+        assert_eq!(limits[2].unit, None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_capabilities() {
+        let status = "Name:\tping\nCapInh:\t0000000000000000\nCapPrm:\t0000000000002000\n\
+                      CapEff:\t0000000000002000\nCapBnd:\t000001ffffffffff\nCapAmb:\t0000000000000000\n";
+        let caps = ProcessCollector::parse_capabilities(status).unwrap();
+        assert!(caps.inheritable.is_empty());
+        assert_eq!(caps.effective, vec!["CAP_NET_RAW".to_string()]);
+        assert_eq!(caps.bounding.len(), 41);
+        assert_eq!(caps.bounding[40], "CAP_CHECKPOINT_RESTORE");
+
+        assert!(ProcessCollector::parse_capabilities("Name:\tkthreadd\n").is_none());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_environ_and_cgroup() {
+        let env = ProcessCollector::parse_environ(b"HOME=/root\0LANG=C.UTF-8\0OPTS=a=b\0");
+        assert_eq!(env.len(), 3);
+        assert_eq!(env[2].name, "OPTS");
+        assert_eq!(env[2].value, "a=b");
+
+        assert_eq!(
+            ProcessCollector::cgroup_path("0::/system.slice/sshd.service\n"),
+            "/system.slice/sshd.service"
+        );
+        assert_eq!(
+            ProcessCollector::cgroup_path(
+                "12:cpu:/foo\n1:name=systemd:/user.slice/session-2.scope\n"
+            ),
+            "/user.slice/session-2.scope"
+        );
+    }
 }
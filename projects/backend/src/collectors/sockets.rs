@@ -0,0 +1,518 @@
+//! Socket table collector
+//!
+//! Dumps TCP/UDP sockets over the sock_diag netlink interface and maps socket inodes
+//! back to the processes that hold them open.
+
+use crate::models::{ListeningPort, PortExposure};
+use std::collections::HashMap;
+use std::net::IpAddr;
+
+#[cfg(target_os = "linux")]
+use crate::collectors::netlink::{Attributes, NetlinkSocket, NLM_F_DUMP};
+#[cfg(target_os = "linux")]
+use crate::models::ConnectionProtocol;
+#[cfg(target_os = "linux")]
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+
+// sock_diag (linux/sock_diag.h, linux/inet_diag.h)
+#[cfg(target_os = "linux")]
+const SOCK_DIAG_BY_FAMILY: u16 = 20;
+#[cfg(target_os = "linux")]
+const INET_DIAG_REQ_LEN: usize = 56;
+#[cfg(target_os = "linux")]
+const INET_DIAG_MSG_LEN: usize = 72;
+#[cfg(target_os = "linux")]
+const INET_DIAG_INFO: u16 = 2;
+
+// Offsets of the byte counters in struct tcp_info (linux/tcp.h, kernel 4.1+)
+#[cfg(target_os = "linux")]
+const TCP_INFO_BYTES_ACKED: usize = 120;
+#[cfg(target_os = "linux")]
+const TCP_INFO_BYTES_RECEIVED: usize = 128;
+
+/// State mask matching every socket state
+#[cfg(target_os = "linux")]
+pub(crate) const ALL_STATES: u32 = u32::MAX;
+/// TCP sockets in TCP_LISTEN
+#[cfg(target_os = "linux")]
+const LISTEN_STATES: u32 = 1 << 10;
+/// Unconnected UDP sockets report TCP_CLOSE
+#[cfg(target_os = "linux")]
+const UDP_UNCONNECTED_STATES: u32 = 1 << 7;
+
+/// A TCP or UDP socket as reported by sock_diag
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone)]
+pub(crate) struct InetSocket {
+    pub local: SocketAddr,
+    pub remote: SocketAddr,
+    pub inode: u64,
+    /// From tcp_info; None for UDP sockets and kernels without the counters
+    pub bytes_acked: Option<u64>,
+    pub bytes_received: Option<u64>,
+}
+
+/// Cumulative byte counters of one socket, attributed to its owning process
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SocketCounters {
+    pub inode: u64,
+    pub pid: u32,
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+}
+
+/// Collector for the kernel socket tables
+pub struct SocketCollector;
+
+impl SocketCollector {
+    /// Get byte counters for every TCP socket owned by a process we can inspect.
+    /// Sockets of other users' processes are only attributed when running as root.
+    pub fn get_tcp_socket_counters() -> Vec<SocketCounters> {
+        #[cfg(target_os = "linux")]
+        {
+            let mut sockets = Vec::new();
+            for family in [libc::AF_INET, libc::AF_INET6] {
+                match Self::dump_inet_sockets(family as u8, libc::IPPROTO_TCP as u8, ALL_STATES) {
+                    Ok(dumped) => sockets.extend(dumped),
+                    Err(e) => log::warn!("Failed to dump TCP sockets: {}", e),
+                }
+            }
+
+            let owners = Self::socket_owners();
+            sockets
+                .iter()
+                .filter_map(|socket| {
+                    Some(SocketCounters {
+                        inode: socket.inode,
+                        pid: *owners.get(&socket.inode)?,
+                        bytes_sent: socket.bytes_acked?,
+                        bytes_received: socket.bytes_received?,
+                    })
+                })
+                .collect()
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            Vec::new()
+        }
+    }
+
+    /// Get every listening TCP socket and unconnected UDP socket with its owning
+    /// process. Allowlist flags are left unset for the caller to apply.
+    pub fn get_listening_ports() -> Vec<ListeningPort> {
+        #[cfg(target_os = "linux")]
+        {
+            let services = std::fs::read_to_string("/etc/services")
+                .map(|content| Self::parse_services(&content))
+                .unwrap_or_default();
+            let owners = Self::socket_owners();
+
+            let queries = [
+                (ConnectionProtocol::TCP, libc::IPPROTO_TCP, LISTEN_STATES),
+                (
+                    ConnectionProtocol::UDP,
+                    libc::IPPROTO_UDP,
+                    UDP_UNCONNECTED_STATES,
+                ),
+            ];
+
+            let mut ports = Vec::new();
+            for (protocol, ip_protocol, states) in queries {
+                for family in [libc::AF_INET, libc::AF_INET6] {
+                    let sockets =
+                        match Self::dump_inet_sockets(family as u8, ip_protocol as u8, states) {
+                            Ok(sockets) => sockets,
+                            Err(e) => {
+                                log::warn!("Failed to dump {:?} sockets: {}", protocol, e);
+                                continue;
+                            }
+                        };
+
+                    // Connected UDP sockets also report TCP_CLOSE
+                    for socket in sockets.iter().filter(|s| s.remote.port() == 0) {
+                        let pid = owners.get(&socket.inode).copied();
+                        let proto_name = match protocol {
+                            ConnectionProtocol::TCP => "tcp",
+                            ConnectionProtocol::UDP => "udp",
+                        };
+
+                        ports.push(ListeningPort {
+                            protocol,
+                            local_address: socket.local.ip().to_string(),
+                            local_port: socket.local.port(),
+                            exposure: Self::exposure(&socket.local.ip()),
+                            service_name: services
+                                .get(&(socket.local.port(), proto_name.to_string()))
+                                .cloned(),
+                            pid,
+                            process_name: pid.and_then(|pid| {
+                                std::fs::read_to_string(format!("/proc/{}/comm", pid))
+                                    .ok()
+                                    .map(|comm| comm.trim().to_string())
+                            }),
+                            exe_path: pid.and_then(|pid| {
+                                std::fs::read_link(format!("/proc/{}/exe", pid))
+                                    .ok()
+                                    .map(|exe| exe.to_string_lossy().to_string())
+                            }),
+                            unit: pid.and_then(|pid| {
+                                std::fs::read_to_string(format!("/proc/{}/cgroup", pid))
+                                    .ok()
+                                    .and_then(|content| Self::unit_from_cgroup(&content))
+                            }),
+                            allowed: false,
+                            flagged: false,
+                        });
+                    }
+                }
+            }
+
+            ports.sort_by(|a, b| {
+                (a.local_port, a.local_address.as_str())
+                    .cmp(&(b.local_port, b.local_address.as_str()))
+            });
+            ports
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            Vec::new()
+        }
+    }
+
+    /// Classify which hosts can reach a socket bound to `address`
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    fn exposure(address: &IpAddr) -> PortExposure {
+        // Dual-stack sockets report IPv4 addresses as ::ffff:a.b.c.d
+        let address = match address {
+            IpAddr::V6(v6) => v6
+                .to_ipv4_mapped()
+                .map(IpAddr::V4)
+                .unwrap_or(IpAddr::V6(*v6)),
+            v4 => *v4,
+        };
+
+        if address.is_loopback() {
+            PortExposure::Loopback
+        } else if address.is_unspecified() {
+            PortExposure::All
+        } else {
+            PortExposure::LAN
+        }
+    }
+
+    /// Parse /etc/services into (port, protocol) -> service name.
+    /// The first name listed for a port wins.
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    fn parse_services(content: &str) -> HashMap<(u16, String), String> {
+        let mut services = HashMap::new();
+
+        for line in content.lines() {
+            let line = line.split('#').next().unwrap_or("");
+            let mut fields = line.split_whitespace();
+            let (Some(name), Some(port_proto)) = (fields.next(), fields.next()) else {
+                continue;
+            };
+            let Some((port, proto)) = port_proto.split_once('/') else {
+                continue;
+            };
+            if let Ok(port) = port.parse::<u16>() {
+                services
+                    .entry((port, proto.to_lowercase()))
+                    .or_insert_with(|| name.to_string());
+            }
+        }
+
+        services
+    }
+
+    /// Get the systemd unit (service or scope) from /proc/<pid>/cgroup content
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    pub(crate) fn unit_from_cgroup(content: &str) -> Option<String> {
+        // Prefer the unified hierarchy ("0::/...") and fall back to the systemd controller
+        let path = content
+            .lines()
+            .find_map(|line| line.strip_prefix("0::"))
+            .or_else(|| {
+                content
+                    .lines()
+                    .find_map(|line| line.split_once(":name=systemd:").map(|(_, path)| path))
+            })?;
+
+        path.rsplit('/')
+            .find(|segment| segment.ends_with(".service") || segment.ends_with(".scope"))
+            .map(|unit| unit.to_string())
+    }
+
+    /// Dump sockets of one address family and protocol whose state is in `states`
+    /// (a bit mask of 1 << state)
+    #[cfg(target_os = "linux")]
+    pub(crate) fn dump_inet_sockets(
+        family: u8,
+        protocol: u8,
+        states: u32,
+    ) -> std::io::Result<Vec<InetSocket>> {
+        let mut socket = NetlinkSocket::open(libc::NETLINK_SOCK_DIAG)?;
+
+        // struct inet_diag_req_v2 with an empty socket id (match everything)
+        let mut request = [0u8; INET_DIAG_REQ_LEN];
+        request[0] = family;
+        request[1] = protocol;
+        request[2] = 1 << (INET_DIAG_INFO - 1);
+        request[4..8].copy_from_slice(&states.to_ne_bytes());
+
+        let replies = socket.request(SOCK_DIAG_BY_FAMILY, NLM_F_DUMP, &request)?;
+        Ok(replies
+            .iter()
+            .filter(|m| m.msg_type == SOCK_DIAG_BY_FAMILY)
+            .filter_map(|m| Self::parse_inet_diag_message(&m.payload))
+            .collect())
+    }
+
+    /// Parse a struct inet_diag_msg followed by INET_DIAG_* attributes
+    #[cfg(target_os = "linux")]
+    fn parse_inet_diag_message(payload: &[u8]) -> Option<InetSocket> {
+        let header = payload.get(..INET_DIAG_MSG_LEN)?;
+        let inode = u32::from_ne_bytes([header[68], header[69], header[70], header[71]]);
+
+        let address = |offset: usize| -> Option<IpAddr> {
+            match header[0] as i32 {
+                libc::AF_INET => {
+                    let octets: [u8; 4] = header[offset..offset + 4].try_into().ok()?;
+                    Some(IpAddr::V4(Ipv4Addr::from(octets)))
+                }
+                libc::AF_INET6 => {
+                    let octets: [u8; 16] = header[offset..offset + 16].try_into().ok()?;
+                    Some(IpAddr::V6(Ipv6Addr::from(octets)))
+                }
+                _ => None,
+            }
+        };
+        // Ports are in network byte order
+        let local_port = u16::from_be_bytes([header[4], header[5]]);
+        let remote_port = u16::from_be_bytes([header[6], header[7]]);
+
+        let attrs = Attributes::parse(&payload[INET_DIAG_MSG_LEN..]);
+        let tcp_info = attrs.get(INET_DIAG_INFO);
+        let tcp_counter = |offset: usize| -> Option<u64> {
+            let bytes = tcp_info?.get(offset..offset + 8)?;
+            Some(u64::from_ne_bytes(bytes.try_into().ok()?))
+        };
+
+        Some(InetSocket {
+            local: SocketAddr::new(address(8)?, local_port),
+            remote: SocketAddr::new(address(24)?, remote_port),
+            inode: inode as u64,
+            bytes_acked: tcp_counter(TCP_INFO_BYTES_ACKED),
+            bytes_received: tcp_counter(TCP_INFO_BYTES_RECEIVED),
+        })
+    }
+
+    /// Map socket inodes to the PID holding them by scanning /proc/<pid>/fd.
+    /// A socket shared across a fork is attributed to the lowest PID.
+    pub(crate) fn socket_owners() -> HashMap<u64, u32> {
+        let mut owners = HashMap::new();
+
+        #[cfg(target_os = "linux")]
+        {
+            let Ok(entries) = std::fs::read_dir("/proc") else {
+                return owners;
+            };
+
+            let mut pids: Vec<u32> = entries
+                .flatten()
+                .filter_map(|e| e.file_name().to_str()?.parse().ok())
+                .collect();
+            pids.sort_unstable();
+
+            for pid in pids {
+                // Processes of other users are unreadable without root
+                let Ok(fds) = std::fs::read_dir(format!("/proc/{}/fd", pid)) else {
+                    continue;
+                };
+                for fd in fds.flatten() {
+                    if let Some(inode) = std::fs::read_link(fd.path())
+                        .ok()
+                        .and_then(|target| Self::parse_socket_link(&target.to_string_lossy()))
+                    {
+                        owners.entry(inode).or_insert(pid);
+                    }
+                }
+            }
+        }
+
+        owners
+    }
+
+    /// Parse a "socket:[12345]" fd link target
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    pub(crate) fn parse_socket_link(target: &str) -> Option<u64> {
+        target
+            .strip_prefix("socket:[")?
+            .strip_suffix(']')?
+            .parse()
+            .ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_socket_link() {
+        assert_eq!(
+            SocketCollector::parse_socket_link("socket:[48213]"),
+            Some(48213)
+        );
+        assert_eq!(SocketCollector::parse_socket_link("pipe:[48213]"), None);
+        assert_eq!(SocketCollector::parse_socket_link("/dev/null"), None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_recorded_inet_diag_message() {
+        // inet_diag_msg: 127.0.0.1:40000 -> 127.0.0.1:8080, ESTABLISHED, uid 1000,
+        // inode 48213, followed by a tcp_info attribute with bytes_acked 4096 and
+        // bytes_received 1234
+        let mut payload = vec![libc::AF_INET as u8, 1, 0, 0];
+        payload.extend_from_slice(&40000u16.to_be_bytes());
+        payload.extend_from_slice(&8080u16.to_be_bytes());
+        for _ in 0..2 {
+            payload.extend_from_slice(&[127, 0, 0, 1]);
+            payload.extend_from_slice(&[0; 12]);
+        }
+        payload.extend_from_slice(&[0; 24]);
+        payload.extend_from_slice(&1000u32.to_ne_bytes());
+        payload.extend_from_slice(&48213u32.to_ne_bytes());
+
+        let mut tcp_info = vec![0u8; 136];
+        tcp_info[120..128].copy_from_slice(&4096u64.to_ne_bytes());
+        tcp_info[128..136].copy_from_slice(&1234u64.to_ne_bytes());
+        crate::collectors::netlink::push_attribute(&mut payload, INET_DIAG_INFO, &tcp_info);
+
+        let socket = SocketCollector::parse_inet_diag_message(&payload).unwrap();
+        assert_eq!(socket.local.to_string(), "127.0.0.1:40000");
+        assert_eq!(socket.remote.to_string(), "127.0.0.1:8080");
+        assert_eq!(socket.inode, 48213);
+        assert_eq!(socket.bytes_acked, Some(4096));
+        assert_eq!(socket.bytes_received, Some(1234));
+
+        // Without tcp_info (e.g. UDP) the counters are unknown
+        let socket = SocketCollector::parse_inet_diag_message(&payload[..72]).unwrap();
+        assert_eq!(socket.bytes_acked, None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_loopback_socket_attributed_to_self() {
+        use std::io::{Read, Write};
+        use std::net::{TcpListener, TcpStream};
+        use std::os::fd::AsRawFd;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (mut server, _) = listener.accept().unwrap();
+        client.write_all(&[0u8; 1000]).unwrap();
+        let mut buf = [0u8; 1000];
+        server.read_exact(&mut buf).unwrap();
+
+        // sock_diag may be unavailable in restricted sandboxes
+        let Ok(sockets) = SocketCollector::dump_inet_sockets(
+            libc::AF_INET as u8,
+            libc::IPPROTO_TCP as u8,
+            ALL_STATES,
+        ) else {
+            return;
+        };
+        let link = std::fs::read_link(format!("/proc/self/fd/{}", server.as_raw_fd())).unwrap();
+        let inode = SocketCollector::parse_socket_link(&link.to_string_lossy()).unwrap();
+        let socket = sockets.iter().find(|s| s.inode == inode).unwrap();
+        assert_eq!(socket.bytes_received, Some(1000));
+
+        let owners = SocketCollector::socket_owners();
+        assert_eq!(owners.get(&inode), Some(&std::process::id()));
+    }
+
+    #[test]
+    fn test_exposure() {
+        let exposure = |addr: &str| SocketCollector::exposure(&addr.parse().unwrap());
+        assert_eq!(exposure("127.0.0.1"), PortExposure::Loopback);
+        assert_eq!(exposure("::1"), PortExposure::Loopback);
+        assert_eq!(exposure("::ffff:127.0.0.53"), PortExposure::Loopback);
+        assert_eq!(exposure("0.0.0.0"), PortExposure::All);
+        assert_eq!(exposure("::"), PortExposure::All);
+        assert_eq!(exposure("192.168.1.20"), PortExposure::LAN);
+        assert_eq!(exposure("fe80::1"), PortExposure::LAN);
+    }
+
+    #[test]
+    fn test_parse_services() {
+        let content = "\
+# Network services, Internet style
+ssh             22/tcp                          # SSH Remote Login Protocol
+domain          53/tcp
+domain          53/udp
+http            80/tcp          www             # WorldWideWeb HTTP
+www-alt         80/tcp
+";
+        let services = SocketCollector::parse_services(content);
+        assert_eq!(
+            services.get(&(22, "tcp".to_string())).map(String::as_str),
+            Some("ssh")
+        );
+        assert_eq!(
+            services.get(&(53, "udp".to_string())).map(String::as_str),
+            Some("domain")
+        );
+        assert_eq!(
+            services.get(&(80, "tcp".to_string())).map(String::as_str),
+            Some("http")
+        );
+        assert_eq!(services.get(&(22, "udp".to_string())), None);
+    }
+
+    #[test]
+    fn test_unit_from_cgroup() {
+        assert_eq!(
+            SocketCollector::unit_from_cgroup("0::/system.slice/sshd.service\n").as_deref(),
+            Some("sshd.service")
+        );
+        assert_eq!(
+            SocketCollector::unit_from_cgroup(
+                "0::/user.slice/user-1000.slice/user@1000.service/app.slice/app-firefox-1234.scope\n"
+            )
+            .as_deref(),
+            Some("app-firefox-1234.scope")
+        );
+        // cgroup v1 systemd hierarchy
+        assert_eq!(
+            SocketCollector::unit_from_cgroup(
+                "12:cpu,cpuacct:/system.slice/nginx.service\n1:name=systemd:/system.slice/nginx.service\n"
+            )
+            .as_deref(),
+            Some("nginx.service")
+        );
+        assert_eq!(SocketCollector::unit_from_cgroup("0::/\n"), None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_listening_port_found() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let ports = SocketCollector::get_listening_ports();
+        // sock_diag may be unavailable in restricted sandboxes
+        if ports.is_empty() {
+            return;
+        }
+        let found = ports
+            .iter()
+            .find(|p| p.local_port == port && p.protocol == ConnectionProtocol::TCP)
+            .unwrap();
+        assert_eq!(found.exposure, PortExposure::Loopback);
+        assert_eq!(found.pid, Some(std::process::id()));
+    }
+}
@@ -2,13 +2,40 @@
 
 use crate::models::{
     DiskHealth, DiskPerformance, HealthStatus, InterfaceType, MediaType, NetworkDrive,
-    NetworkDriveStatus, Partition, PartitionStyle, PhysicalDisk, Volume,
+    NetworkDriveProtocol, NetworkDriveStatus, Partition, PartitionStyle, PhysicalDisk, Volume,
 };
 use sysinfo::{Disk, DiskKind, Disks};
 
+#[cfg(target_os = "linux")]
+use std::sync::{mpsc, Mutex};
+#[cfg(target_os = "linux")]
+use std::time::{Duration, Instant};
+
+/// How long to wait for a network mount to answer statvfs before reporting it as unresponsive
+#[cfg(target_os = "linux")]
+const NETWORK_MOUNT_PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Mount points with a statvfs probe still stuck in the kernel (hung NFS server etc.).
+/// New probes are not started for these so repeated polling cannot pile up blocked threads.
+#[cfg(target_os = "linux")]
+static PENDING_MOUNT_PROBES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
 /// Collector for storage information
 pub struct StorageCollector;
 
+/// A single mount from /proc/self/mountinfo
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone)]
+struct MountEntry {
+    mount_point: String,
+    /// Per-mount options (e.g. "rw", "relatime")
+    mount_options: Vec<String>,
+    fs_type: String,
+    source: String,
+    /// Filesystem-wide options (e.g. "compress=zstd:3" on btrfs)
+    super_options: Vec<String>,
+}
+
 /// Helper struct to hold MSFT_PhysicalDisk data
 #[cfg(target_os = "windows")]
 #[derive(Debug, Clone)]
@@ -27,12 +54,232 @@ impl StorageCollector {
             Self::get_physical_disks_windows()
         }
 
-        #[cfg(not(target_os = "windows"))]
+        #[cfg(target_os = "linux")]
+        {
+            Self::get_linux_block_disks()
+                .into_iter()
+                .map(|(_, disk)| disk)
+                .collect()
+        }
+
+        #[cfg(not(any(target_os = "windows", target_os = "linux")))]
         {
             Vec::new()
         }
     }
 
+    /// Physical disks from /sys/block, paired with their kernel name ("nvme0n1", "sda").
+    /// Device IDs are assigned in kernel-name order.
+    #[cfg(target_os = "linux")]
+    pub(crate) fn get_linux_block_disks() -> Vec<(String, PhysicalDisk)> {
+        let mut names: Vec<String> = match std::fs::read_dir("/sys/block") {
+            Ok(entries) => entries
+                .flatten()
+                .map(|e| e.file_name().to_string_lossy().to_string())
+                // Virtual devices (loop, dm, md, zram) have no backing hardware device
+                .filter(|name| {
+                    std::path::Path::new("/sys/block")
+                        .join(name)
+                        .join("device")
+                        .exists()
+                })
+                .collect(),
+            Err(e) => {
+                log::warn!("Failed to read /sys/block: {}", e);
+                return Vec::new();
+            }
+        };
+        names.sort();
+
+        names
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, name)| {
+                let disk = Self::read_linux_disk(&name, index as u32)?;
+                Some((name, disk))
+            })
+            .collect()
+    }
+
+    #[cfg(target_os = "linux")]
+    fn read_linux_disk(name: &str, device_id: u32) -> Option<PhysicalDisk> {
+        let sys_path = std::path::Path::new("/sys/block").join(name);
+        let read = |file: &str| {
+            std::fs::read_to_string(sys_path.join(file))
+                .map(|s| s.trim().to_string())
+                .ok()
+                .filter(|s| !s.is_empty())
+        };
+
+        let size_bytes = read("size")?.parse::<u64>().ok()? * 512;
+        // Empty card readers and optical drives report a size of zero
+        if size_bytes == 0 {
+            return None;
+        }
+
+        let model = read("device/model").unwrap_or_default();
+        let udev = Self::read_udev_properties(&read("dev").unwrap_or_default());
+
+        // Bus is visible in the resolved sysfs path (.../usb1/..., .../ata1/..., .../nvme/...)
+        let device_path = std::fs::canonicalize(&sys_path)
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let interface_type = if name.starts_with("nvme") {
+            InterfaceType::NVMe
+        } else if device_path.contains("/usb") {
+            InterfaceType::USB
+        } else if device_path.contains("/ata") {
+            InterfaceType::SATA
+        } else if name.starts_with("sd") {
+            InterfaceType::SCSI
+        } else {
+            InterfaceType::Unknown
+        };
+
+        let media_type = if name.starts_with("nvme") {
+            MediaType::NVMe
+        } else if read("removable").as_deref() == Some("1")
+            || matches!(interface_type, InterfaceType::USB)
+        {
+            MediaType::Removable
+        } else {
+            match read("queue/rotational").as_deref() {
+                Some("1") => MediaType::HDD,
+                Some("0") => MediaType::SSD,
+                _ => MediaType::Unknown,
+            }
+        };
+
+        let partition_style = match udev.get("ID_PART_TABLE_TYPE").map(|s| s.as_str()) {
+            Some("gpt") => PartitionStyle::GPT,
+            Some("dos") => PartitionStyle::MBR,
+            _ => PartitionStyle::RAW,
+        };
+
+        // SATA/SCSI vendor is usually the useless "ATA"; fall back to the model name
+        let manufacturer = read("device/vendor")
+            .filter(|v| v != "ATA")
+            .unwrap_or_else(|| Self::extract_manufacturer(&model));
+
+        let status = match read("device/state").as_deref() {
+            Some("running") | Some("live") | None => "OK".to_string(),
+            Some(other) => other.to_string(),
+        };
+
+        Some(PhysicalDisk {
+            device_id,
+            model,
+            manufacturer,
+            serial_number: read("device/serial")
+                .or_else(|| udev.get("ID_SERIAL_SHORT").cloned())
+                .unwrap_or_default(),
+            media_type,
+            interface_type,
+            size_bytes,
+            partition_style,
+            status,
+            firmware: read("device/firmware_rev")
+                .or_else(|| read("device/rev"))
+                .unwrap_or_else(|| "N/A".to_string()),
+        })
+    }
+
+    /// Partitions of a disk from /sys/block/<disk>/<partition>, paired with their kernel name
+    #[cfg(target_os = "linux")]
+    pub(crate) fn get_linux_partitions(disk_name: &str, disk_id: u32) -> Vec<(String, Partition)> {
+        let disk_path = std::path::Path::new("/sys/block").join(disk_name);
+        let mut partitions = Vec::new();
+
+        let Ok(entries) = std::fs::read_dir(&disk_path) else {
+            return partitions;
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let read = |file: &str| {
+                std::fs::read_to_string(path.join(file))
+                    .ok()
+                    .and_then(|s| s.trim().parse::<u64>().ok())
+            };
+
+            let Some(partition_number) = read("partition") else {
+                continue;
+            };
+
+            let name = entry.file_name().to_string_lossy().to_string();
+            let dev = std::fs::read_to_string(path.join("dev")).unwrap_or_default();
+            let udev = Self::read_udev_properties(dev.trim());
+
+            let type_code = udev.get("ID_PART_ENTRY_TYPE").cloned().unwrap_or_default();
+            // MBR boot flag is 0x80; on GPT the EFI System Partition is what boots
+            let is_bootable = udev.get("ID_PART_ENTRY_FLAGS").map(|f| f.as_str()) == Some("0x80")
+                || type_code.eq_ignore_ascii_case("c12a7328-f81f-11d2-ba4b-00a0c93ec93b")
+                || type_code == "0xef";
+
+            partitions.push((
+                name,
+                Partition {
+                    partition_number: partition_number as u32,
+                    disk_id,
+                    partition_type: Self::partition_type_name(&type_code),
+                    size_bytes: read("size").unwrap_or(0) * 512,
+                    offset_bytes: read("start").unwrap_or(0) * 512,
+                    is_bootable,
+                    is_active: udev.get("ID_PART_ENTRY_FLAGS").map(|f| f.as_str()) == Some("0x80"),
+                },
+            ));
+        }
+
+        partitions.sort_by_key(|(_, p)| p.partition_number);
+        partitions
+    }
+
+    /// Read the udev database entry for a block device ("major:minor") as KEY=VALUE pairs
+    #[cfg(target_os = "linux")]
+    fn read_udev_properties(dev: &str) -> std::collections::HashMap<String, String> {
+        if dev.is_empty() {
+            return std::collections::HashMap::new();
+        }
+
+        std::fs::read_to_string(format!("/run/udev/data/b{}", dev))
+            .map(|content| Self::parse_udev_properties(&content))
+            .unwrap_or_default()
+    }
+
+    #[cfg(target_os = "linux")]
+    fn parse_udev_properties(content: &str) -> std::collections::HashMap<String, String> {
+        content
+            .lines()
+            .filter_map(|line| line.strip_prefix("E:"))
+            .filter_map(|prop| prop.split_once('='))
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    /// Human-readable name for a GPT type GUID or MBR type byte
+    #[cfg(target_os = "linux")]
+    fn partition_type_name(type_code: &str) -> String {
+        let name = match type_code.to_lowercase().as_str() {
+            "c12a7328-f81f-11d2-ba4b-00a0c93ec93b" | "0xef" => "EFI System",
+            "0fc63daf-8483-4772-8e79-3d69d8477de4" | "0x83" => "Linux filesystem",
+            "4f68bce3-e8cd-4db1-96e7-fbcaf984b709" => "Linux root (x86-64)",
+            "933ac7e1-2eb4-4f13-b844-0e14e2aef915" => "Linux home",
+            "0657fd6d-a4ab-43c4-84e5-0933c84b4f4f" | "0x82" => "Linux swap",
+            "e6d6d379-f507-44c2-a23c-238f2a3df928" | "0x8e" => "Linux LVM",
+            "a19d880f-05fc-4d3b-a006-743f0f84911e" | "0xfd" => "Linux RAID",
+            "ca7d7ccb-63ed-4c53-861c-1742536059cc" => "LUKS",
+            "21686148-6449-6e6f-744e-656564454649" => "BIOS boot",
+            "ebd0a0a2-b9e5-4433-87c0-68b6b72699c7" | "0x07" => "Microsoft basic data",
+            "e3c9e316-0b5c-4db8-817d-f92df00215ae" => "Microsoft reserved",
+            "de94bba4-06d1-4d40-a16a-bfd50179d6ac" | "0x27" => "Windows recovery",
+            "0x0b" | "0x0c" => "FAT32",
+            "0x05" | "0x0f" => "Extended",
+            "" => "Unknown",
+            other => return other.to_string(),
+        };
+        name.to_string()
+    }
+
     #[cfg(target_os = "windows")]
     fn get_physical_disks_windows() -> Vec<PhysicalDisk> {
         use serde::Deserialize;
@@ -346,7 +593,7 @@ impl StorageCollector {
         MediaType::Unknown
     }
 
-    #[cfg(target_os = "windows")]
+    #[cfg(any(target_os = "windows", target_os = "linux"))]
     fn extract_manufacturer(model: &str) -> String {
         let model_upper = model.to_uppercase();
         let known_manufacturers = [
@@ -382,9 +629,27 @@ impl StorageCollector {
     }
 
     /// Get partitions for a disk
-    pub fn get_partitions(_disk_id: u32) -> Vec<Partition> {
-        // Would need platform-specific implementation
-        Vec::new()
+    pub fn get_partitions(disk_id: u32) -> Vec<Partition> {
+        #[cfg(target_os = "linux")]
+        {
+            Self::get_linux_block_disks()
+                .into_iter()
+                .find(|(_, disk)| disk.device_id == disk_id)
+                .map(|(name, _)| {
+                    Self::get_linux_partitions(&name, disk_id)
+                        .into_iter()
+                        .map(|(_, partition)| partition)
+                        .collect()
+                })
+                .unwrap_or_default()
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            // Would need platform-specific implementation
+            let _ = disk_id;
+            Vec::new()
+        }
     }
 
     /// Get all volumes (logical drives)
@@ -392,6 +657,13 @@ impl StorageCollector {
         let disks = Disks::new_with_refreshed_list();
         let mut volumes = Vec::new();
 
+        #[cfg(target_os = "linux")]
+        let mounts = Self::read_mountinfo();
+        #[cfg(target_os = "linux")]
+        let uuids = Self::read_disk_links("/dev/disk/by-uuid");
+        #[cfg(target_os = "linux")]
+        let labels = Self::read_disk_links("/dev/disk/by-label");
+
         for disk in disks.list() {
             let total = disk.total_space();
             let available = disk.available_space();
@@ -402,17 +674,17 @@ impl StorageCollector {
                 0.0
             };
 
-            // Extract drive letter from mount point
-            let mount_point = disk.mount_point().to_string_lossy();
-            let drive_letter = mount_point
-                .chars()
-                .next()
-                .filter(|c| c.is_ascii_alphabetic());
+            let mount_point = disk.mount_point().to_string_lossy().to_string();
 
-            let volume = Volume {
-                drive_letter,
+            #[allow(unused_mut)]
+            let mut volume = Volume {
+                drive_letter: Self::drive_letter_from_mount_point(&mount_point),
+                mount_point,
+                device: None,
                 label: disk.name().to_string_lossy().to_string(),
+                uuid: None,
                 file_system: disk.file_system().to_string_lossy().to_string(),
+                mount_options: Vec::new(),
                 total_bytes: total,
                 free_bytes: available,
                 used_bytes: used,
@@ -420,15 +692,153 @@ impl StorageCollector {
                 volume_serial: String::new(),
                 is_compressed: false,
                 is_encrypted: false,
+                is_read_only: false,
                 is_system: Self::is_system_drive(disk),
                 is_boot: Self::is_boot_drive(disk),
             };
+
+            #[cfg(target_os = "linux")]
+            Self::apply_linux_volume_details(&mut volume, &mounts, &uuids, &labels);
+
             volumes.push(volume);
         }
 
         volumes
     }
 
+    /// Extract the drive letter from a Windows mount point ("C:\\" -> 'C')
+    fn drive_letter_from_mount_point(mount_point: &str) -> Option<char> {
+        let mut chars = mount_point.chars();
+        match (chars.next(), chars.next()) {
+            (Some(letter), Some(':')) if letter.is_ascii_alphabetic() => {
+                Some(letter.to_ascii_uppercase())
+            }
+            _ => None,
+        }
+    }
+
+    /// Fill in the mount source, UUID, label, options and encryption/compression state
+    #[cfg(target_os = "linux")]
+    fn apply_linux_volume_details(
+        volume: &mut Volume,
+        mounts: &[MountEntry],
+        uuids: &std::collections::HashMap<std::path::PathBuf, String>,
+        labels: &std::collections::HashMap<std::path::PathBuf, String>,
+    ) {
+        // The last entry for a path is the one visible (it may be mounted over an earlier one)
+        let Some(mount) = mounts
+            .iter()
+            .rev()
+            .find(|m| m.mount_point == volume.mount_point)
+        else {
+            return;
+        };
+
+        let mut options = mount.mount_options.clone();
+        for option in &mount.super_options {
+            if !options.contains(option) {
+                options.push(option.clone());
+            }
+        }
+
+        volume.is_read_only = mount.mount_options.iter().any(|o| o == "ro")
+            || mount.super_options.iter().any(|o| o == "ro");
+        volume.is_compressed =
+            mount.fs_type == "btrfs" && Self::has_btrfs_compression(&mount.super_options);
+        volume.mount_options = options;
+
+        if !mount.source.starts_with("/dev/") {
+            return;
+        }
+        volume.device = Some(mount.source.clone());
+
+        // /dev/mapper/* and /dev/disk/by-* are symlinks to the kernel device node
+        let Ok(device_path) = std::fs::canonicalize(&mount.source) else {
+            return;
+        };
+
+        volume.uuid = uuids.get(&device_path).cloned();
+        volume.volume_serial = volume.uuid.clone().unwrap_or_default();
+        volume.label = labels.get(&device_path).cloned().unwrap_or_default();
+
+        if let Some(kernel_name) = device_path.file_name() {
+            volume.is_encrypted = Self::is_dm_crypt_backed(&kernel_name.to_string_lossy());
+        }
+    }
+
+    /// Map the canonical device node of every symlink in a /dev/disk/by-* directory to its name
+    #[cfg(target_os = "linux")]
+    fn read_disk_links(dir: &str) -> std::collections::HashMap<std::path::PathBuf, String> {
+        let mut links = std::collections::HashMap::new();
+
+        if let Ok(entries) = std::fs::read_dir(dir) {
+            for entry in entries.flatten() {
+                if let Ok(target) = std::fs::canonicalize(entry.path()) {
+                    let name = Self::decode_udev_name(&entry.file_name().to_string_lossy());
+                    links.insert(target, name);
+                }
+            }
+        }
+
+        links
+    }
+
+    /// Decode the \xNN escapes udev uses in /dev/disk/by-label names
+    #[cfg(target_os = "linux")]
+    fn decode_udev_name(name: &str) -> String {
+        let bytes = name.as_bytes();
+        let mut out = Vec::with_capacity(bytes.len());
+        let mut i = 0;
+
+        while i < bytes.len() {
+            if bytes[i] == b'\\' && bytes.get(i + 1) == Some(&b'x') {
+                let hex = name
+                    .get(i + 2..i + 4)
+                    .and_then(|h| u8::from_str_radix(h, 16).ok());
+                if let Some(value) = hex {
+                    out.push(value);
+                    i += 4;
+                    continue;
+                }
+            }
+            out.push(bytes[i]);
+            i += 1;
+        }
+
+        String::from_utf8_lossy(&out).to_string()
+    }
+
+    /// Check whether a block device sits on top of a dm-crypt (LUKS or plain) mapping,
+    /// following /sys/class/block/*/slaves down through LVM and RAID layers
+    #[cfg(target_os = "linux")]
+    fn is_dm_crypt_backed(kernel_name: &str) -> bool {
+        let sys_path = std::path::Path::new("/sys/class/block").join(kernel_name);
+
+        let is_crypt = std::fs::read_to_string(sys_path.join("dm/uuid"))
+            .map(|uuid| uuid.starts_with("CRYPT-"))
+            .unwrap_or(false);
+        if is_crypt {
+            return true;
+        }
+
+        std::fs::read_dir(sys_path.join("slaves"))
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .any(|e| Self::is_dm_crypt_backed(&e.file_name().to_string_lossy()))
+            })
+            .unwrap_or(false)
+    }
+
+    /// btrfs enables transparent compression with compress=<alg> or compress-force=<alg>
+    #[cfg(target_os = "linux")]
+    fn has_btrfs_compression(options: &[String]) -> bool {
+        options.iter().any(|option| {
+            let (key, value) = option.split_once('=').unwrap_or((option, ""));
+            (key == "compress" || key == "compress-force") && value != "no"
+        })
+    }
+
     /// Get disk health information (S.M.A.R.T.)
     pub fn get_disk_health(disk_id: u32) -> DiskHealth {
         #[cfg(target_os = "windows")]
@@ -551,7 +961,12 @@ impl StorageCollector {
             Self::get_network_drives_windows()
         }
 
-        #[cfg(not(target_os = "windows"))]
+        #[cfg(target_os = "linux")]
+        {
+            Self::get_network_drives_linux()
+        }
+
+        #[cfg(not(any(target_os = "windows", target_os = "linux")))]
         {
             Vec::new()
         }
@@ -570,6 +985,7 @@ impl StorageCollector {
             drive_type: Option<u32>,       // 4 = Network Drive (used in WHERE clause)
             provider_name: Option<String>, // UNC path (e.g., "\\\\server\\share")
             volume_name: Option<String>,   // Volume label
+            file_system: Option<String>,   // Remote filesystem (e.g., "NTFS")
             size: Option<u64>,             // Total size in bytes
             free_space: Option<u64>,       // Free space in bytes
         }
@@ -582,7 +998,7 @@ impl StorageCollector {
 
         if let Ok(wmi_con) = WMIConnection::new(com) {
             // Query for network drives (DriveType = 4)
-            let query = "SELECT DeviceID, DriveType, ProviderName, VolumeName, Size, FreeSpace FROM Win32_LogicalDisk WHERE DriveType = 4";
+            let query = "SELECT DeviceID, DriveType, ProviderName, VolumeName, FileSystem, Size, FreeSpace FROM Win32_LogicalDisk WHERE DriveType = 4";
             if let Ok(results) = wmi_con.raw_query::<Win32LogicalDisk>(query) {
                 for disk in results {
                     let drive_letter = disk
@@ -607,7 +1023,8 @@ impl StorageCollector {
                     };
 
                     // Check if drive is accessible
-                    let status = if std::path::Path::new(&format!("{}:\\", drive_letter)).exists() {
+                    let mount_point = format!("{}:\\", drive_letter);
+                    let status = if std::path::Path::new(&mount_point).exists() {
                         NetworkDriveStatus::Connected
                     } else {
                         NetworkDriveStatus::Disconnected
@@ -624,6 +1041,9 @@ impl StorageCollector {
                         unc_path,
                         server,
                         share_name,
+                        mount_point,
+                        file_system: disk.file_system.unwrap_or_default(),
+                        protocol: NetworkDriveProtocol::SMB,
                         status,
                         total_bytes: disk.size,
                         free_bytes: disk.free_space,
@@ -636,6 +1056,227 @@ impl StorageCollector {
         network_drives
     }
 
+    #[cfg(target_os = "linux")]
+    fn get_network_drives_linux() -> Vec<NetworkDrive> {
+        let mounts: Vec<(MountEntry, NetworkDriveProtocol)> = Self::read_mountinfo()
+            .into_iter()
+            .filter_map(|mount| {
+                Self::network_protocol(&mount.fs_type).map(|protocol| (mount, protocol))
+            })
+            .collect();
+
+        // Probe every mount in parallel so several hung servers cost a single timeout
+        let probes: Vec<_> = mounts
+            .iter()
+            .map(|(mount, _)| Self::spawn_mount_probe(&mount.mount_point))
+            .collect();
+        let deadline = Instant::now() + NETWORK_MOUNT_PROBE_TIMEOUT;
+
+        mounts
+            .into_iter()
+            .zip(probes)
+            .map(|((mount, protocol), probe)| {
+                let (status, usage) = match probe {
+                    Some(rx) => {
+                        match rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                            Ok(Some(usage)) => (NetworkDriveStatus::Connected, Some(usage)),
+                            Ok(None) => (NetworkDriveStatus::Disconnected, None),
+                            Err(_) => {
+                                log::warn!(
+                                    "Network mount {} did not respond within {:?}",
+                                    mount.mount_point,
+                                    NETWORK_MOUNT_PROBE_TIMEOUT
+                                );
+                                (NetworkDriveStatus::Unresponsive, None)
+                            }
+                        }
+                    }
+                    // An earlier probe of this mount is still blocked
+                    None => (NetworkDriveStatus::Unresponsive, None),
+                };
+
+                let (server, share_name) = Self::split_network_source(&protocol, &mount.source);
+
+                NetworkDrive {
+                    drive_letter: String::new(),
+                    unc_path: mount.source,
+                    server,
+                    share_name,
+                    mount_point: mount.mount_point,
+                    file_system: mount.fs_type,
+                    protocol,
+                    status,
+                    total_bytes: usage.map(|(total, _)| total),
+                    free_bytes: usage.map(|(_, free)| free),
+                    used_bytes: usage.map(|(total, free)| total.saturating_sub(free)),
+                }
+            })
+            .collect()
+    }
+
+    /// Run statvfs on a background thread so a hung mount cannot block the caller.
+    /// Returns None if a previous probe of the same mount point has not returned yet.
+    #[cfg(target_os = "linux")]
+    fn spawn_mount_probe(mount_point: &str) -> Option<mpsc::Receiver<Option<(u64, u64)>>> {
+        {
+            let mut pending = PENDING_MOUNT_PROBES.lock().unwrap();
+            if pending.iter().any(|p| p == mount_point) {
+                return None;
+            }
+            pending.push(mount_point.to_string());
+        }
+
+        let (tx, rx) = mpsc::channel();
+        let path = mount_point.to_string();
+        let spawned = std::thread::Builder::new()
+            .name("mount-probe".to_string())
+            .spawn(move || {
+                let usage = Self::statvfs_usage(&path);
+                PENDING_MOUNT_PROBES.lock().unwrap().retain(|p| p != &path);
+                let _ = tx.send(usage);
+            });
+
+        if let Err(e) = spawned {
+            log::warn!("Failed to spawn mount probe for {}: {}", mount_point, e);
+            PENDING_MOUNT_PROBES
+                .lock()
+                .unwrap()
+                .retain(|p| p != mount_point);
+            return None;
+        }
+
+        Some(rx)
+    }
+
+    /// Get (total, available) bytes for a mounted filesystem
+    #[cfg(target_os = "linux")]
+    fn statvfs_usage(path: &str) -> Option<(u64, u64)> {
+        let c_path = std::ffi::CString::new(path).ok()?;
+        let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+
+        if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+            return None;
+        }
+
+        let block_size = if stat.f_frsize > 0 {
+            stat.f_frsize as u64
+        } else {
+            stat.f_bsize as u64
+        };
+
+        Some((
+            stat.f_blocks as u64 * block_size,
+            stat.f_bavail as u64 * block_size,
+        ))
+    }
+
+    /// Read and parse the mount table of the current mount namespace
+    #[cfg(target_os = "linux")]
+    fn read_mountinfo() -> Vec<MountEntry> {
+        match std::fs::read_to_string("/proc/self/mountinfo") {
+            Ok(content) => Self::parse_mountinfo(&content),
+            Err(e) => {
+                log::warn!("Failed to read /proc/self/mountinfo: {}", e);
+                Vec::new()
+            }
+        }
+    }
+
+    /// Parse /proc/self/mountinfo.
+    /// Format: id parent major:minor root mount_point options [optional...] - fstype source super_options
+    #[cfg(target_os = "linux")]
+    fn parse_mountinfo(content: &str) -> Vec<MountEntry> {
+        content
+            .lines()
+            .filter_map(|line| {
+                let (before, after) = line.split_once(" - ")?;
+                let fields: Vec<&str> = before.split(' ').collect();
+                if fields.len() < 6 {
+                    return None;
+                }
+
+                let mut after_fields = after.split(' ');
+                let fs_type = after_fields.next()?;
+                let source = after_fields.next().unwrap_or_default();
+                let super_options = after_fields.next().unwrap_or_default();
+
+                Some(MountEntry {
+                    mount_point: Self::unescape_mount_field(fields[4]),
+                    mount_options: Self::split_mount_options(fields[5]),
+                    fs_type: fs_type.to_string(),
+                    source: Self::unescape_mount_field(source),
+                    super_options: Self::split_mount_options(super_options),
+                })
+            })
+            .collect()
+    }
+
+    #[cfg(target_os = "linux")]
+    fn split_mount_options(options: &str) -> Vec<String> {
+        options
+            .split(',')
+            .filter(|o| !o.is_empty())
+            .map(Self::unescape_mount_field)
+            .collect()
+    }
+
+    /// Decode the octal escapes (\040 for space etc.) the kernel uses in mount tables
+    #[cfg(target_os = "linux")]
+    fn unescape_mount_field(field: &str) -> String {
+        let bytes = field.as_bytes();
+        let mut out = Vec::with_capacity(bytes.len());
+        let mut i = 0;
+
+        while i < bytes.len() {
+            let octal = bytes.get(i + 1..i + 4).filter(|digits| {
+                digits.iter().all(|d| (b'0'..=b'7').contains(d)) && digits[0] <= b'3'
+            });
+            if let (b'\\', Some(digits)) = (bytes[i], octal) {
+                out.push(digits.iter().fold(0u8, |acc, d| acc * 8 + (d - b'0')));
+                i += 4;
+                continue;
+            }
+            out.push(bytes[i]);
+            i += 1;
+        }
+
+        String::from_utf8_lossy(&out).to_string()
+    }
+
+    /// Map a Linux filesystem type to a network protocol (None for local filesystems)
+    #[cfg(target_os = "linux")]
+    fn network_protocol(fs_type: &str) -> Option<NetworkDriveProtocol> {
+        match fs_type {
+            "cifs" | "smb3" | "smbfs" => Some(NetworkDriveProtocol::SMB),
+            "nfs" | "nfs4" => Some(NetworkDriveProtocol::NFS),
+            "fuse.sshfs" | "sshfs" => Some(NetworkDriveProtocol::SSHFS),
+            "ceph" | "glusterfs" | "fuse.glusterfs" | "afs" | "davfs" | "fuse.rclone" => {
+                Some(NetworkDriveProtocol::Other)
+            }
+            _ => None,
+        }
+    }
+
+    /// Split a mount source into (server, share/export).
+    /// SMB: "//server/share", NFS: "server:/export" or "[fe80::1]:/export", sshfs: "user@host:path"
+    #[cfg(target_os = "linux")]
+    fn split_network_source(protocol: &NetworkDriveProtocol, source: &str) -> (String, String) {
+        if *protocol == NetworkDriveProtocol::SMB {
+            let trimmed = source.trim_start_matches(['/', '\\']);
+            let (server, share) = trimmed.split_once(['/', '\\']).unwrap_or((trimmed, ""));
+            return (server.to_string(), share.to_string());
+        }
+
+        let (host, path) = match source.find("]:") {
+            Some(idx) if source.contains('[') => (&source[..=idx], &source[idx + 2..]),
+            _ => source.split_once(':').unwrap_or((source, "")),
+        };
+        let host = host.rsplit_once('@').map_or(host, |(_, h)| h);
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+
+        (host.to_string(), path.to_string())
+    }
+
     /// Detect media type from disk kind
     #[allow(dead_code)]
     fn detect_media_type(kind: DiskKind) -> MediaType {
@@ -687,4 +1328,104 @@ mod tests {
             assert!(vol.percent_used >= 0.0 && vol.percent_used <= 100.0);
         }
     }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_mountinfo() {
+        let content = "\
+22 1 259:2 / / rw,relatime shared:1 - ext4 /dev/nvme0n1p2 rw
+61 22 0:52 / /mnt/nas rw,relatime shared:30 - nfs4 nas.local:/volume1/media rw,vers=4.2
+62 22 0:53 / /mnt/My\\040Share rw,relatime - cifs //fileserver/Team\\040Docs rw,vers=3.1.1
+63 22 0:54 / /home/me/remote rw,nosuid - fuse.sshfs me@build-box:/srv/data rw,user_id=1000
+malformed line without separator";
+
+        let mounts = StorageCollector::parse_mountinfo(content);
+        assert_eq!(mounts.len(), 4);
+        assert_eq!(mounts[1].mount_point, "/mnt/nas");
+        assert_eq!(mounts[1].fs_type, "nfs4");
+        assert_eq!(mounts[1].source, "nas.local:/volume1/media");
+        assert_eq!(mounts[2].mount_point, "/mnt/My Share");
+        assert_eq!(mounts[2].source, "//fileserver/Team Docs");
+
+        let network: Vec<_> = mounts
+            .iter()
+            .filter_map(|m| StorageCollector::network_protocol(&m.fs_type))
+            .collect();
+        assert_eq!(
+            network,
+            vec![
+                NetworkDriveProtocol::NFS,
+                NetworkDriveProtocol::SMB,
+                NetworkDriveProtocol::SSHFS
+            ]
+        );
+    }
+
+    #[test]
+    fn test_drive_letter_from_mount_point() {
+        assert_eq!(
+            StorageCollector::drive_letter_from_mount_point("C:\\"),
+            Some('C')
+        );
+        assert_eq!(
+            StorageCollector::drive_letter_from_mount_point("d:\\"),
+            Some('D')
+        );
+        assert_eq!(StorageCollector::drive_letter_from_mount_point("/"), None);
+        assert_eq!(
+            StorageCollector::drive_letter_from_mount_point("/home"),
+            None
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_mount_options_and_compression() {
+        let content = "\
+40 22 0:35 /@home /home rw,noatime shared:5 - btrfs /dev/mapper/luks-home rw,ssd,compress=zstd:3,subvol=/@home
+41 22 0:36 / /mnt/iso ro,relatime - iso9660 /dev/loop0 ro
+42 22 0:37 / /data rw - btrfs /dev/sdb1 rw,compress=no";
+
+        let mounts = StorageCollector::parse_mountinfo(content);
+        assert_eq!(mounts[0].mount_options, vec!["rw", "noatime"]);
+        assert!(mounts[0]
+            .super_options
+            .contains(&"compress=zstd:3".to_string()));
+        assert!(StorageCollector::has_btrfs_compression(
+            &mounts[0].super_options
+        ));
+        assert!(mounts[1].mount_options.contains(&"ro".to_string()));
+        assert!(!StorageCollector::has_btrfs_compression(
+            &mounts[2].super_options
+        ));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_decode_udev_name() {
+        assert_eq!(StorageCollector::decode_udev_name("My\\x20Disk"), "My Disk");
+        assert_eq!(StorageCollector::decode_udev_name("BOOT"), "BOOT");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_split_network_source() {
+        let split = StorageCollector::split_network_source;
+        assert_eq!(
+            split(&NetworkDriveProtocol::SMB, "//fileserver/Team Docs"),
+            ("fileserver".to_string(), "Team Docs".to_string())
+        );
+        assert_eq!(
+            split(&NetworkDriveProtocol::NFS, "nas.local:/volume1/media"),
+            ("nas.local".to_string(), "/volume1/media".to_string())
+        );
+        assert_eq!(
+            split(&NetworkDriveProtocol::NFS, "[fd00::10]:/export"),
+            ("fd00::10".to_string(), "/export".to_string())
+        );
+        assert_eq!(
+            split(&NetworkDriveProtocol::SSHFS, "me@build-box:/srv/data"),
+            ("build-box".to_string(), "/srv/data".to_string())
+        );
+    }
 }
@@ -0,0 +1,528 @@
+//! Storage topology collector
+//!
+//! Links physical disks, partitions, virtual block devices (md RAID, LVM, dm-crypt, loop)
+//! and mounted filesystems into a single graph.
+
+use crate::collectors::StorageCollector;
+use crate::models::{StorageEdge, StorageNode, StorageNodeKind, StorageTopology};
+use std::collections::HashSet;
+
+#[cfg(target_os = "linux")]
+use crate::models::{CryptInfo, LvmInfo, RaidInfo, RaidMember};
+#[cfg(target_os = "linux")]
+use std::collections::HashMap;
+#[cfg(target_os = "linux")]
+use std::path::Path;
+
+/// Collector for the storage topology graph
+pub struct StorageTopologyCollector;
+
+impl StorageTopologyCollector {
+    /// Build the storage topology graph
+    pub fn get_topology() -> StorageTopology {
+        #[cfg(target_os = "linux")]
+        let mut topology = Self::get_topology_linux();
+
+        #[cfg(not(target_os = "linux"))]
+        let mut topology = Self::get_topology_flat();
+
+        // Several lower devices can lead to the same volume group; keep each link once
+        let mut seen = HashSet::new();
+        topology
+            .edges
+            .retain(|e| seen.insert((e.parent.clone(), e.child.clone())));
+
+        topology
+    }
+
+    /// Without block-layer relationships, link disks to their partitions and list volumes on their own
+    #[cfg(not(target_os = "linux"))]
+    fn get_topology_flat() -> StorageTopology {
+        let mut topology = StorageTopology::default();
+
+        for disk in StorageCollector::get_physical_disks() {
+            let disk_id = format!("disk{}", disk.device_id);
+
+            for partition in StorageCollector::get_partitions(disk.device_id) {
+                let id = format!("{}p{}", disk_id, partition.partition_number);
+                let mut node =
+                    Self::node(&id, &id, StorageNodeKind::Partition, partition.size_bytes);
+                node.partition = Some(partition);
+                topology.nodes.push(node);
+                topology.edges.push(Self::edge(&disk_id, &id));
+            }
+
+            let mut node = Self::node(
+                &disk_id,
+                &disk.model,
+                StorageNodeKind::Disk,
+                disk.size_bytes,
+            );
+            node.disk = Some(disk);
+            topology.nodes.push(node);
+        }
+
+        for volume in StorageCollector::get_volumes() {
+            let id = format!("fs:{}", volume.mount_point);
+            let mut node = Self::node(
+                &id,
+                &volume.mount_point,
+                StorageNodeKind::Filesystem,
+                volume.total_bytes,
+            );
+            node.volume = Some(volume);
+            topology.nodes.push(node);
+        }
+
+        topology
+    }
+
+    #[cfg(target_os = "linux")]
+    fn get_topology_linux() -> StorageTopology {
+        let mut topology = StorageTopology::default();
+        let mut known: HashSet<String> = HashSet::new();
+
+        // Physical disks and their partitions carry the regular collector data
+        for (name, disk) in StorageCollector::get_linux_block_disks() {
+            for (part_name, partition) in
+                StorageCollector::get_linux_partitions(&name, disk.device_id)
+            {
+                let mut node = Self::node(
+                    &part_name,
+                    &part_name,
+                    StorageNodeKind::Partition,
+                    partition.size_bytes,
+                );
+                node.partition = Some(partition);
+                topology.nodes.push(node);
+                topology.edges.push(Self::edge(&name, &part_name));
+                known.insert(part_name);
+            }
+
+            let display_name = if disk.model.is_empty() {
+                name.clone()
+            } else {
+                format!("{} ({})", name, disk.model)
+            };
+            let mut node = Self::node(&name, &display_name, StorageNodeKind::Disk, disk.size_bytes);
+            node.disk = Some(disk);
+            topology.nodes.push(node);
+            known.insert(name);
+        }
+
+        // Virtual block devices stacked on top of them
+        let mdstat = std::fs::read_to_string("/proc/mdstat")
+            .map(|content| Self::parse_mdstat(&content))
+            .unwrap_or_default();
+        let mut volume_groups: HashMap<String, HashSet<String>> = HashMap::new();
+
+        let mut names: Vec<String> = std::fs::read_dir("/sys/class/block")
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|e| e.file_name().to_string_lossy().to_string())
+                    .filter(|name| !known.contains(name))
+                    .collect()
+            })
+            .unwrap_or_default();
+        names.sort();
+
+        for name in names {
+            let sys_path = Path::new("/sys/class/block").join(&name);
+            let read = |file: &str| {
+                std::fs::read_to_string(sys_path.join(file))
+                    .map(|s| s.trim().to_string())
+                    .unwrap_or_default()
+            };
+            let size_bytes = read("size").parse::<u64>().unwrap_or(0) * 512;
+            let slaves = Self::read_slaves(&sys_path);
+
+            let node = if sys_path.join("partition").exists() {
+                // Partition on a virtual device (md0p1, loop0p1)
+                if let Some(parent) = std::fs::canonicalize(&sys_path).ok().and_then(|p| {
+                    p.parent()?
+                        .file_name()
+                        .map(|n| n.to_string_lossy().to_string())
+                }) {
+                    topology.edges.push(Self::edge(&parent, &name));
+                }
+                Self::node(&name, &name, StorageNodeKind::Partition, size_bytes)
+            } else if name.starts_with("dm-") {
+                let dm_name = read("dm/name");
+                let dm_uuid = read("dm/uuid");
+
+                if let Some(crypt_type) = Self::crypt_type_from_dm_uuid(&dm_uuid) {
+                    let mut node = Self::node(&name, &dm_name, StorageNodeKind::Crypt, size_bytes);
+                    node.crypt = Some(CryptInfo {
+                        mapping_name: dm_name,
+                        crypt_type,
+                    });
+                    node
+                } else if dm_uuid.starts_with("LVM-") {
+                    let (vg_name, lv_name) = Self::split_lvm_dm_name(&dm_name);
+                    let vg_id = format!("vg:{}", vg_name);
+
+                    // Physical volumes feed the volume group; internal LVs (thin pools) link directly
+                    for slave in &slaves {
+                        if Self::is_lvm_dm_device(slave) {
+                            topology.edges.push(Self::edge(slave, &name));
+                        } else {
+                            topology.edges.push(Self::edge(slave, &vg_id));
+                            volume_groups
+                                .entry(vg_name.clone())
+                                .or_default()
+                                .insert(slave.clone());
+                        }
+                    }
+                    volume_groups.entry(vg_name.clone()).or_default();
+                    topology.edges.push(Self::edge(&vg_id, &name));
+
+                    let mut node = Self::node(
+                        &name,
+                        &format!("{}/{}", vg_name, lv_name),
+                        StorageNodeKind::LogicalVolume,
+                        size_bytes,
+                    );
+                    node.lvm = Some(LvmInfo {
+                        vg_name,
+                        lv_name: Some(lv_name),
+                    });
+                    topology.nodes.push(node);
+                    continue;
+                } else {
+                    Self::node(&name, &dm_name, StorageNodeKind::DeviceMapper, size_bytes)
+                }
+            } else if sys_path.join("md").exists() {
+                let mut node = Self::node(&name, &name, StorageNodeKind::Raid, size_bytes);
+                node.raid = mdstat.get(&name).cloned();
+                node
+            } else if name.starts_with("loop") {
+                // Unused loop devices have no backing file and a size of zero
+                if size_bytes == 0 {
+                    continue;
+                }
+                let backing_file = read("loop/backing_file");
+                let display_name = if backing_file.is_empty() {
+                    name.clone()
+                } else {
+                    backing_file
+                };
+                Self::node(&name, &display_name, StorageNodeKind::Loop, size_bytes)
+            } else {
+                if size_bytes == 0 {
+                    continue;
+                }
+                Self::node(&name, &name, StorageNodeKind::Other, size_bytes)
+            };
+
+            for slave in &slaves {
+                topology.edges.push(Self::edge(slave, &name));
+            }
+            topology.nodes.push(node);
+        }
+
+        let mut volume_groups: Vec<_> = volume_groups.into_iter().collect();
+        volume_groups.sort_by(|a, b| a.0.cmp(&b.0));
+        for (vg_name, physical_volumes) in volume_groups {
+            let size_bytes = physical_volumes
+                .iter()
+                .map(|pv| Self::block_size_bytes(pv))
+                .sum();
+            let mut node = Self::node(
+                &format!("vg:{}", vg_name),
+                &vg_name,
+                StorageNodeKind::VolumeGroup,
+                size_bytes,
+            );
+            node.lvm = Some(LvmInfo {
+                vg_name,
+                lv_name: None,
+            });
+            topology.nodes.push(node);
+        }
+
+        // Mounted filesystems sit on top of whichever block device they were mounted from
+        for volume in StorageCollector::get_volumes() {
+            let id = format!("fs:{}", volume.mount_point);
+            let kernel_name = volume
+                .device
+                .as_ref()
+                .and_then(|device| std::fs::canonicalize(device).ok())
+                .and_then(|path| path.file_name().map(|n| n.to_string_lossy().to_string()));
+
+            if let Some(kernel_name) = kernel_name {
+                topology.edges.push(Self::edge(&kernel_name, &id));
+            }
+
+            let mut node = Self::node(
+                &id,
+                &volume.mount_point,
+                StorageNodeKind::Filesystem,
+                volume.total_bytes,
+            );
+            node.volume = Some(volume);
+            topology.nodes.push(node);
+        }
+
+        topology
+    }
+
+    fn node(id: &str, name: &str, kind: StorageNodeKind, size_bytes: u64) -> StorageNode {
+        StorageNode {
+            id: id.to_string(),
+            name: name.to_string(),
+            kind,
+            size_bytes,
+            disk: None,
+            partition: None,
+            volume: None,
+            lvm: None,
+            raid: None,
+            crypt: None,
+        }
+    }
+
+    fn edge(parent: &str, child: &str) -> StorageEdge {
+        StorageEdge {
+            parent: parent.to_string(),
+            child: child.to_string(),
+        }
+    }
+
+    /// Lower devices a block device is built on (/sys/class/block/<name>/slaves)
+    #[cfg(target_os = "linux")]
+    fn read_slaves(sys_path: &Path) -> Vec<String> {
+        let mut slaves: Vec<String> = std::fs::read_dir(sys_path.join("slaves"))
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|e| e.file_name().to_string_lossy().to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+        slaves.sort();
+        slaves
+    }
+
+    #[cfg(target_os = "linux")]
+    fn block_size_bytes(name: &str) -> u64 {
+        std::fs::read_to_string(Path::new("/sys/class/block").join(name).join("size"))
+            .ok()
+            .and_then(|s| s.trim().parse::<u64>().ok())
+            .unwrap_or(0)
+            * 512
+    }
+
+    #[cfg(target_os = "linux")]
+    fn is_lvm_dm_device(name: &str) -> bool {
+        std::fs::read_to_string(Path::new("/sys/class/block").join(name).join("dm/uuid"))
+            .map(|uuid| uuid.starts_with("LVM-"))
+            .unwrap_or(false)
+    }
+
+    /// dm-crypt mappings have a uuid of "CRYPT-<TYPE>-..." (e.g. "CRYPT-LUKS2-3f2a...-luks-3f2a...")
+    #[cfg(target_os = "linux")]
+    fn crypt_type_from_dm_uuid(dm_uuid: &str) -> Option<String> {
+        let rest = dm_uuid.strip_prefix("CRYPT-")?;
+        let crypt_type = rest.split('-').next().filter(|t| !t.is_empty())?;
+        Some(crypt_type.to_string())
+    }
+
+    /// Split an LVM device-mapper name into (volume group, logical volume).
+    /// LVM joins them with '-' and escapes dashes inside either name as "--".
+    #[cfg(target_os = "linux")]
+    fn split_lvm_dm_name(dm_name: &str) -> (String, String) {
+        let bytes = dm_name.as_bytes();
+        let mut i = 0;
+
+        while i < bytes.len() {
+            if bytes[i] == b'-' {
+                if bytes.get(i + 1) == Some(&b'-') {
+                    i += 2;
+                    continue;
+                }
+                return (
+                    dm_name[..i].replace("--", "-"),
+                    dm_name[i + 1..].replace("--", "-"),
+                );
+            }
+            i += 1;
+        }
+
+        (dm_name.replace("--", "-"), String::new())
+    }
+
+    /// Parse /proc/mdstat into per-array RAID details
+    #[cfg(target_os = "linux")]
+    fn parse_mdstat(content: &str) -> HashMap<String, RaidInfo> {
+        let mut arrays: HashMap<String, RaidInfo> = HashMap::new();
+        let mut current: Option<String> = None;
+
+        for line in content.lines() {
+            // Array header: "md0 : active raid1 sdb1[1] sda1[0]"
+            if let Some((name, rest)) = line.split_once(" : ") {
+                if name.starts_with("md") && !name.contains(' ') {
+                    let mut tokens = rest.split_whitespace().peekable();
+                    let mut state = tokens.next().unwrap_or("unknown").to_string();
+                    while let Some(qualifier) = tokens.next_if(|t| t.starts_with('(')) {
+                        state.push(' ');
+                        state.push_str(qualifier);
+                    }
+                    let level = tokens.next_if(|t| !t.contains('[')).map(|t| t.to_string());
+                    let members = tokens.filter_map(Self::parse_md_member).collect();
+
+                    arrays.insert(
+                        name.to_string(),
+                        RaidInfo {
+                            level,
+                            state,
+                            total_devices: None,
+                            active_devices: None,
+                            members,
+                            sync_action: None,
+                            sync_progress_percent: None,
+                        },
+                    );
+                    current = Some(name.to_string());
+                    continue;
+                }
+            }
+
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                current = None;
+                continue;
+            }
+            let Some(info) = current.as_ref().and_then(|name| arrays.get_mut(name)) else {
+                continue;
+            };
+
+            // Status line: "976630464 blocks super 1.2 [2/1] [U_]"
+            for token in trimmed.split_whitespace() {
+                let counts = token
+                    .strip_prefix('[')
+                    .and_then(|t| t.strip_suffix(']'))
+                    .and_then(|t| t.split_once('/'));
+                if let Some((total, active)) = counts {
+                    if let (Ok(total), Ok(active)) = (total.parse(), active.parse()) {
+                        info.total_devices = Some(total);
+                        info.active_devices = Some(active);
+                    }
+                }
+            }
+
+            // Progress line: "[==>.......]  recovery = 12.6% (123/976630464) finish=..."
+            for action in ["resync", "recovery", "check", "repair", "reshape"] {
+                if let Some((_, rest)) = trimmed.split_once(&format!("{} =", action)) {
+                    info.sync_action = Some(action.to_string());
+                    info.sync_progress_percent =
+                        rest.split('%').next().and_then(|p| p.trim().parse().ok());
+                }
+            }
+        }
+
+        arrays
+    }
+
+    /// Parse an mdstat member token such as "sdb1[1]", "sdc1[2](F)" or "sdd1[3](S)"
+    #[cfg(target_os = "linux")]
+    fn parse_md_member(token: &str) -> Option<RaidMember> {
+        let (device, rest) = token.split_once('[')?;
+        let (role, flags) = rest.split_once(']')?;
+
+        Some(RaidMember {
+            device: device.to_string(),
+            role: role.parse().ok()?,
+            is_faulty: flags.contains("(F)"),
+            is_spare: flags.contains("(S)"),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_topology() {
+        let topology = StorageTopologyCollector::get_topology();
+        let ids: HashSet<&str> = topology.nodes.iter().map(|n| n.id.as_str()).collect();
+
+        // Every edge must lead to a node in the graph
+        for edge in &topology.edges {
+            assert!(
+                ids.contains(edge.child.as_str()),
+                "Dangling edge {:?}",
+                edge
+            );
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_split_lvm_dm_name() {
+        assert_eq!(
+            StorageTopologyCollector::split_lvm_dm_name("vg0-home"),
+            ("vg0".to_string(), "home".to_string())
+        );
+        assert_eq!(
+            StorageTopologyCollector::split_lvm_dm_name("my--vg-root--fs"),
+            ("my-vg".to_string(), "root-fs".to_string())
+        );
+        assert_eq!(
+            StorageTopologyCollector::crypt_type_from_dm_uuid(
+                "CRYPT-LUKS2-3f2a9c1e5b7d4e0f8a6b2c4d6e8f0a1b-luks-3f2a9c1e"
+            ),
+            Some("LUKS2".to_string())
+        );
+        assert_eq!(
+            StorageTopologyCollector::crypt_type_from_dm_uuid("LVM-abc"),
+            None
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_mdstat() {
+        let content = "\
+Personalities : [raid1] [raid6] [raid5] [raid4]
+md1 : active raid5 sdd1[3] sdc1[1] sdb2[0](F)
+      1953260544 blocks super 1.2 level 5, 512k chunk, algorithm 2 [3/2] [UU_]
+      [==>..................]  recovery = 12.6% (123264/976630272) finish=80.1min speed=180000K/sec
+
+md0 : active (auto-read-only) raid1 sdb1[1] sda1[0]
+      976630464 blocks super 1.2 [2/2] [UU]
+      bitmap: 0/8 pages [0KB], 65536KB chunk
+
+md127 : inactive sde[0](S)
+      976630464 blocks super 1.2
+
+unused devices: <none>
+";
+
+        let arrays = StorageTopologyCollector::parse_mdstat(content);
+        assert_eq!(arrays.len(), 3);
+
+        let md1 = &arrays["md1"];
+        assert_eq!(md1.level.as_deref(), Some("raid5"));
+        assert_eq!(md1.state, "active");
+        assert_eq!((md1.total_devices, md1.active_devices), (Some(3), Some(2)));
+        assert_eq!(md1.members.len(), 3);
+        assert!(md1
+            .members
+            .iter()
+            .any(|m| m.device == "sdb2" && m.is_faulty));
+        assert_eq!(md1.sync_action.as_deref(), Some("recovery"));
+        assert_eq!(md1.sync_progress_percent, Some(12.6));
+
+        let md0 = &arrays["md0"];
+        assert_eq!(md0.state, "active (auto-read-only)");
+        assert_eq!(md0.level.as_deref(), Some("raid1"));
+        assert_eq!(md0.sync_action, None);
+
+        let md127 = &arrays["md127"];
+        assert_eq!(md127.state, "inactive");
+        assert_eq!(md127.level, None);
+        assert!(md127.members[0].is_spare);
+    }
+}
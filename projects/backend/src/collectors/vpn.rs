@@ -0,0 +1,538 @@
+//! VPN and tunnel interface collector
+//!
+//! Detects tunnel interfaces from their link kind, reads WireGuard peers over the
+//! wireguard generic netlink family, finds the process holding tun/tap devices and
+//! asks the kernel which interface internet traffic is routed through.
+
+use crate::models::{TunnelInterface, TunnelKind, VpnStatus};
+use std::collections::HashMap;
+
+#[cfg(target_os = "linux")]
+use crate::collectors::links;
+#[cfg(target_os = "linux")]
+use crate::collectors::netlink::{
+    genl_message, push_attribute, Attributes, NetlinkSocket, NLM_F_DUMP,
+};
+#[cfg(target_os = "linux")]
+use crate::models::{WireGuardInfo, WireGuardPeer};
+#[cfg(target_os = "linux")]
+use base64::engine::general_purpose::STANDARD;
+#[cfg(target_os = "linux")]
+use base64::Engine;
+#[cfg(target_os = "linux")]
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+#[cfg(not(target_os = "linux"))]
+use crate::collectors::NetworkCollector;
+#[cfg(not(target_os = "linux"))]
+use crate::models::AdapterType;
+
+// wireguard generic netlink family (linux/wireguard.h)
+#[cfg(target_os = "linux")]
+const WG_CMD_GET_DEVICE: u8 = 0;
+#[cfg(target_os = "linux")]
+const WGDEVICE_A_IFNAME: u16 = 2;
+#[cfg(target_os = "linux")]
+const WGDEVICE_A_PUBLIC_KEY: u16 = 4;
+#[cfg(target_os = "linux")]
+const WGDEVICE_A_LISTEN_PORT: u16 = 6;
+#[cfg(target_os = "linux")]
+const WGDEVICE_A_PEERS: u16 = 8;
+#[cfg(target_os = "linux")]
+const WGPEER_A_PUBLIC_KEY: u16 = 1;
+#[cfg(target_os = "linux")]
+const WGPEER_A_ENDPOINT: u16 = 4;
+#[cfg(target_os = "linux")]
+const WGPEER_A_PERSISTENT_KEEPALIVE_INTERVAL: u16 = 5;
+#[cfg(target_os = "linux")]
+const WGPEER_A_LAST_HANDSHAKE_TIME: u16 = 6;
+#[cfg(target_os = "linux")]
+const WGPEER_A_RX_BYTES: u16 = 7;
+#[cfg(target_os = "linux")]
+const WGPEER_A_TX_BYTES: u16 = 8;
+#[cfg(target_os = "linux")]
+const WGPEER_A_ALLOWEDIPS: u16 = 9;
+#[cfg(target_os = "linux")]
+const WGALLOWEDIP_A_FAMILY: u16 = 1;
+#[cfg(target_os = "linux")]
+const WGALLOWEDIP_A_IPADDR: u16 = 2;
+#[cfg(target_os = "linux")]
+const WGALLOWEDIP_A_CIDR_MASK: u16 = 3;
+
+// rtnetlink route lookup (linux/rtnetlink.h)
+#[cfg(target_os = "linux")]
+const RTM_NEWROUTE: u16 = 24;
+#[cfg(target_os = "linux")]
+const RTM_GETROUTE: u16 = 26;
+#[cfg(target_os = "linux")]
+const RTMSG_LEN: usize = 12;
+#[cfg(target_os = "linux")]
+const RTA_DST: u16 = 1;
+#[cfg(target_os = "linux")]
+const RTA_OIF: u16 = 4;
+
+/// tun_flags bit of TAP devices (linux/if_tun.h)
+#[cfg(target_os = "linux")]
+const IFF_TAP: u32 = 0x0002;
+
+/// Internet destinations used for the route lookups (no packets are sent)
+#[cfg(target_os = "linux")]
+const IPV4_PROBE_DESTINATION: Ipv4Addr = Ipv4Addr::new(1, 1, 1, 1);
+#[cfg(target_os = "linux")]
+const IPV6_PROBE_DESTINATION: Ipv6Addr = Ipv6Addr::new(0x2606, 0x4700, 0x4700, 0, 0, 0, 0, 0x1111);
+
+/// Collector for VPN and tunnel interfaces
+pub struct VpnCollector;
+
+impl VpnCollector {
+    /// Get tunnel interfaces and whether internet traffic is routed through them
+    pub fn get_vpn_status() -> VpnStatus {
+        #[cfg(target_os = "linux")]
+        {
+            let links = links::dump_links().unwrap_or_else(|e| {
+                log::warn!("Failed to dump network links: {}", e);
+                Vec::new()
+            });
+            let names: HashMap<u32, &str> =
+                links.iter().map(|l| (l.index, l.name.as_str())).collect();
+
+            let route_interface = |destination: IpAddr| {
+                Self::route_interface(destination)
+                    .map_err(|e| log::debug!("Route lookup for {} failed: {}", destination, e))
+                    .ok()
+                    .flatten()
+                    .and_then(|index| names.get(&index).map(|name| name.to_string()))
+            };
+            let default_route_interface = route_interface(IpAddr::V4(IPV4_PROBE_DESTINATION));
+            let ipv6_default_route_interface = route_interface(IpAddr::V6(IPV6_PROBE_DESTINATION));
+
+            let tun_owners = Self::tun_owners();
+            let tunnels: Vec<TunnelInterface> = links
+                .iter()
+                .filter_map(|link| {
+                    let kind = Self::tunnel_kind(&link.name, link.kind.as_deref()?)?;
+                    let owner_pid = tun_owners.get(&link.name).copied();
+                    let carries_default_route = [
+                        default_route_interface.as_deref(),
+                        ipv6_default_route_interface.as_deref(),
+                    ]
+                    .contains(&Some(link.name.as_str()));
+
+                    Some(TunnelInterface {
+                        name: link.name.clone(),
+                        kind,
+                        is_up: link.is_up,
+                        carries_default_route,
+                        owner_pid,
+                        owner_process: owner_pid.and_then(|pid| {
+                            std::fs::read_to_string(format!("/proc/{}/comm", pid))
+                                .ok()
+                                .map(|comm| comm.trim().to_string())
+                        }),
+                        wireguard: (kind == TunnelKind::WireGuard)
+                            .then(|| {
+                                Self::get_wireguard_info(&link.name)
+                                    .map_err(|e| {
+                                        log::debug!("Failed to read WireGuard {}: {}", link.name, e)
+                                    })
+                                    .ok()
+                            })
+                            .flatten(),
+                    })
+                })
+                .collect();
+
+            VpnStatus {
+                default_route_via_tunnel: tunnels.iter().any(|t| t.carries_default_route),
+                tunnels,
+                default_route_interface,
+                ipv6_default_route_interface,
+            }
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            let tunnels = NetworkCollector::get_adapters()
+                .into_iter()
+                .filter(|a| matches!(a.adapter_type, AdapterType::Tunnel))
+                .map(|a| TunnelInterface {
+                    name: a.name,
+                    kind: TunnelKind::Other,
+                    is_up: matches!(a.status, crate::models::AdapterStatus::Up),
+                    carries_default_route: false,
+                    owner_pid: None,
+                    owner_process: None,
+                    wireguard: None,
+                })
+                .collect();
+
+            VpnStatus {
+                tunnels,
+                default_route_interface: None,
+                ipv6_default_route_interface: None,
+                default_route_via_tunnel: false,
+            }
+        }
+    }
+
+    /// Get the tunnel kind of every tunnel interface, by name
+    pub fn tunnel_kinds() -> HashMap<String, TunnelKind> {
+        #[cfg(target_os = "linux")]
+        {
+            links::dump_links()
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|link| {
+                    let kind = Self::tunnel_kind(&link.name, link.kind.as_deref()?)?;
+                    Some((link.name, kind))
+                })
+                .collect()
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            HashMap::new()
+        }
+    }
+
+    /// Map an rtnetlink link kind to a tunnel kind. tun and tap devices share the
+    /// "tun" kind and are told apart by their tun_flags.
+    #[cfg(target_os = "linux")]
+    pub(crate) fn tunnel_kind(name: &str, link_kind: &str) -> Option<TunnelKind> {
+        match link_kind {
+            "wireguard" => Some(TunnelKind::WireGuard),
+            "tun" => {
+                let flags = std::fs::read_to_string(format!("/sys/class/net/{}/tun_flags", name))
+                    .ok()
+                    .and_then(|f| u32::from_str_radix(f.trim().trim_start_matches("0x"), 16).ok())
+                    .unwrap_or(0);
+                Some(if flags & IFF_TAP != 0 {
+                    TunnelKind::Tap
+                } else {
+                    TunnelKind::Tun
+                })
+            }
+            "xfrm" | "vti" | "vti6" => Some(TunnelKind::IPsec),
+            "gre" | "gretap" | "ip6gre" | "ip6gretap" | "erspan" | "ip6erspan" => {
+                Some(TunnelKind::GRE)
+            }
+            "ipip" | "ip6tnl" => Some(TunnelKind::IPIP),
+            "sit" => Some(TunnelKind::SIT),
+            "geneve" | "vxlan" | "l2tp" | "ipvtap" => Some(TunnelKind::Other),
+            _ => None,
+        }
+    }
+
+    /// Read a WireGuard device's keys, port and peers (requires CAP_NET_ADMIN)
+    #[cfg(target_os = "linux")]
+    fn get_wireguard_info(interface: &str) -> std::io::Result<WireGuardInfo> {
+        let mut socket = NetlinkSocket::open(libc::NETLINK_GENERIC)?;
+        let family = socket.resolve_genl_family("wireguard")?;
+
+        let mut name = interface.as_bytes().to_vec();
+        name.push(0);
+        let mut attributes = Vec::new();
+        push_attribute(&mut attributes, WGDEVICE_A_IFNAME, &name);
+
+        let replies = socket.request(
+            family,
+            NLM_F_DUMP,
+            &genl_message(WG_CMD_GET_DEVICE, &attributes),
+        )?;
+        let payloads: Vec<&[u8]> = replies.iter().map(|m| m.genl_payload()).collect();
+        Ok(Self::parse_wireguard_device(&payloads))
+    }
+
+    /// Parse WG_CMD_GET_DEVICE replies. Devices with many peers are split across
+    /// several messages, each carrying the device attributes and a slice of peers.
+    #[cfg(target_os = "linux")]
+    fn parse_wireguard_device(payloads: &[&[u8]]) -> WireGuardInfo {
+        let mut info = WireGuardInfo {
+            public_key: None,
+            listen_port: None,
+            peers: Vec::new(),
+        };
+
+        for payload in payloads {
+            let attrs = Attributes::parse(payload);
+            if let Some(key) = attrs.get(WGDEVICE_A_PUBLIC_KEY) {
+                info.public_key = Some(STANDARD.encode(key));
+            }
+            if let Some(port) = attrs.get_u16(WGDEVICE_A_LISTEN_PORT) {
+                info.listen_port = Some(port);
+            }
+            if let Some(peers) = attrs.get_nested(WGDEVICE_A_PEERS) {
+                info.peers.extend(
+                    peers
+                        .iter()
+                        .filter_map(|(_, peer)| Self::parse_wireguard_peer(peer)),
+                );
+            }
+        }
+
+        info
+    }
+
+    #[cfg(target_os = "linux")]
+    fn parse_wireguard_peer(data: &[u8]) -> Option<WireGuardPeer> {
+        let attrs = Attributes::parse(data);
+
+        // struct __kernel_timespec; zero means no handshake yet
+        let latest_handshake = attrs
+            .get(WGPEER_A_LAST_HANDSHAKE_TIME)
+            .and_then(|t| t.get(..8))
+            .map(|secs| i64::from_ne_bytes(secs.try_into().unwrap()))
+            .filter(|&secs| secs > 0)
+            .map(|secs| secs as u64);
+
+        let allowed_ips = attrs
+            .get_nested(WGPEER_A_ALLOWEDIPS)
+            .map(|list| {
+                list.iter()
+                    .filter_map(|(_, entry)| {
+                        let entry = Attributes::parse(entry);
+                        let address = entry.get(WGALLOWEDIP_A_IPADDR)?;
+                        let address = match entry.get_u16(WGALLOWEDIP_A_FAMILY)? as i32 {
+                            libc::AF_INET => IpAddr::from(<[u8; 4]>::try_from(address).ok()?),
+                            libc::AF_INET6 => IpAddr::from(<[u8; 16]>::try_from(address).ok()?),
+                            _ => return None,
+                        };
+                        Some(format!(
+                            "{}/{}",
+                            address,
+                            entry.get_u8(WGALLOWEDIP_A_CIDR_MASK)?
+                        ))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Some(WireGuardPeer {
+            public_key: STANDARD.encode(attrs.get(WGPEER_A_PUBLIC_KEY)?),
+            endpoint: attrs
+                .get(WGPEER_A_ENDPOINT)
+                .and_then(Self::parse_endpoint)
+                .map(|e| e.to_string()),
+            latest_handshake,
+            rx_bytes: attrs.get_u64(WGPEER_A_RX_BYTES).unwrap_or(0),
+            tx_bytes: attrs.get_u64(WGPEER_A_TX_BYTES).unwrap_or(0),
+            allowed_ips,
+            persistent_keepalive_secs: attrs
+                .get_u16(WGPEER_A_PERSISTENT_KEEPALIVE_INTERVAL)
+                .filter(|&interval| interval > 0),
+        })
+    }
+
+    /// Parse a struct sockaddr_in/sockaddr_in6 endpoint
+    #[cfg(target_os = "linux")]
+    fn parse_endpoint(data: &[u8]) -> Option<SocketAddr> {
+        let family = u16::from_ne_bytes(data.get(..2)?.try_into().ok()?) as i32;
+        let port = u16::from_be_bytes(data.get(2..4)?.try_into().ok()?);
+        let address = match family {
+            libc::AF_INET => IpAddr::from(<[u8; 4]>::try_from(data.get(4..8)?).ok()?),
+            libc::AF_INET6 => IpAddr::from(<[u8; 16]>::try_from(data.get(8..24)?).ok()?),
+            _ => return None,
+        };
+        Some(SocketAddr::new(address, port))
+    }
+
+    /// Map tun/tap interface names to the PID holding /dev/net/tun open for them
+    #[cfg(target_os = "linux")]
+    fn tun_owners() -> HashMap<String, u32> {
+        let mut owners = HashMap::new();
+        let Ok(entries) = std::fs::read_dir("/proc") else {
+            return owners;
+        };
+
+        for pid in entries
+            .flatten()
+            .filter_map(|e| e.file_name().to_str()?.parse::<u32>().ok())
+        {
+            let Ok(fds) = std::fs::read_dir(format!("/proc/{}/fd", pid)) else {
+                continue;
+            };
+            for fd in fds.flatten() {
+                let is_tun = std::fs::read_link(fd.path())
+                    .map(|target| target.as_os_str() == "/dev/net/tun")
+                    .unwrap_or(false);
+                if !is_tun {
+                    continue;
+                }
+
+                let fdinfo = format!("/proc/{}/fdinfo/{}", pid, fd.file_name().to_string_lossy());
+                if let Some(name) = std::fs::read_to_string(fdinfo)
+                    .ok()
+                    .and_then(|content| Self::parse_tun_fdinfo(&content))
+                {
+                    owners.entry(name).or_insert(pid);
+                }
+            }
+        }
+
+        owners
+    }
+
+    /// Get the interface name from the "iff:" line of a /dev/net/tun fdinfo
+    #[cfg(target_os = "linux")]
+    fn parse_tun_fdinfo(content: &str) -> Option<String> {
+        content
+            .lines()
+            .find_map(|line| line.strip_prefix("iff:"))
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+    }
+
+    /// Ask the kernel which interface it would use to reach `destination`.
+    /// The lookup honors policy routing rules (e.g. wg-quick's fwmark table).
+    #[cfg(target_os = "linux")]
+    fn route_interface(destination: IpAddr) -> std::io::Result<Option<u32>> {
+        let mut socket = NetlinkSocket::open(libc::NETLINK_ROUTE)?;
+
+        // struct rtmsg followed by RTA_DST
+        let mut request = vec![0u8; RTMSG_LEN];
+        match destination {
+            IpAddr::V4(v4) => {
+                request[0] = libc::AF_INET as u8;
+                request[1] = 32;
+                push_attribute(&mut request, RTA_DST, &v4.octets());
+            }
+            IpAddr::V6(v6) => {
+                request[0] = libc::AF_INET6 as u8;
+                request[1] = 128;
+                push_attribute(&mut request, RTA_DST, &v6.octets());
+            }
+        }
+
+        let replies = socket.request(RTM_GETROUTE, 0, &request)?;
+        Ok(replies
+            .iter()
+            .filter(|m| m.msg_type == RTM_NEWROUTE)
+            .find_map(|m| Self::parse_route_oif(&m.payload)))
+    }
+
+    #[cfg(target_os = "linux")]
+    fn parse_route_oif(payload: &[u8]) -> Option<u32> {
+        Attributes::parse(payload.get(RTMSG_LEN..)?).get_u32(RTA_OIF)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_vpn_status() {
+        let status = VpnCollector::get_vpn_status();
+        assert_eq!(
+            status.default_route_via_tunnel,
+            status.tunnels.iter().any(|t| t.carries_default_route)
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_tunnel_kind() {
+        assert_eq!(
+            VpnCollector::tunnel_kind("wg0", "wireguard"),
+            Some(TunnelKind::WireGuard)
+        );
+        assert_eq!(
+            VpnCollector::tunnel_kind("ipsec0", "xfrm"),
+            Some(TunnelKind::IPsec)
+        );
+        // Without tun_flags the device is treated as a layer 3 tun
+        assert_eq!(
+            VpnCollector::tunnel_kind("nonexistent0", "tun"),
+            Some(TunnelKind::Tun)
+        );
+        assert_eq!(VpnCollector::tunnel_kind("br0", "bridge"), None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_tun_fdinfo() {
+        let content = "pos:\t0\nflags:\t0104002\nmnt_id:\t15\nino:\t1042\niff:\ttun0\n";
+        assert_eq!(
+            VpnCollector::parse_tun_fdinfo(content).as_deref(),
+            Some("tun0")
+        );
+        // Opened but not yet attached to an interface
+        assert_eq!(
+            VpnCollector::parse_tun_fdinfo("pos:\t0\nflags:\t02\n"),
+            None
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_wireguard_device() {
+        let mut allowed_ip = Vec::new();
+        push_attribute(
+            &mut allowed_ip,
+            WGALLOWEDIP_A_FAMILY,
+            &(libc::AF_INET as u16).to_ne_bytes(),
+        );
+        push_attribute(&mut allowed_ip, WGALLOWEDIP_A_IPADDR, &[10, 8, 0, 0]);
+        push_attribute(&mut allowed_ip, WGALLOWEDIP_A_CIDR_MASK, &[24]);
+        let mut allowed_ips = Vec::new();
+        push_attribute(&mut allowed_ips, 0, &allowed_ip);
+
+        let mut endpoint = (libc::AF_INET as u16).to_ne_bytes().to_vec();
+        endpoint.extend_from_slice(&51820u16.to_be_bytes());
+        endpoint.extend_from_slice(&[203, 0, 113, 7]);
+        endpoint.extend_from_slice(&[0; 8]);
+
+        let mut handshake = 1_700_000_000i64.to_ne_bytes().to_vec();
+        handshake.extend_from_slice(&0i64.to_ne_bytes());
+
+        let mut peer = Vec::new();
+        push_attribute(&mut peer, WGPEER_A_PUBLIC_KEY, &[0xAB; 32]);
+        push_attribute(&mut peer, WGPEER_A_ENDPOINT, &endpoint);
+        push_attribute(
+            &mut peer,
+            WGPEER_A_PERSISTENT_KEEPALIVE_INTERVAL,
+            &25u16.to_ne_bytes(),
+        );
+        push_attribute(&mut peer, WGPEER_A_LAST_HANDSHAKE_TIME, &handshake);
+        push_attribute(&mut peer, WGPEER_A_RX_BYTES, &4096u64.to_ne_bytes());
+        push_attribute(&mut peer, WGPEER_A_TX_BYTES, &1024u64.to_ne_bytes());
+        push_attribute(&mut peer, WGPEER_A_ALLOWEDIPS, &allowed_ips);
+        let mut peers = Vec::new();
+        push_attribute(&mut peers, 0, &peer);
+
+        let mut first = Vec::new();
+        push_attribute(&mut first, WGDEVICE_A_IFNAME, b"wg0\0");
+        push_attribute(&mut first, WGDEVICE_A_PUBLIC_KEY, &[0x01; 32]);
+        push_attribute(&mut first, WGDEVICE_A_LISTEN_PORT, &51820u16.to_ne_bytes());
+        push_attribute(&mut first, WGDEVICE_A_PEERS, &peers);
+        // Continuation message carrying another copy of the peer list
+        let mut second = Vec::new();
+        push_attribute(&mut second, WGDEVICE_A_IFNAME, b"wg0\0");
+        push_attribute(&mut second, WGDEVICE_A_PEERS, &peers);
+
+        let info = VpnCollector::parse_wireguard_device(&[&first, &second]);
+        assert_eq!(info.listen_port, Some(51820));
+        assert_eq!(
+            info.public_key.as_deref(),
+            Some(STANDARD.encode([0x01; 32]).as_str())
+        );
+        assert_eq!(info.peers.len(), 2);
+
+        let peer = &info.peers[0];
+        assert_eq!(peer.endpoint.as_deref(), Some("203.0.113.7:51820"));
+        assert_eq!(peer.latest_handshake, Some(1_700_000_000));
+        assert_eq!(peer.rx_bytes, 4096);
+        assert_eq!(peer.tx_bytes, 1024);
+        assert_eq!(peer.allowed_ips, vec!["10.8.0.0/24"]);
+        assert_eq!(peer.persistent_keepalive_secs, Some(25));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_route_oif() {
+        let mut payload = vec![libc::AF_INET as u8, 32, 0, 0, 254, 0, 0, 1, 0, 0, 0, 0];
+        push_attribute(&mut payload, RTA_DST, &[1, 1, 1, 1]);
+        push_attribute(&mut payload, RTA_OIF, &3u32.to_ne_bytes());
+        assert_eq!(VpnCollector::parse_route_oif(&payload), Some(3));
+    }
+}
@@ -0,0 +1,27 @@
+//! Container-related Tauri commands
+
+use crate::collectors::ContainerCollector;
+use crate::models::{ContainerError, ContainerInventory, ContainerStats};
+
+/// Get containers, images, volumes and networks of the local Docker or Podman engine
+#[tauri::command]
+pub async fn get_container_inventory() -> Result<ContainerInventory, ContainerError> {
+    log::debug!("Command: get_container_inventory");
+    tokio::task::spawn_blocking(ContainerCollector::get_inventory)
+        .await
+        .map_err(|e| ContainerError::InvalidResponse {
+            message: format!("Container inventory task failed: {}", e),
+        })?
+}
+
+/// Get live resource usage of one container. The engine samples CPU usage over
+/// about a second before answering.
+#[tauri::command]
+pub async fn get_container_stats(id: String) -> Result<ContainerStats, ContainerError> {
+    log::debug!("Command: get_container_stats({})", id);
+    tokio::task::spawn_blocking(move || ContainerCollector::get_stats(&id))
+        .await
+        .map_err(|e| ContainerError::InvalidResponse {
+            message: format!("Container stats task failed: {}", e),
+        })?
+}
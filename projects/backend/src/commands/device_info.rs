@@ -5,8 +5,8 @@ use crate::services::device_sources::{
     fetch_from_all_sources, merge_results, DeviceSource, WikipediaSource,
 };
 use crate::services::{
-    AiAgent, CacheManager, ClaudeClient, ImageCache, InternetFetcher, KnowledgeStore,
-    LocalDatabaseManager,
+    AiAgent, AuditLog, CacheManager, ClaudeClient, CommandPolicy, ImageCache, InternetFetcher,
+    KnowledgeStore, LocalDatabaseManager,
 };
 use chrono::{Duration, Utc};
 use std::sync::OnceLock;
@@ -374,8 +374,18 @@ pub fn clear_device_cache(
     let cache = get_cache_manager();
 
     match (device_id, device_type) {
-        (Some(id), Some(dt)) => cache.remove(&id, &dt).map_err(|e| e.to_string()),
-        _ => cache.clear().map_err(|e| e.to_string()),
+        (Some(id), Some(dt)) => CommandPolicy::global().run_settings_operation(
+            AuditLog::global(),
+            "clear_device_cache",
+            &format!("device cache entry {}", id),
+            || cache.remove(&id, &dt).map_err(|e| e.to_string()),
+        ),
+        _ => CommandPolicy::global().run_settings_operation(
+            AuditLog::global(),
+            "clear_device_cache",
+            "device cache",
+            || cache.clear().map_err(|e| e.to_string()),
+        ),
     }
 }
 
@@ -383,7 +393,12 @@ pub fn clear_device_cache(
 #[tauri::command]
 pub fn cleanup_device_cache() -> Result<usize, String> {
     let cache = get_cache_manager();
-    cache.cleanup_expired().map_err(|e| e.to_string())
+    CommandPolicy::global().run_settings_operation(
+        AuditLog::global(),
+        "cleanup_device_cache",
+        "device cache",
+        || cache.cleanup_expired().map_err(|e| e.to_string()),
+    )
 }
 
 /// Get database statistics.
@@ -574,10 +589,19 @@ pub async fn cleanup_image_cache(max_age_days: u64) -> Result<usize, String> {
     let image_cache = get_image_cache();
     let max_age = std::time::Duration::from_secs(max_age_days * 24 * 60 * 60);
 
-    image_cache
-        .cleanup_older_than(max_age)
+    CommandPolicy::global()
+        .run_settings_operation_async(
+            AuditLog::global(),
+            "cleanup_image_cache",
+            "image cache",
+            async {
+                image_cache
+                    .cleanup_older_than(max_age)
+                    .await
+                    .map_err(|e| e.to_string())
+            },
+        )
         .await
-        .map_err(|e| e.to_string())
 }
 
 // =============================================================================
@@ -660,10 +684,19 @@ pub fn list_enrichment_sources() -> Vec<EnrichmentSourceResponse> {
 pub async fn cleanup_enrichment_cache(max_age_days: u64) -> Result<CleanupResponse, String> {
     let enrichment_service = get_enrichment_service();
 
-    let result = enrichment_service
-        .cleanup(max_age_days)
-        .await
-        .map_err(|e| e.to_string())?;
+    let result = CommandPolicy::global()
+        .run_settings_operation_async(
+            AuditLog::global(),
+            "cleanup_enrichment_cache",
+            "enrichment cache",
+            async {
+                enrichment_service
+                    .cleanup(max_age_days)
+                    .await
+                    .map_err(|e| e.to_string())
+            },
+        )
+        .await?;
 
     Ok(CleanupResponse {
         images_removed: result.images_removed,
@@ -0,0 +1,114 @@
+//! Network diagnostics Tauri commands
+//!
+//! Results stream to the frontend as events tagged with the caller's `run_id`
+//! while the command runs; the command itself resolves with the summary.
+
+use crate::models::{
+    DiagnosticUpdate, DnsLookupResult, DnsRecordType, PingResult, TcpProbeResult, TracerouteResult,
+};
+use crate::services::NetworkDiagnostics;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+const DEFAULT_PING_COUNT: u32 = 4;
+const MAX_PING_COUNT: u32 = 100;
+const DEFAULT_MAX_HOPS: u8 = 30;
+
+fn emit_update<T: serde::Serialize + Clone>(app: &AppHandle, event: &str, run_id: &str, data: &T) {
+    let update = DiagnosticUpdate {
+        run_id: run_id.to_string(),
+        data: data.clone(),
+    };
+    if let Err(e) = app.emit(event, update) {
+        log::warn!("Failed to emit {}: {}", event, e);
+    }
+}
+
+/// Ping a host, emitting `diagnostics:ping-reply` for every echo request
+#[tauri::command]
+pub async fn run_ping(
+    app: AppHandle,
+    run_id: String,
+    target: String,
+    count: Option<u32>,
+) -> Result<PingResult, String> {
+    log::info!("Command: run_ping({}, {:?})", target, count);
+    let count = count.unwrap_or(DEFAULT_PING_COUNT).clamp(1, MAX_PING_COUNT);
+
+    tokio::task::spawn_blocking(move || {
+        NetworkDiagnostics::ping(&target, count, PROBE_TIMEOUT, |reply| {
+            emit_update(&app, "diagnostics:ping-reply", &run_id, reply)
+        })
+    })
+    .await
+    .map_err(|e| format!("Ping task failed: {}", e))?
+}
+
+/// Test TCP reachability of a host port, emitting `diagnostics:tcp-probe` per address
+#[tauri::command]
+pub async fn run_tcp_probe(
+    app: AppHandle,
+    run_id: String,
+    host: String,
+    port: u16,
+) -> Result<Vec<TcpProbeResult>, String> {
+    log::info!("Command: run_tcp_probe({}:{})", host, port);
+
+    NetworkDiagnostics::tcp_probe(&host, port, PROBE_TIMEOUT, |result| {
+        emit_update(&app, "diagnostics:tcp-probe", &run_id, result)
+    })
+    .await
+}
+
+/// Resolve a name against each resolver (the configured ones unless `servers` is given),
+/// emitting `diagnostics:dns-answer` per resolver
+#[tauri::command]
+pub async fn run_dns_lookup(
+    app: AppHandle,
+    run_id: String,
+    name: String,
+    record_type: Option<DnsRecordType>,
+    servers: Option<Vec<String>>,
+) -> Result<DnsLookupResult, String> {
+    log::info!("Command: run_dns_lookup({}, {:?})", name, record_type);
+
+    let servers = match servers {
+        Some(servers) => servers,
+        None => tokio::task::spawn_blocking(NetworkDiagnostics::configured_resolvers)
+            .await
+            .map_err(|e| format!("Failed to read resolvers: {}", e))?,
+    };
+    if servers.is_empty() {
+        return Err("No DNS resolvers configured".to_string());
+    }
+
+    NetworkDiagnostics::dns_lookup(
+        &name,
+        record_type.unwrap_or(DnsRecordType::A),
+        &servers,
+        PROBE_TIMEOUT,
+        |answer| emit_update(&app, "diagnostics:dns-answer", &run_id, answer),
+    )
+    .await
+}
+
+/// Trace the route to a host, emitting `diagnostics:traceroute-hop` per hop
+#[tauri::command]
+pub async fn run_traceroute(
+    app: AppHandle,
+    run_id: String,
+    target: String,
+    max_hops: Option<u8>,
+) -> Result<TracerouteResult, String> {
+    log::info!("Command: run_traceroute({}, {:?})", target, max_hops);
+    let max_hops = max_hops.unwrap_or(DEFAULT_MAX_HOPS).max(1);
+
+    tokio::task::spawn_blocking(move || {
+        NetworkDiagnostics::traceroute(&target, max_hops, PROBE_TIMEOUT, |hop| {
+            emit_update(&app, "diagnostics:traceroute-hop", &run_id, hop)
+        })
+    })
+    .await
+    .map_err(|e| format!("Traceroute task failed: {}", e))?
+}
@@ -5,6 +5,7 @@ use crate::models::{
     AudioDevice, CpuInfo, CpuMetrics, GpuInfo, GpuMetrics, MemoryInfo, MemoryMetrics, Monitor,
     MotherboardInfo, UsbDevice,
 };
+use crate::services::{AuditLog, CommandPolicy};
 use crate::state::SysInfoState;
 use sysinfo::Components;
 use tauri::State;
@@ -107,35 +108,55 @@ pub fn get_monitors() -> Vec<Monitor> {
 }
 
 /// Response for hardware ID database update
-#[derive(serde::Serialize)]
+#[derive(Default, serde::Serialize)]
 pub struct HwIdUpdateResponse {
     pub usb_updated: bool,
     pub pci_updated: bool,
+    pub oui_updated: bool,
     pub usb_vendors: usize,
     pub usb_products: usize,
     pub pci_vendors: usize,
     pub pci_devices: usize,
+    pub oui_vendors: usize,
     pub error: Option<String>,
 }
 
 /// Update hardware ID databases from official sources.
-/// Downloads the latest USB and PCI ID databases if they are outdated.
+/// Downloads the latest USB, PCI and OUI databases if they are outdated.
 #[tauri::command]
 pub async fn update_hardware_ids() -> HwIdUpdateResponse {
     log::info!("Command: update_hardware_ids");
 
     let data_dir = crate::hwids::get_data_dir();
-    let result = crate::hwids::update_databases(&data_dir).await;
-
-    HwIdUpdateResponse {
-        usb_updated: result.usb_updated,
-        pci_updated: result.pci_updated,
-        usb_vendors: result.usb_vendors,
-        usb_products: result.usb_products,
-        pci_vendors: result.pci_vendors,
-        pci_devices: result.pci_devices,
-        error: result.error,
+    let mut response = HwIdUpdateResponse::default();
+    let outcome = CommandPolicy::global()
+        .run_settings_operation_async(
+            AuditLog::global(),
+            "update_hardware_ids",
+            "hardware ID databases",
+            async {
+                let result = crate::hwids::update_databases(&data_dir).await;
+                response = HwIdUpdateResponse {
+                    usb_updated: result.usb_updated,
+                    pci_updated: result.pci_updated,
+                    oui_updated: result.oui_updated,
+                    usb_vendors: result.usb_vendors,
+                    usb_products: result.usb_products,
+                    pci_vendors: result.pci_vendors,
+                    pci_devices: result.pci_devices,
+                    oui_vendors: result.oui_vendors,
+                    error: result.error.clone(),
+                };
+                result.error.map_or(Ok(()), Err)
+            },
+        )
+        .await;
+
+    // Denied by the policy, or failed; a partial update keeps its counts
+    if let Err(e) = outcome {
+        response.error = Some(e);
     }
+    response
 }
 
 #[cfg(test)]
@@ -0,0 +1,56 @@
+//! Executable integrity Tauri commands
+
+use crate::collectors::{IntegrityCollector, ServiceCollector};
+use crate::models::{BinaryIntegrity, IntegrityError};
+use crate::state::SysInfoState;
+use sysinfo::Pid;
+use tauri::State;
+
+/// Hash a process's executable and check it against the package that installed it
+#[tauri::command]
+pub async fn get_process_integrity(
+    pid: u32,
+    state: State<'_, SysInfoState>,
+) -> Result<BinaryIntegrity, IntegrityError> {
+    log::debug!("Command: get_process_integrity({})", pid);
+
+    let exe_path = state.with_processes(|sys, _, _| {
+        sys.process(Pid::from_u32(pid))
+            .and_then(|process| process.exe())
+            .map(|path| path.to_string_lossy().to_string())
+    });
+
+    // Hashing reads the whole binary and the package lookup may scan its database
+    tokio::task::spawn_blocking(move || {
+        IntegrityCollector::verify_process(pid, exe_path.as_deref())
+    })
+    .await
+    .map_err(|e| IntegrityError::Io {
+        path: format!("process {}", pid),
+        message: format!("Integrity check task failed: {}", e),
+    })?
+}
+
+/// Hash a service's executable and check it against the package that installed it
+#[tauri::command]
+pub async fn get_service_integrity(name: String) -> Result<BinaryIntegrity, IntegrityError> {
+    log::debug!("Command: get_service_integrity({})", name);
+
+    tokio::task::spawn_blocking(move || {
+        // Linux services are systemd units, which the collector asks systemd about
+        let binary_path = if cfg!(target_os = "linux") {
+            None
+        } else {
+            ServiceCollector::get_services()
+                .into_iter()
+                .find(|service| service.name.eq_ignore_ascii_case(&name))
+                .and_then(|service| service.binary_path)
+        };
+        IntegrityCollector::verify_service(&name, binary_path.as_deref())
+    })
+    .await
+    .map_err(|e| IntegrityError::Io {
+        path: "service".to_string(),
+        message: format!("Integrity check task failed: {}", e),
+    })?
+}
@@ -2,17 +2,25 @@
 //!
 //! These commands are exposed to the Angular frontend via Tauri's invoke API.
 
+pub mod container;
 pub mod device_info;
+pub mod diagnostics;
 pub mod hardware;
+pub mod integrity;
 pub mod network;
+pub mod policy;
 pub mod process;
 pub mod service;
 pub mod storage;
 pub mod system;
 
+pub use container::*;
 pub use device_info::*;
+pub use diagnostics::*;
 pub use hardware::*;
+pub use integrity::*;
 pub use network::*;
+pub use policy::*;
 pub use process::*;
 pub use service::*;
 pub use storage::*;
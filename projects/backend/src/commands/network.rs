@@ -1,7 +1,25 @@
 //! Network-related Tauri commands
 
-use crate::collectors::NetworkCollector;
-use crate::models::{AdapterStats, NetworkAdapter, NetworkConnection, Route};
+use crate::collectors::{
+    NeighborCollector, NetworkCollector, NetworkTopologyCollector, SocketCollector, VpnCollector,
+    WirelessCollector,
+};
+use crate::models::{
+    AdapterHistory, AdapterStats, ListenerAllowlistEntry, ListeningPort, NeighborEntry,
+    NetworkAdapter, NetworkConnection, NetworkTopology, Route, VpnStatus, WifiInfo,
+};
+use crate::services::{AuditLog, CommandPolicy, ListenerAllowlist, NetworkRateTracker};
+use std::sync::OnceLock;
+use tauri::State;
+
+/// Global listener allowlist instance
+static LISTENER_ALLOWLIST: OnceLock<ListenerAllowlist> = OnceLock::new();
+
+/// Get or initialize the listener allowlist.
+fn get_listener_allowlist_store() -> &'static ListenerAllowlist {
+    LISTENER_ALLOWLIST
+        .get_or_init(|| ListenerAllowlist::new().expect("Failed to initialize ListenerAllowlist"))
+}
 
 /// Get all network adapters with their configuration
 #[tauri::command]
@@ -20,6 +38,17 @@ pub fn set_adapter_enabled(adapter_name: String, enabled: bool) -> Result<bool,
         enabled
     );
 
+    CommandPolicy::global().run_adapter_operation(
+        AuditLog::global(),
+        "set_adapter_enabled",
+        &adapter_name,
+        Some(format!("enabled={}", enabled)),
+        || apply_adapter_enabled(&adapter_name, enabled),
+    )
+}
+
+/// Enable or disable a network adapter through the OS
+fn apply_adapter_enabled(adapter_name: &str, enabled: bool) -> Result<bool, String> {
     #[cfg(target_os = "windows")]
     {
         use std::process::Command;
@@ -32,7 +61,7 @@ pub fn set_adapter_enabled(adapter_name: String, enabled: bool) -> Result<bool,
                 "interface",
                 "set",
                 "interface",
-                &adapter_name,
+                adapter_name,
                 &format!("admin={}", action),
             ])
             .output()
@@ -79,6 +108,7 @@ pub fn set_adapter_enabled(adapter_name: String, enabled: bool) -> Result<bool,
 
     #[cfg(not(target_os = "windows"))]
     {
+        let _ = (adapter_name, enabled);
         log::warn!("Network adapter control is only supported on Windows");
         Err("Network adapter control is only supported on Windows".to_string())
     }
@@ -91,6 +121,20 @@ pub fn get_adapter_stats(adapter_id: String) -> Option<AdapterStats> {
     NetworkCollector::get_adapter_stats(&adapter_id)
 }
 
+/// Get per-second throughput history for an adapter over the last `window` seconds.
+/// Long windows are averaged into buckets to keep the series small.
+#[tauri::command]
+pub fn get_adapter_history(
+    adapter_id: String,
+    window: u64,
+    tracker: State<NetworkRateTracker>,
+) -> Result<AdapterHistory, String> {
+    log::debug!("Command: get_adapter_history({}, {})", adapter_id, window);
+    tracker
+        .history(&adapter_id, window)
+        .ok_or_else(|| format!("Unknown network adapter: {}", adapter_id))
+}
+
 /// Get all active network connections
 #[tauri::command]
 pub fn get_active_connections() -> Vec<NetworkConnection> {
@@ -105,6 +149,66 @@ pub fn get_routing_table() -> Vec<Route> {
     NetworkCollector::get_routing_table()
 }
 
+/// Get Wi-Fi link details (SSID, BSSID, signal, channel, bitrate, security) for wireless adapters
+#[tauri::command]
+pub fn get_wifi_info() -> Vec<WifiInfo> {
+    log::debug!("Command: get_wifi_info");
+    WirelessCollector::get_wifi_info()
+}
+
+/// Get the ARP/NDP neighbor table with MAC vendors
+#[tauri::command]
+pub fn get_neighbors() -> Vec<NeighborEntry> {
+    log::debug!("Command: get_neighbors");
+    NeighborCollector::get_neighbors()
+}
+
+/// Get VPN/tunnel interfaces and whether the default route goes through one
+#[tauri::command]
+pub fn get_vpn_status() -> VpnStatus {
+    log::debug!("Command: get_vpn_status");
+    VpnCollector::get_vpn_status()
+}
+
+/// Get the interface graph (bridges, bonds, VLANs, veth pairs) across network namespaces
+#[tauri::command]
+pub fn get_network_topology() -> NetworkTopology {
+    log::debug!("Command: get_network_topology");
+    NetworkTopologyCollector::get_topology()
+}
+
+/// Get listening TCP/UDP sockets with their owning process, exposure and allowlist status
+#[tauri::command]
+pub fn get_listening_ports() -> Vec<ListeningPort> {
+    log::debug!("Command: get_listening_ports");
+    let mut ports = SocketCollector::get_listening_ports();
+    get_listener_allowlist_store().apply(&mut ports);
+    ports
+}
+
+/// Get the allowlist of expected externally exposed listeners
+#[tauri::command]
+pub fn get_listener_allowlist() -> Vec<ListenerAllowlistEntry> {
+    log::debug!("Command: get_listener_allowlist");
+    get_listener_allowlist_store().entries()
+}
+
+/// Replace the allowlist of expected externally exposed listeners
+#[tauri::command]
+pub fn set_listener_allowlist(entries: Vec<ListenerAllowlistEntry>) -> Result<(), String> {
+    log::info!("Command: set_listener_allowlist({} entries)", entries.len());
+    CommandPolicy::global().run_settings_operation(
+        AuditLog::global(),
+        "set_listener_allowlist",
+        "listener allowlist",
+        || {
+            get_listener_allowlist_store()
+                .set_entries(entries)
+                .map_err(|e| e.to_string())
+        },
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,51 @@
+//! Command policy and audit log Tauri commands
+
+use crate::models::{AuditEntry, AuditOutcome, AuditQuery, CommandPolicyConfig};
+use crate::services::{AuditLog, CommandPolicy};
+
+/// Get the protection settings for mutating commands
+#[tauri::command]
+pub fn get_command_policy() -> CommandPolicyConfig {
+    log::debug!("Command: get_command_policy");
+    CommandPolicy::global().config()
+}
+
+/// Replace the protection settings for mutating commands.
+/// Always allowed, even in read-only mode, so read-only mode can be turned off again.
+#[tauri::command]
+pub fn set_command_policy(config: CommandPolicyConfig) -> Result<(), String> {
+    log::info!(
+        "Command: set_command_policy(read_only={}, {} deny rules)",
+        config.read_only,
+        config.deny_rules.len()
+    );
+
+    let parameters = format!(
+        "readOnly={}, denyRules={}",
+        config.read_only,
+        config.deny_rules.len()
+    );
+    let result = CommandPolicy::global()
+        .set_config(config)
+        .map_err(|e| format!("Failed to save command policy: {}", e));
+
+    let (outcome, message) = match &result {
+        Ok(()) => (AuditOutcome::Success, None),
+        Err(e) => (AuditOutcome::Failed, Some(e.clone())),
+    };
+    AuditLog::global().record(
+        "set_command_policy",
+        "command policy",
+        Some(parameters),
+        outcome,
+        message,
+    );
+    result
+}
+
+/// Get audit log entries of mutating commands, newest first
+#[tauri::command]
+pub fn get_audit_log(query: Option<AuditQuery>) -> Vec<AuditEntry> {
+    log::debug!("Command: get_audit_log({:?})", query);
+    AuditLog::global().query(&query.unwrap_or_default())
+}
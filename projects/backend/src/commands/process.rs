@@ -1,22 +1,26 @@
 //! Process-related Tauri commands
 
-use crate::models::{ProcessInfo, ProcessSummary, SelfMetrics};
-use crate::services::ICON_CACHE;
-use crate::state::SysInfoState;
+use crate::collectors::{CgroupCollector, ProcessCollector};
+use crate::models::{
+    ApplicationMemory, ProcessControlError, ProcessDetails, ProcessEvent, ProcessFeed,
+    ProcessFeedQuery, ProcessGroup, ProcessGroupBy, ProcessInfo, ProcessMemory,
+    ProcessNetworkUsage, ProcessSummary, ProcessTermination, ProcessTreeNode, SelfMetrics,
+};
+use crate::services::{
+    AuditLog, CommandPolicy, ProcessController, ProcessEventLog, ProcessFeedTracker,
+    ProcessNetworkTracker, ICON_CACHE,
+};
+use crate::state::{thread_groups, SysInfoState};
+use std::collections::HashMap;
 use sysinfo::{Pid, Process, ProcessStatus, System, Users};
-use tauri::State;
+use tauri::{AppHandle, Manager, State};
 
 /// Get list of all running processes using shared state for efficiency
 #[tauri::command]
 pub fn get_processes(state: State<SysInfoState>) -> Vec<ProcessInfo> {
     log::debug!("Command: get_processes (optimized)");
 
-    let processes: Vec<ProcessInfo> = state.with_processes(|sys, users, cpu_count| {
-        sys.processes()
-            .iter()
-            .map(|(pid, process)| process_to_info(*pid, process, users, cpu_count))
-            .collect()
-    });
+    let processes = collect_process_infos(&state, true);
 
     // Log icon extraction stats (info level so visible in release builds)
     let with_icons = processes.iter().filter(|p| p.icon_base64.is_some()).count();
@@ -35,17 +39,92 @@ pub fn get_processes(state: State<SysInfoState>) -> Vec<ProcessInfo> {
         cache_total,
         cache_hits
     );
+    ICON_CACHE.save();
 
     processes
 }
 
+/// Get one sorted, filtered page of the process list. When `since_sequence` names a
+/// page the client still holds, only rows that entered or left the page and changed
+/// fields are returned. Icons are referenced by hash; fetch them with `get_icon`.
+#[tauri::command]
+pub fn get_process_feed(
+    query: Option<ProcessFeedQuery>,
+    state: State<SysInfoState>,
+    feed: State<ProcessFeedTracker>,
+) -> ProcessFeed {
+    let query = query.unwrap_or_default();
+    log::debug!("Command: get_process_feed({:?})", query);
+
+    // Rows carry only the icon hash; clients fetch each icon once with `get_icon`
+    let processes = collect_process_infos(&state, false);
+    ICON_CACHE.save();
+    feed.update(processes, &query)
+}
+
+/// Get a process icon (base64-encoded PNG) by the content hash from `icon_hash`
+#[tauri::command]
+pub fn get_icon(hash: String) -> Option<String> {
+    log::debug!("Command: get_icon({})", hash);
+    ICON_CACHE.get_icon_by_hash(&hash)
+}
+
+/// Get CPU, memory and disk usage totalled per container or per systemd unit
+#[tauri::command]
+pub fn get_process_groups(
+    group_by: ProcessGroupBy,
+    state: State<SysInfoState>,
+) -> Vec<ProcessGroup> {
+    log::debug!("Command: get_process_groups({:?})", group_by);
+
+    let processes = collect_process_infos(&state, false);
+    ProcessCollector::group_processes(&processes, group_by)
+}
+
+/// Get PSS/USS/swap memory of the given processes from smaps_rollup. Meant for the
+/// rows on screen; processes that can't be read are left out.
+#[tauri::command]
+pub async fn get_process_memory(pids: Vec<u32>) -> Result<Vec<ProcessMemory>, String> {
+    log::debug!("Command: get_process_memory({} pids)", pids.len());
+    tokio::task::spawn_blocking(move || {
+        pids.into_iter()
+            .filter_map(|pid| ProcessCollector::get_process_memory(pid).ok())
+            .collect()
+    })
+    .await
+    .map_err(|e| format!("Memory accounting task failed: {}", e))
+}
+
+/// Get memory grouped by executable, with PSS so shared libraries are counted once
+#[tauri::command]
+pub async fn get_memory_by_application(
+    state: State<'_, SysInfoState>,
+) -> Result<Vec<ApplicationMemory>, String> {
+    log::debug!("Command: get_memory_by_application");
+
+    let processes = collect_process_infos(&state, false);
+
+    // Reading smaps_rollup walks every mapping of every process, so keep it off
+    // the main thread
+    tokio::task::spawn_blocking(move || {
+        let memory: HashMap<u32, ProcessMemory> = processes
+            .iter()
+            .filter_map(|p| ProcessCollector::get_process_memory(p.pid).ok())
+            .map(|m| (m.pid, m))
+            .collect();
+        ProcessCollector::group_memory_by_application(&processes, &memory)
+    })
+    .await
+    .map_err(|e| format!("Memory accounting task failed: {}", e))
+}
+
 /// Get process summary statistics using shared state for efficiency
 #[tauri::command]
 pub fn get_process_summary(state: State<SysInfoState>) -> ProcessSummary {
     log::debug!("Command: get_process_summary (optimized)");
 
     state.with_processes(|sys, _users, cpu_count| {
-        let processes: Vec<&Process> = sys.processes().values().collect();
+        let processes: Vec<&Process> = thread_groups(sys).map(|(_, p)| p).collect();
 
         let running_count = processes
             .iter()
@@ -73,11 +152,22 @@ pub fn get_process_summary(state: State<SysInfoState>) -> ProcessSummary {
     })
 }
 
+/// Snapshot every running process, leaving out threads. Without `include_icons`
+/// only `icon_hash` is filled, which saves copying every icon on each poll.
+fn collect_process_infos(state: &SysInfoState, include_icons: bool) -> Vec<ProcessInfo> {
+    state.with_processes(|sys, users, cpu_count| {
+        thread_groups(sys)
+            .map(|(pid, process)| process_to_info(*pid, process, users, cpu_count, include_icons))
+            .collect()
+    })
+}
+
 fn process_to_info(
     pid: sysinfo::Pid,
     process: &Process,
     users: &Users,
     cpu_count: f32,
+    include_icon: bool,
 ) -> ProcessInfo {
     let user = process
         .user_id()
@@ -90,8 +180,13 @@ fn process_to_info(
 
     // Get executable path and icon
     let exe_path = process.exe().map(|p| p.to_string_lossy().to_string());
-    let icon_base64 =
-        ICON_CACHE.get_icon_for_process(&process.name().to_string_lossy(), exe_path.as_deref());
+    let attribution = CgroupCollector::get_attribution(pid.as_u32());
+    let icon_hash = ICON_CACHE
+        .get_icon_hash_for_process(&process.name().to_string_lossy(), exe_path.as_deref());
+    let icon_base64 = icon_hash
+        .as_deref()
+        .filter(|_| include_icon)
+        .and_then(|hash| ICON_CACHE.get_icon_by_hash(hash));
 
     ProcessInfo {
         pid: pid.as_u32(),
@@ -113,9 +208,40 @@ fn process_to_info(
         disk_write_bytes: disk_usage.written_bytes,
         exe_path,
         icon_base64,
+        icon_hash,
+        cgroup: attribution.path,
+        systemd_unit: attribution.systemd_unit,
+        systemd_slice: attribution.systemd_slice,
+        container: attribution.container,
     }
 }
 
+/// Get running processes as nested parent/child nodes with resource usage summed
+/// per subtree. With `collapse_by_executable`, processes running the same executable
+/// as their parent or a sibling share one node.
+#[tauri::command]
+pub fn get_process_tree(
+    collapse_by_executable: Option<bool>,
+    state: State<SysInfoState>,
+) -> Vec<ProcessTreeNode> {
+    log::debug!(
+        "Command: get_process_tree(collapse_by_executable={:?})",
+        collapse_by_executable
+    );
+
+    let processes = collect_process_infos(&state, true);
+
+    ProcessCollector::build_process_tree(processes, collapse_by_executable.unwrap_or(false))
+}
+
+/// Get threads, open files, sockets, environment, limits, cgroup, capabilities and
+/// namespaces of a single process
+#[tauri::command]
+pub fn get_process_details(pid: u32) -> Result<ProcessDetails, String> {
+    log::debug!("Command: get_process_details(pid={})", pid);
+    ProcessCollector::get_process_details(pid)
+}
+
 /// Get metrics for the current Syslens process itself
 #[tauri::command]
 pub fn get_self_metrics(state: State<SysInfoState>) -> SelfMetrics {
@@ -145,33 +271,263 @@ pub fn get_self_metrics(state: State<SysInfoState>) -> SelfMetrics {
     })
 }
 
+/// Get per-process TCP traffic rates, busiest first.
+/// With `top`, only the N busiest processes that moved any data are returned.
+#[tauri::command]
+pub async fn get_process_network_usage(
+    top: Option<usize>,
+    app: AppHandle,
+) -> Result<Vec<ProcessNetworkUsage>, String> {
+    log::debug!("Command: get_process_network_usage(top={:?})", top);
+
+    // Sampling walks every socket of every process and, without a recent sample,
+    // waits for a baseline, so keep it off the main thread
+    tokio::task::spawn_blocking(move || {
+        let traffic = app.state::<ProcessNetworkTracker>().sample();
+
+        let state = app.state::<SysInfoState>();
+        let mut usage: Vec<ProcessNetworkUsage> =
+            state.with_processes(|sys, _users, _cpu_count| {
+                traffic
+                    .into_iter()
+                    .map(|(pid, t)| {
+                        let process = sys.process(Pid::from_u32(pid));
+                        ProcessNetworkUsage {
+                            pid,
+                            name: process
+                                .map(|p| p.name().to_string_lossy().to_string())
+                                .unwrap_or_default(),
+                            exe_path: process
+                                .and_then(|p| p.exe())
+                                .map(|p| p.to_string_lossy().to_string()),
+                            bytes_sent_per_sec: t.bytes_sent_per_sec,
+                            bytes_received_per_sec: t.bytes_received_per_sec,
+                            total_bytes_sent: t.total_bytes_sent,
+                            total_bytes_received: t.total_bytes_received,
+                            connection_count: t.connection_count,
+                        }
+                    })
+                    .collect()
+            });
+
+        let rate = |u: &ProcessNetworkUsage| u.bytes_sent_per_sec + u.bytes_received_per_sec;
+        usage.sort_by(|a, b| rate(b).total_cmp(&rate(a)));
+
+        if let Some(top) = top {
+            usage.retain(|u| rate(u) > 0.0);
+            usage.truncate(top);
+        }
+
+        usage
+    })
+    .await
+    .map_err(|e| format!("Network usage task failed: {}", e))
+}
+
+/// Get process start, exec and exit events after the one numbered `since_sequence`,
+/// oldest first. Pass the last event's sequence to get the next page.
+#[tauri::command]
+pub fn get_process_events(
+    since_sequence: Option<u64>,
+    limit: Option<usize>,
+    event_log: State<ProcessEventLog>,
+) -> Vec<ProcessEvent> {
+    log::debug!(
+        "Command: get_process_events(since_sequence={:?}, limit={:?})",
+        since_sequence,
+        limit
+    );
+    event_log.query(since_sequence, limit)
+}
+
+/// Whether process events are also written to the on-disk log
+#[tauri::command]
+pub fn get_process_event_logging(event_log: State<ProcessEventLog>) -> bool {
+    log::debug!("Command: get_process_event_logging");
+    event_log.is_persistent()
+}
+
+/// Turn the on-disk process event log on or off
+#[tauri::command]
+pub fn set_process_event_logging(
+    enabled: bool,
+    event_log: State<ProcessEventLog>,
+) -> Result<(), String> {
+    log::info!("Command: set_process_event_logging({})", enabled);
+    CommandPolicy::global().run_settings_operation(
+        AuditLog::global(),
+        "set_process_event_logging",
+        "process event log",
+        || event_log.set_persistent(enabled).map_err(|e| e.to_string()),
+    )
+}
+
 /// Kill a process by PID
 /// Returns true if the process was successfully killed, false otherwise
 #[tauri::command]
 pub fn kill_process(pid: u32) -> Result<bool, String> {
     log::info!("Command: kill_process(pid={})", pid);
 
-    let mut sys = System::new();
-    sys.refresh_processes(sysinfo::ProcessesToUpdate::Some(&[Pid::from_u32(pid)]));
+    guarded("kill_process", pid, None, || {
+        let mut sys = System::new();
+        sys.refresh_processes(sysinfo::ProcessesToUpdate::Some(&[Pid::from_u32(pid)]));
 
-    if let Some(process) = sys.process(Pid::from_u32(pid)) {
-        let name = process.name().to_string_lossy().to_string();
-        let killed = process.kill();
+        let Some(process) = sys.process(Pid::from_u32(pid)) else {
+            log::warn!("Process not found: PID {}", pid);
+            return Err(ProcessControlError::NotFound { pid });
+        };
 
-        if killed {
+        let name = process.name().to_string_lossy().to_string();
+        if process.kill() {
             log::info!("Successfully killed process: {} (PID: {})", name, pid);
             Ok(true)
         } else {
             log::warn!("Failed to kill process: {} (PID: {})", name, pid);
-            Err(format!(
-                "Failed to kill process {} (PID: {}). Access may be denied.",
-                name, pid
-            ))
+            Err(ProcessControlError::Failed {
+                pid,
+                message: format!(
+                    "Failed to kill process {} (PID: {}). Access may be denied.",
+                    name, pid
+                ),
+            })
         }
-    } else {
-        log::warn!("Process not found: PID {}", pid);
-        Err(format!("Process with PID {} not found", pid))
-    }
+    })
+    .map_err(|e| match e {
+        ProcessControlError::Failed { message, .. } => message,
+        e => e.to_string(),
+    })
+}
+
+/// Send a signal (1-64) to a process
+#[tauri::command]
+pub fn send_process_signal(pid: u32, signal: i32) -> Result<(), ProcessControlError> {
+    log::info!(
+        "Command: send_process_signal(pid={}, signal={})",
+        pid,
+        signal
+    );
+    guarded(
+        "send_process_signal",
+        pid,
+        Some(format!("signal={}", signal)),
+        || ProcessController::send_signal(pid, signal),
+    )
+}
+
+/// Terminate a process with SIGTERM, escalating to SIGKILL after `timeout_ms`
+#[tauri::command]
+pub async fn terminate_process(
+    pid: u32,
+    timeout_ms: Option<u64>,
+) -> Result<ProcessTermination, ProcessControlError> {
+    log::info!(
+        "Command: terminate_process(pid={}, timeout_ms={:?})",
+        pid,
+        timeout_ms
+    );
+    let timeout = timeout_ms
+        .map(std::time::Duration::from_millis)
+        .unwrap_or(ProcessController::DEFAULT_TERMINATE_TIMEOUT);
+
+    tokio::task::spawn_blocking(move || {
+        guarded(
+            "terminate_process",
+            pid,
+            Some(format!("timeoutMs={}", timeout.as_millis())),
+            || ProcessController::terminate(pid, timeout),
+        )
+    })
+    .await
+    .map_err(|e| ProcessControlError::Failed {
+        pid,
+        message: format!("Terminate task failed: {}", e),
+    })?
+}
+
+/// Suspend a process until it is resumed
+#[tauri::command]
+pub fn suspend_process(pid: u32) -> Result<(), ProcessControlError> {
+    log::info!("Command: suspend_process(pid={})", pid);
+    guarded("suspend_process", pid, None, || {
+        ProcessController::suspend(pid)
+    })
+}
+
+/// Resume a suspended process
+#[tauri::command]
+pub fn resume_process(pid: u32) -> Result<(), ProcessControlError> {
+    log::info!("Command: resume_process(pid={})", pid);
+    guarded("resume_process", pid, None, || {
+        ProcessController::resume(pid)
+    })
+}
+
+/// Kill a process and all of its descendants, returning the killed PIDs. Every
+/// member of the tree must pass the command policy before any of them is signalled.
+#[tauri::command]
+pub fn kill_process_tree(pid: u32) -> Result<Vec<u32>, ProcessControlError> {
+    log::info!("Command: kill_process_tree(pid={})", pid);
+    let tree = ProcessController::process_tree(pid);
+    CommandPolicy::global().run_process_tree_operation(
+        AuditLog::global(),
+        "kill_process_tree",
+        pid,
+        &tree,
+        None,
+        || ProcessController::kill_tree(pid, &tree),
+    )
+}
+
+/// Set the nice value of a process (-20 to 19)
+#[tauri::command]
+pub fn set_process_priority(pid: u32, nice: i32) -> Result<(), ProcessControlError> {
+    log::info!("Command: set_process_priority(pid={}, nice={})", pid, nice);
+    guarded(
+        "set_process_priority",
+        pid,
+        Some(format!("nice={}", nice)),
+        || ProcessController::set_priority(pid, nice),
+    )
+}
+
+/// Get the CPUs a process may run on
+#[tauri::command]
+pub fn get_process_affinity(pid: u32) -> Result<Vec<u32>, ProcessControlError> {
+    log::debug!("Command: get_process_affinity(pid={})", pid);
+    ProcessController::get_affinity(pid)
+}
+
+/// Restrict a process to the given CPUs, returning the affinity now in effect
+#[tauri::command]
+pub fn set_process_affinity(pid: u32, cpus: Vec<u32>) -> Result<Vec<u32>, ProcessControlError> {
+    log::info!(
+        "Command: set_process_affinity(pid={}, cpus={:?})",
+        pid,
+        cpus
+    );
+    guarded(
+        "set_process_affinity",
+        pid,
+        Some(format!("cpus={:?}", cpus)),
+        || ProcessController::set_affinity(pid, &cpus),
+    )
+}
+
+/// Run a mutating process operation behind the command policy and record it in the
+/// audit log
+fn guarded<T>(
+    operation: &str,
+    pid: u32,
+    parameters: Option<String>,
+    run: impl FnOnce() -> Result<T, ProcessControlError>,
+) -> Result<T, ProcessControlError> {
+    CommandPolicy::global().run_process_operation(
+        AuditLog::global(),
+        operation,
+        pid,
+        parameters,
+        run,
+    )
 }
 
 #[cfg(test)]
@@ -1,7 +1,9 @@
 //! Storage-related Tauri commands
 
-use crate::collectors::StorageCollector;
-use crate::models::{DiskHealth, DiskPerformance, NetworkDrive, Partition, PhysicalDisk, Volume};
+use crate::collectors::{StorageCollector, StorageTopologyCollector};
+use crate::models::{
+    DiskHealth, DiskPerformance, NetworkDrive, Partition, PhysicalDisk, StorageTopology, Volume,
+};
 
 /// Get all physical disks
 #[tauri::command]
@@ -45,6 +47,13 @@ pub fn get_network_drives() -> Vec<NetworkDrive> {
     StorageCollector::get_network_drives()
 }
 
+/// Get the storage topology graph (disks, partitions, RAID/LVM/crypt layers and filesystems)
+#[tauri::command]
+pub fn get_storage_topology() -> StorageTopology {
+    log::debug!("Command: get_storage_topology");
+    StorageTopologyCollector::get_topology()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,15 +1,17 @@
-//! Hardware ID database module for USB and PCI device identification.
+//! Hardware ID database module for USB, PCI and network device identification.
 //!
 //! This module provides lookup services for vendor and product names
-//! based on hardware IDs (VID/PID for USB, Vendor/Device for PCI).
+//! based on hardware IDs (VID/PID for USB, Vendor/Device for PCI, OUI for MAC addresses).
 //!
 //! The databases include embedded data for common devices and can be
 //! updated from official sources via the updater module.
 
+mod oui_ids;
 mod pci_ids;
 mod updater;
 mod usb_ids;
 
+pub use oui_ids::OuiDatabase;
 pub use pci_ids::PciIdDatabase;
 pub use updater::{needs_update, update_databases, UpdateResult};
 pub use usb_ids::UsbIdDatabase;
@@ -0,0 +1,160 @@
+//! IEEE OUI database for network hardware vendor identification.
+//!
+//! This module resolves the first three bytes of a MAC address (the
+//! Organizationally Unique Identifier) to the registered vendor name.
+//!
+//! Data is loaded from:
+//! 1. Bundled oui.ids file (subset of the IEEE MA-L registry)
+//! 2. Full oui.txt registry downloaded by the updater, when present
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Bundled OUI database - included at compile time
+const BUNDLED_OUI_IDS: &str = include_str!("../../resources/ids/oui.ids");
+
+/// OUI database for looking up MAC address vendors
+pub struct OuiDatabase {
+    /// Map of 24-bit OUI to vendor name
+    vendors: HashMap<u32, String>,
+}
+
+/// Global static instance of the OUI database
+static OUI_DATABASE: OnceLock<OuiDatabase> = OnceLock::new();
+
+impl OuiDatabase {
+    /// Get the global OUI database instance
+    pub fn global() -> &'static OuiDatabase {
+        OUI_DATABASE.get_or_init(|| {
+            let mut db = OuiDatabase::new();
+            db.load_data(BUNDLED_OUI_IDS);
+            // The downloaded registry covers every assignment, not just the bundled subset
+            let downloaded = super::updater::get_oui_ids_path(&super::get_data_dir());
+            if let Ok(content) = std::fs::read_to_string(downloaded) {
+                db.load_data(&content);
+            }
+            log::info!("OUI database loaded: {} vendors", db.vendors.len());
+            db
+        })
+    }
+
+    /// Load entries from oui.txt-formatted content
+    fn load_data(&mut self, content: &str) {
+        for line in content.lines() {
+            if let Some((oui, name)) = Self::parse_oui_line(line) {
+                self.vendors.insert(oui, name.to_string());
+            }
+        }
+    }
+
+    /// Parse a line in format "xx-xx-xx   (hex)\t\tName".
+    /// Address and "(base 16)" lines of the IEEE file are ignored.
+    pub(crate) fn parse_oui_line(line: &str) -> Option<(u32, &str)> {
+        let trimmed = line.trim();
+        let id = trimmed.get(..8)?;
+        let name = trimmed.get(8..)?.trim_start().strip_prefix("(hex)")?.trim();
+
+        let hex: String = id.split('-').collect();
+        if hex.len() != 6 || name.is_empty() {
+            return None;
+        }
+
+        u32::from_str_radix(&hex, 16).ok().map(|oui| (oui, name))
+    }
+
+    /// Create a new empty OUI database
+    pub fn new() -> Self {
+        Self {
+            vendors: HashMap::new(),
+        }
+    }
+
+    /// Look up a vendor name by 24-bit OUI
+    pub fn get_vendor(&self, oui: u32) -> Option<&str> {
+        self.vendors.get(&oui).map(|s| s.as_str())
+    }
+
+    /// Look up the vendor of a MAC address ("AA:BB:CC:DD:EE:FF", "aa-bb-cc-dd-ee-ff", ...)
+    pub fn lookup_mac(&self, mac: &str) -> Option<&str> {
+        let hex: String = mac
+            .chars()
+            .filter(|c| c.is_ascii_hexdigit())
+            .take(6)
+            .collect();
+        if hex.len() != 6 {
+            return None;
+        }
+
+        self.get_vendor(u32::from_str_radix(&hex, 16).ok()?)
+    }
+
+    /// Add a vendor to the database
+    pub fn add_vendor(&mut self, oui: u32, name: &str) {
+        self.vendors.insert(oui, name.to_string());
+    }
+
+    /// Get database statistics
+    pub fn stats(&self) -> usize {
+        self.vendors.len()
+    }
+}
+
+impl Default for OuiDatabase {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup_known_vendor() {
+        let db = OuiDatabase::global();
+        assert_eq!(db.get_vendor(0x00000C), Some("Cisco Systems, Inc"));
+        assert_eq!(db.lookup_mac("00:50:56:c0:00:08"), Some("VMware, Inc."));
+        assert_eq!(
+            db.lookup_mac("B8-27-EB-12-34-56"),
+            Some("Raspberry Pi Foundation")
+        );
+    }
+
+    #[test]
+    fn test_lookup_unknown() {
+        let mut db = OuiDatabase::new();
+        assert_eq!(db.lookup_mac("02:42:ac:11:00:02"), None);
+        assert_eq!(db.lookup_mac("00:1b"), None);
+
+        db.add_vendor(0x02_42_AC, "Test Vendor");
+        assert_eq!(db.lookup_mac("02:42:ac:11:00:02"), Some("Test Vendor"));
+    }
+
+    #[test]
+    fn test_parse_oui_line() {
+        assert_eq!(
+            OuiDatabase::parse_oui_line("00-00-0C   (hex)\t\tCisco Systems, Inc"),
+            Some((0x00000C, "Cisco Systems, Inc"))
+        );
+        // Other lines of the IEEE registry entry
+        assert_eq!(
+            OuiDatabase::parse_oui_line("00000C     (base 16)\t\tCisco Systems, Inc"),
+            None
+        );
+        assert_eq!(
+            OuiDatabase::parse_oui_line("\t\t\t\t170 WEST TASMAN DRIVE"),
+            None
+        );
+        assert_eq!(OuiDatabase::parse_oui_line("# comment"), None);
+    }
+
+    #[test]
+    fn test_bundled_database_loaded() {
+        let db = OuiDatabase::global();
+        assert!(
+            db.stats() > 300,
+            "Expected 300+ vendors from bundled oui.ids, got {}",
+            db.stats()
+        );
+    }
+}
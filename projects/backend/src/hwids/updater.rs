@@ -1,8 +1,9 @@
 //! Hardware ID database updater.
 //!
-//! Downloads and parses official USB and PCI ID databases from:
+//! Downloads and parses official USB, PCI and IEEE OUI databases from:
 //! - USB: http://www.linux-usb.org/usb.ids
 //! - PCI: https://pci-ids.ucw.cz/v2.2/pci.ids
+//! - OUI: https://standards-oui.ieee.org/oui/oui.txt
 
 use std::collections::HashMap;
 use std::fs;
@@ -12,6 +13,7 @@ use std::time::{Duration, SystemTime};
 
 const USB_IDS_URL: &str = "http://www.linux-usb.org/usb.ids";
 const PCI_IDS_URL: &str = "https://pci-ids.ucw.cz/v2.2/pci.ids";
+const OUI_IDS_URL: &str = "https://standards-oui.ieee.org/oui/oui.txt";
 const UPDATE_INTERVAL_DAYS: u64 = 30;
 
 /// Database update result
@@ -19,10 +21,12 @@ const UPDATE_INTERVAL_DAYS: u64 = 30;
 pub struct UpdateResult {
     pub usb_updated: bool,
     pub pci_updated: bool,
+    pub oui_updated: bool,
     pub usb_vendors: usize,
     pub usb_products: usize,
     pub pci_vendors: usize,
     pub pci_devices: usize,
+    pub oui_vendors: usize,
     pub error: Option<String>,
 }
 
@@ -55,20 +59,23 @@ pub fn needs_update(file_path: &Path) -> bool {
     true
 }
 
-/// Download and update both databases.
+/// Download and update all databases.
 pub async fn update_databases(data_dir: &Path) -> UpdateResult {
     let mut result = UpdateResult {
         usb_updated: false,
         pci_updated: false,
+        oui_updated: false,
         usb_vendors: 0,
         usb_products: 0,
         pci_vendors: 0,
         pci_devices: 0,
+        oui_vendors: 0,
         error: None,
     };
 
     let usb_path = data_dir.join("usb.ids");
     let pci_path = data_dir.join("pci.ids");
+    let oui_path = get_oui_ids_path(data_dir);
 
     // Update USB IDs if needed
     if needs_update(&usb_path) {
@@ -104,6 +111,23 @@ pub async fn update_databases(data_dir: &Path) -> UpdateResult {
         }
     }
 
+    // Update OUI registry if needed
+    if needs_update(&oui_path) {
+        match download_file(OUI_IDS_URL, &oui_path).await {
+            Ok(_) => {
+                result.oui_updated = true;
+                log::info!("OUI database updated");
+            }
+            Err(e) => {
+                let msg = format!("Failed to update OUI registry: {}", e);
+                log::warn!("{}", msg);
+                if result.error.is_none() {
+                    result.error = Some(msg);
+                }
+            }
+        }
+    }
+
     // Parse and count entries
     if usb_path.exists() {
         if let Ok(parsed) = parse_usb_ids(&usb_path) {
@@ -119,6 +143,12 @@ pub async fn update_databases(data_dir: &Path) -> UpdateResult {
         }
     }
 
+    if oui_path.exists() {
+        if let Ok(parsed) = parse_oui_ids(&oui_path) {
+            result.oui_vendors = parsed.len();
+        }
+    }
+
     result
 }
 
@@ -240,6 +270,26 @@ pub fn parse_pci_ids(path: &PathBuf) -> Result<ParsedPciIds, std::io::Error> {
     Ok(ParsedPciIds { vendors, devices })
 }
 
+/// Parse the IEEE oui.txt registry into a map of 24-bit OUI to vendor name.
+/// Format:
+/// xx-xx-xx   (hex)\t\tvendor_name
+/// xxxxxx     (base 16)\t\tvendor_name
+/// \t\t\t\taddress lines
+pub fn parse_oui_ids(path: &PathBuf) -> Result<HashMap<u32, String>, std::io::Error> {
+    let file = fs::File::open(path)?;
+    let reader = BufReader::new(file);
+
+    let mut vendors = HashMap::new();
+    for line in reader.lines() {
+        let line = line?;
+        if let Some((oui, name)) = super::OuiDatabase::parse_oui_line(&line) {
+            vendors.insert(oui, name.to_string());
+        }
+    }
+
+    Ok(vendors)
+}
+
 /// Parse a line in format "id  name" or "id\tname"
 fn parse_id_line(line: &str) -> Option<(&str, &str)> {
     // Split on first whitespace
@@ -273,6 +323,11 @@ pub fn get_pci_ids_path(data_dir: &Path) -> PathBuf {
     data_dir.join("pci.ids")
 }
 
+/// Get the path to the cached IEEE OUI registry.
+pub fn get_oui_ids_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("oui.txt")
+}
+
 /// Load USB IDs from cached file, falling back to embedded data if not available.
 #[allow(dead_code)]
 pub fn load_usb_ids(data_dir: &Path) -> Option<ParsedUsbIds> {
@@ -8,6 +8,10 @@
 )]
 
 use syslens::commands;
+use syslens::services::{
+    HardwareEventMonitor, NetworkEventMonitor, NetworkRateTracker, ProcessEventLog,
+    ProcessEventWatcher, ProcessFeedTracker, ProcessNetworkTracker,
+};
 use syslens::state::SysInfoState;
 use tauri::{
     menu::{MenuBuilder, MenuItemBuilder, SubmenuBuilder},
@@ -25,12 +29,26 @@ fn main() {
     // Create shared system state for efficient sysinfo operations
     let sysinfo_state = SysInfoState::new();
 
+    // Sample network counters in the background so rate history is ready when requested
+    let network_rates = NetworkRateTracker::new();
+    network_rates.start();
+
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_dialog::init())
         .manage(sysinfo_state)
+        .manage(network_rates)
+        .manage(ProcessNetworkTracker::new())
+        .manage(ProcessFeedTracker::new())
+        .manage(ProcessEventLog::new())
         .setup(|app| {
+            // Push link, address and route changes to the frontend as they happen
+            NetworkEventMonitor::start(app.handle().clone());
+            HardwareEventMonitor::start(app.handle().clone());
+            // Record process starts and exits for the lifecycle timeline
+            ProcessEventWatcher::start(app.handle().clone());
+
             // Build the application menu
             let toggle_left_sidebar = MenuItemBuilder::new("Toggle Navigation")
                 .id("toggle_left_sidebar")
@@ -169,9 +187,22 @@ fn main() {
             // Network commands
             commands::get_network_adapters,
             commands::get_adapter_stats,
+            commands::get_adapter_history,
             commands::get_active_connections,
             commands::get_routing_table,
+            commands::get_wifi_info,
+            commands::get_neighbors,
+            commands::get_vpn_status,
+            commands::get_network_topology,
+            commands::get_listening_ports,
+            commands::get_listener_allowlist,
+            commands::set_listener_allowlist,
             commands::set_adapter_enabled,
+            // Network diagnostics commands
+            commands::run_ping,
+            commands::run_tcp_probe,
+            commands::run_dns_lookup,
+            commands::run_traceroute,
             // System commands
             commands::get_device_info,
             commands::get_bios_info,
@@ -201,14 +232,44 @@ fn main() {
             commands::get_disk_health,
             commands::get_disk_performance,
             commands::get_network_drives,
+            commands::get_storage_topology,
             // Process commands
             commands::get_processes,
+            commands::get_process_feed,
+            commands::get_icon,
             commands::get_process_summary,
+            commands::get_process_tree,
+            commands::get_process_groups,
+            commands::get_process_details,
+            commands::get_process_memory,
+            commands::get_memory_by_application,
             commands::get_self_metrics,
+            commands::get_process_network_usage,
+            commands::get_process_events,
+            commands::get_process_event_logging,
+            commands::set_process_event_logging,
             commands::kill_process,
+            commands::send_process_signal,
+            commands::terminate_process,
+            commands::suspend_process,
+            commands::resume_process,
+            commands::kill_process_tree,
+            commands::set_process_priority,
+            commands::get_process_affinity,
+            commands::set_process_affinity,
+            // Command policy and audit commands
+            commands::get_command_policy,
+            commands::set_command_policy,
+            commands::get_audit_log,
             // Service commands
             commands::get_services,
             commands::get_service_summary,
+            // Container commands
+            commands::get_container_inventory,
+            commands::get_container_stats,
+            // Executable integrity commands
+            commands::get_process_integrity,
+            commands::get_service_integrity,
             // Device info commands
             commands::get_device_deep_info,
             commands::search_device_info,
@@ -0,0 +1,139 @@
+//! Container-related data models
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The container engine answering on the local socket
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContainerEngine {
+    /// Unix socket the engine API was reached on
+    pub socket_path: String,
+    /// "Docker Engine - Community", "Podman Engine", ...
+    pub name: String,
+    pub version: String,
+    pub api_version: String,
+    pub os: Option<String>,
+    pub arch: Option<String>,
+}
+
+/// A published or exposed container port
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContainerPort {
+    /// Host address the port is published on
+    pub ip: Option<String>,
+    pub private_port: u16,
+    /// Host port; None when the port is only exposed
+    pub public_port: Option<u16>,
+    /// "tcp", "udp" or "sctp"
+    pub protocol: String,
+}
+
+/// A container, running or not
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContainerSummary {
+    /// Full 64-character container ID
+    pub id: String,
+    /// Names without the leading slash
+    pub names: Vec<String>,
+    pub image: String,
+    pub image_id: String,
+    pub command: String,
+    /// Creation time (Unix timestamp in seconds)
+    pub created: i64,
+    /// "running", "exited", "paused", ...
+    pub state: String,
+    /// Human-readable status ("Up 2 hours", "Exited (0) 3 days ago")
+    pub status: String,
+    pub ports: Vec<ContainerPort>,
+    pub labels: BTreeMap<String, String>,
+    /// Names of the networks the container is attached to
+    pub networks: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContainerImage {
+    pub id: String,
+    /// "repository:tag" references; empty for dangling images
+    pub repo_tags: Vec<String>,
+    /// Creation time (Unix timestamp in seconds)
+    pub created: i64,
+    pub size_bytes: u64,
+    /// Containers using the image, when the engine reports it
+    pub containers: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContainerVolume {
+    pub name: String,
+    pub driver: String,
+    pub mountpoint: String,
+    pub created_at: Option<String>,
+    /// "local" or "global"
+    pub scope: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContainerNetwork {
+    pub id: String,
+    pub name: String,
+    /// "bridge", "host", "overlay", "macvlan", ...
+    pub driver: String,
+    pub scope: String,
+    /// Subnets in CIDR notation
+    pub subnets: Vec<String>,
+    pub internal: bool,
+}
+
+/// Everything the local container engine knows about
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContainerInventory {
+    pub engine: ContainerEngine,
+    pub containers: Vec<ContainerSummary>,
+    pub images: Vec<ContainerImage>,
+    pub volumes: Vec<ContainerVolume>,
+    pub networks: Vec<ContainerNetwork>,
+}
+
+/// Resource usage of a running container
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContainerStats {
+    pub id: String,
+    /// CPU usage as a share of the whole machine (0-100), like `ProcessInfo.cpu_usage`
+    pub cpu_usage: f32,
+    /// Memory in use, excluding reclaimable page cache
+    pub memory_usage_bytes: u64,
+    pub memory_limit_bytes: u64,
+    pub network_rx_bytes: u64,
+    pub network_tx_bytes: u64,
+    pub block_read_bytes: u64,
+    pub block_write_bytes: u64,
+    pub pids: u64,
+}
+
+/// Why the container engine could not be queried
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, thiserror::Error)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum ContainerError {
+    /// No engine socket exists, or nothing is listening on it
+    #[error("No container engine is running: {message}")]
+    NotRunning { message: String },
+    /// The socket exists but the user may not connect (not in the docker group)
+    #[error("Permission denied connecting to {socket}")]
+    PermissionDenied { socket: String },
+    #[error("Container engine returned {status}: {message}")]
+    Api { status: u16, message: String },
+    #[error("Invalid response from container engine: {message}")]
+    InvalidResponse { message: String },
+    #[error("Invalid argument: {message}")]
+    InvalidArgument { message: String },
+    #[error("Container engines are not supported on this platform")]
+    Unsupported,
+}
@@ -0,0 +1,99 @@
+//! Network diagnostics data models
+
+use serde::{Deserialize, Serialize};
+
+/// Progress update streamed to the frontend while a diagnostic runs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticUpdate<T> {
+    /// Caller-chosen id that ties updates to the command invocation
+    pub run_id: String,
+    pub data: T,
+}
+
+/// A single ICMP echo exchange
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PingReply {
+    pub sequence: u16,
+    /// None when no reply arrived before the timeout
+    pub rtt_ms: Option<f64>,
+}
+
+/// Summary of a ping run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PingResult {
+    pub target: String,
+    pub address: String,
+    pub sent: u32,
+    pub received: u32,
+    pub loss_percent: f64,
+    pub min_rtt_ms: Option<f64>,
+    pub avg_rtt_ms: Option<f64>,
+    pub max_rtt_ms: Option<f64>,
+    pub replies: Vec<PingReply>,
+}
+
+/// Result of a TCP connect attempt to one resolved address
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TcpProbeResult {
+    pub host: String,
+    pub address: String,
+    pub port: u16,
+    pub reachable: bool,
+    /// Time until the connection was accepted or refused
+    pub connect_ms: Option<f64>,
+    pub error: Option<String>,
+}
+
+/// DNS record types supported by the lookup diagnostic
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DnsRecordType {
+    A,
+    AAAA,
+}
+
+/// Answer from a single resolver
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DnsServerAnswer {
+    pub server: String,
+    pub addresses: Vec<String>,
+    pub response_ms: Option<f64>,
+    pub error: Option<String>,
+}
+
+/// DNS lookup of one name against every configured resolver
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DnsLookupResult {
+    pub name: String,
+    pub record_type: DnsRecordType,
+    pub answers: Vec<DnsServerAnswer>,
+    /// True when every resolver that answered returned the same addresses
+    pub consistent: bool,
+}
+
+/// One hop of a traceroute
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TracerouteHop {
+    pub ttl: u8,
+    /// None when the hop did not respond before the timeout
+    pub address: Option<String>,
+    pub rtt_ms: Option<f64>,
+    /// True for the final hop answered by the target itself
+    pub reached: bool,
+}
+
+/// Result of a traceroute run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TracerouteResult {
+    pub target: String,
+    pub address: String,
+    pub hops: Vec<TracerouteHop>,
+    pub reached: bool,
+}
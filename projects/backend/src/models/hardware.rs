@@ -201,3 +201,16 @@ pub struct Monitor {
     pub hdr_support: bool,
     pub refresh_rate_hz: u32,
 }
+
+/// Hotplug notification pushed to the frontend by the uevent monitor
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HotplugEvent<T> {
+    /// Kernel device name: USB port ("1-2.3"), block device ("sdb"),
+    /// DRM connector ("card0-HDMI-A-1") or interface name
+    pub device_id: String,
+    /// Freshly collected device; None for removals
+    pub device: Option<T>,
+    /// Milliseconds since the Unix epoch
+    pub timestamp: u64,
+}
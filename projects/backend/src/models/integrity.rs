@@ -0,0 +1,85 @@
+//! Executable integrity models
+
+use serde::{Deserialize, Serialize};
+
+/// Package manager whose database owns a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PackageManager {
+    Dpkg,
+    Rpm,
+    Pacman,
+}
+
+/// The installed package a file belongs to
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageOwner {
+    pub manager: PackageManager,
+    /// Package name, with the architecture for multi-arch dpkg packages ("libc6:amd64")
+    pub name: String,
+    pub version: Option<String>,
+}
+
+/// Digest algorithm of a checksum recorded by a package manager
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DigestAlgorithm {
+    Md5,
+    Sha1,
+    Sha224,
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+/// Outcome of checking a binary against its package
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum IntegrityStatus {
+    /// Matches the checksum recorded by the owning package
+    Verified,
+    /// Differs from the checksum recorded by the owning package
+    Modified,
+    /// No installed package owns the file
+    Unowned,
+    /// Owned, but the package recorded no checksum we can compare against
+    Unverified,
+    /// The running binary was deleted or replaced on disk, so the package's checksum
+    /// describes a different file than the one the process runs
+    Replaced,
+    /// No package database to check against on this system
+    Unknown,
+}
+
+/// Hash and package ownership of an executable
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinaryIntegrity {
+    pub path: String,
+    /// The running binary was deleted or replaced on disk (e.g. by an upgrade).
+    /// The hash is still of the image the process runs.
+    pub deleted: bool,
+    pub size_bytes: u64,
+    /// SHA-256 of the file (lowercase hex)
+    pub sha256: String,
+    pub package: Option<PackageOwner>,
+    /// Checksum recorded by the package (lowercase hex)
+    pub expected_digest: Option<String>,
+    pub digest_algorithm: Option<DigestAlgorithm>,
+    pub status: IntegrityStatus,
+}
+
+/// Why a binary could not be checked
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, thiserror::Error)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum IntegrityError {
+    #[error("No executable known for {target}")]
+    NoExecutable { target: String },
+    #[error("File not found: {path}")]
+    NotFound { path: String },
+    #[error("Permission denied reading {path}")]
+    PermissionDenied { path: String },
+    #[error("Failed to read {path}: {message}")]
+    Io { path: String, message: String },
+}
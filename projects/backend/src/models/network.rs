@@ -26,6 +26,8 @@ pub enum AdapterType {
     WiFi,
     Virtual,
     Loopback,
+    /// VPN or tunnel interface (WireGuard, tun/tap, IPsec, GRE, ...)
+    Tunnel,
     Unknown,
 }
 
@@ -49,6 +51,8 @@ pub struct Ipv4Config {
     pub dhcp_server: Option<String>,
     pub lease_obtained: Option<String>,
     pub lease_expires: Option<String>,
+    /// Seconds until the DHCP lease expires; negative once it has expired
+    pub lease_expires_in_secs: Option<i64>,
 }
 
 /// IPv6 configuration
@@ -99,7 +103,7 @@ pub struct NetworkConnection {
 }
 
 /// Network protocol
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ConnectionProtocol {
     TCP,
     UDP,
@@ -141,3 +145,338 @@ pub enum RouteType {
     Remote,
     Default,
 }
+
+/// Wireless link details for a Wi-Fi interface
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WifiInfo {
+    /// Interface name (e.g. "wlan0"), matches `NetworkAdapter::id`
+    pub interface: String,
+    pub connected: bool,
+    pub ssid: Option<String>,
+    /// MAC address of the access point
+    pub bssid: Option<String>,
+    pub frequency_mhz: Option<u32>,
+    pub channel: Option<u32>,
+    pub band: Option<WifiBand>,
+    pub signal_dbm: Option<i32>,
+    /// Signal quality derived from the signal strength (0-100)
+    pub signal_quality: Option<u8>,
+    pub tx_bitrate_mbps: Option<f64>,
+    pub rx_bitrate_mbps: Option<f64>,
+    pub security: Option<WifiSecurity>,
+}
+
+/// Wi-Fi frequency band
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum WifiBand {
+    #[serde(rename = "2.4GHz")]
+    Band2_4GHz,
+    #[serde(rename = "5GHz")]
+    Band5GHz,
+    #[serde(rename = "6GHz")]
+    Band6GHz,
+    #[serde(rename = "60GHz")]
+    Band60GHz,
+}
+
+/// Wi-Fi security type of the associated network
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum WifiSecurity {
+    Open,
+    /// Opportunistic Wireless Encryption (Enhanced Open)
+    OWE,
+    WEP,
+    WPA,
+    WPA2,
+    WPA3,
+    /// WPA2/WPA3 transition mode (PSK and SAE both offered)
+    WPA2WPA3,
+    /// 802.1X authentication (WPA/WPA2/WPA3-Enterprise)
+    Enterprise,
+}
+
+/// Neighbor cache entry (ARP for IPv4, NDP for IPv6)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeighborEntry {
+    pub ip_address: String,
+    /// Hardware address; absent while resolution is incomplete or has failed
+    pub mac_address: Option<String>,
+    pub interface: String,
+    pub state: NeighborState,
+    /// Hardware vendor resolved from the MAC address OUI
+    pub vendor: Option<String>,
+    /// Locally administered MAC (randomized by the device or assigned by a hypervisor)
+    pub is_locally_administered: bool,
+    /// IPv6 neighbor advertised itself as a router
+    pub is_router: bool,
+}
+
+/// Neighbor reachability state
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum NeighborState {
+    Incomplete,
+    Reachable,
+    Stale,
+    Delay,
+    Probe,
+    Failed,
+    NoArp,
+    Permanent,
+    Unknown,
+}
+
+/// Per-second throughput sample for an adapter
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdapterRateSample {
+    /// End of the sampled interval (milliseconds since the Unix epoch)
+    pub timestamp: u64,
+    pub bytes_sent_per_sec: f64,
+    pub bytes_received_per_sec: f64,
+    pub packets_sent_per_sec: f64,
+    pub packets_received_per_sec: f64,
+    pub errors_per_sec: f64,
+    pub drops_per_sec: f64,
+}
+
+/// Rate history for an adapter, averaged into evenly sized buckets
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdapterHistory {
+    pub adapter_id: String,
+    pub window_secs: u64,
+    /// Width of each sample bucket in seconds
+    pub interval_secs: u64,
+    pub samples: Vec<AdapterRateSample>,
+}
+
+/// A socket accepting connections (TCP) or datagrams (unconnected UDP)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListeningPort {
+    pub protocol: ConnectionProtocol,
+    pub local_address: String,
+    pub local_port: u16,
+    pub exposure: PortExposure,
+    /// Well-known service name from /etc/services
+    pub service_name: Option<String>,
+    pub pid: Option<u32>,
+    pub process_name: Option<String>,
+    pub exe_path: Option<String>,
+    /// systemd unit the owning process runs in
+    pub unit: Option<String>,
+    /// Matched by an entry of the listener allowlist
+    pub allowed: bool,
+    /// Reachable from other hosts and not on the allowlist
+    pub flagged: bool,
+}
+
+/// Which hosts can reach a listening socket
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PortExposure {
+    /// Bound to a loopback address
+    Loopback,
+    /// Bound to a specific non-loopback address
+    LAN,
+    /// Bound to the wildcard address (every interface)
+    All,
+}
+
+/// User-maintained entry of expected externally exposed listeners
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListenerAllowlistEntry {
+    pub port: u16,
+    /// Matches both protocols when unset
+    pub protocol: Option<ConnectionProtocol>,
+    /// Matches any process when unset
+    pub process_name: Option<String>,
+    pub comment: Option<String>,
+}
+
+/// VPN and tunnel overview
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VpnStatus {
+    pub tunnels: Vec<TunnelInterface>,
+    /// Interface that carries IPv4 internet traffic
+    pub default_route_interface: Option<String>,
+    /// Interface that carries IPv6 internet traffic
+    pub ipv6_default_route_interface: Option<String>,
+    /// True when IPv4 or IPv6 internet traffic goes through a tunnel
+    pub default_route_via_tunnel: bool,
+}
+
+/// A VPN or tunnel interface
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TunnelInterface {
+    pub name: String,
+    pub kind: TunnelKind,
+    pub is_up: bool,
+    /// Internet traffic is routed through this interface
+    pub carries_default_route: bool,
+    /// Process holding the tun/tap device open (e.g. openvpn)
+    pub owner_pid: Option<u32>,
+    pub owner_process: Option<String>,
+    /// WireGuard configuration; None for other kinds or without CAP_NET_ADMIN
+    pub wireguard: Option<WireGuardInfo>,
+}
+
+/// Kind of tunnel interface
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TunnelKind {
+    WireGuard,
+    Tun,
+    Tap,
+    IPsec,
+    GRE,
+    IPIP,
+    SIT,
+    Other,
+}
+
+/// WireGuard interface state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WireGuardInfo {
+    pub public_key: Option<String>,
+    pub listen_port: Option<u16>,
+    pub peers: Vec<WireGuardPeer>,
+}
+
+/// WireGuard peer state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WireGuardPeer {
+    /// Base64 public key
+    pub public_key: String,
+    pub endpoint: Option<String>,
+    /// Unix timestamp in seconds; None if no handshake has completed
+    pub latest_handshake: Option<u64>,
+    pub rx_bytes: u64,
+    pub tx_bytes: u64,
+    /// Allowed IPs in CIDR notation
+    pub allowed_ips: Vec<String>,
+    pub persistent_keepalive_secs: Option<u16>,
+}
+
+/// Interfaces as a graph: bridge and bond membership, VLAN parents and veth pairs,
+/// across every network namespace
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkTopology {
+    pub namespaces: Vec<NetworkNamespace>,
+    pub nodes: Vec<NetworkNode>,
+    pub edges: Vec<NetworkEdge>,
+}
+
+/// A network namespace (host, container or `ip netns`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkNamespace {
+    /// Namespace inode, as in "net:[4026531840]"
+    pub inode: u64,
+    /// Name under /run/netns, if any
+    pub name: Option<String>,
+    /// The namespace Syslens itself runs in
+    pub is_current: bool,
+    /// Lowest PID running in the namespace
+    pub pid: Option<u32>,
+    pub process_name: Option<String>,
+    /// False when only interface names could be read (entering requires CAP_SYS_ADMIN)
+    pub details_available: bool,
+}
+
+/// An interface in the network topology
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkNode {
+    /// "<namespace inode>/<interface name>"
+    pub id: String,
+    pub name: String,
+    pub namespace: u64,
+    pub index: Option<u32>,
+    pub kind: NetworkNodeKind,
+    /// rtnetlink link kind ("bridge", "veth", "vlan", ...)
+    pub link_kind: Option<String>,
+    pub mac_address: Option<String>,
+    pub mtu: Option<u32>,
+    pub is_up: bool,
+    pub vlan_id: Option<u16>,
+    /// Bonding mode ("802.3ad", "active-backup", ...)
+    pub bond_mode: Option<String>,
+}
+
+/// Kind of network topology node
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum NetworkNodeKind {
+    Physical,
+    Loopback,
+    Bridge,
+    Bond,
+    Vlan,
+    Veth,
+    MacVlan,
+    Tunnel,
+    Virtual,
+}
+
+/// Edge between two interfaces: `child` is attached to or built on `parent`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkEdge {
+    pub parent: String,
+    pub child: String,
+    pub relation: NetworkRelation,
+}
+
+/// How two interfaces in the topology are related
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum NetworkRelation {
+    /// `child` is a port of bridge `parent`
+    BridgePort,
+    /// `child` is a slave of bond `parent`
+    BondSlave,
+    /// `child` is enslaved to another master device (VRF, team)
+    Master,
+    /// `child` is a VLAN on top of `parent`
+    VlanParent,
+    /// `child` (macvlan, ipvlan, ...) is built on `parent`
+    LowerDevice,
+    /// The two ends of a veth pair; direction carries no meaning
+    VethPeer,
+}
+
+/// Network change pushed to the frontend by the rtnetlink monitor
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkChangeEvent {
+    pub kind: NetworkChangeKind,
+    /// None for resync events
+    pub interface: Option<String>,
+    /// Address ("192.168.1.20/24") or route destination ("0.0.0.0/0") that changed
+    pub address: Option<String>,
+    pub gateway: Option<String>,
+    /// The adapter after the change; None once the interface is gone
+    pub adapter: Option<NetworkAdapter>,
+    /// Milliseconds since the Unix epoch
+    pub timestamp: u64,
+}
+
+/// Kind of network change
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NetworkChangeKind {
+    LinkAdded,
+    LinkRemoved,
+    LinkUp,
+    LinkDown,
+    AddressAdded,
+    AddressRemoved,
+    RouteChanged,
+    DefaultGatewayChanged,
+    /// Notifications were lost; refetch everything
+    Resync,
+}
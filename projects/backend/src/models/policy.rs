@@ -0,0 +1,68 @@
+//! Command policy and audit log models
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// User-configurable safeguards for mutating commands
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CommandPolicyConfig {
+    /// Refuse every mutating command except changes to this policy
+    pub read_only: bool,
+    pub deny_rules: Vec<DenyRule>,
+}
+
+/// Blocks matching operations. Every field that is set has to match; a rule
+/// with only `operation` set blocks that command entirely.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DenyRule {
+    /// Command name (e.g. "kill_process", "set_adapter_enabled"); None for all
+    pub operation: Option<String>,
+    /// Process or adapter name, case-insensitive
+    pub target_name: Option<String>,
+    /// Executable path of the target process
+    pub exe_path: Option<String>,
+    /// Owner of the target process
+    pub user: Option<String>,
+    pub comment: Option<String>,
+}
+
+/// One mutating command, as recorded in the audit log
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEntry {
+    pub timestamp: DateTime<Utc>,
+    /// OS user running Syslens
+    pub user: String,
+    /// Command name (e.g. "kill_process")
+    pub operation: String,
+    /// What was changed (e.g. "pid 4242 (firefox)", "adapter eth0")
+    pub target: String,
+    /// Command arguments beyond the target (e.g. "signal=15")
+    pub parameters: Option<String>,
+    pub outcome: AuditOutcome,
+    /// Error or denial reason
+    pub message: Option<String>,
+}
+
+/// Result of an audited command
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AuditOutcome {
+    Success,
+    /// Refused by the command policy before running
+    Denied,
+    Failed,
+}
+
+/// Filter for audit log queries
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AuditQuery {
+    /// Only entries at or after this time
+    pub since: Option<DateTime<Utc>>,
+    pub operation: Option<String>,
+    pub outcome: Option<AuditOutcome>,
+    /// Maximum number of entries, newest first
+    pub limit: Option<usize>,
+}
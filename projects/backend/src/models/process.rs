@@ -1,5 +1,6 @@
 //! Process-related data models
 
+use super::network::{ConnectionProtocol, ConnectionState};
 use serde::{Deserialize, Serialize};
 
 /// Information about a running process
@@ -34,6 +35,192 @@ pub struct ProcessInfo {
     pub exe_path: Option<String>,
     /// Base64-encoded PNG icon (16x16)
     pub icon_base64: Option<String>,
+    /// Content hash of the icon, resolved once with `get_icon`
+    pub icon_hash: Option<String>,
+    /// Cgroup path, preferring the unified hierarchy (Linux)
+    pub cgroup: Option<String>,
+    /// Systemd service or scope the process runs in
+    pub systemd_unit: Option<String>,
+    /// Innermost systemd slice (system.slice, user-1000.slice, ...)
+    pub systemd_slice: Option<String>,
+    /// Container the process runs in, detected from its cgroup path
+    pub container: Option<ContainerInfo>,
+}
+
+/// Container runtime, as told from the cgroup a container's processes run in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ContainerRuntime {
+    Docker,
+    Podman,
+    Containerd,
+    CriO,
+    /// A Kubernetes pod whose runtime uses the cgroupfs driver and can't be told apart
+    Kubernetes,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContainerInfo {
+    /// Full 64-character container ID
+    pub id: String,
+    pub runtime: ContainerRuntime,
+    /// UID of the Kubernetes pod the container belongs to
+    pub pod_uid: Option<String>,
+}
+
+/// What to group processes by in the grouped view
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ProcessGroupBy {
+    Container,
+    Unit,
+}
+
+/// Resource usage totalled over the processes of one container or systemd unit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessGroup {
+    /// Container ID or unit name; None for processes outside any container or unit
+    pub key: Option<String>,
+    pub container: Option<ContainerInfo>,
+    pub pids: Vec<u32>,
+    /// CPU usage of all processes in the group (0-100)
+    pub cpu_usage: f32,
+    pub memory_bytes: u64,
+    pub disk_read_bytes: u64,
+    pub disk_write_bytes: u64,
+}
+
+/// Field to sort the process feed by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ProcessSortKey {
+    Pid,
+    Name,
+    CpuUsage,
+    MemoryBytes,
+    DiskReadBytes,
+    DiskWriteBytes,
+    StartTime,
+    User,
+    Status,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SortDirection {
+    #[default]
+    Ascending,
+    Descending,
+}
+
+/// Which page of the process list to return, and what the client already holds
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ProcessFeedQuery {
+    /// Sort key; processes are ordered by PID when unset or tied
+    pub sort_by: Option<ProcessSortKey>,
+    pub sort_direction: SortDirection,
+    /// Case-insensitive match against name, command, executable path, user, systemd
+    /// unit, container ID prefix or PID
+    pub filter: Option<String>,
+    /// Only processes owned by this user
+    pub user: Option<String>,
+    /// Index of the first row of the page within the sorted, filtered list
+    pub offset: usize,
+    /// Page size; the whole list when unset
+    pub limit: Option<usize>,
+    /// Sequence of the page the client holds. A full page is returned when unset, no
+    /// longer known, or when that page was fetched with a different query.
+    pub since_sequence: Option<u64>,
+}
+
+/// One page of the process list, either in full or as changes since `since_sequence`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessFeed {
+    /// Pass back as `since_sequence` to get the next delta
+    pub sequence: u64,
+    /// `added` holds the whole page and the client should drop what it had
+    pub is_full: bool,
+    /// Processes matching the filter, across all pages
+    pub total_count: usize,
+    pub offset: usize,
+    /// PIDs on the page, in display order
+    pub pids: Vec<u32>,
+    /// Rows new to the page, or whose identity (name, command, owner, parent) changed.
+    /// Icons are sent as `icon_hash` only.
+    pub added: Vec<ProcessInfo>,
+    /// Rows on both pages whose usage or status changed
+    pub changed: Vec<ProcessChange>,
+    /// PIDs that left the page, because they exited or moved to another page
+    pub removed: Vec<u32>,
+}
+
+/// Changed fields of a process row; unchanged fields are omitted
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessChange {
+    pub pid: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_usage: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_bytes: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub virtual_memory_bytes: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disk_read_bytes: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disk_write_bytes: Option<u64>,
+}
+
+/// A process and its descendants, with resource usage summed over the subtree
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessTreeNode {
+    /// The process this node represents; the lowest PID of a collapsed group
+    pub process: ProcessInfo,
+    /// Every process merged into this node when collapsing by executable,
+    /// including `process` itself
+    pub pids: Vec<u32>,
+    /// Parent is set but no longer running; the process is shown as a root
+    pub is_orphan: bool,
+    pub is_zombie: bool,
+    /// CPU usage of this node and all descendants (0-100)
+    pub subtree_cpu_usage: f32,
+    pub subtree_memory_bytes: u64,
+    pub subtree_disk_read_bytes: u64,
+    pub subtree_disk_write_bytes: u64,
+    /// Number of processes in this node and all descendants
+    pub subtree_process_count: usize,
+    /// Zombies in this node and all descendants, waiting for their parent to reap them
+    pub subtree_zombie_count: usize,
+    pub children: Vec<ProcessTreeNode>,
+}
+
+/// Network traffic of a single process, summed over its TCP sockets
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessNetworkUsage {
+    /// Process ID
+    pub pid: u32,
+    /// Process name
+    pub name: String,
+    /// Process executable path
+    pub exe_path: Option<String>,
+    /// Bytes sent per second since the previous sample
+    pub bytes_sent_per_sec: f64,
+    /// Bytes received per second since the previous sample
+    pub bytes_received_per_sec: f64,
+    /// Bytes sent over the lifetime of the currently open sockets
+    pub total_bytes_sent: u64,
+    /// Bytes received over the lifetime of the currently open sockets
+    pub total_bytes_received: u64,
+    /// Number of open TCP sockets
+    pub connection_count: usize,
 }
 
 /// Metrics for the current Syslens process itself
@@ -65,3 +252,282 @@ pub struct ProcessSummary {
     /// Total memory usage across all processes
     pub total_memory_bytes: u64,
 }
+
+/// Memory of a single process from /proc/<pid>/smaps_rollup. Unlike RSS, PSS splits
+/// each shared page between the processes mapping it, so it can be summed.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessMemory {
+    pub pid: u32,
+    /// Resident set size, counting shared pages in full
+    pub rss_bytes: u64,
+    /// Proportional set size: private pages plus a share of each shared page
+    pub pss_bytes: u64,
+    /// Unique set size: pages only this process maps, freed when it exits
+    pub uss_bytes: u64,
+    pub shared_clean_bytes: u64,
+    pub shared_dirty_bytes: u64,
+    pub private_clean_bytes: u64,
+    pub private_dirty_bytes: u64,
+    /// Swapped-out anonymous memory
+    pub swap_bytes: u64,
+    /// Swap with shared pages split like PSS
+    pub swap_pss_bytes: u64,
+}
+
+/// Memory of all processes running one executable
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplicationMemory {
+    pub name: String,
+    pub exe_path: Option<String>,
+    pub pids: Vec<u32>,
+    /// Sum of RSS over all processes; overstates usage when they share libraries
+    pub rss_bytes: u64,
+    /// The fields below sum only processes whose smaps_rollup could be read
+    pub pss_bytes: u64,
+    pub uss_bytes: u64,
+    pub shared_bytes: u64,
+    pub swap_bytes: u64,
+    /// Processes whose smaps_rollup could not be read (another user's without root)
+    pub inaccessible_count: usize,
+}
+
+/// What happened to a process in the lifecycle timeline
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ProcessEventKind {
+    Start,
+    /// The process replaced its program; name, command and executable are the new ones
+    Exec,
+    Exit,
+}
+
+/// How a lifecycle event was detected
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ProcessEventSource {
+    /// Kernel proc connector; sees every process, however short-lived
+    ProcConnector,
+    /// Difference between two process snapshots; misses processes that start and
+    /// exit between them
+    Snapshot,
+}
+
+/// A process start, exec or exit
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessEvent {
+    /// Position in the event log, one more than the event before it. Bursts share a
+    /// timestamp, so queries page by this instead.
+    #[serde(default)]
+    pub sequence: u64,
+    /// Unix timestamp in milliseconds
+    pub timestamp: u64,
+    pub kind: ProcessEventKind,
+    pub source: ProcessEventSource,
+    pub pid: u32,
+    pub parent_pid: Option<u32>,
+    pub name: String,
+    pub command: String,
+    pub exe_path: Option<String>,
+    pub user: Option<String>,
+    /// Unix timestamp in milliseconds
+    pub start_time: u64,
+    /// Exit only
+    pub lifetime_ms: Option<u64>,
+    /// Exit only; None when the process was never seen in a snapshot
+    pub peak_cpu_usage: Option<f32>,
+    pub peak_memory_bytes: Option<u64>,
+    /// Exit status, when the process exited normally (proc connector only)
+    pub exit_code: Option<i32>,
+    /// Signal that terminated the process (proc connector only)
+    pub exit_signal: Option<i32>,
+}
+
+/// A section of process details read from /proc. Sections the caller may not read
+/// (another user's process without root) are marked instead of silently left empty.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "status", content = "value", rename_all = "camelCase")]
+pub enum ProcessDetailField<T> {
+    Available(T),
+    PermissionDenied,
+    /// The process exited while reading, or the kernel doesn't provide the file
+    Unavailable,
+}
+
+impl<T> ProcessDetailField<T> {
+    pub fn value(&self) -> Option<&T> {
+        match self {
+            Self::Available(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// Everything known about a single process, for the process detail view
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessDetails {
+    /// Process ID
+    pub pid: u32,
+    /// Process name
+    pub name: String,
+    /// Process status (Running, Sleeping, etc.)
+    pub status: String,
+    /// Command line arguments
+    pub command_line: ProcessDetailField<Vec<String>>,
+    /// Executable path
+    pub exe_path: ProcessDetailField<String>,
+    /// Working directory
+    pub cwd: ProcessDetailField<String>,
+    pub threads: ProcessDetailField<Vec<ProcessThread>>,
+    pub open_files: ProcessDetailField<Vec<OpenFile>>,
+    pub sockets: ProcessDetailField<Vec<ProcessSocket>>,
+    pub environment: ProcessDetailField<Vec<EnvironmentVariable>>,
+    pub limits: ProcessDetailField<Vec<ResourceLimit>>,
+    /// Cgroup path in the unified hierarchy (e.g. "/system.slice/sshd.service")
+    pub cgroup: ProcessDetailField<String>,
+    /// Systemd service or scope the process belongs to
+    pub systemd_unit: Option<String>,
+    pub capabilities: ProcessDetailField<ProcessCapabilities>,
+    pub namespaces: ProcessDetailField<Vec<ProcessNamespace>>,
+}
+
+/// A thread of a process
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessThread {
+    /// Thread ID
+    pub tid: u32,
+    pub name: String,
+    /// Thread state ("Run", "Sleep", "UninterruptibleDiskSleep", "Idle", etc.)
+    pub state: String,
+    /// CPU time spent in user mode, in milliseconds
+    pub user_time_ms: u64,
+    /// CPU time spent in kernel mode, in milliseconds
+    pub system_time_ms: u64,
+    /// CPU the thread last ran on
+    pub last_cpu: Option<u32>,
+}
+
+/// An open file descriptor
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenFile {
+    pub fd: u32,
+    /// Link target: a path, "socket:[12345]", "pipe:[12345]", "anon_inode:[eventfd]", ...
+    pub target: String,
+    pub kind: OpenFileKind,
+}
+
+/// What a file descriptor refers to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OpenFileKind {
+    File,
+    Device,
+    Socket,
+    Pipe,
+    AnonInode,
+    /// Path of a file that was deleted while open
+    Deleted,
+    Other,
+}
+
+/// A socket held open by a process
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessSocket {
+    pub fd: u32,
+    pub inode: u64,
+    pub family: SocketFamily,
+    /// TCP or UDP for Internet sockets
+    pub protocol: Option<ConnectionProtocol>,
+    /// "address:port" for Internet sockets, the bound path for Unix sockets
+    pub local_address: Option<String>,
+    pub remote_address: Option<String>,
+    /// TCP state; None for UDP and Unix sockets
+    pub state: Option<ConnectionState>,
+}
+
+/// Address family of a socket
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SocketFamily {
+    Ipv4,
+    Ipv6,
+    Unix,
+    /// Netlink, packet and other sockets not listed in /proc/<pid>/net
+    Other,
+}
+
+/// An environment variable from the process's initial environment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvironmentVariable {
+    pub name: String,
+    pub value: String,
+}
+
+/// A resource limit (rlimit); None means unlimited
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceLimit {
+    /// Limit name as the kernel reports it (e.g. "Max open files")
+    pub name: String,
+    pub soft: Option<u64>,
+    pub hard: Option<u64>,
+    /// Unit of the values (e.g. "files", "bytes", "seconds")
+    pub unit: Option<String>,
+}
+
+/// Capability sets of a process, as capability names (e.g. "CAP_NET_ADMIN")
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessCapabilities {
+    pub inheritable: Vec<String>,
+    pub permitted: Vec<String>,
+    pub effective: Vec<String>,
+    pub bounding: Vec<String>,
+    pub ambient: Vec<String>,
+}
+
+/// A namespace the process belongs to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessNamespace {
+    /// Namespace type (cgroup, ipc, mnt, net, pid, time, user, uts)
+    pub kind: String,
+    /// Namespace inode; processes with the same inode share the namespace
+    pub inode: u64,
+}
+
+/// Why a process control operation failed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, thiserror::Error)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum ProcessControlError {
+    #[error("Process with PID {pid} not found")]
+    NotFound { pid: u32 },
+    #[error("Permission denied for process {pid}")]
+    PermissionDenied { pid: u32 },
+    /// The process is critical to the system or to Syslens itself
+    #[error("Process {pid} is protected: {reason}")]
+    Protected { pid: u32, reason: String },
+    #[error("Invalid argument: {message}")]
+    InvalidArgument { message: String },
+    /// Refused by the command policy (read-only mode or a deny rule)
+    #[error("Blocked by policy for process {pid}: {reason}")]
+    PolicyDenied { pid: u32, reason: String },
+    #[error("Process control is not supported on this platform")]
+    Unsupported,
+    #[error("Operation on process {pid} failed: {message}")]
+    Failed { pid: u32, message: String },
+}
+
+/// How a graceful termination ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProcessTermination {
+    /// The process exited after SIGTERM
+    Graceful,
+    /// The process ignored SIGTERM until the timeout and was killed
+    Forced,
+}
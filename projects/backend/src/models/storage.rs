@@ -63,9 +63,18 @@ pub struct Partition {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Volume {
+    /// Drive letter on Windows (None for Unix mount points)
     pub drive_letter: Option<char>,
+    /// Path the volume is mounted at ("C:\\", "/home")
+    pub mount_point: String,
+    /// Source block device (e.g. "/dev/mapper/vg0-home")
+    pub device: Option<String>,
     pub label: String,
+    /// Filesystem UUID
+    pub uuid: Option<String>,
     pub file_system: String,
+    /// Mount options (e.g. "rw", "relatime", "compress=zstd:3")
+    pub mount_options: Vec<String>,
     pub total_bytes: u64,
     pub free_bytes: u64,
     pub used_bytes: u64,
@@ -73,6 +82,7 @@ pub struct Volume {
     pub volume_serial: String,
     pub is_compressed: bool,
     pub is_encrypted: bool,
+    pub is_read_only: bool,
     pub is_system: bool,
     pub is_boot: bool,
 }
@@ -124,24 +134,137 @@ pub struct DiskPerformance {
     pub active_time_percent: f32,
 }
 
-/// Mapped network drive
+/// Mapped network drive or network filesystem mount
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct NetworkDrive {
+    /// Drive letter on Windows (empty on Linux)
     pub drive_letter: String,
+    /// Remote path: UNC path on Windows, mount source on Linux (e.g. "server:/export")
     pub unc_path: String,
     pub server: String,
+    /// SMB share name or NFS/sshfs export path
     pub share_name: String,
+    /// Local mount point ("Z:\\" on Windows, "/mnt/share" on Linux)
+    pub mount_point: String,
+    /// Filesystem type as reported by the OS (e.g. "cifs", "nfs4", "fuse.sshfs")
+    pub file_system: String,
+    pub protocol: NetworkDriveProtocol,
     pub status: NetworkDriveStatus,
     pub total_bytes: Option<u64>,
     pub free_bytes: Option<u64>,
     pub used_bytes: Option<u64>,
 }
 
+/// Protocol used to access a network drive
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum NetworkDriveProtocol {
+    SMB,
+    NFS,
+    SSHFS,
+    Other,
+}
+
 /// Network drive connection status
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum NetworkDriveStatus {
     Connected,
     Disconnected,
+    /// The server did not answer within the probe timeout (e.g. a hung NFS mount)
+    Unresponsive,
     Unknown,
 }
+
+/// Storage stack as a graph: disks → partitions → RAID/LVM/dm-crypt → filesystems
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageTopology {
+    pub nodes: Vec<StorageNode>,
+    pub edges: Vec<StorageEdge>,
+}
+
+/// A block device, volume group or filesystem in the storage topology
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageNode {
+    /// Kernel device name ("nvme0n1p3", "dm-0"), "vg:<name>" or "fs:<mount point>"
+    pub id: String,
+    /// Display name ("vg0/home", "luks-3f2a…", "/home")
+    pub name: String,
+    pub kind: StorageNodeKind,
+    pub size_bytes: u64,
+    pub disk: Option<PhysicalDisk>,
+    pub partition: Option<Partition>,
+    pub volume: Option<Volume>,
+    pub lvm: Option<LvmInfo>,
+    pub raid: Option<RaidInfo>,
+    pub crypt: Option<CryptInfo>,
+}
+
+/// Kind of storage topology node
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum StorageNodeKind {
+    Disk,
+    Partition,
+    Raid,
+    Crypt,
+    VolumeGroup,
+    LogicalVolume,
+    DeviceMapper,
+    Loop,
+    Filesystem,
+    Other,
+}
+
+/// Directed edge: `child` is built on top of `parent`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageEdge {
+    pub parent: String,
+    pub child: String,
+}
+
+/// LVM volume group / logical volume names
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LvmInfo {
+    pub vg_name: String,
+    /// None for the volume group node itself
+    pub lv_name: Option<String>,
+}
+
+/// Software RAID (md) array state from /proc/mdstat
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RaidInfo {
+    /// RAID level ("raid1", "raid5", ...); None for inactive arrays
+    pub level: Option<String>,
+    /// "active" or "inactive", plus "(read-only)" style qualifiers
+    pub state: String,
+    pub total_devices: Option<u32>,
+    pub active_devices: Option<u32>,
+    pub members: Vec<RaidMember>,
+    /// Running resync/recovery/check/reshape operation
+    pub sync_action: Option<String>,
+    pub sync_progress_percent: Option<f32>,
+}
+
+/// Member device of an md array
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RaidMember {
+    pub device: String,
+    pub role: u32,
+    pub is_faulty: bool,
+    pub is_spare: bool,
+}
+
+/// dm-crypt mapping details
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CryptInfo {
+    /// Device-mapper name ("luks-3f2a…", "cryptroot")
+    pub mapping_name: String,
+    /// "LUKS1", "LUKS2" or "PLAIN"
+    pub crypt_type: String,
+}
@@ -0,0 +1,210 @@
+//! Append-only audit log of mutating commands, stored in AppData as JSON lines.
+
+use crate::models::{AuditEntry, AuditOutcome, AuditQuery};
+use anyhow::{Context, Result};
+use chrono::Utc;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+
+/// Entries returned by a query without an explicit limit
+const DEFAULT_QUERY_LIMIT: usize = 500;
+
+/// Global audit log instance
+static AUDIT_LOG: OnceLock<AuditLog> = OnceLock::new();
+
+/// Records who changed what, when, and with what result.
+pub struct AuditLog {
+    /// None when the data directory is unavailable; entries then only go to the log
+    log_file: Option<PathBuf>,
+    /// Serializes appends so concurrent commands never interleave lines
+    write_lock: Mutex<()>,
+}
+
+impl AuditLog {
+    /// Create an AuditLog in the app data directory.
+    pub fn new() -> Result<Self> {
+        let data_dir = dirs::data_dir()
+            .map(|p| p.join("Syslens"))
+            .context("Failed to get app data directory")?;
+        std::fs::create_dir_all(&data_dir).context("Failed to create data directory")?;
+
+        Ok(Self::with_file(Some(data_dir.join("audit.log"))))
+    }
+
+    pub(crate) fn with_file(log_file: Option<PathBuf>) -> Self {
+        Self {
+            log_file,
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    /// Get the shared audit log.
+    pub fn global() -> &'static AuditLog {
+        AUDIT_LOG.get_or_init(|| {
+            Self::new().unwrap_or_else(|e| {
+                log::error!(
+                    "Audit log unavailable, entries will not be persisted: {:#}",
+                    e
+                );
+                Self::with_file(None)
+            })
+        })
+    }
+
+    /// Record the outcome of a command.
+    pub fn record(
+        &self,
+        operation: &str,
+        target: &str,
+        parameters: Option<String>,
+        outcome: AuditOutcome,
+        message: Option<String>,
+    ) {
+        let entry = AuditEntry {
+            timestamp: Utc::now(),
+            user: Self::current_user(),
+            operation: operation.to_string(),
+            target: target.to_string(),
+            parameters,
+            outcome,
+            message,
+        };
+        log::info!(
+            "Audit: {} {} {} -> {:?}{}",
+            entry.user,
+            entry.operation,
+            entry.target,
+            entry.outcome,
+            entry
+                .message
+                .as_deref()
+                .map(|m| format!(" ({})", m))
+                .unwrap_or_default()
+        );
+
+        if let Err(e) = self.append(&entry) {
+            log::error!("Failed to write audit log entry: {:#}", e);
+        }
+    }
+
+    fn append(&self, entry: &AuditEntry) -> Result<()> {
+        let Some(log_file) = &self.log_file else {
+            return Ok(());
+        };
+        let line = serde_json::to_string(entry).context("Failed to serialize audit entry")?;
+
+        let _guard = self
+            .write_lock
+            .lock()
+            .map_err(|_| anyhow::anyhow!("Failed to acquire audit log lock"))?;
+        let mut options = std::fs::OpenOptions::new();
+        options.create(true).append(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut file = options.open(log_file).context("Failed to open audit log")?;
+        writeln!(file, "{}", line).context("Failed to append to audit log")?;
+        Ok(())
+    }
+
+    /// Get entries matching the query, newest first. Unreadable lines are skipped.
+    pub fn query(&self, query: &AuditQuery) -> Vec<AuditEntry> {
+        let Some(content) = self
+            .log_file
+            .as_ref()
+            .and_then(|file| std::fs::read_to_string(file).ok())
+        else {
+            return Vec::new();
+        };
+
+        content
+            .lines()
+            .rev()
+            .filter_map(|line| serde_json::from_str::<AuditEntry>(line).ok())
+            .filter(|entry| query.since.is_none_or(|since| entry.timestamp >= since))
+            .filter(|entry| {
+                query
+                    .operation
+                    .as_deref()
+                    .is_none_or(|operation| entry.operation == operation)
+            })
+            .filter(|entry| query.outcome.is_none_or(|outcome| entry.outcome == outcome))
+            .take(query.limit.unwrap_or(DEFAULT_QUERY_LIMIT))
+            .collect()
+    }
+
+    /// Name of the OS user running Syslens
+    fn current_user() -> String {
+        std::env::var("USER")
+            .or_else(|_| std::env::var("USERNAME"))
+            .unwrap_or_else(|_| "unknown".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_and_query() {
+        let file =
+            std::env::temp_dir().join(format!("syslens_audit_test_{}.log", std::process::id()));
+        let _ = std::fs::remove_file(&file);
+        let audit = AuditLog::with_file(Some(file.clone()));
+
+        audit.record(
+            "kill_process",
+            "pid 4242 (firefox)",
+            None,
+            AuditOutcome::Success,
+            None,
+        );
+        audit.record(
+            "send_process_signal",
+            "pid 1 (systemd)",
+            Some("signal=9".to_string()),
+            AuditOutcome::Denied,
+            Some("System init process is protected".to_string()),
+        );
+        audit.record(
+            "set_adapter_enabled",
+            "adapter eth0",
+            Some("enabled=false".to_string()),
+            AuditOutcome::Failed,
+            Some("Operation not permitted".to_string()),
+        );
+
+        let all = audit.query(&AuditQuery::default());
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[0].operation, "set_adapter_enabled");
+        assert_eq!(all[2].target, "pid 4242 (firefox)");
+
+        let denied = audit.query(&AuditQuery {
+            outcome: Some(AuditOutcome::Denied),
+            ..Default::default()
+        });
+        assert_eq!(denied.len(), 1);
+        assert_eq!(denied[0].parameters.as_deref(), Some("signal=9"));
+
+        let limited = audit.query(&AuditQuery {
+            operation: Some("kill_process".to_string()),
+            limit: Some(1),
+            ..Default::default()
+        });
+        assert_eq!(limited.len(), 1);
+
+        let future = audit.query(&AuditQuery {
+            since: Some(Utc::now() + chrono::Duration::hours(1)),
+            ..Default::default()
+        });
+        assert!(future.is_empty());
+
+        // Appends never rewrite earlier lines
+        let content = std::fs::read_to_string(&file).unwrap();
+        assert_eq!(content.lines().count(), 3);
+        let _ = std::fs::remove_file(file);
+    }
+}
@@ -0,0 +1,745 @@
+//! Safeguards in front of mutating commands: a built-in list of protected processes,
+//! user-configurable deny rules and a read-only mode. The policy is stored in AppData;
+//! every command that passes through it is recorded in the audit log.
+
+use crate::models::{AuditOutcome, CommandPolicyConfig, DenyRule, ProcessControlError};
+use crate::services::{AuditLog, ProcessController};
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+use std::sync::{OnceLock, RwLock};
+use sysinfo::{Pid, System, Users};
+
+/// Processes whose loss takes down the session or the system, by process or
+/// executable file name (case-insensitive)
+const PROTECTED_PROCESS_NAMES: &[&str] = &[
+    // Linux: init, core daemons, display servers and compositors
+    "systemd",
+    "init",
+    "systemd-journald",
+    "systemd-logind",
+    "systemd-udevd",
+    "dbus-daemon",
+    "dbus-broker",
+    "Xorg",
+    "Xwayland",
+    "gnome-shell",
+    "kwin_wayland",
+    "kwin_x11",
+    "sway",
+    "weston",
+    "gdm",
+    "sddm",
+    "lightdm",
+    // macOS
+    "launchd",
+    "kernel_task",
+    "WindowServer",
+    "loginwindow",
+    // Windows
+    "System",
+    "Registry",
+    "smss.exe",
+    "csrss.exe",
+    "wininit.exe",
+    "winlogon.exe",
+    "services.exe",
+    "lsass.exe",
+    "dwm.exe",
+];
+
+/// Global command policy instance
+static COMMAND_POLICY: OnceLock<CommandPolicy> = OnceLock::new();
+
+/// A process a command is about to act on
+#[derive(Debug, Clone, Default)]
+pub struct ProcessTarget {
+    pub pid: u32,
+    pub name: Option<String>,
+    pub exe_path: Option<String>,
+    pub user: Option<String>,
+}
+
+impl ProcessTarget {
+    /// Look up name, executable and owner of a running process
+    pub fn lookup(pid: u32) -> Self {
+        let mut sys = System::new();
+        sys.refresh_processes(sysinfo::ProcessesToUpdate::Some(&[Pid::from_u32(pid)]));
+
+        let Some(process) = sys.process(Pid::from_u32(pid)) else {
+            return Self {
+                pid,
+                ..Default::default()
+            };
+        };
+        let users = Users::new_with_refreshed_list();
+
+        Self {
+            pid,
+            name: Some(process.name().to_string_lossy().to_string()),
+            exe_path: process.exe().map(|p| p.to_string_lossy().to_string()),
+            user: process
+                .user_id()
+                .and_then(|uid| users.get_user_by_id(uid).map(|u| u.name().to_string())),
+        }
+    }
+
+    /// "pid 4242 (firefox)" for the audit log
+    pub fn describe(&self) -> String {
+        match &self.name {
+            Some(name) => format!("pid {} ({})", self.pid, name),
+            None => format!("pid {}", self.pid),
+        }
+    }
+
+    /// File name of the executable, which unlike the process name is never truncated
+    fn exe_name(&self) -> Option<&str> {
+        let exe = self.exe_path.as_deref()?;
+        exe.rsplit(['/', '\\']).next()
+    }
+}
+
+/// Why the policy refused a command
+#[derive(Debug, Clone, PartialEq)]
+pub enum PolicyDenial {
+    ReadOnly,
+    /// Built-in protection, with the reason
+    Protected(String),
+    /// A user deny rule matched, described by its comment or its fields
+    DenyRule(String),
+}
+
+impl std::fmt::Display for PolicyDenial {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PolicyDenial::ReadOnly => write!(f, "Syslens is in read-only mode"),
+            PolicyDenial::Protected(reason) => write!(f, "{} is protected", reason),
+            PolicyDenial::DenyRule(rule) => write!(f, "Blocked by deny rule: {}", rule),
+        }
+    }
+}
+
+impl PolicyDenial {
+    fn into_process_error(self, pid: u32) -> ProcessControlError {
+        match self {
+            PolicyDenial::Protected(reason) => ProcessControlError::Protected { pid, reason },
+            denial => ProcessControlError::PolicyDenied {
+                pid,
+                reason: denial.to_string(),
+            },
+        }
+    }
+}
+
+/// Manages the user-editable command policy file.
+pub struct CommandPolicy {
+    /// None when the data directory is unavailable; the policy then lives in memory
+    policy_file: Option<PathBuf>,
+    config: RwLock<CommandPolicyConfig>,
+}
+
+impl CommandPolicy {
+    /// Create a CommandPolicy, loading the saved policy from disk.
+    pub fn new() -> Result<Self> {
+        let data_dir = dirs::data_dir()
+            .map(|p| p.join("Syslens"))
+            .context("Failed to get app data directory")?;
+        std::fs::create_dir_all(&data_dir).context("Failed to create data directory")?;
+
+        Ok(Self::with_file(Some(data_dir.join("command_policy.json"))))
+    }
+
+    /// Load the policy from a specific file. A missing or invalid file gives the default policy.
+    fn with_file(policy_file: Option<PathBuf>) -> Self {
+        let config = policy_file
+            .as_ref()
+            .and_then(|file| std::fs::read_to_string(file).ok())
+            .and_then(|content| match serde_json::from_str(&content) {
+                Ok(config) => Some(config),
+                Err(e) => {
+                    log::warn!("Ignoring invalid command policy: {}", e);
+                    None
+                }
+            })
+            .unwrap_or_default();
+
+        Self {
+            policy_file,
+            config: RwLock::new(config),
+        }
+    }
+
+    /// Get the shared command policy.
+    pub fn global() -> &'static CommandPolicy {
+        COMMAND_POLICY.get_or_init(|| {
+            Self::new().unwrap_or_else(|e| {
+                log::error!("Command policy will not be persisted: {:#}", e);
+                Self::with_file(None)
+            })
+        })
+    }
+
+    /// Get the current policy.
+    pub fn config(&self) -> CommandPolicyConfig {
+        self.config.read().map(|c| c.clone()).unwrap_or_default()
+    }
+
+    /// Replace the policy and save it to disk.
+    pub fn set_config(&self, config: CommandPolicyConfig) -> Result<()> {
+        if let Some(file) = &self.policy_file {
+            let content =
+                serde_json::to_string_pretty(&config).context("Failed to serialize policy")?;
+            std::fs::write(file, content).context("Failed to write policy file")?;
+        }
+
+        *self
+            .config
+            .write()
+            .map_err(|_| anyhow::anyhow!("Failed to acquire write lock"))? = config;
+        Ok(())
+    }
+
+    /// Check an operation on a process against read-only mode, the built-in protected
+    /// processes and the deny rules.
+    pub fn check_process(
+        &self,
+        operation: &str,
+        target: &ProcessTarget,
+    ) -> Result<(), PolicyDenial> {
+        let config = self.config();
+        if config.read_only {
+            return Err(PolicyDenial::ReadOnly);
+        }
+
+        if let Some(reason) = ProcessController::protection_reason(target.pid) {
+            return Err(PolicyDenial::Protected(reason));
+        }
+        let protected_name = [target.name.as_deref(), target.exe_name()]
+            .into_iter()
+            .flatten()
+            .find(|name| {
+                PROTECTED_PROCESS_NAMES
+                    .iter()
+                    .any(|protected| protected.eq_ignore_ascii_case(name))
+            });
+        if let Some(name) = protected_name {
+            return Err(PolicyDenial::Protected(name.to_string()));
+        }
+
+        let rule = config.deny_rules.iter().find(|rule| {
+            Self::rule_applies(rule, operation)
+                && rule.target_name.as_deref().is_none_or(|name| {
+                    target
+                        .name
+                        .as_deref()
+                        .is_some_and(|target| target.eq_ignore_ascii_case(name))
+                })
+                && rule
+                    .exe_path
+                    .as_deref()
+                    .is_none_or(|exe| target.exe_path.as_deref() == Some(exe))
+                && rule
+                    .user
+                    .as_deref()
+                    .is_none_or(|user| target.user.as_deref() == Some(user))
+        });
+        match rule {
+            Some(rule) => Err(PolicyDenial::DenyRule(Self::describe_rule(rule))),
+            None => Ok(()),
+        }
+    }
+
+    /// Check an operation on a network adapter against read-only mode and the deny rules.
+    pub fn check_adapter(&self, operation: &str, adapter: &str) -> Result<(), PolicyDenial> {
+        let config = self.config();
+        if config.read_only {
+            return Err(PolicyDenial::ReadOnly);
+        }
+
+        let rule = config.deny_rules.iter().find(|rule| {
+            Self::rule_applies(rule, operation)
+                // Process-only criteria never match an adapter
+                && rule.exe_path.is_none()
+                && rule.user.is_none()
+                && rule
+                    .target_name
+                    .as_deref()
+                    .is_none_or(|name| name.eq_ignore_ascii_case(adapter))
+        });
+        match rule {
+            Some(rule) => Err(PolicyDenial::DenyRule(Self::describe_rule(rule))),
+            None => Ok(()),
+        }
+    }
+
+    /// Check a change to Syslens settings against read-only mode and operation-wide rules.
+    pub fn check_settings(&self, operation: &str) -> Result<(), PolicyDenial> {
+        let config = self.config();
+        if config.read_only {
+            return Err(PolicyDenial::ReadOnly);
+        }
+
+        let rule = config.deny_rules.iter().find(|rule| {
+            Self::rule_applies(rule, operation)
+                && rule.target_name.is_none()
+                && rule.exe_path.is_none()
+                && rule.user.is_none()
+        });
+        match rule {
+            Some(rule) => Err(PolicyDenial::DenyRule(Self::describe_rule(rule))),
+            None => Ok(()),
+        }
+    }
+
+    /// Run a process operation if the policy allows it and record the outcome.
+    pub fn run_process_operation<T>(
+        &self,
+        audit: &AuditLog,
+        operation: &str,
+        pid: u32,
+        parameters: Option<String>,
+        run: impl FnOnce() -> Result<T, ProcessControlError>,
+    ) -> Result<T, ProcessControlError> {
+        let target = ProcessTarget::lookup(pid);
+        if let Err(denial) = self.check_process(operation, &target) {
+            audit.record(
+                operation,
+                &target.describe(),
+                parameters,
+                AuditOutcome::Denied,
+                Some(denial.to_string()),
+            );
+            return Err(denial.into_process_error(pid));
+        }
+
+        let result = run();
+        Self::record_result(audit, operation, &target.describe(), parameters, &result);
+        result
+    }
+
+    /// Run an operation on a whole process tree if the policy allows it for every
+    /// member, and record the outcome. `members` is the tree as resolved before the
+    /// operation; a denial is audited against the member that caused it.
+    pub fn run_process_tree_operation<T>(
+        &self,
+        audit: &AuditLog,
+        operation: &str,
+        pid: u32,
+        members: &[u32],
+        parameters: Option<String>,
+        run: impl FnOnce() -> Result<T, ProcessControlError>,
+    ) -> Result<T, ProcessControlError> {
+        let targets: Vec<ProcessTarget> = std::iter::once(pid)
+            .chain(members.iter().copied().filter(|&member| member != pid))
+            .map(ProcessTarget::lookup)
+            .collect();
+        for target in &targets {
+            if let Err(denial) = self.check_process(operation, target) {
+                audit.record(
+                    operation,
+                    &target.describe(),
+                    parameters,
+                    AuditOutcome::Denied,
+                    Some(denial.to_string()),
+                );
+                return Err(denial.into_process_error(target.pid));
+            }
+        }
+
+        let result = run();
+        Self::record_result(
+            audit,
+            operation,
+            &targets[0].describe(),
+            parameters,
+            &result,
+        );
+        result
+    }
+
+    /// Run an operation on a network adapter if the policy allows it and record the outcome.
+    pub fn run_adapter_operation<T>(
+        &self,
+        audit: &AuditLog,
+        operation: &str,
+        adapter: &str,
+        parameters: Option<String>,
+        run: impl FnOnce() -> Result<T, String>,
+    ) -> Result<T, String> {
+        let target = format!("adapter {}", adapter);
+        if let Err(denial) = self.check_adapter(operation, adapter) {
+            audit.record(
+                operation,
+                &target,
+                parameters,
+                AuditOutcome::Denied,
+                Some(denial.to_string()),
+            );
+            return Err(denial.to_string());
+        }
+
+        let result = run();
+        Self::record_result(audit, operation, &target, parameters, &result);
+        result
+    }
+
+    /// Run a change to Syslens settings if the policy allows it and record the outcome.
+    pub fn run_settings_operation<T>(
+        &self,
+        audit: &AuditLog,
+        operation: &str,
+        target: &str,
+        run: impl FnOnce() -> Result<T, String>,
+    ) -> Result<T, String> {
+        if let Err(denial) = self.check_settings(operation) {
+            audit.record(
+                operation,
+                target,
+                None,
+                AuditOutcome::Denied,
+                Some(denial.to_string()),
+            );
+            return Err(denial.to_string());
+        }
+
+        let result = run();
+        Self::record_result(audit, operation, target, None, &result);
+        result
+    }
+
+    /// `run_settings_operation` for operations that have to await, such as downloads
+    pub async fn run_settings_operation_async<T>(
+        &self,
+        audit: &AuditLog,
+        operation: &str,
+        target: &str,
+        run: impl std::future::Future<Output = Result<T, String>>,
+    ) -> Result<T, String> {
+        if let Err(denial) = self.check_settings(operation) {
+            audit.record(
+                operation,
+                target,
+                None,
+                AuditOutcome::Denied,
+                Some(denial.to_string()),
+            );
+            return Err(denial.to_string());
+        }
+
+        let result = run.await;
+        Self::record_result(audit, operation, target, None, &result);
+        result
+    }
+
+    fn record_result<T, E: std::fmt::Display>(
+        audit: &AuditLog,
+        operation: &str,
+        target: &str,
+        parameters: Option<String>,
+        result: &Result<T, E>,
+    ) {
+        match result {
+            Ok(_) => audit.record(operation, target, parameters, AuditOutcome::Success, None),
+            Err(e) => audit.record(
+                operation,
+                target,
+                parameters,
+                AuditOutcome::Failed,
+                Some(e.to_string()),
+            ),
+        }
+    }
+
+    /// Whether a rule covers an operation. Rules without any criteria are ignored
+    /// rather than blocking everything.
+    fn rule_applies(rule: &DenyRule, operation: &str) -> bool {
+        let has_criteria = rule.operation.is_some()
+            || rule.target_name.is_some()
+            || rule.exe_path.is_some()
+            || rule.user.is_some();
+        has_criteria && rule.operation.as_deref().is_none_or(|op| op == operation)
+    }
+
+    fn describe_rule(rule: &DenyRule) -> String {
+        if let Some(comment) = &rule.comment {
+            return comment.clone();
+        }
+        let criteria: Vec<String> = [
+            ("operation", &rule.operation),
+            ("name", &rule.target_name),
+            ("exe", &rule.exe_path),
+            ("user", &rule.user),
+        ]
+        .into_iter()
+        .filter_map(|(key, value)| value.as_ref().map(|v| format!("{}={}", key, v)))
+        .collect();
+        criteria.join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target(pid: u32, name: &str, exe: &str, user: &str) -> ProcessTarget {
+        ProcessTarget {
+            pid,
+            name: Some(name.to_string()),
+            exe_path: Some(exe.to_string()),
+            user: Some(user.to_string()),
+        }
+    }
+
+    fn policy(config: CommandPolicyConfig) -> CommandPolicy {
+        let policy = CommandPolicy::with_file(None);
+        policy.set_config(config).unwrap();
+        policy
+    }
+
+    #[test]
+    fn test_builtin_protection() {
+        let policy = policy(CommandPolicyConfig::default());
+
+        assert!(matches!(
+            policy.check_process(
+                "kill_process",
+                &target(1, "systemd", "/usr/lib/systemd/systemd", "root")
+            ),
+            Err(PolicyDenial::Protected(_))
+        ));
+        assert!(matches!(
+            policy.check_process(
+                "kill_process",
+                &target(std::process::id(), "syslens", "/usr/bin/syslens", "user")
+            ),
+            Err(PolicyDenial::Protected(_))
+        ));
+        // The process name is truncated to 15 characters on Linux; the executable is not
+        assert_eq!(
+            policy.check_process(
+                "kill_process",
+                &target(
+                    4000,
+                    "systemd-journal",
+                    "/usr/lib/systemd/systemd-journald",
+                    "root"
+                )
+            ),
+            Err(PolicyDenial::Protected("systemd-journald".to_string()))
+        );
+        assert_eq!(
+            policy.check_process(
+                "kill_process",
+                &target(4001, "Xorg", "/usr/lib/xorg/Xorg", "root")
+            ),
+            Err(PolicyDenial::Protected("Xorg".to_string()))
+        );
+        assert!(policy
+            .check_process(
+                "kill_process",
+                &target(4002, "firefox", "/usr/bin/firefox", "user")
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn test_deny_rules() {
+        let policy = policy(CommandPolicyConfig {
+            read_only: false,
+            deny_rules: vec![
+                DenyRule {
+                    operation: Some("kill_process".to_string()),
+                    target_name: Some("PostgreS".to_string()),
+                    comment: Some("Database must be stopped with systemctl".to_string()),
+                    ..Default::default()
+                },
+                DenyRule {
+                    user: Some("backup".to_string()),
+                    ..Default::default()
+                },
+                DenyRule {
+                    operation: Some("set_adapter_enabled".to_string()),
+                    target_name: Some("eth0".to_string()),
+                    ..Default::default()
+                },
+                // No criteria: ignored
+                DenyRule::default(),
+            ],
+        });
+
+        let postgres = target(
+            500,
+            "postgres",
+            "/usr/lib/postgresql/16/bin/postgres",
+            "postgres",
+        );
+        assert_eq!(
+            policy.check_process("kill_process", &postgres),
+            Err(PolicyDenial::DenyRule(
+                "Database must be stopped with systemctl".to_string()
+            ))
+        );
+        assert!(policy
+            .check_process("set_process_priority", &postgres)
+            .is_ok());
+
+        let backup = target(600, "restic", "/usr/bin/restic", "backup");
+        assert_eq!(
+            policy.check_process("suspend_process", &backup),
+            Err(PolicyDenial::DenyRule("user=backup".to_string()))
+        );
+
+        assert!(policy.check_adapter("set_adapter_enabled", "ETH0").is_err());
+        assert!(policy.check_adapter("set_adapter_enabled", "wlan0").is_ok());
+        assert!(policy.check_settings("set_listener_allowlist").is_ok());
+    }
+
+    #[test]
+    fn test_read_only_mode() {
+        let policy = policy(CommandPolicyConfig {
+            read_only: true,
+            deny_rules: Vec::new(),
+        });
+
+        let firefox = target(4002, "firefox", "/usr/bin/firefox", "user");
+        assert_eq!(
+            policy.check_process("kill_process", &firefox),
+            Err(PolicyDenial::ReadOnly)
+        );
+        assert_eq!(
+            policy.check_adapter("set_adapter_enabled", "eth0"),
+            Err(PolicyDenial::ReadOnly)
+        );
+        assert_eq!(
+            policy.check_settings("set_listener_allowlist"),
+            Err(PolicyDenial::ReadOnly)
+        );
+    }
+
+    #[test]
+    fn test_denied_operations_are_audited_and_not_run() {
+        let file = std::env::temp_dir().join(format!(
+            "syslens_policy_audit_test_{}.log",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&file);
+        let audit = AuditLog::with_file(Some(file.clone()));
+        let policy = policy(CommandPolicyConfig::default());
+
+        let mut ran = false;
+        let result = policy.run_process_operation(&audit, "kill_process", 1, None, || {
+            ran = true;
+            Ok(())
+        });
+        assert!(!ran);
+        assert!(matches!(
+            result,
+            Err(ProcessControlError::Protected { pid: 1, .. })
+        ));
+
+        let result = policy.run_adapter_operation(
+            &audit,
+            "set_adapter_enabled",
+            "eth0",
+            Some("enabled=false".to_string()),
+            || Err::<bool, _>("Operation not permitted".to_string()),
+        );
+        assert!(result.is_err());
+
+        let entries = audit.query(&Default::default());
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].outcome, AuditOutcome::Failed);
+        assert_eq!(entries[0].target, "adapter eth0");
+        assert_eq!(entries[1].outcome, AuditOutcome::Denied);
+        let _ = std::fs::remove_file(file);
+    }
+
+    #[test]
+    fn test_async_settings_operation_denied() {
+        let file = std::env::temp_dir().join(format!(
+            "syslens_policy_async_test_{}.log",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&file);
+        let audit = AuditLog::with_file(Some(file.clone()));
+        let policy = policy(CommandPolicyConfig {
+            read_only: true,
+            deny_rules: Vec::new(),
+        });
+
+        let mut ran = false;
+        let result = futures::executor::block_on(policy.run_settings_operation_async(
+            &audit,
+            "update_hardware_ids",
+            "hardware ID databases",
+            async {
+                ran = true;
+                Ok(())
+            },
+        ));
+        assert!(!ran);
+        assert_eq!(result, Err(PolicyDenial::ReadOnly.to_string()));
+
+        let entries = audit.query(&Default::default());
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].outcome, AuditOutcome::Denied);
+        assert_eq!(entries[0].target, "hardware ID databases");
+        let _ = std::fs::remove_file(file);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_process_tree_denied_by_member() {
+        let file = std::env::temp_dir().join(format!(
+            "syslens_policy_tree_test_{}.log",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&file);
+        let audit = AuditLog::with_file(Some(file.clone()));
+        let policy = policy(CommandPolicyConfig {
+            read_only: false,
+            deny_rules: vec![DenyRule {
+                operation: Some("kill_process_tree".to_string()),
+                target_name: Some("sleep".to_string()),
+                ..Default::default()
+            }],
+        });
+
+        let mut shell = std::process::Command::new("sh")
+            .arg("-c")
+            .arg("sleep 30 & wait")
+            .spawn()
+            .unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(200));
+        let pid = shell.id();
+        let tree = ProcessController::process_tree(pid);
+        assert_eq!(tree.len(), 2);
+        let sleep = tree[1];
+
+        let mut ran = false;
+        let result = policy.run_process_tree_operation(
+            &audit,
+            "kill_process_tree",
+            pid,
+            &tree,
+            None,
+            || {
+                ran = true;
+                Ok(())
+            },
+        );
+        assert!(!ran);
+        assert!(matches!(
+            result,
+            Err(ProcessControlError::PolicyDenied { pid, .. }) if pid == sleep
+        ));
+
+        let entries = audit.query(&Default::default());
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].outcome, AuditOutcome::Denied);
+        assert_eq!(entries[0].target, format!("pid {} (sleep)", sleep));
+
+        unsafe { libc::kill(sleep as libc::pid_t, libc::SIGKILL) };
+        shell.wait().unwrap();
+        let _ = std::fs::remove_file(file);
+    }
+}
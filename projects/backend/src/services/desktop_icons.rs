@@ -0,0 +1,894 @@
+//! Freedesktop icons for executables on Linux.
+//!
+//! Maps an executable to the .desktop entry that launches it (by Exec/TryExec, then
+//! StartupWMClass and desktop file ID) and resolves the entry's Icon key with the XDG
+//! icon theme lookup algorithm. Executables without an entry get the theme's generic
+//! executable icon.
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use log::trace;
+use resvg::{tiny_skia, usvg};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Icon size in pixels, the same as icons extracted on Windows
+const ICON_SIZE: u32 = 16;
+const FALLBACK_THEME: &str = "hicolor";
+const GENERIC_ICONS: [&str; 2] = ["application-x-executable", "application-default-icon"];
+/// XPM is part of the spec, but there is no decoder for it here
+const EXTENSIONS: [&str; 2] = ["png", "svg"];
+
+/// An icon for an executable, with the files it was derived from. Their mtimes
+/// decide when the icon has to be resolved again.
+pub(crate) struct ResolvedIcon {
+    pub png_base64: Option<String>,
+    pub sources: Vec<PathBuf>,
+}
+
+struct DesktopEntry {
+    /// Desktop file ID ("org.gnome.Nautilus")
+    id: String,
+    path: PathBuf,
+    /// Canonical paths of TryExec and of Exec, when Exec runs the program directly
+    programs: Vec<PathBuf>,
+    /// File names of the same programs
+    program_names: Vec<String>,
+    wm_class: Option<String>,
+    icon: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DirectoryType {
+    Fixed,
+    Scalable,
+    Threshold,
+}
+
+/// A subdirectory of an icon theme, as described in its index.theme
+#[derive(Debug, Clone, PartialEq)]
+struct ThemeDirectory {
+    path: String,
+    size: u32,
+    scale: u32,
+    kind: DirectoryType,
+    min_size: u32,
+    max_size: u32,
+    threshold: u32,
+}
+
+impl ThemeDirectory {
+    fn matches_size(&self, size: u32, scale: u32) -> bool {
+        if self.scale != scale {
+            return false;
+        }
+        match self.kind {
+            DirectoryType::Fixed => self.size == size,
+            DirectoryType::Scalable => (self.min_size..=self.max_size).contains(&size),
+            DirectoryType::Threshold => (self.size.saturating_sub(self.threshold)
+                ..=self.size + self.threshold)
+                .contains(&size),
+        }
+    }
+
+    fn size_distance(&self, size: u32, scale: u32) -> u32 {
+        let wanted = size * scale;
+        let (min, max) = match self.kind {
+            DirectoryType::Fixed => (self.size, self.size),
+            DirectoryType::Scalable => (self.min_size, self.max_size),
+            DirectoryType::Threshold => (
+                self.size.saturating_sub(self.threshold),
+                self.size + self.threshold,
+            ),
+        };
+        if wanted < min * self.scale {
+            min * self.scale - wanted
+        } else {
+            wanted.saturating_sub(max * self.scale)
+        }
+    }
+}
+
+struct IconTheme {
+    inherits: Vec<String>,
+    directories: Vec<ThemeDirectory>,
+}
+
+/// Resolves executables to icons. Desktop entries and themes are loaded on first
+/// use; entries are reloaded when an applications directory changes.
+pub(crate) struct DesktopIcons {
+    /// $XDG_DATA_HOME, then $XDG_DATA_DIRS
+    data_dirs: Vec<PathBuf>,
+    /// Theme base directories: ~/.icons, then <data dir>/icons
+    icon_dirs: Vec<PathBuf>,
+    /// Searched for unthemed icons when no theme has the icon
+    pixmap_dirs: Vec<PathBuf>,
+    /// GTK and KDE settings naming the user's icon theme
+    settings_files: Vec<PathBuf>,
+    entries: Vec<DesktopEntry>,
+    /// Applications directories and their mtimes when `entries` was loaded
+    entries_loaded: Option<Vec<Option<SystemTime>>>,
+    themes: HashMap<String, Option<IconTheme>>,
+    /// Rendered icons (None when the file couldn't be rendered) by icon file, with
+    /// the file's mtime at the time
+    rendered: HashMap<PathBuf, (Option<SystemTime>, Option<String>)>,
+}
+
+impl DesktopIcons {
+    /// Use the XDG base directories of the current user
+    pub fn new() -> Self {
+        let home = dirs::home_dir();
+        let mut data_dirs: Vec<PathBuf> = dirs::data_dir().into_iter().collect();
+        let system_dirs = std::env::var("XDG_DATA_DIRS")
+            .ok()
+            .filter(|dirs| !dirs.is_empty())
+            .unwrap_or_else(|| "/usr/local/share:/usr/share".to_string());
+        data_dirs.extend(std::env::split_paths(&system_dirs));
+        // Flatpak and snap export their entries here; sessions usually add these to
+        // XDG_DATA_DIRS, but not every launcher passes them on
+        let exports = [
+            home.as_ref()
+                .map(|home| home.join(".local/share/flatpak/exports/share")),
+            Some(PathBuf::from("/var/lib/flatpak/exports/share")),
+            Some(PathBuf::from("/var/lib/snapd/desktop")),
+        ];
+        for dir in exports.into_iter().flatten() {
+            if !data_dirs.contains(&dir) {
+                data_dirs.push(dir);
+            }
+        }
+
+        let icon_dirs = home
+            .iter()
+            .map(|home| home.join(".icons"))
+            .chain(data_dirs.iter().map(|dir| dir.join("icons")))
+            .collect();
+        let pixmap_dirs = data_dirs.iter().map(|dir| dir.join("pixmaps")).collect();
+        let settings_files = dirs::config_dir()
+            .map(|config| {
+                vec![
+                    config.join("gtk-4.0/settings.ini"),
+                    config.join("gtk-3.0/settings.ini"),
+                    config.join("kdeglobals"),
+                ]
+            })
+            .unwrap_or_default();
+
+        Self::with_dirs(data_dirs, icon_dirs, pixmap_dirs, settings_files)
+    }
+
+    pub(crate) fn with_dirs(
+        data_dirs: Vec<PathBuf>,
+        icon_dirs: Vec<PathBuf>,
+        pixmap_dirs: Vec<PathBuf>,
+        settings_files: Vec<PathBuf>,
+    ) -> Self {
+        Self {
+            data_dirs,
+            icon_dirs,
+            pixmap_dirs,
+            settings_files,
+            entries: Vec::new(),
+            entries_loaded: None,
+            themes: HashMap::new(),
+            rendered: HashMap::new(),
+        }
+    }
+
+    /// Find the icon of an executable as base64 PNG
+    pub fn resolve(&mut self, exe_path: &str) -> ResolvedIcon {
+        let exe = Path::new(exe_path);
+        let mut sources = vec![exe.to_path_buf()];
+        self.refresh_entries();
+
+        let entry = self
+            .find_entry(exe)
+            .map(|entry| (entry.path.clone(), entry.icon.clone()));
+        match &entry {
+            Some((path, _)) => sources.push(path.clone()),
+            // A matching entry installed later shows up as a change to these
+            None => sources.extend(self.application_dirs()),
+        }
+        sources.extend(self.settings_files.iter().cloned());
+
+        let theme = self.user_theme();
+        let names = entry
+            .map(|(_, icon)| icon)
+            .into_iter()
+            .chain(GENERIC_ICONS.iter().map(|name| name.to_string()));
+        for name in names {
+            if let Some((file, png)) = self.load_icon(&name, theme.as_deref()) {
+                trace!("Desktop icon for {}: {}", exe_path, file.display());
+                sources.push(file);
+                return ResolvedIcon {
+                    png_base64: Some(png),
+                    sources,
+                };
+            }
+        }
+
+        ResolvedIcon {
+            png_base64: None,
+            sources,
+        }
+    }
+
+    fn application_dirs(&self) -> Vec<PathBuf> {
+        self.data_dirs
+            .iter()
+            .map(|dir| dir.join("applications"))
+            .collect()
+    }
+
+    fn refresh_entries(&mut self) {
+        let stamps: Vec<Option<SystemTime>> = self
+            .application_dirs()
+            .iter()
+            .map(|dir| std::fs::metadata(dir).and_then(|m| m.modified()).ok())
+            .collect();
+        if self.entries_loaded.as_ref() == Some(&stamps) {
+            return;
+        }
+
+        let mut entries: Vec<DesktopEntry> = Vec::new();
+        for dir in self.application_dirs() {
+            let mut files = Vec::new();
+            Self::collect_desktop_files(&dir, &dir, &mut files);
+            for (id, path) in files {
+                // Earlier data directories take precedence
+                if entries.iter().any(|entry| entry.id == id) {
+                    continue;
+                }
+                let Ok(content) = std::fs::read_to_string(&path) else {
+                    continue;
+                };
+                if let Some(entry) = Self::parse_desktop_entry(id, path, &content) {
+                    entries.push(entry);
+                }
+            }
+        }
+
+        trace!("Loaded {} desktop entries", entries.len());
+        self.entries = entries;
+        self.entries_loaded = Some(stamps);
+    }
+
+    /// Desktop files under an applications directory with their IDs; files in
+    /// subdirectories get the directory as a dash-separated prefix
+    fn collect_desktop_files(root: &Path, dir: &Path, files: &mut Vec<(String, PathBuf)>) {
+        let Ok(read_dir) = std::fs::read_dir(dir) else {
+            return;
+        };
+        let mut paths: Vec<PathBuf> = read_dir.flatten().map(|entry| entry.path()).collect();
+        paths.sort();
+
+        for path in paths {
+            if path.is_dir() {
+                Self::collect_desktop_files(root, &path, files);
+            } else if path.extension().is_some_and(|ext| ext == "desktop") {
+                let relative = path.strip_prefix(root).unwrap_or(&path).with_extension("");
+                let id = relative.to_string_lossy().replace('/', "-");
+                files.push((id, path));
+            }
+        }
+    }
+
+    fn parse_desktop_entry(id: String, path: PathBuf, content: &str) -> Option<DesktopEntry> {
+        let groups = Self::parse_ini(content);
+        let group = groups.get("Desktop Entry")?;
+        if group.get("Type").is_some_and(|kind| kind != "Application") {
+            return None;
+        }
+        let icon = group.get("Icon")?.trim().to_string();
+        if icon.is_empty() {
+            return None;
+        }
+
+        // An Exec that passes the program more than field codes runs a script or a
+        // wrapper ("python3 app.py", "flatpak run ..."), which says nothing about
+        // other processes of that program
+        let exec = group.get("Exec").and_then(|exec| {
+            let args = Self::exec_args(exec);
+            let mut args = args
+                .into_iter()
+                .skip_while(|arg| arg == "env" || arg.contains('='));
+            let program = args.next()?;
+            args.all(|arg| arg.starts_with('%')).then_some(program)
+        });
+        let names: Vec<String> = group
+            .get("TryExec")
+            .cloned()
+            .into_iter()
+            .chain(exec)
+            .collect();
+
+        Some(DesktopEntry {
+            id,
+            path,
+            programs: names
+                .iter()
+                .filter_map(|name| Self::resolve_program(name))
+                .collect(),
+            program_names: names
+                .iter()
+                .filter_map(|name| Path::new(name).file_name())
+                .map(|name| name.to_string_lossy().to_string())
+                .collect(),
+            wm_class: group.get("StartupWMClass").cloned(),
+            icon,
+        })
+    }
+
+    /// Split an Exec value into arguments, honouring its double-quote rules
+    fn exec_args(exec: &str) -> Vec<String> {
+        let mut args = Vec::new();
+        let mut current = String::new();
+        let mut quoted = false;
+        let mut started = false;
+        let mut chars = exec.chars();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => {
+                    quoted = !quoted;
+                    started = true;
+                }
+                '\\' if quoted => current.extend(chars.next()),
+                c if c.is_whitespace() && !quoted => {
+                    if started || !current.is_empty() {
+                        args.push(std::mem::take(&mut current));
+                        started = false;
+                    }
+                }
+                c => current.push(c),
+            }
+        }
+        if started || !current.is_empty() {
+            args.push(current);
+        }
+        args
+    }
+
+    /// Canonical path of a program named by an absolute path or looked up in PATH
+    fn resolve_program(program: &str) -> Option<PathBuf> {
+        let path = if program.starts_with('/') {
+            PathBuf::from(program)
+        } else {
+            let search = std::env::var_os("PATH")?;
+            std::env::split_paths(&search)
+                .map(|dir| dir.join(program))
+                .find(|path| path.is_file())?
+        };
+        std::fs::canonicalize(path).ok()
+    }
+
+    fn find_entry(&self, exe: &Path) -> Option<&DesktopEntry> {
+        let canonical = std::fs::canonicalize(exe).unwrap_or_else(|_| exe.to_path_buf());
+        let name = exe.file_name()?.to_string_lossy().to_string();
+
+        self.entries
+            .iter()
+            .find(|entry| entry.programs.contains(&canonical))
+            .or_else(|| {
+                self.entries
+                    .iter()
+                    .find(|entry| entry.program_names.contains(&name))
+            })
+            .or_else(|| {
+                self.entries.iter().find(|entry| {
+                    entry
+                        .wm_class
+                        .as_deref()
+                        .is_some_and(|class| class.eq_ignore_ascii_case(&name))
+                })
+            })
+            .or_else(|| {
+                // "org.gnome.Nautilus" for nautilus, "firefox" for firefox
+                self.entries.iter().find(|entry| {
+                    entry
+                        .id
+                        .rsplit('.')
+                        .next()
+                        .is_some_and(|id| id.eq_ignore_ascii_case(&name))
+                })
+            })
+    }
+
+    /// The icon theme chosen in GTK or KDE settings
+    fn user_theme(&self) -> Option<String> {
+        self.settings_files.iter().find_map(|file| {
+            let groups = Self::parse_ini(&std::fs::read_to_string(file).ok()?);
+            groups
+                .get("Settings")
+                .and_then(|group| group.get("gtk-icon-theme-name"))
+                .or_else(|| groups.get("Icons").and_then(|group| group.get("Theme")))
+                .map(|theme| theme.trim_matches('"').to_string())
+                .filter(|theme| !theme.is_empty())
+        })
+    }
+
+    /// Find and render an icon by name or absolute path
+    fn load_icon(&mut self, name: &str, theme: Option<&str>) -> Option<(PathBuf, String)> {
+        if name.starts_with('/') {
+            let path = PathBuf::from(name);
+            return self.render(&path).map(|png| (path, png));
+        }
+
+        // Some entries name the file rather than the icon
+        let name = ["png", "svg", "xpm"]
+            .iter()
+            .find_map(|ext| name.strip_suffix(&format!(".{}", ext)))
+            .unwrap_or(name);
+        let file = self.find_icon(name, theme, &EXTENSIONS)?;
+        if let Some(png) = self.render(&file) {
+            return Some((file, png));
+        }
+
+        // An SVG that couldn't be rasterized; settle for a PNG of another size
+        let file = self.find_icon(name, theme, &["png"])?;
+        self.render(&file).map(|png| (file, png))
+    }
+
+    /// FindIcon from the icon theme spec: the user's theme and its parents, then
+    /// hicolor, then unthemed icons
+    fn find_icon(
+        &mut self,
+        name: &str,
+        theme: Option<&str>,
+        extensions: &[&str],
+    ) -> Option<PathBuf> {
+        let mut visited = Vec::new();
+        theme
+            .into_iter()
+            .chain([FALLBACK_THEME])
+            .find_map(|theme| self.find_in_theme(name, theme, extensions, &mut visited))
+            .or_else(|| {
+                self.pixmap_dirs.iter().find_map(|dir| {
+                    extensions
+                        .iter()
+                        .map(|ext| dir.join(format!("{}.{}", name, ext)))
+                        .find(|path| path.is_file())
+                })
+            })
+    }
+
+    fn find_in_theme(
+        &mut self,
+        name: &str,
+        theme: &str,
+        extensions: &[&str],
+        visited: &mut Vec<String>,
+    ) -> Option<PathBuf> {
+        if visited.iter().any(|seen| seen == theme) {
+            return None;
+        }
+        visited.push(theme.to_string());
+
+        if !self.themes.contains_key(theme) {
+            let loaded = self.load_theme(theme);
+            self.themes.insert(theme.to_string(), loaded);
+        }
+        if let Some(file) = self.lookup_icon(name, theme, extensions) {
+            return Some(file);
+        }
+
+        let parents = self.themes.get(theme)?.as_ref()?.inherits.clone();
+        parents
+            .iter()
+            .find_map(|parent| self.find_in_theme(name, parent, extensions, visited))
+    }
+
+    /// LookupIcon from the icon theme spec: the first icon in a directory of the
+    /// requested size, otherwise the one closest in size
+    fn lookup_icon(&self, name: &str, theme: &str, extensions: &[&str]) -> Option<PathBuf> {
+        let directories = &self.themes.get(theme)?.as_ref()?.directories;
+        let mut closest: Option<(u32, PathBuf)> = None;
+
+        for directory in directories {
+            for base in &self.icon_dirs {
+                for ext in extensions {
+                    let file = base
+                        .join(theme)
+                        .join(&directory.path)
+                        .join(format!("{}.{}", name, ext));
+                    if !file.is_file() {
+                        continue;
+                    }
+                    if directory.matches_size(ICON_SIZE, 1) {
+                        return Some(file);
+                    }
+                    let distance = directory.size_distance(ICON_SIZE, 1);
+                    if closest.as_ref().is_none_or(|(best, _)| distance < *best) {
+                        closest = Some((distance, file));
+                    }
+                }
+            }
+        }
+        closest.map(|(_, file)| file)
+    }
+
+    fn load_theme(&self, theme: &str) -> Option<IconTheme> {
+        let content = self
+            .icon_dirs
+            .iter()
+            .find_map(|base| std::fs::read_to_string(base.join(theme).join("index.theme")).ok())?;
+        let groups = Self::parse_ini(&content);
+        let header = groups.get("Icon Theme")?;
+        let list = |key: &str| -> Vec<String> {
+            header
+                .get(key)
+                .map(|value| {
+                    value
+                        .split(',')
+                        .map(|item| item.trim().to_string())
+                        .filter(|item| !item.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+
+        let directories = list("Directories")
+            .into_iter()
+            .chain(list("ScaledDirectories"))
+            .filter_map(|path| {
+                let group = groups.get(&path)?;
+                let number = |key: &str| group.get(key).and_then(|v| v.trim().parse::<u32>().ok());
+                let size = number("Size")?;
+                Some(ThemeDirectory {
+                    size,
+                    scale: number("Scale").unwrap_or(1),
+                    kind: match group.get("Type").map(|t| t.trim()) {
+                        Some("Fixed") => DirectoryType::Fixed,
+                        Some("Scalable") => DirectoryType::Scalable,
+                        _ => DirectoryType::Threshold,
+                    },
+                    min_size: number("MinSize").unwrap_or(size),
+                    max_size: number("MaxSize").unwrap_or(size),
+                    threshold: number("Threshold").unwrap_or(2),
+                    path,
+                })
+            })
+            .collect();
+
+        Some(IconTheme {
+            inherits: list("Inherits"),
+            directories,
+        })
+    }
+
+    /// Groups of an ini-style file (desktop entries, index.theme, settings.ini).
+    /// The first occurrence of a key wins.
+    fn parse_ini(content: &str) -> HashMap<String, HashMap<String, String>> {
+        let mut groups: HashMap<String, HashMap<String, String>> = HashMap::new();
+        let mut current: Option<String> = None;
+
+        for line in content.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+                continue;
+            }
+            if let Some(group) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                current = Some(group.to_string());
+                continue;
+            }
+            if let (Some(group), Some((key, value))) = (&current, line.split_once('=')) {
+                groups
+                    .entry(group.clone())
+                    .or_default()
+                    .entry(key.trim().to_string())
+                    .or_insert_with(|| value.trim().to_string());
+            }
+        }
+        groups
+    }
+
+    /// Base64 PNG of an icon file at ICON_SIZE. Many executables share an icon, so
+    /// renders are kept until the file changes.
+    fn render(&mut self, file: &Path) -> Option<String> {
+        let modified = std::fs::metadata(file).and_then(|m| m.modified()).ok();
+        if let Some((stamp, png)) = self.rendered.get(file) {
+            if *stamp == modified {
+                return png.clone();
+            }
+        }
+
+        let png = std::fs::read(file).ok().and_then(|data| {
+            match file.extension().and_then(|ext| ext.to_str()) {
+                Some("svg") => Self::rasterize_svg(&data),
+                _ => Self::scale_png(&data),
+            }
+        });
+        let png = png.map(|png| STANDARD.encode(png));
+        self.rendered
+            .insert(file.to_path_buf(), (modified, png.clone()));
+        png
+    }
+
+    /// PNGs already at ICON_SIZE are passed through as they are
+    fn scale_png(data: &[u8]) -> Option<Vec<u8>> {
+        let image = image::load_from_memory_with_format(data, image::ImageFormat::Png).ok()?;
+        if image.width() == ICON_SIZE && image.height() == ICON_SIZE {
+            return Some(data.to_vec());
+        }
+
+        let resized = image.resize(ICON_SIZE, ICON_SIZE, image::imageops::FilterType::Lanczos3);
+        let mut png = Vec::new();
+        resized
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .ok()?;
+        Some(png)
+    }
+
+    /// Fit the SVG into ICON_SIZE, centered, keeping its aspect ratio
+    fn rasterize_svg(data: &[u8]) -> Option<Vec<u8>> {
+        let tree = usvg::Tree::from_data(data, &usvg::Options::default()).ok()?;
+        let size = tree.size();
+        let scale = ICON_SIZE as f32 / size.width().max(size.height());
+        let transform = tiny_skia::Transform::from_scale(scale, scale).post_translate(
+            (ICON_SIZE as f32 - size.width() * scale) / 2.0,
+            (ICON_SIZE as f32 - size.height() * scale) / 2.0,
+        );
+
+        let mut pixmap = tiny_skia::Pixmap::new(ICON_SIZE, ICON_SIZE)?;
+        resvg::render(&tree, transform, &mut pixmap.as_mut());
+        pixmap.encode_png().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Fixture {
+        root: PathBuf,
+    }
+
+    impl Fixture {
+        fn new(name: &str) -> Self {
+            let root = std::env::temp_dir().join(format!(
+                "syslens_desktop_icons_{}_{}",
+                name,
+                std::process::id()
+            ));
+            let _ = std::fs::remove_dir_all(&root);
+            std::fs::create_dir_all(&root).unwrap();
+            Self { root }
+        }
+
+        fn write(&self, path: &str, content: &[u8]) -> PathBuf {
+            let path = self.root.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, content).unwrap();
+            path
+        }
+
+        fn png(&self, path: &str, size: u32) -> PathBuf {
+            let mut png = Vec::new();
+            image::RgbaImage::from_pixel(size, size, image::Rgba([200, 40, 40, 255]))
+                .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+                .unwrap();
+            self.write(path, &png)
+        }
+
+        fn icons(&self) -> DesktopIcons {
+            let share = self.root.join("share");
+            DesktopIcons::with_dirs(
+                vec![share.clone()],
+                vec![share.join("icons")],
+                vec![share.join("pixmaps")],
+                vec![self.root.join("config/gtk-3.0/settings.ini")],
+            )
+        }
+    }
+
+    impl Drop for Fixture {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.root);
+        }
+    }
+
+    const HICOLOR: &[u8] =
+        b"[Icon Theme]\nName=Hicolor\nDirectories=48x48/apps,16x16/apps,scalable/apps\n\n\
+        [48x48/apps]\nSize=48\nType=Fixed\n\n\
+        [16x16/apps]\nSize=16\nType=Fixed\n\n\
+        [scalable/apps]\nSize=128\nMinSize=8\nMaxSize=512\nType=Scalable\n";
+
+    fn decoded_size(icon: &ResolvedIcon) -> (u32, u32) {
+        let png = STANDARD.decode(icon.png_base64.as_ref().unwrap()).unwrap();
+        let image = image::load_from_memory(&png).unwrap();
+        (image.width(), image.height())
+    }
+
+    #[test]
+    fn test_exec_args() {
+        assert_eq!(
+            DesktopIcons::exec_args(r#"env FOO=1 "/opt/My App/app" --flag %U"#),
+            vec!["env", "FOO=1", "/opt/My App/app", "--flag", "%U"]
+        );
+        assert_eq!(
+            DesktopIcons::exec_args(r#""say \"hi\"" """#),
+            vec![r#"say "hi""#, ""]
+        );
+    }
+
+    #[test]
+    fn test_directory_size() {
+        let directory = |kind, size, min_size, max_size| ThemeDirectory {
+            path: String::new(),
+            size,
+            scale: 1,
+            kind,
+            min_size,
+            max_size,
+            threshold: 2,
+        };
+        let fixed = directory(DirectoryType::Fixed, 48, 48, 48);
+        assert!(!fixed.matches_size(16, 1));
+        assert_eq!(fixed.size_distance(16, 1), 32);
+        assert!(!fixed.matches_size(48, 2));
+
+        let scalable = directory(DirectoryType::Scalable, 128, 32, 512);
+        assert!(!scalable.matches_size(16, 1));
+        assert_eq!(scalable.size_distance(16, 1), 16);
+        assert_eq!(scalable.size_distance(64, 1), 0);
+
+        let threshold = directory(DirectoryType::Threshold, 18, 18, 18);
+        assert!(threshold.matches_size(16, 1));
+        assert_eq!(threshold.size_distance(24, 1), 4);
+    }
+
+    #[test]
+    fn test_resolve_by_exec_path() {
+        let fixture = Fixture::new("exec");
+        let exe = fixture.write("opt/editor/bin/editor-bin", b"#!/bin/sh\n");
+        let desktop = fixture.write(
+            "share/applications/org.example.Editor.desktop",
+            format!(
+                "[Desktop Entry]\nType=Application\nName=Editor\nExec={} %F\nIcon=org.example.Editor\n\n\
+                 [Desktop Action new-window]\nExec=other\nIcon=other\n",
+                exe.display()
+            )
+            .as_bytes(),
+        );
+        fixture.write("share/icons/hicolor/index.theme", HICOLOR);
+        fixture.png("share/icons/hicolor/48x48/apps/org.example.Editor.png", 48);
+        let exact = fixture.png("share/icons/hicolor/16x16/apps/org.example.Editor.png", 16);
+
+        let icon = fixture.icons().resolve(&exe.to_string_lossy());
+        assert_eq!(decoded_size(&icon), (16, 16));
+        assert_eq!(
+            STANDARD.decode(icon.png_base64.as_ref().unwrap()).unwrap(),
+            std::fs::read(&exact).unwrap()
+        );
+        assert!(icon.sources.contains(&exe));
+        assert!(icon.sources.contains(&desktop));
+        assert_eq!(icon.sources.last(), Some(&exact));
+    }
+
+    #[test]
+    fn test_user_theme_and_closest_size() {
+        let fixture = Fixture::new("theme");
+        let exe = fixture.write("usr/lib/viewer/Viewer", b"");
+        // Exec runs a script, so only StartupWMClass can tie this entry to Viewer
+        fixture.write(
+            "share/applications/viewer.desktop",
+            b"[Desktop Entry]\nType=Application\nExec=python3 /usr/share/viewer/main.py\nStartupWMClass=viewer\nIcon=viewer\n",
+        );
+        fixture.write(
+            "config/gtk-3.0/settings.ini",
+            b"[Settings]\ngtk-theme-name=Adwaita\ngtk-icon-theme-name=Custom\n",
+        );
+        fixture.write(
+            "share/icons/Custom/index.theme",
+            b"[Icon Theme]\nInherits=Parent\nDirectories=32/apps\n\n[32/apps]\nSize=32\nType=Fixed\n",
+        );
+        fixture.write(
+            "share/icons/Parent/index.theme",
+            b"[Icon Theme]\nInherits=Custom\nDirectories=64/apps\n\n[64/apps]\nSize=64\n",
+        );
+        fixture.write("share/icons/hicolor/index.theme", HICOLOR);
+        // hicolor has the exact size, but the user's theme wins
+        fixture.png("share/icons/hicolor/16x16/apps/viewer.png", 16);
+        let chosen = fixture.png("share/icons/Parent/64/apps/viewer.png", 64);
+
+        let mut icons = fixture.icons();
+        let icon = icons.resolve(&exe.to_string_lossy());
+        assert_eq!(icon.sources.last(), Some(&chosen));
+        assert_eq!(decoded_size(&icon), (16, 16));
+
+        // Without the settings file, hicolor's icon is used
+        std::fs::remove_file(fixture.root.join("config/gtk-3.0/settings.ini")).unwrap();
+        let icon = icons.resolve(&exe.to_string_lossy());
+        assert!(icon
+            .sources
+            .last()
+            .unwrap()
+            .ends_with("hicolor/16x16/apps/viewer.png"));
+    }
+
+    #[test]
+    fn test_generic_and_unthemed_fallbacks() {
+        let fixture = Fixture::new("fallback");
+        let exe = fixture.write("usr/bin/daemon", b"");
+        let pixmap = fixture.png("share/pixmaps/application-x-executable.png", 32);
+
+        let mut icons = fixture.icons();
+        let icon = icons.resolve(&exe.to_string_lossy());
+        assert_eq!(icon.sources.last(), Some(&pixmap));
+        // Unmatched, so a new entry in the applications directory must invalidate it
+        assert!(icon
+            .sources
+            .contains(&fixture.root.join("share/applications")));
+
+        // Matched by desktop file ID once an entry appears
+        fixture.write(
+            "share/applications/daemon.desktop",
+            b"[Desktop Entry]\nType=Application\nExec=sh -c daemon\nIcon=daemon.png\n",
+        );
+        let own = fixture.png("share/pixmaps/daemon.png", 16);
+        let icon = icons.resolve(&exe.to_string_lossy());
+        assert_eq!(icon.sources.last(), Some(&own));
+
+        // Nothing at all
+        std::fs::remove_dir_all(fixture.root.join("share/pixmaps")).unwrap();
+        let icon = icons.resolve(&exe.to_string_lossy());
+        assert!(icon.png_base64.is_none());
+    }
+
+    #[test]
+    fn test_svg_rendering_is_cached_until_modified() {
+        let svg = |color: &str| {
+            format!(
+                "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"32\" height=\"16\">\
+                 <rect width=\"32\" height=\"16\" fill=\"{}\"/></svg>",
+                color
+            )
+        };
+        let pixel = |icon: &ResolvedIcon, x: u32, y: u32| {
+            let png = STANDARD.decode(icon.png_base64.as_ref().unwrap()).unwrap();
+            image::load_from_memory(&png)
+                .unwrap()
+                .to_rgba8()
+                .get_pixel(x, y)
+                .0
+        };
+
+        let fixture = Fixture::new("svg");
+        let exe = fixture.write("usr/bin/tool", b"");
+        fixture.write(
+            "share/applications/tool.desktop",
+            b"[Desktop Entry]\nType=Application\nExec=tool\nIcon=tool\n",
+        );
+        fixture.write("share/icons/hicolor/index.theme", HICOLOR);
+        let file = fixture.write(
+            "share/icons/hicolor/scalable/apps/tool.svg",
+            svg("#ff0000").as_bytes(),
+        );
+        let modified = std::fs::metadata(&file).unwrap().modified().unwrap();
+
+        // Wider than tall, so it is letterboxed in the middle of the square
+        let mut icons = fixture.icons();
+        let icon = icons.resolve(&exe.to_string_lossy());
+        assert_eq!(decoded_size(&icon), (16, 16));
+        assert_eq!(pixel(&icon, 8, 8), [255, 0, 0, 255]);
+        assert_eq!(pixel(&icon, 8, 0)[3], 0);
+
+        // Same mtime: the earlier render is reused
+        std::fs::write(&file, svg("#0000ff")).unwrap();
+        let set_modified = |time| {
+            std::fs::File::options()
+                .write(true)
+                .open(&file)
+                .unwrap()
+                .set_modified(time)
+                .unwrap()
+        };
+        set_modified(modified);
+        let icon = icons.resolve(&exe.to_string_lossy());
+        assert_eq!(pixel(&icon, 8, 8), [255, 0, 0, 255]);
+
+        set_modified(modified + std::time::Duration::from_secs(1));
+        let icon = icons.resolve(&exe.to_string_lossy());
+        assert_eq!(pixel(&icon, 8, 8), [0, 0, 255, 255]);
+    }
+}
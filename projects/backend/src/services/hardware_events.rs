@@ -0,0 +1,443 @@
+//! Hardware hotplug monitor
+//!
+//! Listens for kernel uevents on a NETLINK_KOBJECT_UEVENT socket and emits Tauri
+//! events when a USB device, disk, monitor or network adapter is plugged in or
+//! removed. Each event carries the device as the matching collector returns it, so
+//! the frontend can update its lists without refetching everything.
+
+use tauri::AppHandle;
+
+#[cfg(target_os = "linux")]
+use crate::collectors::netlink::NetlinkSocket;
+#[cfg(target_os = "linux")]
+use crate::collectors::{HardwareCollector, NetworkCollector, StorageCollector};
+#[cfg(target_os = "linux")]
+use crate::models::{HotplugEvent, Monitor};
+#[cfg(target_os = "linux")]
+use serde::Serialize;
+#[cfg(target_os = "linux")]
+use std::collections::{HashMap, HashSet};
+#[cfg(target_os = "linux")]
+use std::path::Path;
+#[cfg(target_os = "linux")]
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+#[cfg(target_os = "linux")]
+use tauri::Emitter;
+
+/// Multicast group the kernel broadcasts uevents on (udev rebroadcasts on group 2)
+#[cfg(target_os = "linux")]
+const UEVENT_KERNEL_GROUP: u32 = 1;
+/// Idle wait between checks of the uevent socket
+#[cfg(target_os = "linux")]
+const IDLE_WAIT: Duration = Duration::from_secs(60);
+
+/// A decoded kernel uevent
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, PartialEq)]
+struct Uevent {
+    action: String,
+    devpath: String,
+    subsystem: String,
+    properties: HashMap<String, String>,
+}
+
+#[cfg(target_os = "linux")]
+impl Uevent {
+    fn property(&self, key: &str) -> Option<&str> {
+        self.properties.get(key).map(String::as_str)
+    }
+
+    /// Last component of the device path ("3-2", "sdb", "card1")
+    fn kernel_name(&self) -> &str {
+        self.devpath.rsplit('/').next().unwrap_or(&self.devpath)
+    }
+}
+
+/// A hotplug change the monitor reports, before the device is collected
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, PartialEq)]
+enum HotplugChange {
+    UsbAdded(String),
+    UsbRemoved(String),
+    DiskAdded(String),
+    DiskChanged(String),
+    DiskRemoved(String),
+    /// A connector changed state; the connected monitors have to be rescanned
+    DisplaysChanged,
+    AdapterAdded(String),
+    AdapterRemoved(String),
+}
+
+/// Devices reported so far, so removals are only sent for devices the frontend knows
+#[cfg(target_os = "linux")]
+#[derive(Debug, Default)]
+struct MonitorState {
+    usb_ports: HashSet<String>,
+    disks: HashSet<String>,
+    monitors: HashSet<String>,
+}
+
+/// Background listener that turns kernel uevents into Tauri events
+pub struct HardwareEventMonitor;
+
+impl HardwareEventMonitor {
+    /// Start the background listener
+    pub fn start(app: AppHandle) {
+        #[cfg(target_os = "linux")]
+        {
+            let result = std::thread::Builder::new()
+                .name("hardware-events".to_string())
+                .spawn(move || {
+                    if let Err(e) = Self::run(&app) {
+                        log::error!("Hardware hotplug monitor stopped: {}", e);
+                    }
+                });
+
+            if let Err(e) = result {
+                log::error!("Failed to start hardware hotplug monitor: {}", e);
+            }
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = app;
+            log::info!("Hardware hotplug events are not supported on this platform");
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn run(app: &AppHandle) -> std::io::Result<()> {
+        let mut socket =
+            NetlinkSocket::subscribe(libc::NETLINK_KOBJECT_UEVENT, UEVENT_KERNEL_GROUP)?;
+        let mut state = MonitorState::seed();
+        log::info!("Hardware hotplug monitor started");
+
+        loop {
+            let datagram = match socket.receive_raw(IDLE_WAIT) {
+                Ok(Some(datagram)) => datagram,
+                Ok(None) => continue,
+                Err(e) if e.raw_os_error() == Some(libc::ENOBUFS) => {
+                    // Lost events can't be replayed; rescan so removals stay accurate
+                    log::warn!("Uevents were dropped; rescanning devices");
+                    state = MonitorState::seed();
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+
+            let Some(change) = parse_uevent(&datagram).as_ref().and_then(classify) else {
+                continue;
+            };
+            log::debug!("Hotplug change: {:?}", change);
+            state.apply(app, change);
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn emit<T: Serialize + Clone>(
+        app: &AppHandle,
+        event: &str,
+        device_id: &str,
+        device: Option<T>,
+    ) {
+        let payload = HotplugEvent {
+            device_id: device_id.to_string(),
+            device,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or(0),
+        };
+        if let Err(e) = app.emit(event, payload) {
+            log::warn!("Failed to emit {}: {}", event, e);
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl MonitorState {
+    /// Record the devices that are present right now
+    fn seed() -> Self {
+        let usb_ports = HardwareCollector::get_usb_devices()
+            .into_iter()
+            .map(|device| device.port)
+            .collect();
+        let disks = StorageCollector::get_linux_block_disks()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        let monitors = HardwareCollector::get_monitors()
+            .into_iter()
+            .map(|monitor| monitor.id)
+            .collect();
+
+        Self {
+            usb_ports,
+            disks,
+            monitors,
+        }
+    }
+
+    /// Collect the affected device and emit the matching event
+    fn apply(&mut self, app: &AppHandle, change: HotplugChange) {
+        match change {
+            HotplugChange::UsbAdded(port) => {
+                let path = Path::new("/sys/bus/usb/devices").join(&port);
+                if let Some(device) = HardwareCollector::read_linux_usb_device(&path) {
+                    self.usb_ports.insert(port.clone());
+                    HardwareEventMonitor::emit(app, "hardware:usb-added", &port, Some(device));
+                }
+            }
+            HotplugChange::UsbRemoved(port) => {
+                if self.usb_ports.remove(&port) {
+                    HardwareEventMonitor::emit::<()>(app, "hardware:usb-removed", &port, None);
+                }
+            }
+            HotplugChange::DiskAdded(name) | HotplugChange::DiskChanged(name) => {
+                let disk = StorageCollector::get_linux_block_disks()
+                    .into_iter()
+                    .find(|(disk_name, _)| *disk_name == name)
+                    .map(|(_, disk)| disk);
+                // Virtual block devices are not listed as disks
+                if let Some(disk) = disk {
+                    let event = if self.disks.insert(name.clone()) {
+                        "hardware:disk-added"
+                    } else {
+                        "hardware:disk-changed"
+                    };
+                    HardwareEventMonitor::emit(app, event, &name, Some(disk));
+                }
+            }
+            HotplugChange::DiskRemoved(name) => {
+                if self.disks.remove(&name) {
+                    HardwareEventMonitor::emit::<()>(app, "hardware:disk-removed", &name, None);
+                }
+            }
+            HotplugChange::DisplaysChanged => {
+                let (added, removed) =
+                    diff_monitors(&mut self.monitors, HardwareCollector::get_monitors());
+                for monitor in added {
+                    let id = monitor.id.clone();
+                    HardwareEventMonitor::emit(app, "hardware:monitor-added", &id, Some(monitor));
+                }
+                for id in removed {
+                    HardwareEventMonitor::emit::<()>(app, "hardware:monitor-removed", &id, None);
+                }
+            }
+            HotplugChange::AdapterAdded(name) => {
+                let adapter = NetworkCollector::get_adapters()
+                    .into_iter()
+                    .find(|adapter| adapter.name == name);
+                HardwareEventMonitor::emit(app, "hardware:network-adapter-added", &name, adapter);
+            }
+            HotplugChange::AdapterRemoved(name) => {
+                HardwareEventMonitor::emit::<()>(
+                    app,
+                    "hardware:network-adapter-removed",
+                    &name,
+                    None,
+                );
+            }
+        }
+    }
+}
+
+/// Decode a kernel uevent datagram: an "action@devpath" header followed by
+/// NUL-separated KEY=value pairs. Messages rebroadcast by udev start with a
+/// "libudev" magic and a binary header and are ignored.
+#[cfg(target_os = "linux")]
+fn parse_uevent(data: &[u8]) -> Option<Uevent> {
+    if data.starts_with(b"libudev\0") {
+        return None;
+    }
+
+    let mut fields = data
+        .split(|&b| b == 0)
+        .filter(|field| !field.is_empty())
+        .map(String::from_utf8_lossy);
+    let header = fields.next()?;
+    let (header_action, header_devpath) = header.split_once('@')?;
+
+    let properties: HashMap<String, String> = fields
+        .filter_map(|field| {
+            let (key, value) = field.split_once('=')?;
+            Some((key.to_string(), value.to_string()))
+        })
+        .collect();
+
+    Some(Uevent {
+        action: properties
+            .get("ACTION")
+            .cloned()
+            .unwrap_or_else(|| header_action.to_string()),
+        devpath: properties
+            .get("DEVPATH")
+            .cloned()
+            .unwrap_or_else(|| header_devpath.to_string()),
+        subsystem: properties.get("SUBSYSTEM").cloned()?,
+        properties,
+    })
+}
+
+/// Decide which hotplug change, if any, a uevent represents
+#[cfg(target_os = "linux")]
+fn classify(event: &Uevent) -> Option<HotplugChange> {
+    let name = event.kernel_name().to_string();
+
+    match (event.subsystem.as_str(), event.action.as_str()) {
+        // Interfaces of a device ("3-2:1.0") arrive as separate usb events
+        ("usb", action) if event.property("DEVTYPE") == Some("usb_device") => match action {
+            "add" => Some(HotplugChange::UsbAdded(name)),
+            "remove" => Some(HotplugChange::UsbRemoved(name)),
+            _ => None,
+        },
+        // Partitions are reported through their disk
+        ("block", action) if event.property("DEVTYPE") == Some("disk") => {
+            let name = event
+                .property("DEVNAME")
+                .map(str::to_string)
+                .unwrap_or(name);
+            match action {
+                "add" => Some(HotplugChange::DiskAdded(name)),
+                "change" => Some(HotplugChange::DiskChanged(name)),
+                "remove" => Some(HotplugChange::DiskRemoved(name)),
+                _ => None,
+            }
+        }
+        ("drm", "change") if event.property("HOTPLUG") == Some("1") => {
+            Some(HotplugChange::DisplaysChanged)
+        }
+        ("net", action) => {
+            let name = event
+                .property("INTERFACE")
+                .map(str::to_string)
+                .unwrap_or(name);
+            match action {
+                "add" => Some(HotplugChange::AdapterAdded(name)),
+                "remove" => Some(HotplugChange::AdapterRemoved(name)),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Compare the connected monitors with the known set and update it.
+/// Returns the monitors that appeared and the ids of those that disappeared.
+#[cfg(target_os = "linux")]
+fn diff_monitors(
+    known: &mut HashSet<String>,
+    current: Vec<Monitor>,
+) -> (Vec<Monitor>, Vec<String>) {
+    let current_ids: HashSet<String> = current.iter().map(|m| m.id.clone()).collect();
+    let mut removed: Vec<String> = known.difference(&current_ids).cloned().collect();
+    removed.sort();
+    let added: Vec<Monitor> = current
+        .into_iter()
+        .filter(|monitor| !known.contains(&monitor.id))
+        .collect();
+
+    *known = current_ids;
+    (added, removed)
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+
+    macro_rules! fixture {
+        ($name:literal) => {
+            include_bytes!(concat!("../../tests/fixtures/uevent/", $name)).as_slice()
+        };
+    }
+
+    fn classify_bytes(data: &[u8]) -> Option<HotplugChange> {
+        classify(&parse_uevent(data).unwrap())
+    }
+
+    fn monitor(id: &str) -> Monitor {
+        Monitor {
+            id: id.to_string(),
+            name: "DELL U2720Q".to_string(),
+            manufacturer: Some("Dell".to_string()),
+            resolution: "3840x2160".to_string(),
+            size_inches: Some(27.0),
+            connection: "DP-1".to_string(),
+            hdr_support: false,
+            refresh_rate_hz: 60,
+        }
+    }
+
+    #[test]
+    fn test_parse_uevent() {
+        let event = parse_uevent(fixture!("usb-add")).unwrap();
+        assert_eq!(event.action, "add");
+        assert_eq!(event.subsystem, "usb");
+        assert_eq!(event.devpath, "/devices/pci0000:00/0000:00:14.0/usb3/3-2");
+        assert_eq!(event.kernel_name(), "3-2");
+        assert_eq!(event.property("DEVTYPE"), Some("usb_device"));
+        assert_eq!(event.property("PRODUCT"), Some("46d/c52b/1211"));
+        assert_eq!(event.property("SEQNUM"), Some("5213"));
+
+        assert!(parse_uevent(fixture!("libudev-usb-add")).is_none());
+        assert!(parse_uevent(b"").is_none());
+        // No SUBSYSTEM, nothing to route it by
+        assert!(parse_uevent(b"add@/devices/virtual\0ACTION=add\0").is_none());
+    }
+
+    #[test]
+    fn test_classify_usb() {
+        assert_eq!(
+            classify_bytes(fixture!("usb-add")),
+            Some(HotplugChange::UsbAdded("3-2".to_string()))
+        );
+        assert_eq!(
+            classify_bytes(fixture!("usb-remove")),
+            Some(HotplugChange::UsbRemoved("3-2".to_string()))
+        );
+        assert_eq!(classify_bytes(fixture!("usb-interface-add")), None);
+    }
+
+    #[test]
+    fn test_classify_block_drm_net() {
+        assert_eq!(
+            classify_bytes(fixture!("block-disk-add")),
+            Some(HotplugChange::DiskAdded("sdb".to_string()))
+        );
+        assert_eq!(
+            classify_bytes(fixture!("block-disk-change")),
+            Some(HotplugChange::DiskChanged("sdb".to_string()))
+        );
+        assert_eq!(classify_bytes(fixture!("block-partition-add")), None);
+        assert_eq!(
+            classify_bytes(fixture!("drm-hotplug")),
+            Some(HotplugChange::DisplaysChanged)
+        );
+        assert_eq!(
+            classify_bytes(fixture!("net-add")),
+            Some(HotplugChange::AdapterAdded("enx00e04c680001".to_string()))
+        );
+        assert_eq!(
+            classify_bytes(fixture!("net-remove")),
+            Some(HotplugChange::AdapterRemoved("enx00e04c680001".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_diff_monitors() {
+        let mut known: HashSet<String> = ["card0-eDP-1".to_string()].into_iter().collect();
+
+        let (added, removed) = diff_monitors(
+            &mut known,
+            vec![monitor("card0-eDP-1"), monitor("card0-DP-1")],
+        );
+        assert_eq!(added.len(), 1);
+        assert_eq!(added[0].id, "card0-DP-1");
+        assert!(removed.is_empty());
+
+        let (added, removed) = diff_monitors(&mut known, vec![monitor("card0-eDP-1")]);
+        assert!(added.is_empty());
+        assert_eq!(removed, vec!["card0-DP-1".to_string()]);
+        assert_eq!(known.len(), 1);
+    }
+}
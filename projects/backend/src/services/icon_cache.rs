@@ -3,10 +3,16 @@
 use base64::engine::general_purpose::STANDARD;
 use base64::Engine;
 use log::{debug, trace};
-use std::collections::HashMap;
-use std::path::Path;
-use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::RwLock;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Mutex, RwLock};
+use std::time::UNIX_EPOCH;
+
+#[cfg(target_os = "linux")]
+use super::desktop_icons::DesktopIcons;
 
 #[cfg(windows)]
 use windows::core::PCWSTR;
@@ -20,37 +26,91 @@ use windows::Win32::UI::Shell::{SHGetFileInfoW, SHFILEINFOW, SHGFI_ICON, SHGFI_S
 #[cfg(windows)]
 use windows::Win32::UI::WindowsAndMessaging::{DestroyIcon, GetIconInfo, ICONINFO};
 
+/// Icons persisted across restarts.
+#[derive(Default, Serialize, Deserialize)]
+struct DiskCache {
+    entries: HashMap<String, DiskEntry>,
+    /// Base64 PNG data keyed by content hash
+    icons: HashMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct DiskEntry {
+    hash: Option<String>,
+    /// Files the icon was derived from, with their mtimes in nanoseconds since the
+    /// epoch (None when missing). The entry is stale once any of them changes.
+    sources: Vec<(PathBuf, Option<u64>)>,
+}
+
 /// Cache for extracted process icons.
-/// Maps executable path -> base64 PNG data.
+/// Maps executable path -> icon content hash, and content hash -> base64 PNG data,
+/// so identical icons are stored and sent to the frontend once.
 pub struct IconCache {
     cache: RwLock<HashMap<String, Option<String>>>,
+    /// Base64 PNG data keyed by the SHA-256 of its content
+    icons: RwLock<HashMap<String, String>>,
     /// Counter for successful extractions (for debugging)
     success_count: AtomicUsize,
     /// Counter for failed extractions (for debugging)
     fail_count: AtomicUsize,
+    /// Where the cache is persisted; None keeps it in memory only
+    cache_file: Option<PathBuf>,
+    /// Persisted entries, read on the first cache miss
+    disk: Mutex<Option<DiskCache>>,
+    /// Whether `disk` has entries not yet written to `cache_file`
+    dirty: AtomicBool,
+    #[cfg(target_os = "linux")]
+    desktop_icons: Mutex<DesktopIcons>,
 }
 
 impl IconCache {
-    /// Create a new icon cache.
+    /// Create a new icon cache, persisted in the app data directory.
     pub fn new() -> Self {
+        Self::with_cache_file(
+            dirs::data_dir().map(|p| p.join("Syslens").join("cache").join("icons.json")),
+        )
+    }
+
+    pub(crate) fn with_cache_file(cache_file: Option<PathBuf>) -> Self {
         Self {
             cache: RwLock::new(HashMap::new()),
+            icons: RwLock::new(HashMap::new()),
             success_count: AtomicUsize::new(0),
             fail_count: AtomicUsize::new(0),
+            cache_file,
+            disk: Mutex::new(None),
+            dirty: AtomicBool::new(false),
+            #[cfg(target_os = "linux")]
+            desktop_icons: Mutex::new(DesktopIcons::new()),
         }
     }
 
     /// Get icon for an executable path.
     /// Returns cached icon if available, otherwise extracts and caches it.
     pub fn get_icon(&self, exe_path: &str) -> Option<String> {
+        let hash = self.get_icon_hash(exe_path)?;
+        self.get_icon_by_hash(&hash)
+    }
+
+    /// Get the content hash of the icon for an executable path, extracting it on first use.
+    pub fn get_icon_hash(&self, exe_path: &str) -> Option<String> {
         // Check cache first
         if let Some(cached) = self.cache.read().ok()?.get(exe_path) {
             trace!("Icon cache hit for: {}", exe_path);
             return cached.clone();
         }
 
+        // Reuse the icon from an earlier run while its sources are unchanged
+        if let Some(cached) = self.load_persisted(exe_path) {
+            trace!("Icon disk cache hit for: {}", exe_path);
+            if let Ok(mut cache) = self.cache.write() {
+                cache.insert(exe_path.to_string(), cached.clone());
+            }
+            return cached;
+        }
+
         // Extract icon and cache it
-        let icon = self.extract_icon(exe_path);
+        let (icon, sources) = self.extract(exe_path);
 
         if icon.is_some() {
             self.success_count.fetch_add(1, Ordering::Relaxed);
@@ -60,9 +120,8 @@ impl IconCache {
             trace!("Icon extraction failed for: {}", exe_path);
         }
 
-        if let Ok(mut cache) = self.cache.write() {
-            cache.insert(exe_path.to_string(), icon.clone());
-        }
+        let hash = self.store(exe_path, icon);
+        self.persist(exe_path, hash.as_deref(), &sources);
 
         // Log stats periodically
         let total =
@@ -75,7 +134,157 @@ impl IconCache {
             );
         }
 
-        icon
+        hash
+    }
+
+    /// Get base64 PNG data by content hash.
+    pub fn get_icon_by_hash(&self, hash: &str) -> Option<String> {
+        self.icons.read().ok()?.get(hash).cloned()
+    }
+
+    /// Cache the extraction result for a key and return the icon's content hash.
+    fn store(&self, key: &str, icon: Option<String>) -> Option<String> {
+        let hash = icon.map(|icon| {
+            let hash = format!("{:x}", Sha256::digest(icon.as_bytes()));
+            if let Ok(mut icons) = self.icons.write() {
+                icons.entry(hash.clone()).or_insert(icon);
+            }
+            hash
+        });
+
+        if let Ok(mut cache) = self.cache.write() {
+            cache.insert(key.to_string(), hash.clone());
+        }
+        hash
+    }
+
+    /// Extract the icon of an executable, with the files it was derived from.
+    fn extract(&self, exe_path: &str) -> (Option<String>, Vec<PathBuf>) {
+        #[cfg(target_os = "linux")]
+        {
+            if !Path::new(exe_path).exists() {
+                return (None, Vec::new());
+            }
+            let resolved = match self.desktop_icons.lock() {
+                Ok(mut desktop_icons) => desktop_icons.resolve(exe_path),
+                Err(_) => return (None, Vec::new()),
+            };
+            (resolved.png_base64, resolved.sources)
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            (self.extract_icon(exe_path), vec![PathBuf::from(exe_path)])
+        }
+    }
+
+    /// The persisted result for a key, if none of its sources changed since.
+    fn load_persisted(&self, key: &str) -> Option<Option<String>> {
+        let mut disk = self.disk.lock().ok()?;
+        let disk = disk.get_or_insert_with(|| self.read_cache_file());
+        let entry = disk.entries.get(key)?;
+        if !entry
+            .sources
+            .iter()
+            .all(|(path, modified)| Self::modified(path) == *modified)
+        {
+            return None;
+        }
+
+        let Some(hash) = &entry.hash else {
+            return Some(None);
+        };
+        let icon = disk.icons.get(hash)?;
+        if let Ok(mut icons) = self.icons.write() {
+            icons.entry(hash.clone()).or_insert_with(|| icon.clone());
+        }
+        Some(Some(hash.clone()))
+    }
+
+    /// Record an extraction result for the next run. Written by `save`.
+    fn persist(&self, key: &str, hash: Option<&str>, sources: &[PathBuf]) {
+        if self.cache_file.is_none() || sources.is_empty() {
+            return;
+        }
+        let Ok(mut disk) = self.disk.lock() else {
+            return;
+        };
+        let disk = disk.get_or_insert_with(|| self.read_cache_file());
+
+        if let Some(hash) = hash {
+            if let Some(icon) = self.get_icon_by_hash(hash) {
+                disk.icons.entry(hash.to_string()).or_insert(icon);
+            }
+        }
+        disk.entries.insert(
+            key.to_string(),
+            DiskEntry {
+                hash: hash.map(str::to_string),
+                sources: sources
+                    .iter()
+                    .map(|path| (path.clone(), Self::modified(path)))
+                    .collect(),
+            },
+        );
+        self.dirty.store(true, Ordering::Relaxed);
+    }
+
+    /// Write entries added since the last save to the cache file.
+    pub fn save(&self) {
+        let Some(cache_file) = &self.cache_file else {
+            return;
+        };
+        if !self.dirty.swap(false, Ordering::Relaxed) {
+            return;
+        }
+        let Ok(mut disk) = self.disk.lock() else {
+            return;
+        };
+        let Some(disk) = disk.as_mut() else {
+            return;
+        };
+
+        // Drop icons that only stale entries referred to
+        let referenced: HashSet<String> = disk
+            .entries
+            .values()
+            .filter_map(|entry| entry.hash.clone())
+            .collect();
+        disk.icons.retain(|hash, _| referenced.contains(hash));
+
+        match Self::write_cache_file(cache_file, disk) {
+            Ok(()) => debug!("Saved {} icon cache entries", disk.entries.len()),
+            Err(e) => debug!("Failed to save icon cache: {}", e),
+        }
+    }
+
+    fn write_cache_file(cache_file: &Path, disk: &DiskCache) -> std::io::Result<()> {
+        if let Some(dir) = cache_file.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        // Replace the file in one step so a crash can't leave it half written
+        let temp = cache_file.with_extension("json.tmp");
+        std::fs::write(&temp, serde_json::to_vec(disk)?)?;
+        std::fs::rename(temp, cache_file)
+    }
+
+    fn read_cache_file(&self) -> DiskCache {
+        let mut disk: DiskCache = self
+            .cache_file
+            .as_ref()
+            .and_then(|path| std::fs::read(path).ok())
+            .and_then(|content| serde_json::from_slice(&content).ok())
+            .unwrap_or_default();
+        // Forget executables that no longer exist
+        disk.entries
+            .retain(|_, entry| entry.sources.first().is_some_and(|(path, _)| path.exists()));
+        disk
+    }
+
+    fn modified(path: &Path) -> Option<u64> {
+        let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok()?;
+        let since_epoch = modified.duration_since(UNIX_EPOCH).ok()?;
+        Some(since_epoch.as_nanos() as u64)
     }
 
     /// Extract icon from an executable file.
@@ -283,7 +492,7 @@ impl IconCache {
         }
     }
 
-    #[cfg(not(windows))]
+    #[cfg(not(any(windows, target_os = "linux")))]
     fn extract_icon(&self, _exe_path: &str) -> Option<String> {
         None
     }
@@ -292,9 +501,16 @@ impl IconCache {
     /// This is a simpler method that just returns a default icon
     /// when no executable path is available.
     pub fn get_icon_for_process(&self, name: &str, exe_path: Option<&str>) -> Option<String> {
+        let hash = self.get_icon_hash_for_process(name, exe_path)?;
+        self.get_icon_by_hash(&hash)
+    }
+
+    /// Get the icon content hash for a process, falling back to its name
+    /// when no executable path is available.
+    pub fn get_icon_hash_for_process(&self, name: &str, exe_path: Option<&str>) -> Option<String> {
         if let Some(path) = exe_path {
             if !path.is_empty() {
-                return self.get_icon(path);
+                return self.get_icon_hash(path);
             }
         }
 
@@ -306,11 +522,18 @@ impl IconCache {
         None
     }
 
-    /// Clear the cache.
+    /// Clear the cache, including what was persisted once `save` runs.
     pub fn clear(&self) {
         if let Ok(mut cache) = self.cache.write() {
             cache.clear();
         }
+        if let Ok(mut icons) = self.icons.write() {
+            icons.clear();
+        }
+        if let Ok(mut disk) = self.disk.lock() {
+            *disk = Some(DiskCache::default());
+            self.dirty.store(true, Ordering::Relaxed);
+        }
     }
 
     /// Get cache statistics.
@@ -367,4 +590,70 @@ mod tests {
         cache.clear();
         assert_eq!(cache.stats(), (0, 0));
     }
+
+    #[test]
+    fn test_icon_cache_deduplicates_by_hash() {
+        let cache = IconCache::new();
+        let first = cache.store("/usr/bin/a", Some("iVBORw0KGgo=".to_string()));
+        let second = cache.store("/opt/b/a", Some("iVBORw0KGgo=".to_string()));
+        let missing = cache.store("/usr/bin/c", None);
+
+        assert!(first.is_some());
+        assert_eq!(first, second);
+        assert_eq!(missing, None);
+        assert_eq!(cache.icons.read().unwrap().len(), 1);
+        assert_eq!(cache.stats(), (3, 2));
+
+        let hash = first.unwrap();
+        assert_eq!(hash.len(), 64);
+        assert_eq!(cache.get_icon_hash("/opt/b/a"), Some(hash.clone()));
+        assert_eq!(
+            cache.get_icon_by_hash(&hash).as_deref(),
+            Some("iVBORw0KGgo=")
+        );
+        assert_eq!(
+            cache.get_icon("/usr/bin/a").as_deref(),
+            Some("iVBORw0KGgo=")
+        );
+        assert_eq!(cache.get_icon_by_hash("unknown"), None);
+    }
+
+    #[test]
+    fn test_icon_cache_persists_until_source_changes() {
+        let dir = std::env::temp_dir().join(format!("syslens_icon_cache_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let exe = dir.join("app");
+        let desktop = dir.join("app.desktop");
+        std::fs::write(&exe, b"").unwrap();
+        std::fs::write(&desktop, b"").unwrap();
+        let cache_file = dir.join("icons.json");
+        let key = exe.to_string_lossy().to_string();
+
+        let first = IconCache::with_cache_file(Some(cache_file.clone()));
+        let hash = first.store(&key, Some("iVBORw0KGgo=".to_string()));
+        first.persist(&key, hash.as_deref(), &[exe.clone(), desktop.clone()]);
+        first.save();
+
+        let second = IconCache::with_cache_file(Some(cache_file.clone()));
+        assert_eq!(second.get_icon_hash(&key), hash);
+        assert_eq!(second.get_icon(&key).as_deref(), Some("iVBORw0KGgo="));
+
+        // Touching any source makes the entry stale
+        let later = std::time::SystemTime::now() + std::time::Duration::from_secs(60);
+        std::fs::File::options()
+            .write(true)
+            .open(&desktop)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+        let third = IconCache::with_cache_file(Some(cache_file.clone()));
+        assert_eq!(third.load_persisted(&key), None);
+
+        // Entries of executables that are gone are dropped when loading
+        std::fs::remove_file(&exe).unwrap();
+        assert!(third.read_cache_file().entries.is_empty());
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
 }
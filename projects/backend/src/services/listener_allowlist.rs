@@ -0,0 +1,152 @@
+//! Allowlist of expected externally exposed listeners, stored in AppData.
+
+use crate::models::{ListenerAllowlistEntry, ListeningPort, PortExposure};
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+/// Manages the user-editable listener allowlist file.
+pub struct ListenerAllowlist {
+    allowlist_file: PathBuf,
+    entries: RwLock<Vec<ListenerAllowlistEntry>>,
+}
+
+impl ListenerAllowlist {
+    /// Create a new ListenerAllowlist, loading existing entries from disk.
+    pub fn new() -> Result<Self> {
+        let data_dir = dirs::data_dir()
+            .map(|p| p.join("Syslens"))
+            .context("Failed to get app data directory")?;
+        std::fs::create_dir_all(&data_dir).context("Failed to create data directory")?;
+
+        Ok(Self::with_file(data_dir.join("listener_allowlist.json")))
+    }
+
+    /// Load the allowlist from a specific file. A missing or invalid file is treated as empty.
+    fn with_file(allowlist_file: PathBuf) -> Self {
+        let entries = std::fs::read_to_string(&allowlist_file)
+            .ok()
+            .and_then(|content| match serde_json::from_str(&content) {
+                Ok(entries) => Some(entries),
+                Err(e) => {
+                    log::warn!("Ignoring invalid listener allowlist: {}", e);
+                    None
+                }
+            })
+            .unwrap_or_default();
+
+        Self {
+            allowlist_file,
+            entries: RwLock::new(entries),
+        }
+    }
+
+    /// Get all allowlist entries.
+    pub fn entries(&self) -> Vec<ListenerAllowlistEntry> {
+        self.entries.read().map(|e| e.clone()).unwrap_or_default()
+    }
+
+    /// Replace the allowlist and save it to disk.
+    pub fn set_entries(&self, entries: Vec<ListenerAllowlistEntry>) -> Result<()> {
+        let content =
+            serde_json::to_string_pretty(&entries).context("Failed to serialize allowlist")?;
+        std::fs::write(&self.allowlist_file, content).context("Failed to write allowlist file")?;
+
+        *self
+            .entries
+            .write()
+            .map_err(|_| anyhow::anyhow!("Failed to acquire write lock"))? = entries;
+        Ok(())
+    }
+
+    /// Mark allowlisted ports and flag the remaining ones reachable from other hosts.
+    pub fn apply(&self, ports: &mut [ListeningPort]) {
+        let entries = self.entries();
+        for port in ports {
+            port.allowed = entries.iter().any(|entry| Self::matches(entry, port));
+            port.flagged = port.exposure != PortExposure::Loopback && !port.allowed;
+        }
+    }
+
+    fn matches(entry: &ListenerAllowlistEntry, port: &ListeningPort) -> bool {
+        entry.port == port.local_port
+            && entry.protocol.is_none_or(|p| p == port.protocol)
+            && entry.process_name.as_deref().is_none_or(|name| {
+                port.process_name
+                    .as_deref()
+                    .is_some_and(|process| process.eq_ignore_ascii_case(name))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ConnectionProtocol;
+
+    fn port(
+        protocol: ConnectionProtocol,
+        local_port: u16,
+        exposure: PortExposure,
+    ) -> ListeningPort {
+        ListeningPort {
+            protocol,
+            local_address: "0.0.0.0".to_string(),
+            local_port,
+            exposure,
+            service_name: None,
+            pid: Some(1234),
+            process_name: Some("sshd".to_string()),
+            exe_path: None,
+            unit: None,
+            allowed: false,
+            flagged: false,
+        }
+    }
+
+    #[test]
+    fn test_apply_allowlist() {
+        let file = std::env::temp_dir().join(format!(
+            "syslens_allowlist_test_{}.json",
+            std::process::id()
+        ));
+        let allowlist = ListenerAllowlist::with_file(file.clone());
+        allowlist
+            .set_entries(vec![
+                ListenerAllowlistEntry {
+                    port: 22,
+                    protocol: Some(ConnectionProtocol::TCP),
+                    process_name: Some("SSHD".to_string()),
+                    comment: Some("Remote administration".to_string()),
+                },
+                ListenerAllowlistEntry {
+                    port: 5353,
+                    protocol: None,
+                    process_name: Some("avahi-daemon".to_string()),
+                    comment: None,
+                },
+            ])
+            .unwrap();
+
+        let mut ports = vec![
+            port(ConnectionProtocol::TCP, 22, PortExposure::All),
+            port(ConnectionProtocol::UDP, 22, PortExposure::All),
+            port(ConnectionProtocol::UDP, 5353, PortExposure::LAN),
+            port(ConnectionProtocol::TCP, 631, PortExposure::Loopback),
+        ];
+        allowlist.apply(&mut ports);
+
+        assert!(ports[0].allowed && !ports[0].flagged);
+        // Protocol does not match the entry
+        assert!(!ports[1].allowed && ports[1].flagged);
+        // Process name does not match the entry
+        assert!(!ports[2].allowed && ports[2].flagged);
+        // Loopback-only listeners are never flagged
+        assert!(!ports[3].allowed && !ports[3].flagged);
+
+        // Entries persist across reloads
+        let reloaded = ListenerAllowlist::with_file(file.clone());
+        assert_eq!(reloaded.entries(), allowlist.entries());
+        let _ = std::fs::remove_file(file);
+    }
+}
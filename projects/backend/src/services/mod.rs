@@ -3,23 +3,47 @@
 //! Business logic and data management services.
 
 pub mod ai_agent;
+pub mod audit_log;
 pub mod cache_manager;
 pub mod claude_client;
+pub mod command_policy;
+#[cfg(target_os = "linux")]
+mod desktop_icons;
 pub mod device_enrichment;
 pub mod device_sources;
+pub mod hardware_events;
 pub mod icon_cache;
 pub mod image_cache;
 pub mod internet_fetcher;
 pub mod knowledge_store;
+pub mod listener_allowlist;
 pub mod local_database;
+pub mod network_diagnostics;
+pub mod network_events;
+pub mod network_rates;
+pub mod process_control;
+pub mod process_events;
+pub mod process_feed;
+pub mod process_network;
 
 pub use ai_agent::AiAgent;
+pub use audit_log::AuditLog;
 pub use cache_manager::CacheManager;
 pub use claude_client::ClaudeClient;
+pub use command_policy::{CommandPolicy, PolicyDenial, ProcessTarget};
 pub use device_enrichment::{DeviceEnrichmentService, EnrichedDeviceInfo};
 pub use device_sources::{DeviceSource, SourceResult};
+pub use hardware_events::HardwareEventMonitor;
 pub use icon_cache::ICON_CACHE;
 pub use image_cache::ImageCache;
 pub use internet_fetcher::InternetFetcher;
 pub use knowledge_store::{KnowledgeStore, PartialDeviceInfo};
+pub use listener_allowlist::ListenerAllowlist;
 pub use local_database::LocalDatabaseManager;
+pub use network_diagnostics::NetworkDiagnostics;
+pub use network_events::NetworkEventMonitor;
+pub use network_rates::NetworkRateTracker;
+pub use process_control::ProcessController;
+pub use process_events::{ProcessEventLog, ProcessEventWatcher};
+pub use process_feed::ProcessFeedTracker;
+pub use process_network::ProcessNetworkTracker;
@@ -0,0 +1,1081 @@
+//! Network diagnostics: ping, TCP connect probe, DNS lookup and traceroute
+//!
+//! Each diagnostic reports progress through a callback as results arrive, so commands
+//! can stream them to the frontend, and returns a summary when it finishes.
+
+use crate::collectors::NetworkCollector;
+use crate::models::{
+    DnsLookupResult, DnsRecordType, DnsServerAnswer, PingReply, PingResult, TcpProbeResult,
+    TracerouteHop, TracerouteResult,
+};
+use std::net::{IpAddr, SocketAddr, ToSocketAddrs};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+#[cfg(target_os = "linux")]
+use std::io;
+#[cfg(target_os = "linux")]
+use std::net::{Ipv4Addr, Ipv6Addr, UdpSocket};
+#[cfg(target_os = "linux")]
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd, RawFd};
+
+/// Time between echo requests
+#[cfg(target_os = "linux")]
+const PING_INTERVAL: Duration = Duration::from_secs(1);
+/// Echo payload size used by the classic ping tool
+#[cfg(target_os = "linux")]
+const PING_PAYLOAD_SIZE: usize = 56;
+/// First UDP destination port probed by traceroute
+#[cfg(target_os = "linux")]
+const TRACEROUTE_BASE_PORT: u16 = 33434;
+
+// ICMP message types
+#[cfg(target_os = "linux")]
+const ICMP_ECHO_REPLY: u8 = 0;
+#[cfg(target_os = "linux")]
+const ICMP_ECHO_REQUEST: u8 = 8;
+#[cfg(target_os = "linux")]
+const ICMP_TIME_EXCEEDED: u8 = 11;
+#[cfg(target_os = "linux")]
+const ICMPV6_TIME_EXCEEDED: u8 = 3;
+#[cfg(target_os = "linux")]
+const ICMPV6_ECHO_REQUEST: u8 = 128;
+#[cfg(target_os = "linux")]
+const ICMPV6_ECHO_REPLY: u8 = 129;
+
+// Extended socket errors (linux/errqueue.h)
+#[cfg(target_os = "linux")]
+const SO_EE_ORIGIN_ICMP: u8 = 2;
+#[cfg(target_os = "linux")]
+const SO_EE_ORIGIN_ICMP6: u8 = 3;
+#[cfg(target_os = "linux")]
+const SOCK_EXTENDED_ERR_LEN: usize = 16;
+
+// DNS wire format (RFC 1035)
+const DNS_PORT: u16 = 53;
+const DNS_CLASS_IN: u16 = 1;
+const DNS_HEADER_LEN: usize = 12;
+const DNS_RCODE_NXDOMAIN: u8 = 3;
+
+/// ICMP error reported for a traceroute probe
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, PartialEq)]
+struct IcmpError {
+    origin: u8,
+    icmp_type: u8,
+    offender: Option<IpAddr>,
+}
+
+/// Network diagnostics runner
+pub struct NetworkDiagnostics;
+
+impl NetworkDiagnostics {
+    /// Resolve a host name or literal address to the first address returned
+    pub fn resolve(target: &str) -> Result<IpAddr, String> {
+        if let Ok(address) = target.parse::<IpAddr>() {
+            return Ok(address);
+        }
+
+        (target, 0)
+            .to_socket_addrs()
+            .map_err(|e| format!("Failed to resolve {}: {}", target, e))?
+            .next()
+            .map(|addr| addr.ip())
+            .ok_or_else(|| format!("No addresses found for {}", target))
+    }
+
+    /// Send `count` ICMP echo requests, one per second.
+    /// Uses an unprivileged datagram ICMP socket when net.ipv4.ping_group_range allows it,
+    /// falling back to a raw socket (requires CAP_NET_RAW).
+    pub fn ping(
+        target: &str,
+        count: u32,
+        timeout: Duration,
+        mut on_reply: impl FnMut(&PingReply),
+    ) -> Result<PingResult, String> {
+        #[cfg(target_os = "linux")]
+        {
+            let address = Self::resolve(target)?;
+            let socket = IcmpSocket::open(address.is_ipv6())?;
+            let mut replies = Vec::new();
+
+            for sequence in 1..=count as u16 {
+                let started = Instant::now();
+                socket
+                    .send_echo(address, sequence)
+                    .map_err(|e| format!("Failed to send echo request: {}", e))?;
+
+                let reply = PingReply {
+                    sequence,
+                    rtt_ms: socket
+                        .wait_for_reply(sequence, started + timeout)
+                        .map_err(|e| format!("Failed to receive echo reply: {}", e))?
+                        .then(|| started.elapsed().as_secs_f64() * 1000.0),
+                };
+                on_reply(&reply);
+                replies.push(reply);
+
+                if sequence < count as u16 {
+                    std::thread::sleep(PING_INTERVAL.saturating_sub(started.elapsed()));
+                }
+            }
+
+            Ok(Self::summarize_ping(target, address, replies))
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = (target, count, timeout, &mut on_reply);
+            Err("Ping is not supported on this platform".to_string())
+        }
+    }
+
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    fn summarize_ping(target: &str, address: IpAddr, replies: Vec<PingReply>) -> PingResult {
+        let rtts: Vec<f64> = replies.iter().filter_map(|r| r.rtt_ms).collect();
+        let sent = replies.len() as u32;
+        let received = rtts.len() as u32;
+
+        PingResult {
+            target: target.to_string(),
+            address: address.to_string(),
+            sent,
+            received,
+            loss_percent: if sent > 0 {
+                (sent - received) as f64 * 100.0 / sent as f64
+            } else {
+                0.0
+            },
+            min_rtt_ms: rtts.iter().copied().reduce(f64::min),
+            avg_rtt_ms: (!rtts.is_empty()).then(|| rtts.iter().sum::<f64>() / rtts.len() as f64),
+            max_rtt_ms: rtts.iter().copied().reduce(f64::max),
+            replies,
+        }
+    }
+
+    /// Try a TCP connection to every address `host` resolves to.
+    /// A refused connection still reports the time the refusal took to arrive.
+    pub async fn tcp_probe(
+        host: &str,
+        port: u16,
+        timeout: Duration,
+        mut on_result: impl FnMut(&TcpProbeResult),
+    ) -> Result<Vec<TcpProbeResult>, String> {
+        let addresses: Vec<SocketAddr> = tokio::net::lookup_host((host, port))
+            .await
+            .map_err(|e| format!("Failed to resolve {}: {}", host, e))?
+            .collect();
+
+        let mut results = Vec::new();
+        for address in addresses {
+            let started = Instant::now();
+            let outcome =
+                tokio::time::timeout(timeout, tokio::net::TcpStream::connect(address)).await;
+            let elapsed_ms = started.elapsed().as_secs_f64() * 1000.0;
+
+            let (reachable, connect_ms, error) = match outcome {
+                Ok(Ok(_stream)) => (true, Some(elapsed_ms), None),
+                Ok(Err(e)) => (false, Some(elapsed_ms), Some(e.to_string())),
+                Err(_) => (false, None, Some("Connection timed out".to_string())),
+            };
+
+            let result = TcpProbeResult {
+                host: host.to_string(),
+                address: address.ip().to_string(),
+                port,
+                reachable,
+                connect_ms,
+                error,
+            };
+            on_result(&result);
+            results.push(result);
+        }
+
+        Ok(results)
+    }
+
+    /// Get the resolvers configured on all adapters, without duplicates
+    pub fn configured_resolvers() -> Vec<String> {
+        let mut servers: Vec<String> = Vec::new();
+        for adapter in NetworkCollector::get_adapters() {
+            for server in adapter.dns_config.servers {
+                if !servers.contains(&server) {
+                    servers.push(server);
+                }
+            }
+        }
+        servers
+    }
+
+    /// Look up `name` against each resolver separately and compare the answers.
+    /// Resolvers are "address" or "address:port" strings.
+    pub async fn dns_lookup(
+        name: &str,
+        record_type: DnsRecordType,
+        servers: &[String],
+        timeout: Duration,
+        mut on_answer: impl FnMut(&DnsServerAnswer),
+    ) -> Result<DnsLookupResult, String> {
+        let query_id = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .subsec_nanos() as u16;
+        let query = Self::build_dns_query(query_id, name, record_type)?;
+
+        let mut answers = Vec::new();
+        for server in servers {
+            let started = Instant::now();
+            let outcome = match Self::parse_resolver_address(server) {
+                Some(address) => {
+                    match tokio::time::timeout(timeout, Self::exchange_dns(address, &query)).await {
+                        Ok(Ok(response)) => {
+                            Self::parse_dns_response(&response, query_id, record_type)
+                        }
+                        Ok(Err(e)) => Err(e.to_string()),
+                        Err(_) => Err("Query timed out".to_string()),
+                    }
+                }
+                None => Err("Invalid resolver address".to_string()),
+            };
+
+            let answer = match outcome {
+                Ok(mut addresses) => {
+                    addresses.sort();
+                    DnsServerAnswer {
+                        server: server.clone(),
+                        addresses: addresses.iter().map(|a| a.to_string()).collect(),
+                        response_ms: Some(started.elapsed().as_secs_f64() * 1000.0),
+                        error: None,
+                    }
+                }
+                Err(error) => DnsServerAnswer {
+                    server: server.clone(),
+                    addresses: Vec::new(),
+                    response_ms: None,
+                    error: Some(error),
+                },
+            };
+            on_answer(&answer);
+            answers.push(answer);
+        }
+
+        let mut successful = answers.iter().filter(|a| a.error.is_none());
+        let consistent = match successful.next() {
+            Some(first) => successful.all(|a| a.addresses == first.addresses),
+            None => false,
+        };
+
+        Ok(DnsLookupResult {
+            name: name.to_string(),
+            record_type,
+            answers,
+            consistent,
+        })
+    }
+
+    fn parse_resolver_address(server: &str) -> Option<SocketAddr> {
+        server.parse::<SocketAddr>().ok().or_else(|| {
+            server
+                .parse::<IpAddr>()
+                .ok()
+                .map(|ip| SocketAddr::new(ip, DNS_PORT))
+        })
+    }
+
+    async fn exchange_dns(server: SocketAddr, query: &[u8]) -> std::io::Result<Vec<u8>> {
+        let bind: SocketAddr = if server.is_ipv6() {
+            "[::]:0".parse().unwrap()
+        } else {
+            "0.0.0.0:0".parse().unwrap()
+        };
+        let socket = tokio::net::UdpSocket::bind(bind).await?;
+        socket.connect(server).await?;
+        socket.send(query).await?;
+
+        let mut buf = vec![0u8; 4096];
+        let len = socket.recv(&mut buf).await?;
+        buf.truncate(len);
+        Ok(buf)
+    }
+
+    /// Build a recursive query for a single question
+    fn build_dns_query(id: u16, name: &str, record_type: DnsRecordType) -> Result<Vec<u8>, String> {
+        let mut query = Vec::with_capacity(DNS_HEADER_LEN + name.len() + 6);
+        query.extend_from_slice(&id.to_be_bytes());
+        // Flags: recursion desired
+        query.extend_from_slice(&0x0100u16.to_be_bytes());
+        // One question, no answer/authority/additional records
+        query.extend_from_slice(&[0, 1, 0, 0, 0, 0, 0, 0]);
+
+        for label in name.trim_end_matches('.').split('.') {
+            if label.is_empty() || label.len() > 63 {
+                return Err(format!("Invalid DNS name: {}", name));
+            }
+            query.push(label.len() as u8);
+            query.extend_from_slice(label.as_bytes());
+        }
+        query.push(0);
+        query.extend_from_slice(&Self::dns_type_code(record_type).to_be_bytes());
+        query.extend_from_slice(&DNS_CLASS_IN.to_be_bytes());
+
+        Ok(query)
+    }
+
+    fn dns_type_code(record_type: DnsRecordType) -> u16 {
+        match record_type {
+            DnsRecordType::A => 1,
+            DnsRecordType::AAAA => 28,
+        }
+    }
+
+    /// Extract the addresses of the requested type from a response.
+    /// NXDOMAIN is an empty answer; other failure codes are errors.
+    fn parse_dns_response(
+        response: &[u8],
+        id: u16,
+        record_type: DnsRecordType,
+    ) -> Result<Vec<IpAddr>, String> {
+        let malformed = || "Malformed DNS response".to_string();
+        let header = response.get(..DNS_HEADER_LEN).ok_or_else(malformed)?;
+        let u16_at = |offset: usize| -> Option<u16> {
+            let bytes = response.get(offset..offset + 2)?;
+            Some(u16::from_be_bytes([bytes[0], bytes[1]]))
+        };
+
+        if u16::from_be_bytes([header[0], header[1]]) != id {
+            return Err("DNS response ID mismatch".to_string());
+        }
+        match header[3] & 0x0f {
+            0 => {}
+            DNS_RCODE_NXDOMAIN => return Ok(Vec::new()),
+            2 => return Err("Server failure (SERVFAIL)".to_string()),
+            5 => return Err("Query refused (REFUSED)".to_string()),
+            rcode => return Err(format!("DNS error code {}", rcode)),
+        }
+
+        let question_count = u16_at(4).ok_or_else(malformed)?;
+        let answer_count = u16_at(6).ok_or_else(malformed)?;
+
+        let mut offset = DNS_HEADER_LEN;
+        for _ in 0..question_count {
+            offset = Self::skip_dns_name(response, offset).ok_or_else(malformed)? + 4;
+        }
+
+        let wanted = Self::dns_type_code(record_type);
+        let mut addresses = Vec::new();
+        for _ in 0..answer_count {
+            offset = Self::skip_dns_name(response, offset).ok_or_else(malformed)?;
+            let kind = u16_at(offset).ok_or_else(malformed)?;
+            let class = u16_at(offset + 2).ok_or_else(malformed)?;
+            let length = u16_at(offset + 8).ok_or_else(malformed)? as usize;
+            let data = response
+                .get(offset + 10..offset + 10 + length)
+                .ok_or_else(malformed)?;
+            offset += 10 + length;
+
+            // CNAME records in the chain are skipped
+            if kind != wanted || class != DNS_CLASS_IN {
+                continue;
+            }
+            match record_type {
+                DnsRecordType::A => {
+                    let octets: [u8; 4] = data.try_into().map_err(|_| malformed())?;
+                    addresses.push(IpAddr::from(octets));
+                }
+                DnsRecordType::AAAA => {
+                    let octets: [u8; 16] = data.try_into().map_err(|_| malformed())?;
+                    addresses.push(IpAddr::from(octets));
+                }
+            }
+        }
+
+        Ok(addresses)
+    }
+
+    /// Skip a possibly compressed name, returning the offset after it
+    fn skip_dns_name(message: &[u8], mut offset: usize) -> Option<usize> {
+        loop {
+            let len = *message.get(offset)? as usize;
+            match len {
+                0 => return Some(offset + 1),
+                // Compression pointer ends the name
+                l if l & 0xC0 == 0xC0 => return Some(offset + 2),
+                l => offset += 1 + l,
+            }
+        }
+    }
+
+    /// Trace the route to `target` with UDP probes of increasing TTL, reading the
+    /// ICMP replies from the socket error queue (works without privileges).
+    pub fn traceroute(
+        target: &str,
+        max_hops: u8,
+        timeout: Duration,
+        mut on_hop: impl FnMut(&TracerouteHop),
+    ) -> Result<TracerouteResult, String> {
+        #[cfg(target_os = "linux")]
+        {
+            let address = Self::resolve(target)?;
+            let bind: SocketAddr = if address.is_ipv6() {
+                (Ipv6Addr::UNSPECIFIED, 0).into()
+            } else {
+                (Ipv4Addr::UNSPECIFIED, 0).into()
+            };
+            let socket = UdpSocket::bind(bind).map_err(|e| e.to_string())?;
+            Self::enable_recverr(&socket, address.is_ipv6()).map_err(|e| e.to_string())?;
+
+            let mut hops = Vec::new();
+            let mut reached = false;
+
+            for ttl in 1..=max_hops {
+                Self::set_ttl(&socket, address.is_ipv6(), ttl).map_err(|e| e.to_string())?;
+                let destination = SocketAddr::new(address, TRACEROUTE_BASE_PORT + ttl as u16);
+
+                let started = Instant::now();
+                socket
+                    .send_to(&[0u8; 32], destination)
+                    .map_err(|e| format!("Failed to send probe: {}", e))?;
+                let response = Self::wait_for_icmp_error(&socket, destination, started + timeout)
+                    .map_err(|e| format!("Failed to read probe reply: {}", e))?;
+                let rtt_ms = response
+                    .as_ref()
+                    .map(|_| started.elapsed().as_secs_f64() * 1000.0);
+
+                let (hop_address, final_hop) = match &response {
+                    Some(error) => (error.offender, Self::is_final_hop(error)),
+                    None => (None, false),
+                };
+                let hop = TracerouteHop {
+                    ttl,
+                    address: hop_address.map(|a| a.to_string()),
+                    rtt_ms,
+                    reached: final_hop && hop_address == Some(address),
+                };
+                on_hop(&hop);
+                hops.push(hop);
+
+                if final_hop {
+                    reached = hop_address == Some(address);
+                    break;
+                }
+            }
+
+            Ok(TracerouteResult {
+                target: target.to_string(),
+                address: address.to_string(),
+                hops,
+                reached,
+            })
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = (target, max_hops, timeout, &mut on_hop);
+            Err("Traceroute is not supported on this platform".to_string())
+        }
+    }
+
+    /// Anything other than "time exceeded" ends the trace
+    #[cfg(target_os = "linux")]
+    fn is_final_hop(error: &IcmpError) -> bool {
+        match error.origin {
+            SO_EE_ORIGIN_ICMP => error.icmp_type != ICMP_TIME_EXCEEDED,
+            SO_EE_ORIGIN_ICMP6 => error.icmp_type != ICMPV6_TIME_EXCEEDED,
+            _ => true,
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn enable_recverr(socket: &UdpSocket, ipv6: bool) -> io::Result<()> {
+        let (level, option) = if ipv6 {
+            (libc::SOL_IPV6, libc::IPV6_RECVERR)
+        } else {
+            (libc::SOL_IP, libc::IP_RECVERR)
+        };
+        set_int_option(socket.as_raw_fd(), level, option, 1)
+    }
+
+    #[cfg(target_os = "linux")]
+    fn set_ttl(socket: &UdpSocket, ipv6: bool, ttl: u8) -> io::Result<()> {
+        let (level, option) = if ipv6 {
+            (libc::SOL_IPV6, libc::IPV6_UNICAST_HOPS)
+        } else {
+            (libc::SOL_IP, libc::IP_TTL)
+        };
+        set_int_option(socket.as_raw_fd(), level, option, ttl as libc::c_int)
+    }
+
+    /// Wait for an ICMP error on the socket error queue. A direct UDP reply means the
+    /// target answered on the probed port. Late replies to earlier probes, which went to
+    /// other ports, are dropped.
+    #[cfg(target_os = "linux")]
+    fn wait_for_icmp_error(
+        socket: &UdpSocket,
+        probe: SocketAddr,
+        deadline: Instant,
+    ) -> io::Result<Option<IcmpError>> {
+        loop {
+            let Some(revents) = poll_fd(socket.as_raw_fd(), deadline)? else {
+                return Ok(None);
+            };
+
+            if revents & libc::POLLERR != 0 {
+                let mut data = [0u8; 512];
+                let mut control = [0u8; 512];
+                let mut iov = libc::iovec {
+                    iov_base: data.as_mut_ptr() as *mut libc::c_void,
+                    iov_len: data.len(),
+                };
+                // The destination of the datagram that caused the error
+                let mut name: libc::sockaddr_storage = unsafe { std::mem::zeroed() };
+                let mut message: libc::msghdr = unsafe { std::mem::zeroed() };
+                message.msg_name = &mut name as *mut _ as *mut libc::c_void;
+                message.msg_namelen = std::mem::size_of::<libc::sockaddr_storage>() as _;
+                message.msg_iov = &mut iov;
+                message.msg_iovlen = 1;
+                message.msg_control = control.as_mut_ptr() as *mut libc::c_void;
+                message.msg_controllen = control.len() as _;
+
+                let received =
+                    unsafe { libc::recvmsg(socket.as_raw_fd(), &mut message, libc::MSG_ERRQUEUE) };
+                if received < 0 {
+                    return Err(io::Error::last_os_error());
+                }
+                let name = unsafe {
+                    std::slice::from_raw_parts(
+                        &name as *const _ as *const u8,
+                        message.msg_namelen as usize,
+                    )
+                };
+                if Self::parse_sockaddr_port(name) != Some(probe.port()) {
+                    continue;
+                }
+                let control = &control[..message.msg_controllen as usize];
+                if let Some(error) = Self::parse_recverr_control(control) {
+                    return Ok(Some(error));
+                }
+            } else if revents & libc::POLLIN != 0 {
+                let mut buf = [0u8; 512];
+                let (_, from) = socket.recv_from(&mut buf)?;
+                if from.port() != probe.port() {
+                    continue;
+                }
+                return Ok(Some(IcmpError {
+                    origin: 0,
+                    icmp_type: 0,
+                    offender: Some(from.ip()),
+                }));
+            }
+        }
+    }
+
+    /// Find the IP_RECVERR/IPV6_RECVERR control message and decode the
+    /// sock_extended_err and offending address that follow it
+    #[cfg(target_os = "linux")]
+    fn parse_recverr_control(control: &[u8]) -> Option<IcmpError> {
+        let header_len = std::mem::size_of::<libc::cmsghdr>();
+        let align = |len: usize| {
+            let word = std::mem::size_of::<usize>();
+            (len + word - 1) & !(word - 1)
+        };
+
+        let mut offset = 0;
+        while offset + header_len <= control.len() {
+            let header = &control[offset..offset + header_len];
+            let len = usize::from_ne_bytes(header[..std::mem::size_of::<usize>()].try_into().ok()?);
+            let level_offset = std::mem::size_of::<usize>();
+            let level = i32::from_ne_bytes(header[level_offset..level_offset + 4].try_into().ok()?);
+            let kind =
+                i32::from_ne_bytes(header[level_offset + 4..level_offset + 8].try_into().ok()?);
+            if len < header_len || offset + len > control.len() {
+                return None;
+            }
+
+            let data = &control[offset + header_len..offset + len];
+            let is_recverr = (level == libc::SOL_IP && kind == libc::IP_RECVERR)
+                || (level == libc::SOL_IPV6 && kind == libc::IPV6_RECVERR);
+            if is_recverr && data.len() >= SOCK_EXTENDED_ERR_LEN {
+                return Some(IcmpError {
+                    origin: data[4],
+                    icmp_type: data[5],
+                    offender: Self::parse_sockaddr(&data[SOCK_EXTENDED_ERR_LEN..]),
+                });
+            }
+
+            offset += align(len);
+        }
+
+        None
+    }
+
+    #[cfg(target_os = "linux")]
+    fn parse_sockaddr(data: &[u8]) -> Option<IpAddr> {
+        let family = u16::from_ne_bytes(data.get(..2)?.try_into().ok()?) as i32;
+        match family {
+            libc::AF_INET => {
+                let octets: [u8; 4] = data.get(4..8)?.try_into().ok()?;
+                Some(IpAddr::V4(Ipv4Addr::from(octets)))
+            }
+            libc::AF_INET6 => {
+                let octets: [u8; 16] = data.get(8..24)?.try_into().ok()?;
+                Some(IpAddr::V6(Ipv6Addr::from(octets)))
+            }
+            _ => None,
+        }
+    }
+
+    /// Port of a sockaddr_in or sockaddr_in6, which both keep it in network byte order
+    /// right after the family
+    #[cfg(target_os = "linux")]
+    fn parse_sockaddr_port(data: &[u8]) -> Option<u16> {
+        let family = u16::from_ne_bytes(data.get(..2)?.try_into().ok()?) as i32;
+        match family {
+            libc::AF_INET | libc::AF_INET6 => {
+                Some(u16::from_be_bytes(data.get(2..4)?.try_into().ok()?))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// ICMP echo socket: datagram when permitted, raw otherwise
+#[cfg(target_os = "linux")]
+struct IcmpSocket {
+    fd: OwnedFd,
+    raw: bool,
+    ipv6: bool,
+    identifier: u16,
+}
+
+#[cfg(target_os = "linux")]
+impl IcmpSocket {
+    fn open(ipv6: bool) -> Result<Self, String> {
+        let (domain, protocol) = if ipv6 {
+            (libc::AF_INET6, libc::IPPROTO_ICMPV6)
+        } else {
+            (libc::AF_INET, libc::IPPROTO_ICMP)
+        };
+
+        let mut last_error = None;
+        for (kind, raw) in [(libc::SOCK_DGRAM, false), (libc::SOCK_RAW, true)] {
+            let fd = unsafe { libc::socket(domain, kind | libc::SOCK_CLOEXEC, protocol) };
+            if fd >= 0 {
+                return Ok(Self {
+                    fd: unsafe { OwnedFd::from_raw_fd(fd) },
+                    raw,
+                    ipv6,
+                    identifier: std::process::id() as u16,
+                });
+            }
+            last_error = Some(io::Error::last_os_error());
+        }
+
+        Err(format!(
+            "ICMP sockets are not permitted ({}). Allow unprivileged ping with \
+             sysctl net.ipv4.ping_group_range",
+            last_error.map(|e| e.to_string()).unwrap_or_default()
+        ))
+    }
+
+    fn send_echo(&self, address: IpAddr, sequence: u16) -> io::Result<()> {
+        let packet = build_echo_request(self.ipv6, self.identifier, sequence);
+        let (storage, len) = socket_address(SocketAddr::new(address, 0));
+
+        let sent = unsafe {
+            libc::sendto(
+                self.fd.as_raw_fd(),
+                packet.as_ptr() as *const libc::c_void,
+                packet.len(),
+                0,
+                &storage as *const libc::sockaddr_storage as *const libc::sockaddr,
+                len,
+            )
+        };
+        if sent < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    /// Wait until the reply for `sequence` arrives (true) or the deadline passes (false)
+    fn wait_for_reply(&self, sequence: u16, deadline: Instant) -> io::Result<bool> {
+        let mut buf = [0u8; 1500];
+        while poll_fd(self.fd.as_raw_fd(), deadline)?.is_some() {
+            let received = unsafe {
+                libc::recv(
+                    self.fd.as_raw_fd(),
+                    buf.as_mut_ptr() as *mut libc::c_void,
+                    buf.len(),
+                    0,
+                )
+            };
+            if received < 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            // Raw IPv4 sockets deliver the IP header; raw sockets also see other processes' replies
+            let packet = &buf[..received as usize];
+            let identifier = self.raw.then_some(self.identifier);
+            if parse_echo_reply(packet, self.ipv6, self.raw, identifier) == Some(sequence) {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+}
+
+/// Build an ICMP/ICMPv6 echo request. The kernel fills in the ICMPv6 checksum, and the
+/// identifier of datagram sockets.
+#[cfg(target_os = "linux")]
+fn build_echo_request(ipv6: bool, identifier: u16, sequence: u16) -> Vec<u8> {
+    let mut packet = vec![if ipv6 {
+        ICMPV6_ECHO_REQUEST
+    } else {
+        ICMP_ECHO_REQUEST
+    }];
+    packet.extend_from_slice(&[0, 0, 0]);
+    packet.extend_from_slice(&identifier.to_be_bytes());
+    packet.extend_from_slice(&sequence.to_be_bytes());
+    packet.extend((0..PING_PAYLOAD_SIZE).map(|i| i as u8));
+
+    if !ipv6 {
+        let checksum = internet_checksum(&packet);
+        packet[2..4].copy_from_slice(&checksum.to_be_bytes());
+    }
+    packet
+}
+
+/// Return the sequence number of an echo reply, if `packet` is one
+#[cfg(target_os = "linux")]
+fn parse_echo_reply(packet: &[u8], ipv6: bool, raw: bool, identifier: Option<u16>) -> Option<u16> {
+    let icmp = if raw && !ipv6 {
+        let header_len = ((*packet.first()? & 0x0f) as usize) * 4;
+        packet.get(header_len..)?
+    } else {
+        packet
+    };
+
+    let expected = if ipv6 {
+        ICMPV6_ECHO_REPLY
+    } else {
+        ICMP_ECHO_REPLY
+    };
+    if *icmp.first()? != expected || icmp.len() < 8 {
+        return None;
+    }
+    if identifier.is_some_and(|id| u16::from_be_bytes([icmp[4], icmp[5]]) != id) {
+        return None;
+    }
+    Some(u16::from_be_bytes([icmp[6], icmp[7]]))
+}
+
+/// RFC 1071 checksum
+#[cfg(target_os = "linux")]
+fn internet_checksum(data: &[u8]) -> u16 {
+    let mut sum: u32 = data
+        .chunks(2)
+        .map(|pair| u16::from_be_bytes([pair[0], *pair.get(1).unwrap_or(&0)]) as u32)
+        .sum();
+    while sum >> 16 != 0 {
+        sum = (sum & 0xffff) + (sum >> 16);
+    }
+    !(sum as u16)
+}
+
+#[cfg(target_os = "linux")]
+fn socket_address(address: SocketAddr) -> (libc::sockaddr_storage, libc::socklen_t) {
+    let mut storage: libc::sockaddr_storage = unsafe { std::mem::zeroed() };
+    let len = match address {
+        SocketAddr::V4(v4) => {
+            let sin = libc::sockaddr_in {
+                sin_family: libc::AF_INET as libc::sa_family_t,
+                sin_port: v4.port().to_be(),
+                sin_addr: libc::in_addr {
+                    s_addr: u32::from_ne_bytes(v4.ip().octets()),
+                },
+                sin_zero: [0; 8],
+            };
+            unsafe { std::ptr::write(&mut storage as *mut _ as *mut libc::sockaddr_in, sin) };
+            std::mem::size_of::<libc::sockaddr_in>()
+        }
+        SocketAddr::V6(v6) => {
+            let sin6 = libc::sockaddr_in6 {
+                sin6_family: libc::AF_INET6 as libc::sa_family_t,
+                sin6_port: v6.port().to_be(),
+                sin6_flowinfo: 0,
+                sin6_addr: libc::in6_addr {
+                    s6_addr: v6.ip().octets(),
+                },
+                sin6_scope_id: v6.scope_id(),
+            };
+            unsafe { std::ptr::write(&mut storage as *mut _ as *mut libc::sockaddr_in6, sin6) };
+            std::mem::size_of::<libc::sockaddr_in6>()
+        }
+    };
+    (storage, len as libc::socklen_t)
+}
+
+#[cfg(target_os = "linux")]
+fn set_int_option(
+    fd: RawFd,
+    level: libc::c_int,
+    option: libc::c_int,
+    value: libc::c_int,
+) -> io::Result<()> {
+    let result = unsafe {
+        libc::setsockopt(
+            fd,
+            level,
+            option,
+            &value as *const libc::c_int as *const libc::c_void,
+            std::mem::size_of::<libc::c_int>() as libc::socklen_t,
+        )
+    };
+    if result < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Poll a socket until it has data or an error queued. Returns None at the deadline.
+#[cfg(target_os = "linux")]
+fn poll_fd(fd: RawFd, deadline: Instant) -> io::Result<Option<libc::c_short>> {
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Ok(None);
+        }
+
+        let mut pollfd = libc::pollfd {
+            fd,
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        let result = unsafe { libc::poll(&mut pollfd, 1, remaining.as_millis().max(1) as i32) };
+        if result < 0 {
+            let error = io::Error::last_os_error();
+            if error.kind() == io::ErrorKind::Interrupted {
+                continue;
+            }
+            return Err(error);
+        }
+        if result > 0 {
+            return Ok(Some(pollfd.revents));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dns_query_and_response() {
+        let query =
+            NetworkDiagnostics::build_dns_query(0x1234, "example.com", DnsRecordType::A).unwrap();
+        assert_eq!(&query[..2], &[0x12, 0x34]);
+        assert_eq!(&query[12..25], b"\x07example\x03com\x00");
+        assert!(NetworkDiagnostics::build_dns_query(1, "bad..name", DnsRecordType::A).is_err());
+
+        // Response: the question, a CNAME to a compressed name, then two A records
+        let mut response = query.clone();
+        response[2] = 0x81;
+        response[3] = 0x80;
+        response[7] = 3;
+        response.extend_from_slice(&[0xC0, 0x0C, 0, 5, 0, 1, 0, 0, 0, 60, 0, 2, 0xC0, 0x0C]);
+        for last in [35u8, 34] {
+            response.extend_from_slice(&[0xC0, 0x0C, 0, 1, 0, 1, 0, 0, 0, 60, 0, 4]);
+            response.extend_from_slice(&[93, 184, 216, last]);
+        }
+
+        let addresses =
+            NetworkDiagnostics::parse_dns_response(&response, 0x1234, DnsRecordType::A).unwrap();
+        assert_eq!(addresses.len(), 2);
+        assert_eq!(addresses[0].to_string(), "93.184.216.35");
+
+        // Mismatched ID, NXDOMAIN and SERVFAIL
+        assert!(NetworkDiagnostics::parse_dns_response(&response, 1, DnsRecordType::A).is_err());
+        response[3] = 0x83;
+        assert_eq!(
+            NetworkDiagnostics::parse_dns_response(&response, 0x1234, DnsRecordType::A),
+            Ok(Vec::new())
+        );
+        response[3] = 0x82;
+        assert!(
+            NetworkDiagnostics::parse_dns_response(&response, 0x1234, DnsRecordType::A).is_err()
+        );
+    }
+
+    #[test]
+    fn test_dns_lookup_compares_resolvers() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(async {
+            // Two loopback resolvers that disagree about the answer
+            let mut servers = Vec::new();
+            for last_octet in [1u8, 2] {
+                let socket = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+                servers.push(socket.local_addr().unwrap().to_string());
+                tokio::spawn(async move {
+                    let mut buf = [0u8; 512];
+                    let (len, peer) = socket.recv_from(&mut buf).await.unwrap();
+                    let mut response = buf[..len].to_vec();
+                    response[2] |= 0x80;
+                    response[7] = 1;
+                    response.extend_from_slice(&[0xC0, 0x0C, 0, 1, 0, 1, 0, 0, 0, 60, 0, 4]);
+                    response.extend_from_slice(&[10, 0, 0, last_octet]);
+                    socket.send_to(&response, peer).await.unwrap();
+                });
+            }
+            servers.push("not-an-address".to_string());
+
+            let mut streamed = 0;
+            let result = NetworkDiagnostics::dns_lookup(
+                "syslens.test",
+                DnsRecordType::A,
+                &servers,
+                Duration::from_secs(2),
+                |_| streamed += 1,
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(streamed, 3);
+            assert_eq!(result.answers[0].addresses, vec!["10.0.0.1"]);
+            assert_eq!(result.answers[1].addresses, vec!["10.0.0.2"]);
+            assert!(result.answers[2].error.is_some());
+            assert!(!result.consistent);
+        });
+    }
+
+    #[test]
+    fn test_tcp_probe_loopback() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(async {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let open_port = listener.local_addr().unwrap().port();
+
+            let results = NetworkDiagnostics::tcp_probe(
+                "127.0.0.1",
+                open_port,
+                Duration::from_secs(2),
+                |_| {},
+            )
+            .await
+            .unwrap();
+            assert_eq!(results.len(), 1);
+            assert!(results[0].reachable);
+            assert!(results[0].connect_ms.is_some());
+
+            // Closing the listener leaves the port refusing connections
+            drop(listener);
+            let results = NetworkDiagnostics::tcp_probe(
+                "127.0.0.1",
+                open_port,
+                Duration::from_secs(2),
+                |_| {},
+            )
+            .await
+            .unwrap();
+            assert!(!results[0].reachable);
+            assert!(results[0].error.is_some());
+        });
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_echo_packets() {
+        let request = build_echo_request(false, 0x4242, 7);
+        assert_eq!(request.len(), 8 + PING_PAYLOAD_SIZE);
+        // A packet including its checksum sums to zero
+        assert_eq!(internet_checksum(&request), 0);
+
+        let mut reply = request.clone();
+        reply[0] = ICMP_ECHO_REPLY;
+        assert_eq!(parse_echo_reply(&reply, false, false, None), Some(7));
+        assert_eq!(
+            parse_echo_reply(&reply, false, false, Some(0x4242)),
+            Some(7)
+        );
+        assert_eq!(parse_echo_reply(&reply, false, false, Some(1)), None);
+        assert_eq!(parse_echo_reply(&request, false, false, None), None);
+
+        // Raw IPv4 sockets prefix a 20-byte IP header
+        let mut with_header = vec![0x45];
+        with_header.extend_from_slice(&[0; 19]);
+        with_header.extend_from_slice(&reply);
+        assert_eq!(parse_echo_reply(&with_header, false, true, None), Some(7));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_ping_loopback() {
+        let mut streamed = Vec::new();
+        let result = match NetworkDiagnostics::ping("127.0.0.1", 2, Duration::from_secs(1), |r| {
+            streamed.push(r.sequence)
+        }) {
+            Ok(result) => result,
+            // Neither unprivileged nor raw ICMP sockets are permitted here
+            Err(e) if e.starts_with("ICMP sockets are not permitted") => return,
+            Err(e) => panic!("{}", e),
+        };
+
+        assert_eq!(streamed, vec![1, 2]);
+        assert_eq!(result.sent, 2);
+        assert_eq!(result.received, 2);
+        assert_eq!(result.loss_percent, 0.0);
+        assert!(result.min_rtt_ms.unwrap() <= result.max_rtt_ms.unwrap());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_traceroute_loopback() {
+        let mut hops = 0;
+        let result =
+            NetworkDiagnostics::traceroute("127.0.0.1", 5, Duration::from_secs(1), |_| hops += 1)
+                .unwrap();
+
+        // The closed probe port answers with "port unreachable" on the first hop
+        assert_eq!(hops, 1);
+        assert!(result.reached);
+        assert_eq!(result.hops[0].address.as_deref(), Some("127.0.0.1"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_recverr_control() {
+        // cmsghdr(IP_RECVERR) + sock_extended_err(ICMP time exceeded) + sockaddr_in(10.0.0.1)
+        let header_len = std::mem::size_of::<libc::cmsghdr>();
+        let mut data = vec![0u8; SOCK_EXTENDED_ERR_LEN];
+        data[0..4].copy_from_slice(&(libc::EHOSTUNREACH as u32).to_ne_bytes());
+        data[4] = SO_EE_ORIGIN_ICMP;
+        data[5] = ICMP_TIME_EXCEEDED;
+        data.extend_from_slice(&(libc::AF_INET as u16).to_ne_bytes());
+        data.extend_from_slice(&[0, 0, 10, 0, 0, 1]);
+        data.extend_from_slice(&[0; 8]);
+
+        let mut control = Vec::new();
+        control.extend_from_slice(&(header_len + data.len()).to_ne_bytes());
+        control.extend_from_slice(&libc::SOL_IP.to_ne_bytes());
+        control.extend_from_slice(&libc::IP_RECVERR.to_ne_bytes());
+        control.extend_from_slice(&data);
+
+        let error = NetworkDiagnostics::parse_recverr_control(&control).unwrap();
+        assert_eq!(error.icmp_type, ICMP_TIME_EXCEEDED);
+        assert_eq!(error.offender, Some("10.0.0.1".parse().unwrap()));
+        assert!(!NetworkDiagnostics::is_final_hop(&error));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_sockaddr_port() {
+        let port = |address: &str| {
+            let (storage, len) = socket_address(address.parse().unwrap());
+            let data = unsafe {
+                std::slice::from_raw_parts(&storage as *const _ as *const u8, len as usize)
+            };
+            NetworkDiagnostics::parse_sockaddr_port(data)
+        };
+        assert_eq!(port("192.0.2.1:33435"), Some(33435));
+        assert_eq!(port("[2001:db8::1]:33440"), Some(33440));
+        assert_eq!(NetworkDiagnostics::parse_sockaddr_port(&[]), None);
+    }
+}
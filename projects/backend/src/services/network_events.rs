@@ -0,0 +1,674 @@
+//! Network change monitor
+//!
+//! Listens for rtnetlink notifications on a background thread and emits Tauri events
+//! when a link goes up or down, an address or route changes, or the default gateway
+//! switches. Each event carries the adapter as `get_network_adapters` returns it, so
+//! the frontend does not have to poll.
+
+use crate::models::NetworkChangeKind;
+use tauri::AppHandle;
+
+#[cfg(target_os = "linux")]
+use crate::collectors::links;
+#[cfg(target_os = "linux")]
+use crate::collectors::netlink::{Attributes, NetlinkMessage, NetlinkSocket, NLM_F_DUMP};
+#[cfg(target_os = "linux")]
+use crate::collectors::NetworkCollector;
+#[cfg(target_os = "linux")]
+use crate::models::{NetworkAdapter, NetworkChangeEvent};
+#[cfg(target_os = "linux")]
+use std::collections::{HashMap, HashSet};
+#[cfg(target_os = "linux")]
+use std::net::IpAddr;
+#[cfg(target_os = "linux")]
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+#[cfg(target_os = "linux")]
+use tauri::Emitter;
+
+// rtnetlink messages and multicast groups (linux/rtnetlink.h)
+#[cfg(target_os = "linux")]
+const RTM_NEWLINK: u16 = 16;
+#[cfg(target_os = "linux")]
+const RTM_DELLINK: u16 = 17;
+#[cfg(target_os = "linux")]
+const RTM_NEWADDR: u16 = 20;
+#[cfg(target_os = "linux")]
+const RTM_DELADDR: u16 = 21;
+#[cfg(target_os = "linux")]
+const RTM_NEWROUTE: u16 = 24;
+#[cfg(target_os = "linux")]
+const RTM_DELROUTE: u16 = 25;
+#[cfg(target_os = "linux")]
+const RTM_GETROUTE: u16 = 26;
+#[cfg(target_os = "linux")]
+const RTMGRP_LINK: u32 = 0x1;
+#[cfg(target_os = "linux")]
+const RTMGRP_IPV4_IFADDR: u32 = 0x10;
+#[cfg(target_os = "linux")]
+const RTMGRP_IPV4_ROUTE: u32 = 0x40;
+#[cfg(target_os = "linux")]
+const RTMGRP_IPV6_IFADDR: u32 = 0x100;
+#[cfg(target_os = "linux")]
+const RTMGRP_IPV6_ROUTE: u32 = 0x400;
+
+#[cfg(target_os = "linux")]
+const IFADDRMSG_LEN: usize = 8;
+#[cfg(target_os = "linux")]
+const IFA_ADDRESS: u16 = 1;
+#[cfg(target_os = "linux")]
+const IFA_LOCAL: u16 = 2;
+#[cfg(target_os = "linux")]
+const RTMSG_LEN: usize = 12;
+#[cfg(target_os = "linux")]
+const RTA_DST: u16 = 1;
+#[cfg(target_os = "linux")]
+const RTA_OIF: u16 = 4;
+#[cfg(target_os = "linux")]
+const RTA_GATEWAY: u16 = 5;
+#[cfg(target_os = "linux")]
+const RTA_PRIORITY: u16 = 6;
+#[cfg(target_os = "linux")]
+const RTA_TABLE: u16 = 15;
+#[cfg(target_os = "linux")]
+const RT_TABLE_MAIN: u32 = 254;
+#[cfg(target_os = "linux")]
+const RT_TABLE_LOCAL: u32 = 255;
+
+/// Idle wait between checks of the subscription socket
+#[cfg(target_os = "linux")]
+const IDLE_WAIT: Duration = Duration::from_secs(60);
+/// Notifications arriving this close together are reported as one batch
+/// (e.g. a DHCP bind adds an address and several routes at once)
+#[cfg(target_os = "linux")]
+const COALESCE_WINDOW: Duration = Duration::from_millis(150);
+
+/// A default route of the main table. Several may exist per family (e.g. Ethernet
+/// and Wi-Fi both connected); the kernel uses the one with the lowest metric.
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct DefaultRoute {
+    family: u8,
+    gateway: Option<String>,
+    interface: Option<u32>,
+    metric: u32,
+}
+
+/// A change detected from a notification, before the adapter is attached
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, PartialEq)]
+struct PendingChange {
+    kind: NetworkChangeKind,
+    interface: Option<String>,
+    address: Option<String>,
+    gateway: Option<String>,
+}
+
+/// Link and default route state, used to turn notifications into transitions
+#[cfg(target_os = "linux")]
+#[derive(Debug, Default)]
+struct MonitorState {
+    names: HashMap<u32, String>,
+    /// Whether each link is administratively up with carrier
+    operational: HashMap<u32, bool>,
+    /// Default routes of the main table
+    default_routes: HashSet<DefaultRoute>,
+}
+
+/// Background listener that turns rtnetlink notifications into Tauri events
+pub struct NetworkEventMonitor;
+
+impl NetworkEventMonitor {
+    /// Start the background listener
+    pub fn start(app: AppHandle) {
+        #[cfg(target_os = "linux")]
+        {
+            let result = std::thread::Builder::new()
+                .name("network-events".to_string())
+                .spawn(move || {
+                    if let Err(e) = Self::run(&app) {
+                        log::error!("Network change monitor stopped: {}", e);
+                    }
+                });
+
+            if let Err(e) = result {
+                log::error!("Failed to start network change monitor: {}", e);
+            }
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = app;
+            log::info!("Network change events are not supported on this platform");
+        }
+    }
+
+    /// Event name for a change kind
+    pub fn event_name(kind: NetworkChangeKind) -> &'static str {
+        match kind {
+            NetworkChangeKind::LinkAdded => "network:link-added",
+            NetworkChangeKind::LinkRemoved => "network:link-removed",
+            NetworkChangeKind::LinkUp => "network:link-up",
+            NetworkChangeKind::LinkDown => "network:link-down",
+            NetworkChangeKind::AddressAdded => "network:address-added",
+            NetworkChangeKind::AddressRemoved => "network:address-removed",
+            NetworkChangeKind::RouteChanged => "network:route-changed",
+            NetworkChangeKind::DefaultGatewayChanged => "network:gateway-changed",
+            NetworkChangeKind::Resync => "network:resync",
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn run(app: &AppHandle) -> std::io::Result<()> {
+        let groups = RTMGRP_LINK
+            | RTMGRP_IPV4_IFADDR
+            | RTMGRP_IPV6_IFADDR
+            | RTMGRP_IPV4_ROUTE
+            | RTMGRP_IPV6_ROUTE;
+        let mut socket = NetlinkSocket::subscribe(libc::NETLINK_ROUTE, groups)?;
+        let mut state = MonitorState::seed();
+        log::info!("Network change monitor started");
+
+        loop {
+            let default_routes = state.default_routes.clone();
+            let mut changes = Vec::new();
+            let mut overflowed = false;
+
+            // Block until something happens, then collect the rest of the burst
+            let mut timeout = IDLE_WAIT;
+            loop {
+                match socket.receive(timeout) {
+                    Ok(messages) if messages.is_empty() => break,
+                    Ok(messages) => changes.extend(state.process_all(&messages)),
+                    Err(e) if e.raw_os_error() == Some(libc::ENOBUFS) => {
+                        overflowed = true;
+                        break;
+                    }
+                    Err(e) => return Err(e),
+                }
+                timeout = COALESCE_WINDOW;
+            }
+
+            if overflowed {
+                log::warn!("Network notifications were dropped; resynchronizing");
+                state = MonitorState::seed();
+                let resync = PendingChange {
+                    kind: NetworkChangeKind::Resync,
+                    interface: None,
+                    address: None,
+                    gateway: None,
+                };
+                Self::emit(app, resync, None);
+                continue;
+            }
+
+            changes.extend(state.gateway_changes(&default_routes));
+            if changes.is_empty() {
+                continue;
+            }
+
+            let adapters = NetworkCollector::get_adapters();
+            for change in changes {
+                let adapter = change
+                    .interface
+                    .as_ref()
+                    .and_then(|name| adapters.iter().find(|a| &a.name == name))
+                    .cloned();
+                Self::emit(app, change, adapter);
+            }
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn emit(app: &AppHandle, change: PendingChange, adapter: Option<NetworkAdapter>) {
+        let event = Self::event_name(change.kind);
+        let payload = NetworkChangeEvent {
+            kind: change.kind,
+            interface: change.interface,
+            address: change.address,
+            gateway: change.gateway,
+            adapter,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or(0),
+        };
+        if let Err(e) = app.emit(event, payload) {
+            log::warn!("Failed to emit {}: {}", event, e);
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl MonitorState {
+    /// Build the initial state from link and route dumps
+    fn seed() -> Self {
+        let mut state = Self::default();
+
+        match links::dump_links() {
+            Ok(links) => {
+                for link in links {
+                    state
+                        .operational
+                        .insert(link.index, link.is_up && link.has_carrier);
+                    state.names.insert(link.index, link.name);
+                }
+            }
+            Err(e) => log::warn!("Failed to dump links: {}", e),
+        }
+
+        let routes = NetlinkSocket::open(libc::NETLINK_ROUTE).and_then(|mut socket| {
+            let mut request = [0u8; RTMSG_LEN];
+            request[0] = libc::AF_UNSPEC as u8;
+            socket.request(RTM_GETROUTE, NLM_F_DUMP, &request)
+        });
+        match routes {
+            Ok(routes) => {
+                state.process_all(&routes);
+            }
+            Err(e) => log::warn!("Failed to dump routes: {}", e),
+        }
+
+        state
+    }
+
+    fn process_all(&mut self, messages: &[NetlinkMessage]) -> Vec<PendingChange> {
+        messages.iter().filter_map(|m| self.process(m)).collect()
+    }
+
+    fn interface_name(&self, index: u32) -> Option<String> {
+        self.names.get(&index).cloned()
+    }
+
+    /// Update state from one notification and describe what changed, if anything
+    fn process(&mut self, message: &NetlinkMessage) -> Option<PendingChange> {
+        let change = |kind, interface, address, gateway| PendingChange {
+            kind,
+            interface,
+            address,
+            gateway,
+        };
+
+        match message.msg_type {
+            RTM_NEWLINK => {
+                let link = links::parse_link_message(&message.payload)?;
+                let operational = link.is_up && link.has_carrier;
+                self.names.insert(link.index, link.name.clone());
+
+                // Other attribute updates (MTU, statistics, ...) arrive as RTM_NEWLINK too
+                let kind = match self.operational.insert(link.index, operational) {
+                    None => NetworkChangeKind::LinkAdded,
+                    Some(previous) if previous != operational => {
+                        if operational {
+                            NetworkChangeKind::LinkUp
+                        } else {
+                            NetworkChangeKind::LinkDown
+                        }
+                    }
+                    Some(_) => return None,
+                };
+                Some(change(kind, Some(link.name), None, None))
+            }
+            RTM_DELLINK => {
+                let link = links::parse_link_message(&message.payload)?;
+                self.operational.remove(&link.index);
+                self.names.remove(&link.index);
+                Some(change(
+                    NetworkChangeKind::LinkRemoved,
+                    Some(link.name),
+                    None,
+                    None,
+                ))
+            }
+            RTM_NEWADDR | RTM_DELADDR => {
+                let (index, address) = parse_address_message(&message.payload)?;
+                let kind = if message.msg_type == RTM_NEWADDR {
+                    NetworkChangeKind::AddressAdded
+                } else {
+                    NetworkChangeKind::AddressRemoved
+                };
+                Some(change(
+                    kind,
+                    self.interface_name(index),
+                    Some(address),
+                    None,
+                ))
+            }
+            RTM_NEWROUTE | RTM_DELROUTE => {
+                let route = parse_route_message(&message.payload)?;
+                if route.table == RT_TABLE_LOCAL {
+                    return None;
+                }
+
+                if route.table == RT_TABLE_MAIN && route.prefix_len == 0 {
+                    let default_route = DefaultRoute {
+                        family: route.family,
+                        gateway: route.gateway.clone(),
+                        interface: route.interface,
+                        metric: route.metric,
+                    };
+                    if message.msg_type == RTM_NEWROUTE {
+                        self.default_routes.insert(default_route);
+                    } else {
+                        self.default_routes.remove(&default_route);
+                    }
+                }
+
+                Some(change(
+                    NetworkChangeKind::RouteChanged,
+                    route.interface.and_then(|i| self.interface_name(i)),
+                    Some(route.destination),
+                    route.gateway,
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    /// Compare the default route in use against an earlier snapshot. Comparing once
+    /// per batch hides the remove-then-add sequence of a route being replaced.
+    fn gateway_changes(&self, before: &HashSet<DefaultRoute>) -> Vec<PendingChange> {
+        let before = preferred_default_routes(before);
+        let after = preferred_default_routes(&self.default_routes);
+        let mut families: Vec<u8> = before.keys().chain(after.keys()).copied().collect();
+        families.sort_unstable();
+        families.dedup();
+
+        // A metric change alone leaves traffic on the same gateway
+        let next_hop = |route: Option<&&DefaultRoute>| {
+            route.map(|route| (route.gateway.clone(), route.interface))
+        };
+        families
+            .into_iter()
+            .filter(|family| next_hop(before.get(family)) != next_hop(after.get(family)))
+            .map(|family| {
+                let (gateway, interface) = next_hop(after.get(&family)).unwrap_or_default();
+                PendingChange {
+                    kind: NetworkChangeKind::DefaultGatewayChanged,
+                    interface: interface.and_then(|i| self.interface_name(i)),
+                    address: Some(default_destination(family).to_string()),
+                    gateway,
+                }
+            })
+            .collect()
+    }
+}
+
+/// The default route the kernel uses for each address family: the lowest metric
+#[cfg(target_os = "linux")]
+fn preferred_default_routes(routes: &HashSet<DefaultRoute>) -> HashMap<u8, &DefaultRoute> {
+    let mut preferred: HashMap<u8, &DefaultRoute> = HashMap::new();
+    for route in routes {
+        // Ties break on gateway and interface, so the choice doesn't depend on
+        // hash order
+        let key = |r: &DefaultRoute| (r.metric, r.gateway.clone(), r.interface);
+        match preferred.get(&route.family) {
+            Some(current) if key(current) <= key(route) => {}
+            _ => {
+                preferred.insert(route.family, route);
+            }
+        }
+    }
+    preferred
+}
+
+#[cfg(target_os = "linux")]
+fn default_destination(family: u8) -> &'static str {
+    if family as i32 == libc::AF_INET6 {
+        "::/0"
+    } else {
+        "0.0.0.0/0"
+    }
+}
+
+/// Address bytes of the given family as an IpAddr
+#[cfg(target_os = "linux")]
+fn ip_address(family: u8, bytes: &[u8]) -> Option<IpAddr> {
+    match family as i32 {
+        libc::AF_INET => Some(IpAddr::from(<[u8; 4]>::try_from(bytes).ok()?)),
+        libc::AF_INET6 => Some(IpAddr::from(<[u8; 16]>::try_from(bytes).ok()?)),
+        _ => None,
+    }
+}
+
+/// Parse an RTM_NEWADDR/RTM_DELADDR payload into (interface index, "address/prefix")
+#[cfg(target_os = "linux")]
+fn parse_address_message(payload: &[u8]) -> Option<(u32, String)> {
+    let header = payload.get(..IFADDRMSG_LEN)?;
+    let family = header[0];
+    let prefix_len = header[1];
+    let index = u32::from_ne_bytes(header[4..8].try_into().ok()?);
+
+    // For IPv4, IFA_ADDRESS is the peer on point-to-point links; IFA_LOCAL is ours
+    let attrs = Attributes::parse(&payload[IFADDRMSG_LEN..]);
+    let address = attrs.get(IFA_LOCAL).or_else(|| attrs.get(IFA_ADDRESS))?;
+    let address = ip_address(family, address)?;
+    Some((index, format!("{}/{}", address, prefix_len)))
+}
+
+/// Fields of an RTM_NEWROUTE/RTM_DELROUTE message
+#[cfg(target_os = "linux")]
+#[derive(Debug, PartialEq)]
+struct RouteMessage {
+    family: u8,
+    prefix_len: u8,
+    table: u32,
+    destination: String,
+    gateway: Option<String>,
+    interface: Option<u32>,
+    /// Route priority; the kernel omits it for metric 0
+    metric: u32,
+}
+
+#[cfg(target_os = "linux")]
+fn parse_route_message(payload: &[u8]) -> Option<RouteMessage> {
+    let header = payload.get(..RTMSG_LEN)?;
+    let family = header[0];
+    let prefix_len = header[1];
+    let attrs = Attributes::parse(&payload[RTMSG_LEN..]);
+
+    let destination = match attrs.get(RTA_DST).and_then(|d| ip_address(family, d)) {
+        Some(destination) => format!("{}/{}", destination, prefix_len),
+        None => default_destination(family).to_string(),
+    };
+
+    Some(RouteMessage {
+        family,
+        prefix_len,
+        // Tables above 255 only fit in RTA_TABLE
+        table: attrs.get_u32(RTA_TABLE).unwrap_or(header[4] as u32),
+        destination,
+        gateway: attrs
+            .get(RTA_GATEWAY)
+            .and_then(|g| ip_address(family, g))
+            .map(|g| g.to_string()),
+        interface: attrs.get_u32(RTA_OIF),
+        metric: attrs.get_u32(RTA_PRIORITY).unwrap_or(0),
+    })
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+    use crate::collectors::netlink::push_attribute;
+
+    fn message(msg_type: u16, payload: Vec<u8>) -> NetlinkMessage {
+        NetlinkMessage {
+            msg_type,
+            flags: 0,
+            seq: 0,
+            payload,
+        }
+    }
+
+    fn link(msg_type: u16, index: i32, name: &str, flags: u32) -> NetlinkMessage {
+        let mut payload = vec![0u8; 16];
+        payload[4..8].copy_from_slice(&index.to_ne_bytes());
+        payload[8..12].copy_from_slice(&flags.to_ne_bytes());
+        push_attribute(&mut payload, 3, format!("{}\0", name).as_bytes());
+        message(msg_type, payload)
+    }
+
+    fn address(msg_type: u16, index: u32, octets: [u8; 4], prefix_len: u8) -> NetlinkMessage {
+        let mut payload = vec![libc::AF_INET as u8, prefix_len, 0, 0];
+        payload.extend_from_slice(&index.to_ne_bytes());
+        push_attribute(&mut payload, IFA_ADDRESS, &octets);
+        push_attribute(&mut payload, IFA_LOCAL, &octets);
+        message(msg_type, payload)
+    }
+
+    fn default_route(msg_type: u16, gateway: [u8; 4], oif: u32, metric: u32) -> NetlinkMessage {
+        let mut payload = vec![libc::AF_INET as u8, 0, 0, 0, RT_TABLE_MAIN as u8, 3, 0, 1];
+        payload.extend_from_slice(&0u32.to_ne_bytes());
+        push_attribute(&mut payload, RTA_TABLE, &RT_TABLE_MAIN.to_ne_bytes());
+        push_attribute(&mut payload, RTA_GATEWAY, &gateway);
+        push_attribute(&mut payload, RTA_OIF, &oif.to_ne_bytes());
+        if metric != 0 {
+            push_attribute(&mut payload, RTA_PRIORITY, &metric.to_ne_bytes());
+        }
+        message(msg_type, payload)
+    }
+
+    const UP_WITH_CARRIER: u32 = 0x1 | 0x40 | 0x10000;
+
+    #[test]
+    fn test_link_transitions() {
+        let mut state = MonitorState::default();
+
+        let added = state.process(&link(RTM_NEWLINK, 3, "eth0", UP_WITH_CARRIER));
+        assert_eq!(added.unwrap().kind, NetworkChangeKind::LinkAdded);
+
+        // An attribute update without a state change is not reported
+        assert_eq!(
+            state.process(&link(RTM_NEWLINK, 3, "eth0", UP_WITH_CARRIER)),
+            None
+        );
+
+        // Cable unplugged: still administratively up, but no carrier
+        let down = state
+            .process(&link(RTM_NEWLINK, 3, "eth0", 0x1 | 0x1000))
+            .unwrap();
+        assert_eq!(down.kind, NetworkChangeKind::LinkDown);
+        assert_eq!(down.interface.as_deref(), Some("eth0"));
+
+        let up = state.process(&link(RTM_NEWLINK, 3, "eth0", UP_WITH_CARRIER));
+        assert_eq!(up.unwrap().kind, NetworkChangeKind::LinkUp);
+
+        let removed = state.process(&link(RTM_DELLINK, 3, "eth0", 0));
+        assert_eq!(removed.unwrap().kind, NetworkChangeKind::LinkRemoved);
+        assert!(state.names.is_empty());
+    }
+
+    #[test]
+    fn test_address_changes() {
+        let mut state = MonitorState::default();
+        state.process(&link(RTM_NEWLINK, 2, "wlan0", UP_WITH_CARRIER));
+
+        let added = state
+            .process(&address(RTM_NEWADDR, 2, [192, 168, 1, 20], 24))
+            .unwrap();
+        assert_eq!(added.kind, NetworkChangeKind::AddressAdded);
+        assert_eq!(added.interface.as_deref(), Some("wlan0"));
+        assert_eq!(added.address.as_deref(), Some("192.168.1.20/24"));
+
+        let removed = state
+            .process(&address(RTM_DELADDR, 2, [192, 168, 1, 20], 24))
+            .unwrap();
+        assert_eq!(removed.kind, NetworkChangeKind::AddressRemoved);
+    }
+
+    #[test]
+    fn test_default_gateway_switch() {
+        let mut state = MonitorState::default();
+        state.process(&link(RTM_NEWLINK, 2, "eth0", UP_WITH_CARRIER));
+        state.process(&link(RTM_NEWLINK, 5, "wg0", UP_WITH_CARRIER));
+        state.process(&default_route(RTM_NEWROUTE, [192, 168, 1, 1], 2, 0));
+
+        // Replacing the route with the same gateway is not a switch
+        let before = state.default_routes.clone();
+        let changes = state.process_all(&[
+            default_route(RTM_DELROUTE, [192, 168, 1, 1], 2, 0),
+            default_route(RTM_NEWROUTE, [192, 168, 1, 1], 2, 0),
+        ]);
+        assert!(changes
+            .iter()
+            .all(|c| c.kind == NetworkChangeKind::RouteChanged));
+        assert!(state.gateway_changes(&before).is_empty());
+
+        let before = state.default_routes.clone();
+        let changes = state.process_all(&[
+            default_route(RTM_DELROUTE, [192, 168, 1, 1], 2, 0),
+            default_route(RTM_NEWROUTE, [10, 8, 0, 1], 5, 0),
+        ]);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[1].address.as_deref(), Some("0.0.0.0/0"));
+
+        let switched = state.gateway_changes(&before);
+        assert_eq!(
+            switched,
+            vec![PendingChange {
+                kind: NetworkChangeKind::DefaultGatewayChanged,
+                interface: Some("wg0".to_string()),
+                address: Some("0.0.0.0/0".to_string()),
+                gateway: Some("10.8.0.1".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_lowest_metric_default_route() {
+        let mut state = MonitorState::default();
+        state.process(&link(RTM_NEWLINK, 2, "eth0", UP_WITH_CARRIER));
+        state.process(&link(RTM_NEWLINK, 3, "wlan0", UP_WITH_CARRIER));
+        state.process(&default_route(RTM_NEWROUTE, [192, 168, 1, 1], 2, 100));
+
+        // A second connection with a higher metric is a fallback, not a switch
+        let before = state.default_routes.clone();
+        state.process(&default_route(RTM_NEWROUTE, [192, 168, 8, 1], 3, 600));
+        assert!(state.gateway_changes(&before).is_empty());
+
+        // Losing the fallback leaves the route in use alone
+        let before = state.default_routes.clone();
+        state.process(&default_route(RTM_DELROUTE, [192, 168, 8, 1], 3, 600));
+        assert!(state.gateway_changes(&before).is_empty());
+        assert_eq!(state.default_routes.len(), 1);
+
+        // Unplugging Ethernet moves traffic to Wi-Fi
+        state.process(&default_route(RTM_NEWROUTE, [192, 168, 8, 1], 3, 600));
+        let before = state.default_routes.clone();
+        state.process(&default_route(RTM_DELROUTE, [192, 168, 1, 1], 2, 100));
+        assert_eq!(
+            state.gateway_changes(&before),
+            vec![PendingChange {
+                kind: NetworkChangeKind::DefaultGatewayChanged,
+                interface: Some("wlan0".to_string()),
+                address: Some("0.0.0.0/0".to_string()),
+                gateway: Some("192.168.8.1".to_string()),
+            }]
+        );
+
+        // Removing the last default route reports the gateway as gone
+        let before = state.default_routes.clone();
+        state.process(&default_route(RTM_DELROUTE, [192, 168, 8, 1], 3, 600));
+        let removed = state.gateway_changes(&before);
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].gateway, None);
+    }
+
+    #[test]
+    fn test_local_table_routes_are_ignored() {
+        let mut state = MonitorState::default();
+        let mut payload = vec![libc::AF_INET as u8, 32, 0, 0, RT_TABLE_LOCAL as u8, 0, 0, 2];
+        payload.extend_from_slice(&0u32.to_ne_bytes());
+        push_attribute(&mut payload, RTA_DST, &[127, 0, 0, 1]);
+        assert_eq!(state.process(&message(RTM_NEWROUTE, payload)), None);
+    }
+
+    #[test]
+    fn test_seed() {
+        // Seeding reads the live link table, which always has a loopback device
+        let state = MonitorState::seed();
+        if !state.names.is_empty() {
+            assert!(state.names.values().any(|name| name == "lo"));
+        }
+    }
+}
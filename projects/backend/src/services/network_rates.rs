@@ -0,0 +1,248 @@
+//! Network rate tracker
+//!
+//! Samples interface counters once per second on a background thread and keeps a
+//! per-adapter ring buffer of rates, so the UI can request history instead of diffing totals.
+
+use crate::collectors::network::InterfaceCounters;
+use crate::collectors::NetworkCollector;
+use crate::models::{AdapterHistory, AdapterRateSample};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use sysinfo::Networks;
+
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+/// One hour of per-second samples
+const HISTORY_CAPACITY: usize = 3600;
+/// Longer windows are averaged down to at most this many points
+const MAX_HISTORY_POINTS: usize = 120;
+
+/// Rate samples and the last counter snapshot for one adapter
+#[derive(Default)]
+struct AdapterRates {
+    previous: Option<(InterfaceCounters, u64)>,
+    samples: VecDeque<AdapterRateSample>,
+}
+
+/// Tracks per-second network rates for every adapter (held in Tauri managed state)
+pub struct NetworkRateTracker {
+    adapters: Arc<Mutex<HashMap<String, AdapterRates>>>,
+}
+
+impl NetworkRateTracker {
+    /// Create an empty tracker. Call `start` to begin sampling.
+    pub fn new() -> Self {
+        Self {
+            adapters: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Start the background sampling thread
+    pub fn start(&self) {
+        let adapters = Arc::clone(&self.adapters);
+
+        let result = std::thread::Builder::new()
+            .name("network-rates".to_string())
+            .spawn(move || {
+                let mut networks = Networks::new_with_refreshed_list();
+                loop {
+                    // Refreshing the list picks up adapters that appear while running
+                    networks.refresh_list();
+                    let counters = NetworkCollector::get_interface_counters(&networks);
+                    Self::record_into(&adapters, counters, now_millis());
+                    std::thread::sleep(SAMPLE_INTERVAL);
+                }
+            });
+
+        if let Err(e) = result {
+            log::error!("Failed to start network rate tracker: {}", e);
+        }
+    }
+
+    /// Record a counter snapshot taken at `timestamp` (milliseconds since the Unix epoch)
+    pub fn record(&self, counters: HashMap<String, InterfaceCounters>, timestamp: u64) {
+        Self::record_into(&self.adapters, counters, timestamp);
+    }
+
+    fn record_into(
+        adapters: &Mutex<HashMap<String, AdapterRates>>,
+        counters: HashMap<String, InterfaceCounters>,
+        timestamp: u64,
+    ) {
+        let mut adapters = adapters.lock().unwrap();
+
+        // Forget adapters that have been removed
+        adapters.retain(|name, _| counters.contains_key(name));
+
+        for (name, current) in counters {
+            let rates = adapters.entry(name).or_default();
+
+            if let Some((previous, previous_timestamp)) = rates.previous {
+                let elapsed = timestamp.saturating_sub(previous_timestamp) as f64 / 1000.0;
+                if elapsed > 0.0 {
+                    if let Some(sample) = Self::rate_sample(&previous, &current, elapsed, timestamp)
+                    {
+                        rates.samples.push_back(sample);
+                        if rates.samples.len() > HISTORY_CAPACITY {
+                            rates.samples.pop_front();
+                        }
+                    }
+                }
+            }
+
+            rates.previous = Some((current, timestamp));
+        }
+    }
+
+    /// Compute per-second rates between two snapshots.
+    /// Returns None when a counter went backwards (interface reset or re-created).
+    fn rate_sample(
+        previous: &InterfaceCounters,
+        current: &InterfaceCounters,
+        elapsed_secs: f64,
+        timestamp: u64,
+    ) -> Option<AdapterRateSample> {
+        let rate = |now: u64, before: u64| -> Option<f64> {
+            now.checked_sub(before).map(|d| d as f64 / elapsed_secs)
+        };
+
+        Some(AdapterRateSample {
+            timestamp,
+            bytes_sent_per_sec: rate(current.bytes_sent, previous.bytes_sent)?,
+            bytes_received_per_sec: rate(current.bytes_received, previous.bytes_received)?,
+            packets_sent_per_sec: rate(current.packets_sent, previous.packets_sent)?,
+            packets_received_per_sec: rate(current.packets_received, previous.packets_received)?,
+            errors_per_sec: rate(current.errors, previous.errors)?,
+            drops_per_sec: rate(current.drops, previous.drops)?,
+        })
+    }
+
+    /// Get the rate history of an adapter over the last `window_secs` seconds.
+    /// Returns None for adapters the tracker has not seen.
+    pub fn history(&self, adapter_id: &str, window_secs: u64) -> Option<AdapterHistory> {
+        let adapters = self.adapters.lock().unwrap();
+        let rates = adapters.get(adapter_id)?;
+
+        let window_secs = window_secs.clamp(1, HISTORY_CAPACITY as u64);
+        let newest = rates.samples.back().map(|s| s.timestamp).unwrap_or(0);
+        let cutoff = newest.saturating_sub(window_secs * 1000);
+        let recent: Vec<&AdapterRateSample> = rates
+            .samples
+            .iter()
+            .filter(|s| s.timestamp > cutoff)
+            .collect();
+
+        let bucket_size = recent.len().div_ceil(MAX_HISTORY_POINTS).max(1);
+        let samples = recent.chunks(bucket_size).map(Self::average).collect();
+
+        Some(AdapterHistory {
+            adapter_id: adapter_id.to_string(),
+            window_secs,
+            interval_secs: bucket_size as u64 * SAMPLE_INTERVAL.as_secs(),
+            samples,
+        })
+    }
+
+    /// Average a bucket of samples, stamped with the end of the bucket
+    fn average(bucket: &[&AdapterRateSample]) -> AdapterRateSample {
+        let count = bucket.len() as f64;
+        let mean =
+            |f: fn(&AdapterRateSample) -> f64| bucket.iter().map(|s| f(s)).sum::<f64>() / count;
+
+        AdapterRateSample {
+            timestamp: bucket.last().map(|s| s.timestamp).unwrap_or(0),
+            bytes_sent_per_sec: mean(|s| s.bytes_sent_per_sec),
+            bytes_received_per_sec: mean(|s| s.bytes_received_per_sec),
+            packets_sent_per_sec: mean(|s| s.packets_sent_per_sec),
+            packets_received_per_sec: mean(|s| s.packets_received_per_sec),
+            errors_per_sec: mean(|s| s.errors_per_sec),
+            drops_per_sec: mean(|s| s.drops_per_sec),
+        }
+    }
+}
+
+impl Default for NetworkRateTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counters(name: &str, bytes: u64, drops: u64) -> HashMap<String, InterfaceCounters> {
+        let mut map = HashMap::new();
+        map.insert(
+            name.to_string(),
+            InterfaceCounters {
+                bytes_sent: bytes,
+                bytes_received: bytes * 2,
+                packets_sent: bytes / 100,
+                packets_received: bytes / 50,
+                errors: 0,
+                drops,
+            },
+        );
+        map
+    }
+
+    #[test]
+    fn test_rates_from_counters() {
+        let tracker = NetworkRateTracker::new();
+        tracker.record(counters("eth0", 1_000, 0), 10_000);
+        tracker.record(counters("eth0", 3_000, 4), 12_000);
+
+        let history = tracker.history("eth0", 60).unwrap();
+        assert_eq!(history.samples.len(), 1);
+        let sample = &history.samples[0];
+        assert_eq!(sample.timestamp, 12_000);
+        assert_eq!(sample.bytes_sent_per_sec, 1_000.0);
+        assert_eq!(sample.bytes_received_per_sec, 2_000.0);
+        assert_eq!(sample.drops_per_sec, 2.0);
+
+        assert!(tracker.history("wlan0", 60).is_none());
+    }
+
+    #[test]
+    fn test_counter_reset_and_removed_adapter() {
+        let tracker = NetworkRateTracker::new();
+        tracker.record(counters("eth0", 5_000, 0), 1_000);
+        // Counters went backwards: no sample, but tracking resumes from the new baseline
+        tracker.record(counters("eth0", 100, 0), 2_000);
+        tracker.record(counters("eth0", 600, 0), 3_000);
+
+        let history = tracker.history("eth0", 60).unwrap();
+        assert_eq!(history.samples.len(), 1);
+        assert_eq!(history.samples[0].bytes_sent_per_sec, 500.0);
+
+        tracker.record(counters("eth1", 0, 0), 4_000);
+        assert!(tracker.history("eth0", 60).is_none());
+    }
+
+    #[test]
+    fn test_history_window_and_downsampling() {
+        let tracker = NetworkRateTracker::new();
+        for second in 0..=600u64 {
+            tracker.record(counters("eth0", second * second * 10, 0), second * 1000);
+        }
+
+        // 600 samples over a 10 minute window are averaged into 5 second buckets
+        let history = tracker.history("eth0", 600).unwrap();
+        assert_eq!(history.samples.len(), MAX_HISTORY_POINTS);
+        assert_eq!(history.interval_secs, 5);
+        assert_eq!(history.samples.last().unwrap().timestamp, 600_000);
+
+        // A short window returns raw per-second samples
+        let history = tracker.history("eth0", 30).unwrap();
+        assert_eq!(history.samples.len(), 30);
+        assert_eq!(history.interval_secs, 1);
+    }
+}
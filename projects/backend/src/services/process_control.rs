@@ -0,0 +1,645 @@
+//! Process control
+//!
+//! Signals, graceful termination, suspend/resume, kill-tree, priority and CPU affinity.
+//! Every operation refuses to touch protected processes (init, kernel threads and
+//! Syslens itself) and reports failures as a `ProcessControlError` the frontend can
+//! tell apart.
+
+use crate::models::{ProcessControlError, ProcessTermination};
+use crate::state::thread_groups;
+use std::collections::HashMap;
+use std::time::Duration;
+use sysinfo::System;
+
+#[cfg(unix)]
+use std::io;
+#[cfg(unix)]
+use std::time::Instant;
+
+/// How often a terminating process is checked for exit
+#[cfg(unix)]
+const EXIT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+/// Time a process gets to exit after SIGKILL before it is reported anyway
+#[cfg(unix)]
+const KILL_GRACE: Duration = Duration::from_secs(1);
+/// Highest real-time signal number on Linux
+#[cfg(unix)]
+const MAX_SIGNAL: i32 = 64;
+/// /proc/<pid>/stat flag of kernel threads (include/linux/sched.h)
+#[cfg(target_os = "linux")]
+const PF_KTHREAD: u64 = 0x0020_0000;
+
+/// Sends signals to processes and changes their scheduling
+pub struct ProcessController;
+
+impl ProcessController {
+    /// Default time a process gets to exit after SIGTERM before it is killed
+    pub const DEFAULT_TERMINATE_TIMEOUT: Duration = Duration::from_secs(5);
+
+    /// Send any signal (1-64) to a process
+    pub fn send_signal(pid: u32, signal: i32) -> Result<(), ProcessControlError> {
+        #[cfg(unix)]
+        {
+            if !(1..=MAX_SIGNAL).contains(&signal) {
+                return Err(ProcessControlError::InvalidArgument {
+                    message: format!("Signal {} is out of range 1-{}", signal, MAX_SIGNAL),
+                });
+            }
+            Self::check_protected(pid)?;
+            Self::signal(pid, signal)
+        }
+
+        #[cfg(not(unix))]
+        {
+            let _ = (pid, signal);
+            Err(ProcessControlError::Unsupported)
+        }
+    }
+
+    /// Ask a process to exit with SIGTERM and kill it with SIGKILL if it is still
+    /// running after `timeout`. Blocks until the process is gone.
+    pub fn terminate(
+        pid: u32,
+        timeout: Duration,
+    ) -> Result<ProcessTermination, ProcessControlError> {
+        #[cfg(unix)]
+        {
+            Self::check_protected(pid)?;
+            Self::signal(pid, libc::SIGTERM)?;
+            if Self::wait_for_exit(pid, timeout) {
+                return Ok(ProcessTermination::Graceful);
+            }
+
+            log::info!("Process {} ignored SIGTERM for {:?}; killing", pid, timeout);
+            match Self::signal(pid, libc::SIGKILL) {
+                // Exited between the last check and the kill
+                Err(ProcessControlError::NotFound { .. }) => Ok(ProcessTermination::Graceful),
+                Err(e) => Err(e),
+                Ok(()) => {
+                    if !Self::wait_for_exit(pid, KILL_GRACE) {
+                        log::warn!("Process {} is still present after SIGKILL", pid);
+                    }
+                    Ok(ProcessTermination::Forced)
+                }
+            }
+        }
+
+        #[cfg(not(unix))]
+        {
+            let _ = (pid, timeout);
+            Err(ProcessControlError::Unsupported)
+        }
+    }
+
+    /// Stop a process (SIGSTOP) until it is resumed
+    pub fn suspend(pid: u32) -> Result<(), ProcessControlError> {
+        #[cfg(unix)]
+        {
+            Self::check_protected(pid)?;
+            Self::signal(pid, libc::SIGSTOP)
+        }
+
+        #[cfg(not(unix))]
+        {
+            let _ = pid;
+            Err(ProcessControlError::Unsupported)
+        }
+    }
+
+    /// Continue a suspended process (SIGCONT)
+    pub fn resume(pid: u32) -> Result<(), ProcessControlError> {
+        #[cfg(unix)]
+        {
+            Self::check_protected(pid)?;
+            Self::signal(pid, libc::SIGCONT)
+        }
+
+        #[cfg(not(unix))]
+        {
+            let _ = pid;
+            Err(ProcessControlError::Unsupported)
+        }
+    }
+
+    /// Kill a process and all of its descendants. `tree` is `pid` followed by its
+    /// descendants as resolved by `process_tree`, so the caller can vet every member
+    /// before anything is signalled. The whole tree is stopped first so nothing can
+    /// fork replacements while it is being killed. Returns the PIDs that were killed,
+    /// deepest first.
+    pub fn kill_tree(pid: u32, tree: &[u32]) -> Result<Vec<u32>, ProcessControlError> {
+        #[cfg(unix)]
+        {
+            if tree.first() != Some(&pid) {
+                return Err(ProcessControlError::NotFound { pid });
+            }
+            // Refuse the whole operation rather than leave a half-killed tree
+            for &member in tree {
+                Self::check_protected(member)?;
+            }
+
+            Self::signal(pid, libc::SIGSTOP)?;
+            for &member in &tree[1..] {
+                let _ = Self::signal(member, libc::SIGSTOP);
+            }
+
+            let mut killed = Vec::new();
+            for &member in tree.iter().rev() {
+                match Self::signal(member, libc::SIGKILL) {
+                    Ok(()) => killed.push(member),
+                    Err(ProcessControlError::NotFound { .. }) => {}
+                    Err(e) if member == pid => return Err(e),
+                    Err(e) => log::warn!("Failed to kill process {}: {}", member, e),
+                }
+            }
+            Ok(killed)
+        }
+
+        #[cfg(not(unix))]
+        {
+            let _ = (pid, tree);
+            Err(ProcessControlError::Unsupported)
+        }
+    }
+
+    /// Set the nice value (-20 highest priority to 19 lowest). Raising priority
+    /// requires root or CAP_SYS_NICE.
+    pub fn set_priority(pid: u32, nice: i32) -> Result<(), ProcessControlError> {
+        #[cfg(unix)]
+        {
+            if !(-20..=19).contains(&nice) {
+                return Err(ProcessControlError::InvalidArgument {
+                    message: format!("Nice value {} is out of range -20 to 19", nice),
+                });
+            }
+            Self::check_protected(pid)?;
+
+            let id = Self::pid_t(pid)? as libc::id_t;
+            if unsafe { libc::setpriority(libc::PRIO_PROCESS, id, nice) } != 0 {
+                return Err(Self::error(pid, io::Error::last_os_error()));
+            }
+            Ok(())
+        }
+
+        #[cfg(not(unix))]
+        {
+            let _ = (pid, nice);
+            Err(ProcessControlError::Unsupported)
+        }
+    }
+
+    /// Get the CPUs a process may run on
+    pub fn get_affinity(pid: u32) -> Result<Vec<u32>, ProcessControlError> {
+        #[cfg(target_os = "linux")]
+        {
+            let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+            let result = unsafe {
+                libc::sched_getaffinity(
+                    Self::pid_t(pid)?,
+                    std::mem::size_of::<libc::cpu_set_t>(),
+                    &mut set,
+                )
+            };
+            if result != 0 {
+                return Err(Self::error(pid, io::Error::last_os_error()));
+            }
+
+            Ok((0..libc::CPU_SETSIZE as usize)
+                .filter(|&cpu| unsafe { libc::CPU_ISSET(cpu, &set) })
+                .map(|cpu| cpu as u32)
+                .collect())
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = pid;
+            Err(ProcessControlError::Unsupported)
+        }
+    }
+
+    /// Restrict a process to the given CPUs and return the affinity now in effect
+    pub fn set_affinity(pid: u32, cpus: &[u32]) -> Result<Vec<u32>, ProcessControlError> {
+        #[cfg(target_os = "linux")]
+        {
+            if cpus.is_empty() {
+                return Err(ProcessControlError::InvalidArgument {
+                    message: "At least one CPU is required".to_string(),
+                });
+            }
+            if let Some(cpu) = cpus.iter().find(|&&cpu| cpu >= libc::CPU_SETSIZE as u32) {
+                return Err(ProcessControlError::InvalidArgument {
+                    message: format!("CPU {} does not exist", cpu),
+                });
+            }
+            Self::check_protected(pid)?;
+
+            let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+            for &cpu in cpus {
+                unsafe { libc::CPU_SET(cpu as usize, &mut set) };
+            }
+            let result = unsafe {
+                libc::sched_setaffinity(
+                    Self::pid_t(pid)?,
+                    std::mem::size_of::<libc::cpu_set_t>(),
+                    &set,
+                )
+            };
+            if result != 0 {
+                // EINVAL: none of the CPUs is online or allowed by the cpuset
+                return Err(Self::error(pid, io::Error::last_os_error()));
+            }
+            Self::get_affinity(pid)
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = (pid, cpus);
+            Err(ProcessControlError::Unsupported)
+        }
+    }
+
+    /// Why a process must not be controlled, if it is protected
+    pub fn protection_reason(pid: u32) -> Option<String> {
+        if pid == 0 {
+            // kill(0) would signal our own process group
+            return Some("PID 0 is not a process".to_string());
+        }
+        if pid == 1 {
+            return Some("System init process".to_string());
+        }
+        if pid == std::process::id() {
+            return Some("Syslens itself".to_string());
+        }
+
+        #[cfg(target_os = "linux")]
+        {
+            let is_kernel_thread = std::fs::read_to_string(format!("/proc/{}/stat", pid))
+                .ok()
+                .and_then(|stat| Self::stat_flags(&stat))
+                .is_some_and(|flags| flags & PF_KTHREAD != 0);
+            if is_kernel_thread {
+                return Some("Kernel thread".to_string());
+            }
+        }
+
+        None
+    }
+
+    #[cfg_attr(not(unix), allow(dead_code))]
+    fn check_protected(pid: u32) -> Result<(), ProcessControlError> {
+        match Self::protection_reason(pid) {
+            Some(reason) => Err(ProcessControlError::Protected { pid, reason }),
+            None => Ok(()),
+        }
+    }
+
+    /// The flags field (9) of /proc/<pid>/stat
+    #[cfg(target_os = "linux")]
+    fn stat_flags(stat: &str) -> Option<u64> {
+        // The command name may contain spaces, so count fields after its closing ')'
+        stat[stat.rfind(')')? + 1..]
+            .split_whitespace()
+            .nth(6)?
+            .parse()
+            .ok()
+    }
+
+    /// PID 0 means the caller and PIDs above i32::MAX turn into negative pid_t values,
+    /// which address process groups
+    #[cfg(unix)]
+    fn pid_t(pid: u32) -> Result<libc::pid_t, ProcessControlError> {
+        match libc::pid_t::try_from(pid) {
+            Ok(id) if id > 0 => Ok(id),
+            _ => Err(ProcessControlError::NotFound { pid }),
+        }
+    }
+
+    #[cfg(unix)]
+    fn signal(pid: u32, signal: i32) -> Result<(), ProcessControlError> {
+        if unsafe { libc::kill(Self::pid_t(pid)?, signal) } != 0 {
+            return Err(Self::error(pid, io::Error::last_os_error()));
+        }
+        Ok(())
+    }
+
+    /// Map an OS error from a syscall on `pid` to a control error
+    #[cfg(unix)]
+    fn error(pid: u32, error: io::Error) -> ProcessControlError {
+        match error.raw_os_error() {
+            Some(libc::ESRCH) => ProcessControlError::NotFound { pid },
+            Some(libc::EPERM) | Some(libc::EACCES) => ProcessControlError::PermissionDenied { pid },
+            Some(libc::EINVAL) => ProcessControlError::InvalidArgument {
+                message: error.to_string(),
+            },
+            _ => ProcessControlError::Failed {
+                pid,
+                message: error.to_string(),
+            },
+        }
+    }
+
+    /// Wait until the process is gone or a zombie, up to `timeout`
+    #[cfg(unix)]
+    fn wait_for_exit(pid: u32, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if Self::has_exited(pid) {
+                return true;
+            }
+            if Instant::now() >= deadline {
+                return false;
+            }
+            std::thread::sleep(EXIT_POLL_INTERVAL);
+        }
+    }
+
+    #[cfg(unix)]
+    fn has_exited(pid: u32) -> bool {
+        #[cfg(target_os = "linux")]
+        {
+            // A zombie has exited; only its parent can remove it
+            match std::fs::read_to_string(format!("/proc/{}/stat", pid)) {
+                Ok(stat) => stat
+                    .rfind(')')
+                    .and_then(|end| stat[end + 1..].split_whitespace().next())
+                    .is_some_and(|state| state == "Z" || state == "X"),
+                Err(_) => true,
+            }
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            matches!(
+                Self::signal(pid, 0),
+                Err(ProcessControlError::NotFound { .. })
+            )
+        }
+    }
+
+    /// `pid` followed by all of its descendants, parents before children.
+    /// Empty when the process doesn't exist.
+    pub fn process_tree(pid: u32) -> Vec<u32> {
+        let mut sys = System::new();
+        sys.refresh_processes(sysinfo::ProcessesToUpdate::All);
+
+        let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+        for (child, process) in thread_groups(&sys) {
+            if let Some(parent) = process.parent() {
+                children
+                    .entry(parent.as_u32())
+                    .or_default()
+                    .push(child.as_u32());
+            }
+        }
+        if sys.process(sysinfo::Pid::from_u32(pid)).is_none() {
+            return Vec::new();
+        }
+
+        Self::descendants(pid, &children)
+    }
+
+    /// Breadth-first walk of a parent -> children map starting at `pid`
+    fn descendants(pid: u32, children: &HashMap<u32, Vec<u32>>) -> Vec<u32> {
+        let mut tree = vec![pid];
+        let mut index = 0;
+        while let Some(&current) = tree.get(index) {
+            let mut direct = children.get(&current).cloned().unwrap_or_default();
+            direct.sort_unstable();
+            for child in direct {
+                // Guard against cycles from PID reuse
+                if !tree.contains(&child) {
+                    tree.push(child);
+                }
+            }
+            index += 1;
+        }
+        tree
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+    use std::process::{Child, Command};
+
+    fn spawn(script: &str) -> Child {
+        Command::new("sh").arg("-c").arg(script).spawn().unwrap()
+    }
+
+    /// Poll until `condition` holds, failing the test after a few seconds
+    fn wait_until(what: &str, mut condition: impl FnMut() -> bool) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !condition() {
+            assert!(Instant::now() < deadline, "Timed out waiting for {}", what);
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    /// Whether a process ignores `signal`, from the SigIgn mask in /proc/<pid>/status
+    fn ignores(pid: u32, signal: i32) -> bool {
+        std::fs::read_to_string(format!("/proc/{}/status", pid))
+            .ok()
+            .and_then(|status| {
+                let mask = status
+                    .lines()
+                    .find_map(|line| line.strip_prefix("SigIgn:"))?;
+                u64::from_str_radix(mask.trim(), 16).ok()
+            })
+            .is_some_and(|mask| mask & (1 << (signal - 1)) != 0)
+    }
+
+    /// kthreadd, the parent of all kernel threads. Usually PID 2, but not visible in
+    /// a PID namespace such as a container.
+    fn kthreadd() -> Option<u32> {
+        std::fs::read_dir("/proc")
+            .ok()?
+            .flatten()
+            .filter_map(|entry| entry.file_name().to_str()?.parse().ok())
+            .find(|pid| {
+                std::fs::read_to_string(format!("/proc/{}/comm", pid))
+                    .is_ok_and(|comm| comm.trim_end() == "kthreadd")
+            })
+    }
+
+    fn state(pid: u32) -> String {
+        let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid)).unwrap();
+        let end = stat.rfind(')').unwrap();
+        stat[end + 1..]
+            .split_whitespace()
+            .next()
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn test_protected_processes() {
+        for pid in [0, 1, std::process::id()] {
+            assert!(matches!(
+                ProcessController::send_signal(pid, libc::SIGTERM),
+                Err(ProcessControlError::Protected { .. })
+            ));
+        }
+        if let Some(pid) = kthreadd() {
+            assert_eq!(
+                ProcessController::protection_reason(pid).as_deref(),
+                Some("Kernel thread")
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        assert!(matches!(
+            ProcessController::send_signal(12345, 0),
+            Err(ProcessControlError::InvalidArgument { .. })
+        ));
+        assert!(matches!(
+            ProcessController::set_priority(12345, 20),
+            Err(ProcessControlError::InvalidArgument { .. })
+        ));
+        assert!(matches!(
+            ProcessController::set_affinity(12345, &[]),
+            Err(ProcessControlError::InvalidArgument { .. })
+        ));
+        // Would be a negative pid_t, i.e. a process group
+        assert_eq!(
+            ProcessController::suspend(u32::MAX),
+            Err(ProcessControlError::NotFound { pid: u32::MAX })
+        );
+    }
+
+    #[test]
+    fn test_error_mapping() {
+        let error = |code| ProcessController::error(42, io::Error::from_raw_os_error(code));
+        assert_eq!(
+            error(libc::ESRCH),
+            ProcessControlError::NotFound { pid: 42 }
+        );
+        assert_eq!(
+            error(libc::EPERM),
+            ProcessControlError::PermissionDenied { pid: 42 }
+        );
+        assert!(matches!(
+            error(libc::EIO),
+            ProcessControlError::Failed { pid: 42, .. }
+        ));
+
+        let json = serde_json::to_string(&ProcessControlError::Protected {
+            pid: 1,
+            reason: "System init process".to_string(),
+        })
+        .unwrap();
+        assert_eq!(
+            json,
+            r#"{"kind":"protected","pid":1,"reason":"System init process"}"#
+        );
+    }
+
+    #[test]
+    fn test_descendants() {
+        let children: HashMap<u32, Vec<u32>> = [(10, vec![12, 11]), (11, vec![13]), (13, vec![10])]
+            .into_iter()
+            .collect();
+        assert_eq!(
+            ProcessController::descendants(10, &children),
+            vec![10, 11, 12, 13]
+        );
+    }
+
+    #[test]
+    fn test_suspend_resume_and_terminate() {
+        let mut child = spawn("sleep 30");
+        let pid = child.id();
+
+        ProcessController::suspend(pid).unwrap();
+        wait_until("the process to stop", || state(pid) == "T");
+        ProcessController::resume(pid).unwrap();
+        wait_until("the process to continue", || state(pid) != "T");
+
+        ProcessController::set_priority(pid, 10).unwrap();
+        let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid)).unwrap();
+        let nice = stat[stat.rfind(')').unwrap() + 1..]
+            .split_whitespace()
+            .nth(16);
+        assert_eq!(nice, Some("10"));
+
+        assert_eq!(
+            ProcessController::terminate(pid, Duration::from_secs(2)),
+            Ok(ProcessTermination::Graceful)
+        );
+        child.wait().unwrap();
+        assert_eq!(
+            ProcessController::send_signal(pid, libc::SIGTERM),
+            Err(ProcessControlError::NotFound { pid })
+        );
+    }
+
+    #[test]
+    fn test_terminate_escalates_to_kill() {
+        let mut child = spawn("trap '' TERM; while true; do sleep 0.05; done");
+        let pid = child.id();
+        wait_until("the shell to ignore SIGTERM", || {
+            ignores(pid, libc::SIGTERM)
+        });
+
+        assert_eq!(
+            ProcessController::terminate(pid, Duration::from_millis(300)),
+            Ok(ProcessTermination::Forced)
+        );
+        child.wait().unwrap();
+    }
+
+    #[test]
+    fn test_kill_tree() {
+        let mut child = spawn("sleep 30 & sleep 30 & wait");
+        let pid = child.id();
+        wait_until("the shell to start both children", || {
+            ProcessController::process_tree(pid).len() == 3
+        });
+        let tree = ProcessController::process_tree(pid);
+
+        let killed = ProcessController::kill_tree(pid, &tree).unwrap();
+        assert_eq!(killed.len(), 3);
+        assert_eq!(killed.last(), Some(&pid));
+        child.wait().unwrap();
+
+        wait_until("the children to be reaped", || {
+            tree[1..]
+                .iter()
+                .all(|member| ProcessController::has_exited(*member))
+        });
+    }
+
+    #[test]
+    fn test_process_tree_skips_threads() {
+        let (tid_tx, tid_rx) = std::sync::mpsc::channel();
+        let (done_tx, done_rx) = std::sync::mpsc::channel::<()>();
+        let thread = std::thread::spawn(move || {
+            tid_tx
+                .send(unsafe { libc::syscall(libc::SYS_gettid) } as u32)
+                .unwrap();
+            let _ = done_rx.recv();
+        });
+        let tid = tid_rx.recv().unwrap();
+
+        let tree = ProcessController::process_tree(std::process::id());
+        done_tx.send(()).unwrap();
+        thread.join().unwrap();
+        assert!(!tree.contains(&tid));
+    }
+
+    #[test]
+    fn test_affinity() {
+        let mut child = spawn("sleep 30");
+        let pid = child.id();
+
+        let cpus = ProcessController::get_affinity(pid).unwrap();
+        assert!(!cpus.is_empty());
+        assert_eq!(
+            ProcessController::set_affinity(pid, &cpus[..1]),
+            Ok(vec![cpus[0]])
+        );
+
+        child.kill().unwrap();
+        child.wait().unwrap();
+    }
+}
@@ -0,0 +1,1023 @@
+//! Process lifecycle timeline
+//!
+//! A background watcher records process starts, execs and exits. Where Syslens may
+//! subscribe to the kernel proc connector (CAP_NET_ADMIN), events arrive as they
+//! happen, so even processes that live for a few milliseconds are recorded.
+//! Otherwise, and to catch anything the connector dropped, they come from diffing
+//! `SysInfoState` snapshots. Events are kept in an in-memory ring and, when enabled,
+//! appended to a log in the app data directory.
+
+use crate::models::{ProcessEvent, ProcessEventKind, ProcessEventSource};
+use crate::state::{thread_groups, SysInfoState};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use sysinfo::ProcessStatus;
+use tauri::{AppHandle, Manager};
+
+#[cfg(target_os = "linux")]
+use crate::collectors::netlink::{NetlinkSocket, NLMSG_DONE};
+#[cfg(target_os = "linux")]
+use std::str::FromStr;
+#[cfg(target_os = "linux")]
+use sysinfo::{Uid, Users};
+
+/// Events kept in memory
+const RING_CAPACITY: usize = 10_000;
+/// Events returned by a query without an explicit limit
+const DEFAULT_QUERY_LIMIT: usize = 1_000;
+/// The on-disk log is rotated to `process-events.log.1` beyond this size
+const MAX_LOG_SIZE: u64 = 16 * 1024 * 1024;
+const LOG_FILE: &str = "process-events.log";
+const ROTATED_LOG_FILE: &str = "process-events.log.1";
+const SETTINGS_FILE: &str = "process-events.json";
+/// Interval between snapshots, which also update peak CPU and memory
+const SNAPSHOT_INTERVAL: Duration = Duration::from_secs(2);
+/// Interval between snapshots while the proc connector reports starts and exits.
+/// They then only catch dropped events and update peaks, and a full process
+/// refresh every 2 seconds would cost more than the connector saves.
+#[cfg(target_os = "linux")]
+const CONNECTOR_SNAPSHOT_INTERVAL: Duration = Duration::from_secs(30);
+
+// Proc connector (linux/connector.h, linux/cn_proc.h)
+#[cfg(target_os = "linux")]
+const CN_IDX_PROC: u32 = 1;
+#[cfg(target_os = "linux")]
+const CN_VAL_PROC: u32 = 1;
+#[cfg(target_os = "linux")]
+const PROC_CN_MCAST_LISTEN: u32 = 1;
+#[cfg(target_os = "linux")]
+const PROC_EVENT_FORK: u32 = 0x0000_0001;
+#[cfg(target_os = "linux")]
+const PROC_EVENT_EXEC: u32 = 0x0000_0002;
+#[cfg(target_os = "linux")]
+const PROC_EVENT_EXIT: u32 = 0x8000_0000;
+/// struct cn_msg without its data
+#[cfg(target_os = "linux")]
+const CN_MSG_LEN: usize = 20;
+/// what, cpu and timestamp_ns of struct proc_event, before the event data
+#[cfg(target_os = "linux")]
+const PROC_EVENT_HEADER_LEN: usize = 16;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct LogSettings {
+    persist: bool,
+}
+
+struct Ring {
+    events: VecDeque<ProcessEvent>,
+    /// Older events have been dropped to make room
+    truncated: bool,
+    /// Sequence of the next recorded event
+    next_sequence: u64,
+}
+
+/// In-memory ring of lifecycle events with an optional on-disk log (held in Tauri
+/// managed state)
+pub struct ProcessEventLog {
+    ring: Mutex<Ring>,
+    /// None when the data directory is unavailable; events then stay in memory
+    data_dir: Option<PathBuf>,
+    persist: AtomicBool,
+}
+
+impl ProcessEventLog {
+    /// Create a log in the app data directory, restoring whether it persists events
+    pub fn new() -> Self {
+        Self::with_data_dir(dirs::data_dir().map(|p| p.join("Syslens")))
+    }
+
+    pub(crate) fn with_data_dir(data_dir: Option<PathBuf>) -> Self {
+        let persist = data_dir
+            .as_ref()
+            .and_then(|dir| std::fs::read_to_string(dir.join(SETTINGS_FILE)).ok())
+            .and_then(|content| serde_json::from_str::<LogSettings>(&content).ok())
+            .is_some_and(|settings| settings.persist);
+
+        // Continue numbering after the on-disk log, so that its events and new ones
+        // page as one sequence
+        let next_sequence = data_dir
+            .as_deref()
+            .and_then(Self::last_logged_sequence)
+            .map_or(0, |sequence| sequence + 1);
+
+        Self {
+            ring: Mutex::new(Ring {
+                events: VecDeque::new(),
+                truncated: false,
+                next_sequence,
+            }),
+            data_dir,
+            persist: AtomicBool::new(persist),
+        }
+    }
+
+    /// Whether events are also appended to the on-disk log
+    pub fn is_persistent(&self) -> bool {
+        self.persist.load(Ordering::Relaxed)
+    }
+
+    /// Turn the on-disk log on or off; the choice is kept across restarts
+    pub fn set_persistent(&self, persist: bool) -> Result<()> {
+        let data_dir = self
+            .data_dir
+            .as_ref()
+            .context("Failed to get app data directory")?;
+        std::fs::create_dir_all(data_dir).context("Failed to create data directory")?;
+
+        let content = serde_json::to_string_pretty(&LogSettings { persist })
+            .context("Failed to serialize process event settings")?;
+        std::fs::write(data_dir.join(SETTINGS_FILE), content)
+            .context("Failed to write process event settings")?;
+
+        self.persist.store(persist, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Add events, oldest first, numbering them in that order
+    pub fn record(&self, mut events: Vec<ProcessEvent>) {
+        if events.is_empty() {
+            return;
+        }
+        let mut ring = self.ring.lock().unwrap();
+        for event in &mut events {
+            event.sequence = ring.next_sequence;
+            ring.next_sequence += 1;
+        }
+
+        if self.is_persistent() {
+            if let Err(e) = self.append(&events) {
+                log::error!("Failed to write process events: {:#}", e);
+            }
+        }
+
+        for event in events {
+            if ring.events.len() >= RING_CAPACITY {
+                ring.events.pop_front();
+                ring.truncated = true;
+            }
+            ring.events.push_back(event);
+        }
+    }
+
+    fn append(&self, events: &[ProcessEvent]) -> Result<()> {
+        let data_dir = self
+            .data_dir
+            .as_ref()
+            .context("Failed to get app data directory")?;
+        std::fs::create_dir_all(data_dir).context("Failed to create data directory")?;
+
+        let log_file = data_dir.join(LOG_FILE);
+        if std::fs::metadata(&log_file).is_ok_and(|m| m.len() >= MAX_LOG_SIZE) {
+            std::fs::rename(&log_file, data_dir.join(ROTATED_LOG_FILE))
+                .context("Failed to rotate process event log")?;
+        }
+
+        // Command lines can carry secrets, so the log is private to the user
+        let mut options = std::fs::OpenOptions::new();
+        options.create(true).append(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut file = options
+            .open(&log_file)
+            .context("Failed to open process event log")?;
+
+        let mut lines = String::new();
+        for event in events {
+            lines.push_str(
+                &serde_json::to_string(event).context("Failed to serialize process event")?,
+            );
+            lines.push('\n');
+        }
+        file.write_all(lines.as_bytes())
+            .context("Failed to append to process event log")?;
+        Ok(())
+    }
+
+    /// Get events after the one numbered `since_sequence`, oldest first. Events that no
+    /// longer fit in memory are read from the on-disk log when it is enabled.
+    pub fn query(&self, since_sequence: Option<u64>, limit: Option<usize>) -> Vec<ProcessEvent> {
+        let after =
+            |event: &ProcessEvent| since_sequence.is_none_or(|since| event.sequence > since);
+        let ring = self.ring.lock().unwrap();
+
+        let oldest_in_memory = ring.events.front().map(|event| event.sequence);
+        let needs_disk = ring.truncated
+            && self.is_persistent()
+            && oldest_in_memory
+                .is_some_and(|oldest| since_sequence.is_none_or(|since| since + 1 < oldest));
+
+        let mut events = Vec::new();
+        if needs_disk {
+            events.extend(
+                self.read_log()
+                    .into_iter()
+                    .filter(|event| oldest_in_memory.is_some_and(|o| event.sequence < o))
+                    .filter(|event| after(event)),
+            );
+        }
+        events.extend(ring.events.iter().filter(|event| after(event)).cloned());
+        events.truncate(limit.unwrap_or(DEFAULT_QUERY_LIMIT));
+        events
+    }
+
+    /// Sequence of the newest event in the on-disk log
+    fn last_logged_sequence(data_dir: &Path) -> Option<u64> {
+        [LOG_FILE, ROTATED_LOG_FILE].iter().find_map(|file| {
+            let content = std::fs::read_to_string(data_dir.join(file)).ok()?;
+            content
+                .lines()
+                .rev()
+                .find_map(|line| serde_json::from_str::<ProcessEvent>(line).ok())
+                .map(|event| event.sequence)
+        })
+    }
+
+    /// Events in the rotated and current log files, oldest first. Unreadable lines
+    /// are skipped.
+    fn read_log(&self) -> Vec<ProcessEvent> {
+        let Some(data_dir) = &self.data_dir else {
+            return Vec::new();
+        };
+        [ROTATED_LOG_FILE, LOG_FILE]
+            .iter()
+            .filter_map(|file| std::fs::read_to_string(data_dir.join(file)).ok())
+            .flat_map(|content| {
+                content
+                    .lines()
+                    .filter_map(|line| serde_json::from_str::<ProcessEvent>(line).ok())
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+}
+
+impl Default for ProcessEventLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A process as seen in a snapshot, or read from /proc after a connector event
+#[derive(Debug, Clone, PartialEq)]
+struct ObservedProcess {
+    pid: u32,
+    parent_pid: Option<u32>,
+    name: String,
+    command: String,
+    exe_path: Option<String>,
+    user: Option<String>,
+    /// Unix timestamp in milliseconds
+    start_time: u64,
+    cpu_usage: f32,
+    memory_bytes: u64,
+    is_zombie: bool,
+}
+
+struct TrackedProcess {
+    process: ObservedProcess,
+    /// Seen in a snapshot, so its start time is exact and peaks are known
+    in_snapshot: bool,
+    peak_cpu_usage: f32,
+    peak_memory_bytes: u64,
+}
+
+impl TrackedProcess {
+    fn new(process: ObservedProcess, in_snapshot: bool) -> Self {
+        Self {
+            peak_cpu_usage: process.cpu_usage,
+            peak_memory_bytes: process.memory_bytes,
+            process,
+            in_snapshot,
+        }
+    }
+}
+
+/// Processes currently alive, and the events that change them
+#[derive(Default)]
+struct ProcessTimeline {
+    tracked: HashMap<u32, TrackedProcess>,
+}
+
+impl ProcessTimeline {
+    /// Take the processes running at startup as known, without events
+    fn seed(&mut self, snapshot: Vec<ObservedProcess>) {
+        self.tracked = snapshot
+            .into_iter()
+            .filter(|process| !process.is_zombie)
+            .map(|process| (process.pid, TrackedProcess::new(process, true)))
+            .collect();
+    }
+
+    /// Diff a snapshot against the tracked processes: record starts and exits the
+    /// connector did not report, and update peak usage
+    fn reconcile(&mut self, now: u64, snapshot: Vec<ObservedProcess>) -> Vec<ProcessEvent> {
+        let mut events = Vec::new();
+        let mut alive = HashSet::new();
+
+        for process in snapshot {
+            let pid = process.pid;
+            if process.is_zombie {
+                // A zombie has exited and only waits for its parent to reap it
+                if let Some(tracked) = self.tracked.remove(&pid) {
+                    events.push(Self::exit_event(
+                        now,
+                        ProcessEventSource::Snapshot,
+                        &tracked,
+                        None,
+                    ));
+                }
+                continue;
+            }
+            alive.insert(pid);
+
+            match self.tracked.get_mut(&pid) {
+                Some(tracked)
+                    if tracked.in_snapshot && tracked.process.start_time != process.start_time =>
+                {
+                    // The PID was reused between snapshots
+                    events.push(Self::exit_event(
+                        now,
+                        ProcessEventSource::Snapshot,
+                        tracked,
+                        None,
+                    ));
+                    events.push(Self::event(
+                        now,
+                        ProcessEventKind::Start,
+                        ProcessEventSource::Snapshot,
+                        &process,
+                    ));
+                    *tracked = TrackedProcess::new(process, true);
+                }
+                Some(tracked) => {
+                    tracked.peak_cpu_usage = tracked.peak_cpu_usage.max(process.cpu_usage);
+                    tracked.peak_memory_bytes = tracked.peak_memory_bytes.max(process.memory_bytes);
+                    tracked.process = process;
+                    tracked.in_snapshot = true;
+                }
+                None => {
+                    events.push(Self::event(
+                        now,
+                        ProcessEventKind::Start,
+                        ProcessEventSource::Snapshot,
+                        &process,
+                    ));
+                    self.tracked.insert(pid, TrackedProcess::new(process, true));
+                }
+            }
+        }
+
+        let mut exited: Vec<u32> = self
+            .tracked
+            .keys()
+            .filter(|pid| !alive.contains(pid))
+            .copied()
+            .collect();
+        exited.sort_unstable();
+        for pid in exited {
+            if let Some(tracked) = self.tracked.remove(&pid) {
+                events.push(Self::exit_event(
+                    now,
+                    ProcessEventSource::Snapshot,
+                    &tracked,
+                    None,
+                ));
+            }
+        }
+
+        events
+    }
+
+    /// A process was forked. `process` is None when it was gone before /proc could
+    /// be read; it is then recorded as a copy of its parent, which it still was.
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    fn fork(
+        &mut self,
+        now: u64,
+        pid: u32,
+        parent_pid: u32,
+        process: Option<ObservedProcess>,
+    ) -> Option<ProcessEvent> {
+        if self.tracked.contains_key(&pid) {
+            return None;
+        }
+        let process = process.or_else(|| {
+            let parent = &self.tracked.get(&parent_pid)?.process;
+            Some(ObservedProcess {
+                pid,
+                parent_pid: Some(parent_pid),
+                start_time: now,
+                cpu_usage: 0.0,
+                memory_bytes: 0,
+                is_zombie: false,
+                ..parent.clone()
+            })
+        })?;
+
+        let event = Self::event(
+            now,
+            ProcessEventKind::Start,
+            ProcessEventSource::ProcConnector,
+            &process,
+        );
+        self.tracked
+            .insert(pid, TrackedProcess::new(process, false));
+        Some(event)
+    }
+
+    /// A process replaced its program; start time and peaks carry over
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    fn exec(&mut self, now: u64, process: ObservedProcess) -> ProcessEvent {
+        let tracked = self
+            .tracked
+            .entry(process.pid)
+            .or_insert_with(|| TrackedProcess::new(process.clone(), false));
+        tracked.process = ObservedProcess {
+            start_time: tracked.process.start_time,
+            ..process
+        };
+        Self::event(
+            now,
+            ProcessEventKind::Exec,
+            ProcessEventSource::ProcConnector,
+            &tracked.process,
+        )
+    }
+
+    /// A process exited with a wait(2) status
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    fn exit(&mut self, now: u64, pid: u32, status: u32) -> Option<ProcessEvent> {
+        let tracked = self.tracked.remove(&pid)?;
+        Some(Self::exit_event(
+            now,
+            ProcessEventSource::ProcConnector,
+            &tracked,
+            Some(status),
+        ))
+    }
+
+    fn event(
+        now: u64,
+        kind: ProcessEventKind,
+        source: ProcessEventSource,
+        process: &ObservedProcess,
+    ) -> ProcessEvent {
+        ProcessEvent {
+            // Assigned by the log when recorded
+            sequence: 0,
+            timestamp: now,
+            kind,
+            source,
+            pid: process.pid,
+            parent_pid: process.parent_pid,
+            name: process.name.clone(),
+            command: process.command.clone(),
+            exe_path: process.exe_path.clone(),
+            user: process.user.clone(),
+            start_time: process.start_time,
+            lifetime_ms: None,
+            peak_cpu_usage: None,
+            peak_memory_bytes: None,
+            exit_code: None,
+            exit_signal: None,
+        }
+    }
+
+    fn exit_event(
+        now: u64,
+        source: ProcessEventSource,
+        tracked: &TrackedProcess,
+        status: Option<u32>,
+    ) -> ProcessEvent {
+        let (exit_code, exit_signal) = match status {
+            Some(status) if status & 0x7f == 0 => (Some(((status >> 8) & 0xff) as i32), None),
+            Some(status) => (None, Some((status & 0x7f) as i32)),
+            None => (None, None),
+        };
+        ProcessEvent {
+            lifetime_ms: Some(now.saturating_sub(tracked.process.start_time)),
+            peak_cpu_usage: tracked.in_snapshot.then_some(tracked.peak_cpu_usage),
+            peak_memory_bytes: tracked.in_snapshot.then_some(tracked.peak_memory_bytes),
+            exit_code,
+            exit_signal,
+            ..Self::event(now, ProcessEventKind::Exit, source, &tracked.process)
+        }
+    }
+}
+
+/// A proc connector notification about a thread group
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, PartialEq)]
+enum ConnectorEvent {
+    Fork {
+        parent_tgid: u32,
+        child_pid: u32,
+        child_tgid: u32,
+    },
+    Exec {
+        pid: u32,
+    },
+    Exit {
+        pid: u32,
+        tgid: u32,
+        status: u32,
+    },
+}
+
+/// Background watcher that feeds the process event log
+pub struct ProcessEventWatcher;
+
+impl ProcessEventWatcher {
+    /// Start the background watcher. Needs `SysInfoState` and `ProcessEventLog` in
+    /// managed state.
+    pub fn start(app: AppHandle) {
+        let result = std::thread::Builder::new()
+            .name("process-events".to_string())
+            .spawn(move || Self::run(&app));
+
+        if let Err(e) = result {
+            log::error!("Failed to start process event watcher: {}", e);
+        }
+    }
+
+    fn run(app: &AppHandle) {
+        let state = app.state::<SysInfoState>();
+        let event_log = app.state::<ProcessEventLog>();
+
+        #[cfg_attr(not(target_os = "linux"), allow(unused_mut))]
+        let mut snapshot_interval = SNAPSHOT_INTERVAL;
+        #[cfg(target_os = "linux")]
+        let mut connector = match Self::open_proc_connector() {
+            Ok(socket) => {
+                log::info!("Process event watcher using the proc connector");
+                snapshot_interval = CONNECTOR_SNAPSHOT_INTERVAL;
+                Some(socket)
+            }
+            Err(e) => {
+                log::info!(
+                    "Proc connector unavailable ({}); detecting process starts and exits from snapshots",
+                    e
+                );
+                None
+            }
+        };
+        #[cfg(target_os = "linux")]
+        let users = Users::new_with_refreshed_list();
+
+        let mut timeline = ProcessTimeline::default();
+        timeline.seed(Self::snapshot(&state));
+        let mut next_snapshot = Instant::now() + snapshot_interval;
+
+        loop {
+            let wait = next_snapshot.saturating_duration_since(Instant::now());
+
+            #[cfg(target_os = "linux")]
+            match connector.as_mut().map(|socket| socket.receive(wait)) {
+                Some(Ok(messages)) => {
+                    let events = messages
+                        .iter()
+                        .filter_map(|message| Self::parse_connector_event(&message.payload))
+                        .filter_map(|event| Self::apply(&mut timeline, &users, event))
+                        .collect();
+                    event_log.record(events);
+                }
+                Some(Err(e)) if e.raw_os_error() == Some(libc::ENOBUFS) => {
+                    // Snapshot right away to pick up what was dropped
+                    log::warn!("Proc connector events were dropped");
+                    next_snapshot = Instant::now();
+                }
+                Some(Err(e)) => {
+                    log::warn!("Proc connector failed, falling back to snapshots: {}", e);
+                    connector = None;
+                    snapshot_interval = SNAPSHOT_INTERVAL;
+                    next_snapshot = Instant::now();
+                }
+                None => std::thread::sleep(wait),
+            }
+
+            #[cfg(not(target_os = "linux"))]
+            std::thread::sleep(wait);
+
+            if Instant::now() >= next_snapshot {
+                let snapshot = Self::snapshot(&state);
+                event_log.record(timeline.reconcile(Self::now(), snapshot));
+                next_snapshot = Instant::now() + snapshot_interval;
+            }
+        }
+    }
+
+    fn snapshot(state: &SysInfoState) -> Vec<ObservedProcess> {
+        state.with_processes(|sys, users, cpu_count| {
+            thread_groups(sys)
+                .map(|(pid, process)| ObservedProcess {
+                    pid: pid.as_u32(),
+                    parent_pid: process.parent().map(|p| p.as_u32()),
+                    name: process.name().to_string_lossy().to_string(),
+                    command: process
+                        .cmd()
+                        .iter()
+                        .map(|s| s.to_string_lossy())
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                    exe_path: process.exe().map(|p| p.to_string_lossy().to_string()),
+                    user: process
+                        .user_id()
+                        .and_then(|uid| users.get_user_by_id(uid).map(|u| u.name().to_string())),
+                    start_time: process.start_time() * 1000,
+                    cpu_usage: process.cpu_usage() / cpu_count.max(1.0),
+                    memory_bytes: process.memory(),
+                    is_zombie: matches!(process.status(), ProcessStatus::Zombie),
+                })
+                .collect()
+        })
+    }
+
+    fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0)
+    }
+
+    /// Subscribe to process notifications; needs CAP_NET_ADMIN
+    #[cfg(target_os = "linux")]
+    fn open_proc_connector() -> std::io::Result<NetlinkSocket> {
+        let mut socket = NetlinkSocket::subscribe(libc::NETLINK_CONNECTOR, CN_IDX_PROC)?;
+
+        let op = PROC_CN_MCAST_LISTEN.to_ne_bytes();
+        let mut payload = Vec::with_capacity(CN_MSG_LEN + op.len());
+        payload.extend_from_slice(&CN_IDX_PROC.to_ne_bytes());
+        payload.extend_from_slice(&CN_VAL_PROC.to_ne_bytes());
+        payload.extend_from_slice(&0u32.to_ne_bytes()); // seq
+        payload.extend_from_slice(&0u32.to_ne_bytes()); // ack
+        payload.extend_from_slice(&(op.len() as u16).to_ne_bytes());
+        payload.extend_from_slice(&0u16.to_ne_bytes()); // flags
+        payload.extend_from_slice(&op);
+        socket.send(NLMSG_DONE, 0, &payload)?;
+
+        Ok(socket)
+    }
+
+    /// Decode the cn_msg and proc_event in a connector message payload
+    #[cfg(target_os = "linux")]
+    fn parse_connector_event(payload: &[u8]) -> Option<ConnectorEvent> {
+        let u32_at = |offset: usize| {
+            let bytes = payload.get(offset..offset + 4)?;
+            Some(u32::from_ne_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+        };
+        if u32_at(0)? != CN_IDX_PROC || u32_at(4)? != CN_VAL_PROC {
+            return None;
+        }
+
+        let data = CN_MSG_LEN + PROC_EVENT_HEADER_LEN;
+        match u32_at(CN_MSG_LEN)? {
+            PROC_EVENT_FORK => Some(ConnectorEvent::Fork {
+                parent_tgid: u32_at(data + 4)?,
+                child_pid: u32_at(data + 8)?,
+                child_tgid: u32_at(data + 12)?,
+            }),
+            PROC_EVENT_EXEC => Some(ConnectorEvent::Exec {
+                pid: u32_at(data + 4)?,
+            }),
+            PROC_EVENT_EXIT => Some(ConnectorEvent::Exit {
+                pid: u32_at(data)?,
+                tgid: u32_at(data + 4)?,
+                status: u32_at(data + 8)?,
+            }),
+            _ => None,
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn apply(
+        timeline: &mut ProcessTimeline,
+        users: &Users,
+        event: ConnectorEvent,
+    ) -> Option<ProcessEvent> {
+        let now = Self::now();
+        match event {
+            // Threads share their creator's thread group and have no lifecycle of their own
+            ConnectorEvent::Fork {
+                parent_tgid,
+                child_pid,
+                child_tgid,
+            } if child_pid == child_tgid => {
+                let process = Self::read_proc(child_pid, users, now);
+                timeline.fork(now, child_pid, parent_tgid, process)
+            }
+            ConnectorEvent::Exec { pid } => {
+                Self::read_proc(pid, users, now).map(|process| timeline.exec(now, process))
+            }
+            ConnectorEvent::Exit { pid, tgid, status } if pid == tgid => {
+                timeline.exit(now, pid, status)
+            }
+            _ => None,
+        }
+    }
+
+    /// Read a process that was just reported by the connector
+    #[cfg(target_os = "linux")]
+    fn read_proc(pid: u32, users: &Users, now: u64) -> Option<ObservedProcess> {
+        let dir = std::path::Path::new("/proc").join(pid.to_string());
+        let status = std::fs::read_to_string(dir.join("status")).ok()?;
+        let field = |name: &str| {
+            status
+                .lines()
+                .find_map(|line| line.strip_prefix(name)?.strip_prefix(':'))
+                .map(str::trim)
+        };
+
+        let command = std::fs::read(dir.join("cmdline"))
+            .map(|data| {
+                data.split(|&b| b == 0)
+                    .filter(|arg| !arg.is_empty())
+                    .map(String::from_utf8_lossy)
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .unwrap_or_default();
+        let user = field("Uid")
+            .and_then(|uids| uids.split_whitespace().next())
+            .and_then(|uid| Uid::from_str(uid).ok())
+            .and_then(|uid| users.get_user_by_id(&uid).map(|u| u.name().to_string()));
+
+        Some(ObservedProcess {
+            pid,
+            parent_pid: field("PPid").and_then(|ppid| ppid.parse().ok()),
+            name: field("Name").unwrap_or_default().to_string(),
+            command,
+            exe_path: std::fs::read_link(dir.join("exe"))
+                .ok()
+                .map(|p| p.to_string_lossy().to_string()),
+            user,
+            start_time: now,
+            cpu_usage: 0.0,
+            memory_bytes: 0,
+            is_zombie: false,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn observed(pid: u32, name: &str, start_time: u64) -> ObservedProcess {
+        ObservedProcess {
+            pid,
+            parent_pid: Some(1),
+            name: name.to_string(),
+            command: format!("/usr/bin/{} --flag", name),
+            exe_path: Some(format!("/usr/bin/{}", name)),
+            user: Some("alice".to_string()),
+            start_time,
+            cpu_usage: 1.0,
+            memory_bytes: 1024,
+            is_zombie: false,
+        }
+    }
+
+    fn event(timestamp: u64, pid: u32) -> ProcessEvent {
+        ProcessTimeline::event(
+            timestamp,
+            ProcessEventKind::Start,
+            ProcessEventSource::Snapshot,
+            &observed(pid, "sleep", timestamp),
+        )
+    }
+
+    #[test]
+    fn test_reconcile_snapshots() {
+        let mut timeline = ProcessTimeline::default();
+        timeline.seed(vec![observed(1, "init", 0), observed(10, "bash", 1_000)]);
+
+        let mut busy = observed(10, "bash", 1_000);
+        busy.cpu_usage = 40.0;
+        busy.memory_bytes = 8192;
+        let events = timeline.reconcile(
+            10_000,
+            vec![observed(1, "init", 0), busy, observed(20, "make", 9_000)],
+        );
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, ProcessEventKind::Start);
+        assert_eq!(events[0].pid, 20);
+        assert_eq!(events[0].command, "/usr/bin/make --flag");
+
+        // bash exits after peaking, make becomes a zombie, and its PID is then reused
+        let mut zombie = observed(20, "make", 9_000);
+        zombie.is_zombie = true;
+        let events = timeline.reconcile(12_000, vec![observed(1, "init", 0), zombie]);
+        let summary: Vec<(ProcessEventKind, u32)> =
+            events.iter().map(|e| (e.kind, e.pid)).collect();
+        assert_eq!(
+            summary,
+            vec![(ProcessEventKind::Exit, 20), (ProcessEventKind::Exit, 10)]
+        );
+        let bash = &events[1];
+        assert_eq!(bash.lifetime_ms, Some(11_000));
+        assert_eq!(bash.peak_cpu_usage, Some(40.0));
+        assert_eq!(bash.peak_memory_bytes, Some(8192));
+        assert_eq!(bash.source, ProcessEventSource::Snapshot);
+
+        let events = timeline.reconcile(
+            14_000,
+            vec![observed(1, "init", 0), observed(20, "cc", 13_000)],
+        );
+        assert_eq!(events.len(), 1);
+        assert_eq!(
+            (events[0].kind, events[0].pid),
+            (ProcessEventKind::Start, 20)
+        );
+
+        let events = timeline.reconcile(
+            16_000,
+            vec![observed(1, "init", 0), observed(20, "ld", 15_000)],
+        );
+        let summary: Vec<(ProcessEventKind, &str)> =
+            events.iter().map(|e| (e.kind, e.name.as_str())).collect();
+        assert_eq!(
+            summary,
+            vec![
+                (ProcessEventKind::Exit, "cc"),
+                (ProcessEventKind::Start, "ld")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_connector_events_catch_short_lived_processes() {
+        let mut timeline = ProcessTimeline::default();
+        timeline.seed(vec![observed(1, "init", 0), observed(10, "bash", 1_000)]);
+
+        // Forked and gone before /proc was read: recorded as a copy of the parent
+        let start = timeline.fork(5_000, 30, 10, None).unwrap();
+        assert_eq!(start.source, ProcessEventSource::ProcConnector);
+        assert_eq!(start.name, "bash");
+        assert_eq!(start.parent_pid, Some(10));
+        assert!(timeline.fork(5_000, 30, 10, None).is_none());
+
+        let exec = timeline.exec(5_002, observed(30, "true", 5_002));
+        assert_eq!(exec.kind, ProcessEventKind::Exec);
+        assert_eq!(exec.name, "true");
+        assert_eq!(exec.start_time, 5_000);
+
+        let exit = timeline.exit(5_007, 30, 1 << 8).unwrap();
+        assert_eq!(exit.lifetime_ms, Some(7));
+        assert_eq!(exit.exit_code, Some(1));
+        assert_eq!(exit.exit_signal, None);
+        assert_eq!(exit.peak_memory_bytes, None);
+        assert!(timeline.exit(5_008, 30, 0).is_none());
+
+        // Killed by SIGKILL
+        timeline.fork(6_000, 31, 10, Some(observed(31, "sleep", 6_000)));
+        let exit = timeline.exit(6_500, 31, 9).unwrap();
+        assert_eq!(exit.exit_code, None);
+        assert_eq!(exit.exit_signal, Some(9));
+
+        // Nothing left over for the next snapshot to report
+        let events = timeline.reconcile(
+            7_000,
+            vec![observed(1, "init", 0), observed(10, "bash", 1_000)],
+        );
+        assert!(events.is_empty());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_connector_event() {
+        fn message(what: u32, data: &[u32]) -> Vec<u8> {
+            let mut payload = Vec::new();
+            for value in [CN_IDX_PROC, CN_VAL_PROC, 7, 0] {
+                payload.extend_from_slice(&value.to_ne_bytes());
+            }
+            payload.extend_from_slice(
+                &((PROC_EVENT_HEADER_LEN + data.len() * 4) as u16).to_ne_bytes(),
+            );
+            payload.extend_from_slice(&0u16.to_ne_bytes());
+            payload.extend_from_slice(&what.to_ne_bytes());
+            payload.extend_from_slice(&3u32.to_ne_bytes()); // cpu
+            payload.extend_from_slice(&123_456_789u64.to_ne_bytes());
+            for value in data {
+                payload.extend_from_slice(&value.to_ne_bytes());
+            }
+            payload
+        }
+
+        assert_eq!(
+            ProcessEventWatcher::parse_connector_event(&message(
+                PROC_EVENT_FORK,
+                &[10, 10, 30, 30]
+            )),
+            Some(ConnectorEvent::Fork {
+                parent_tgid: 10,
+                child_pid: 30,
+                child_tgid: 30
+            })
+        );
+        assert_eq!(
+            ProcessEventWatcher::parse_connector_event(&message(PROC_EVENT_EXEC, &[30, 30])),
+            Some(ConnectorEvent::Exec { pid: 30 })
+        );
+        assert_eq!(
+            ProcessEventWatcher::parse_connector_event(&message(
+                PROC_EVENT_EXIT,
+                &[31, 30, 256, 17, 10, 10]
+            )),
+            Some(ConnectorEvent::Exit {
+                pid: 31,
+                tgid: 30,
+                status: 256
+            })
+        );
+        // The acknowledgement of the listen request carries no event
+        assert_eq!(
+            ProcessEventWatcher::parse_connector_event(&message(0, &[0])),
+            None
+        );
+        assert_eq!(ProcessEventWatcher::parse_connector_event(&[1, 0, 0]), None);
+    }
+
+    #[test]
+    fn test_event_log_ring_and_disk() {
+        let dir = std::env::temp_dir().join(format!(
+            "syslens_process_events_test_{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let log = ProcessEventLog::with_data_dir(Some(dir.clone()));
+        assert!(!log.is_persistent());
+        log.record(vec![event(1_000, 1), event(2_000, 2)]);
+        assert!(!dir.join(LOG_FILE).exists());
+
+        log.set_persistent(true).unwrap();
+        log.record(
+            (0..RING_CAPACITY as u64)
+                .map(|i| event(3_000 + i, 3))
+                .collect(),
+        );
+
+        // The first events no longer fit in memory and were not persisted
+        let all = log.query(None, Some(usize::MAX));
+        assert_eq!(all.len(), RING_CAPACITY);
+        assert_eq!(all[0].timestamp, 3_000);
+
+        // Sequences 0 and 1 went to the first two events
+        let recent = log.query(Some(RING_CAPACITY as u64 - 1), None);
+        let timestamps: Vec<u64> = recent.iter().map(|e| e.timestamp).collect();
+        assert_eq!(
+            timestamps,
+            vec![
+                3_000 + RING_CAPACITY as u64 - 2,
+                3_000 + RING_CAPACITY as u64 - 1
+            ]
+        );
+        assert_eq!(log.query(None, None).len(), DEFAULT_QUERY_LIMIT);
+
+        // Evicted events are read back from disk
+        log.record(vec![event(100_000, 4)]);
+        let from_disk = log.query(Some(1), Some(2));
+        assert_eq!(from_disk[0].timestamp, 3_000);
+        assert_eq!(from_disk[1].timestamp, 3_001);
+
+        // The setting survives a restart, and numbering continues after the log
+        let restarted = ProcessEventLog::with_data_dir(Some(dir.clone()));
+        assert!(restarted.is_persistent());
+        restarted.record(vec![event(200_000, 5)]);
+        assert_eq!(
+            restarted.query(None, None)[0].sequence,
+            RING_CAPACITY as u64 + 3
+        );
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_event_log_pages_through_bursts() {
+        let log = ProcessEventLog::with_data_dir(None);
+        log.record((0..7).map(|pid| event(5_000, pid)).collect());
+        log.record(vec![event(6_000, 7)]);
+
+        // Events sharing a timestamp are split across pages without losing any
+        let mut pids = Vec::new();
+        let mut since = None;
+        loop {
+            let page = log.query(since, Some(3));
+            let Some(last) = page.last() else {
+                break;
+            };
+            since = Some(last.sequence);
+            pids.extend(page.iter().map(|event| event.pid));
+        }
+        assert_eq!(pids, (0..8).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,470 @@
+//! Incremental, paginated process feed
+//!
+//! Keeps the last few process snapshots keyed by sequence number. A client that
+//! holds the page for one sequence is sent only the rows that entered or left the
+//! page and the fields that changed, instead of the whole list on every poll.
+
+use crate::models::{
+    ProcessChange, ProcessFeed, ProcessFeedQuery, ProcessInfo, ProcessSortKey, SortDirection,
+};
+use std::cmp::Ordering;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+/// Snapshots kept for clients to diff against; older sequences get a full page
+const HISTORY_LEN: usize = 16;
+
+/// Process rows keyed by PID, as they were at one sequence
+struct Snapshot {
+    sequence: u64,
+    /// The query the page was selected with, without `since_sequence`
+    query: ProcessFeedQuery,
+    processes: HashMap<u32, ProcessInfo>,
+}
+
+struct FeedState {
+    next_sequence: u64,
+    history: VecDeque<Snapshot>,
+}
+
+/// Serves the process feed (held in Tauri managed state)
+pub struct ProcessFeedTracker {
+    state: Mutex<FeedState>,
+}
+
+impl ProcessFeedTracker {
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(FeedState {
+                next_sequence: 1,
+                history: VecDeque::new(),
+            }),
+        }
+    }
+
+    /// Record the current process list and return the requested page, as a delta
+    /// against `query.since_sequence` when that snapshot is still known
+    pub fn update(&self, processes: Vec<ProcessInfo>, query: &ProcessFeedQuery) -> ProcessFeed {
+        let current: HashMap<u32, ProcessInfo> = processes
+            .into_iter()
+            .map(|mut process| {
+                // Icons are fetched once by hash rather than sent with every row
+                process.icon_base64 = None;
+                (process.pid, process)
+            })
+            .collect();
+
+        let mut state = self.state.lock().unwrap();
+        let sequence = state.next_sequence;
+        state.next_sequence += 1;
+
+        let page_query = ProcessFeedQuery {
+            since_sequence: None,
+            ..query.clone()
+        };
+        let (total_count, page) = Self::select(&current, query);
+        // A page fetched with another sort, filter or offset can't be diffed against
+        let previous = query.since_sequence.and_then(|since| {
+            state
+                .history
+                .iter()
+                .find(|snapshot| snapshot.sequence == since && snapshot.query == page_query)
+        });
+
+        let feed = match previous {
+            Some(previous) => {
+                let (_, previous_page) = Self::select(&previous.processes, query);
+                Self::delta(sequence, total_count, query.offset, &previous_page, &page)
+            }
+            None => ProcessFeed {
+                sequence,
+                is_full: true,
+                total_count,
+                offset: query.offset,
+                pids: page.iter().map(|p| p.pid).collect(),
+                added: page.iter().map(|p| (*p).clone()).collect(),
+                changed: Vec::new(),
+                removed: Vec::new(),
+            },
+        };
+
+        state.history.push_back(Snapshot {
+            sequence,
+            query: page_query,
+            processes: current,
+        });
+        while state.history.len() > HISTORY_LEN {
+            state.history.pop_front();
+        }
+
+        feed
+    }
+
+    /// Filter, sort and page processes; returns the filtered count and the page
+    fn select<'a>(
+        processes: &'a HashMap<u32, ProcessInfo>,
+        query: &ProcessFeedQuery,
+    ) -> (usize, Vec<&'a ProcessInfo>) {
+        let filter = query
+            .filter
+            .as_deref()
+            .map(str::trim)
+            .filter(|f| !f.is_empty())
+            .map(str::to_lowercase);
+
+        let mut matching: Vec<&ProcessInfo> = processes
+            .values()
+            .filter(|p| {
+                query
+                    .user
+                    .as_deref()
+                    .is_none_or(|user| p.user.as_deref() == Some(user))
+            })
+            .filter(|p| filter.as_deref().is_none_or(|f| Self::matches(p, f)))
+            .collect();
+
+        matching.sort_by(|a, b| {
+            let ordering = query
+                .sort_by
+                .map_or(Ordering::Equal, |key| Self::compare(a, b, key));
+            let ordering = match query.sort_direction {
+                SortDirection::Ascending => ordering,
+                SortDirection::Descending => ordering.reverse(),
+            };
+            ordering.then(a.pid.cmp(&b.pid))
+        });
+
+        let total_count = matching.len();
+        let page = matching
+            .into_iter()
+            .skip(query.offset)
+            .take(query.limit.unwrap_or(usize::MAX))
+            .collect();
+        (total_count, page)
+    }
+
+    /// `filter` is already lowercased; container IDs match by prefix
+    fn matches(process: &ProcessInfo, filter: &str) -> bool {
+        process.pid.to_string() == filter
+            || process.name.to_lowercase().contains(filter)
+            || process.command.to_lowercase().contains(filter)
+            || process
+                .exe_path
+                .as_deref()
+                .is_some_and(|path| path.to_lowercase().contains(filter))
+            || process
+                .user
+                .as_deref()
+                .is_some_and(|user| user.to_lowercase().contains(filter))
+            || process
+                .systemd_unit
+                .as_deref()
+                .is_some_and(|unit| unit.to_lowercase().contains(filter))
+            || process
+                .container
+                .as_ref()
+                .is_some_and(|container| container.id.starts_with(filter))
+    }
+
+    fn compare(a: &ProcessInfo, b: &ProcessInfo, key: ProcessSortKey) -> Ordering {
+        match key {
+            ProcessSortKey::Pid => a.pid.cmp(&b.pid),
+            ProcessSortKey::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            ProcessSortKey::CpuUsage => a.cpu_usage.total_cmp(&b.cpu_usage),
+            ProcessSortKey::MemoryBytes => a.memory_bytes.cmp(&b.memory_bytes),
+            ProcessSortKey::DiskReadBytes => a.disk_read_bytes.cmp(&b.disk_read_bytes),
+            ProcessSortKey::DiskWriteBytes => a.disk_write_bytes.cmp(&b.disk_write_bytes),
+            ProcessSortKey::StartTime => a.start_time.cmp(&b.start_time),
+            ProcessSortKey::User => a.user.cmp(&b.user),
+            ProcessSortKey::Status => a.status.cmp(&b.status),
+        }
+    }
+
+    fn delta(
+        sequence: u64,
+        total_count: usize,
+        offset: usize,
+        previous: &[&ProcessInfo],
+        current: &[&ProcessInfo],
+    ) -> ProcessFeed {
+        let previous_by_pid: HashMap<u32, &ProcessInfo> =
+            previous.iter().map(|p| (p.pid, *p)).collect();
+        let current_by_pid: HashMap<u32, &ProcessInfo> =
+            current.iter().map(|p| (p.pid, *p)).collect();
+
+        let mut added = Vec::new();
+        let mut changed = Vec::new();
+        for process in current {
+            match previous_by_pid.get(&process.pid) {
+                Some(before) if Self::same_identity(before, process) => {
+                    if let Some(change) = Self::diff(before, process) {
+                        changed.push(change);
+                    }
+                }
+                _ => added.push((*process).clone()),
+            }
+        }
+
+        let removed = previous
+            .iter()
+            .map(|p| p.pid)
+            .filter(|pid| !current_by_pid.contains_key(pid))
+            .collect();
+
+        ProcessFeed {
+            sequence,
+            is_full: false,
+            total_count,
+            offset,
+            pids: current.iter().map(|p| p.pid).collect(),
+            added,
+            changed,
+            removed,
+        }
+    }
+
+    /// Whether the row still describes the same program; a reused PID or an exec
+    /// replaces the whole row
+    fn same_identity(before: &ProcessInfo, after: &ProcessInfo) -> bool {
+        before.start_time == after.start_time
+            && before.parent_pid == after.parent_pid
+            && before.name == after.name
+            && before.command == after.command
+            && before.exe_path == after.exe_path
+            && before.user == after.user
+            && before.icon_hash == after.icon_hash
+            && before.cgroup == after.cgroup
+    }
+
+    fn diff(before: &ProcessInfo, after: &ProcessInfo) -> Option<ProcessChange> {
+        fn changed<T: PartialEq + Clone>(before: &T, after: &T) -> Option<T> {
+            (before != after).then(|| after.clone())
+        }
+
+        let change = ProcessChange {
+            pid: after.pid,
+            cpu_usage: changed(&before.cpu_usage, &after.cpu_usage),
+            memory_bytes: changed(&before.memory_bytes, &after.memory_bytes),
+            virtual_memory_bytes: changed(
+                &before.virtual_memory_bytes,
+                &after.virtual_memory_bytes,
+            ),
+            status: changed(&before.status, &after.status),
+            disk_read_bytes: changed(&before.disk_read_bytes, &after.disk_read_bytes),
+            disk_write_bytes: changed(&before.disk_write_bytes, &after.disk_write_bytes),
+        };
+
+        (change
+            != ProcessChange {
+                pid: after.pid,
+                ..Default::default()
+            })
+        .then_some(change)
+    }
+}
+
+impl Default for ProcessFeedTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(pid: u32, name: &str, cpu_usage: f32, memory_bytes: u64) -> ProcessInfo {
+        ProcessInfo {
+            pid,
+            parent_pid: Some(1),
+            name: name.to_string(),
+            cpu_usage,
+            memory_bytes,
+            virtual_memory_bytes: 0,
+            status: "Sleep".to_string(),
+            user: Some("alice".to_string()),
+            command: format!("/usr/bin/{}", name),
+            start_time: 1_700_000_000,
+            disk_read_bytes: 0,
+            disk_write_bytes: 0,
+            exe_path: Some(format!("/usr/bin/{}", name)),
+            icon_base64: Some("iVBORw0KGgo=".to_string()),
+            icon_hash: Some("abc".to_string()),
+            cgroup: None,
+            systemd_unit: None,
+            systemd_slice: None,
+            container: None,
+        }
+    }
+
+    #[test]
+    fn test_full_page_sorted_filtered_and_paged() {
+        let tracker = ProcessFeedTracker::new();
+        let processes = vec![
+            process(10, "firefox", 30.0, 500),
+            process(11, "bash", 0.5, 10),
+            process(12, "firefox-bin", 5.0, 200),
+            process(13, "Xorg", 12.0, 300),
+        ];
+
+        let feed = tracker.update(
+            processes.clone(),
+            &ProcessFeedQuery {
+                sort_by: Some(ProcessSortKey::CpuUsage),
+                sort_direction: SortDirection::Descending,
+                limit: Some(2),
+                ..Default::default()
+            },
+        );
+        assert!(feed.is_full);
+        assert_eq!(feed.sequence, 1);
+        assert_eq!(feed.total_count, 4);
+        assert_eq!(feed.pids, vec![10, 13]);
+        assert!(feed.added.iter().all(|p| p.icon_base64.is_none()));
+        assert_eq!(feed.added[0].icon_hash.as_deref(), Some("abc"));
+
+        let feed = tracker.update(
+            processes.clone(),
+            &ProcessFeedQuery {
+                sort_by: Some(ProcessSortKey::Name),
+                filter: Some("FIREFOX".to_string()),
+                offset: 1,
+                ..Default::default()
+            },
+        );
+        assert_eq!(feed.total_count, 2);
+        assert_eq!(feed.pids, vec![12]);
+
+        let feed = tracker.update(
+            processes,
+            &ProcessFeedQuery {
+                filter: Some("11".to_string()),
+                ..Default::default()
+            },
+        );
+        assert_eq!(feed.pids, vec![11]);
+    }
+
+    #[test]
+    fn test_delta_since_sequence() {
+        let tracker = ProcessFeedTracker::new();
+        let query = ProcessFeedQuery {
+            sort_by: Some(ProcessSortKey::MemoryBytes),
+            sort_direction: SortDirection::Descending,
+            limit: Some(3),
+            ..Default::default()
+        };
+
+        let first = tracker.update(
+            vec![
+                process(10, "firefox", 30.0, 500),
+                process(11, "bash", 0.5, 10),
+                process(12, "code", 5.0, 200),
+                process(13, "Xorg", 12.0, 300),
+            ],
+            &query,
+        );
+        assert_eq!(first.pids, vec![10, 13, 12]);
+
+        // firefox uses less CPU, code exits, bash moves onto the page, Xorg is
+        // replaced by a new process reusing its PID
+        let mut replaced = process(13, "Xorg", 12.0, 300);
+        replaced.start_time += 60;
+        let delta = tracker.update(
+            vec![
+                process(10, "firefox", 25.0, 500),
+                process(11, "bash", 0.5, 10),
+                replaced,
+            ],
+            &ProcessFeedQuery {
+                since_sequence: Some(first.sequence),
+                ..query.clone()
+            },
+        );
+        assert!(!delta.is_full);
+        assert_eq!(delta.pids, vec![10, 13, 11]);
+        assert_eq!(delta.removed, vec![12]);
+        let added: Vec<u32> = delta.added.iter().map(|p| p.pid).collect();
+        assert_eq!(added, vec![13, 11]);
+        assert_eq!(
+            delta.changed,
+            vec![ProcessChange {
+                pid: 10,
+                cpu_usage: Some(25.0),
+                ..Default::default()
+            }]
+        );
+
+        // Nothing changed since the delta
+        let unchanged = tracker.update(
+            vec![
+                process(10, "firefox", 25.0, 500),
+                process(11, "bash", 0.5, 10),
+                {
+                    let mut p = process(13, "Xorg", 12.0, 300);
+                    p.start_time += 60;
+                    p
+                },
+            ],
+            &ProcessFeedQuery {
+                since_sequence: Some(delta.sequence),
+                ..query.clone()
+            },
+        );
+        assert!(unchanged.added.is_empty());
+        assert!(unchanged.changed.is_empty());
+        assert!(unchanged.removed.is_empty());
+    }
+
+    #[test]
+    fn test_unknown_sequence_returns_full_page() {
+        let tracker = ProcessFeedTracker::new();
+        let query = ProcessFeedQuery::default();
+        let first = tracker.update(vec![process(10, "firefox", 1.0, 1)], &query);
+
+        for _ in 0..HISTORY_LEN {
+            tracker.update(vec![process(10, "firefox", 1.0, 1)], &query);
+        }
+
+        let feed = tracker.update(
+            vec![process(10, "firefox", 1.0, 1)],
+            &ProcessFeedQuery {
+                since_sequence: Some(first.sequence),
+                ..Default::default()
+            },
+        );
+        assert!(feed.is_full);
+        assert_eq!(feed.added.len(), 1);
+    }
+
+    #[test]
+    fn test_changed_query_returns_full_page() {
+        let tracker = ProcessFeedTracker::new();
+        let processes = vec![
+            process(10, "firefox", 30.0, 500),
+            process(11, "bash", 0.5, 10),
+            process(12, "code", 5.0, 200),
+        ];
+        let query = ProcessFeedQuery {
+            sort_by: Some(ProcessSortKey::CpuUsage),
+            limit: Some(2),
+            ..Default::default()
+        };
+        let first = tracker.update(processes.clone(), &query);
+        assert_eq!(first.pids, vec![11, 12]);
+
+        // Same sequence, but the client has since scrolled to the next page
+        let feed = tracker.update(
+            processes,
+            &ProcessFeedQuery {
+                offset: 2,
+                since_sequence: Some(first.sequence),
+                ..query
+            },
+        );
+        assert!(feed.is_full);
+        assert_eq!(feed.pids, vec![10]);
+        assert!(feed.removed.is_empty());
+        assert_eq!(feed.added.len(), 1);
+    }
+}
@@ -0,0 +1,171 @@
+//! Per-process network traffic tracker
+//!
+//! Attributes TCP socket byte counters to their owning processes and turns the
+//! difference between two samples into per-process send/receive rates.
+
+use crate::collectors::sockets::SocketCounters;
+use crate::collectors::SocketCollector;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Samples older than this are too stale to diff against, so a fresh baseline is taken
+const MAX_SAMPLE_AGE: Duration = Duration::from_secs(5);
+/// Interval between the baseline and the measurement when no recent sample exists
+const BASELINE_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Traffic of one process between two samples
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProcessTraffic {
+    pub bytes_sent_per_sec: f64,
+    pub bytes_received_per_sec: f64,
+    pub total_bytes_sent: u64,
+    pub total_bytes_received: u64,
+    pub connection_count: usize,
+}
+
+/// Socket counters keyed by inode, taken at one point in time
+struct Snapshot {
+    taken: Instant,
+    sockets: HashMap<u64, SocketCounters>,
+}
+
+/// Tracks per-process network rates between calls (held in Tauri managed state)
+pub struct ProcessNetworkTracker {
+    previous: Mutex<Option<Snapshot>>,
+}
+
+impl ProcessNetworkTracker {
+    pub fn new() -> Self {
+        Self {
+            previous: Mutex::new(None),
+        }
+    }
+
+    /// Sample socket counters and return per-process traffic since the previous sample
+    pub fn sample(&self) -> HashMap<u32, ProcessTraffic> {
+        let mut previous = self.previous.lock().unwrap();
+
+        let needs_baseline = previous
+            .as_ref()
+            .is_none_or(|p| p.taken.elapsed() > MAX_SAMPLE_AGE);
+        if needs_baseline {
+            *previous = Some(Self::snapshot());
+            std::thread::sleep(BASELINE_INTERVAL);
+        }
+
+        let current = Self::snapshot();
+        let baseline = previous.as_ref().unwrap();
+        let elapsed = current.taken.duration_since(baseline.taken).as_secs_f64();
+        let traffic = Self::traffic_between(&baseline.sockets, &current.sockets, elapsed);
+
+        *previous = Some(current);
+        traffic
+    }
+
+    fn snapshot() -> Snapshot {
+        Snapshot {
+            taken: Instant::now(),
+            sockets: SocketCollector::get_tcp_socket_counters()
+                .into_iter()
+                .map(|s| (s.inode, s))
+                .collect(),
+        }
+    }
+
+    /// Sum socket deltas per process. Sockets opened since the baseline count in full;
+    /// traffic of sockets closed since the baseline is lost.
+    fn traffic_between(
+        baseline: &HashMap<u64, SocketCounters>,
+        current: &HashMap<u64, SocketCounters>,
+        elapsed_secs: f64,
+    ) -> HashMap<u32, ProcessTraffic> {
+        let mut traffic: HashMap<u32, ProcessTraffic> = HashMap::new();
+
+        for socket in current.values() {
+            // An inode reused by a new socket has counters that restarted from zero
+            let (sent_before, received_before) = baseline
+                .get(&socket.inode)
+                .filter(|b| b.pid == socket.pid)
+                .filter(|b| b.bytes_sent <= socket.bytes_sent)
+                .filter(|b| b.bytes_received <= socket.bytes_received)
+                .map_or((0, 0), |b| (b.bytes_sent, b.bytes_received));
+
+            let entry = traffic.entry(socket.pid).or_default();
+            entry.connection_count += 1;
+            entry.total_bytes_sent += socket.bytes_sent;
+            entry.total_bytes_received += socket.bytes_received;
+            if elapsed_secs > 0.0 {
+                entry.bytes_sent_per_sec += (socket.bytes_sent - sent_before) as f64 / elapsed_secs;
+                entry.bytes_received_per_sec +=
+                    (socket.bytes_received - received_before) as f64 / elapsed_secs;
+            }
+        }
+
+        traffic
+    }
+}
+
+impl Default for ProcessNetworkTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sockets(entries: &[(u64, u32, u64, u64)]) -> HashMap<u64, SocketCounters> {
+        entries
+            .iter()
+            .map(|&(inode, pid, bytes_sent, bytes_received)| {
+                (
+                    inode,
+                    SocketCounters {
+                        inode,
+                        pid,
+                        bytes_sent,
+                        bytes_received,
+                    },
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_traffic_between_samples() {
+        let baseline = sockets(&[(1, 100, 1_000, 5_000), (2, 100, 0, 0), (3, 200, 700, 700)]);
+        let current = sockets(&[
+            // Two sockets of pid 100 sent 1000 and 500 bytes
+            (1, 100, 2_000, 5_000),
+            (2, 100, 500, 2_000),
+            // Inode 3 was reused by a new socket of pid 300
+            (3, 300, 100, 50),
+            // New socket opened since the baseline
+            (4, 200, 400, 0),
+        ]);
+
+        let traffic = ProcessNetworkTracker::traffic_between(&baseline, &current, 2.0);
+
+        let p100 = &traffic[&100];
+        assert_eq!(p100.connection_count, 2);
+        assert_eq!(p100.bytes_sent_per_sec, 750.0);
+        assert_eq!(p100.bytes_received_per_sec, 1_000.0);
+        assert_eq!(p100.total_bytes_sent, 2_500);
+        assert_eq!(p100.total_bytes_received, 7_000);
+
+        assert_eq!(traffic[&200].bytes_sent_per_sec, 200.0);
+        assert_eq!(traffic[&300].bytes_sent_per_sec, 50.0);
+        assert_eq!(traffic[&300].bytes_received_per_sec, 25.0);
+    }
+
+    #[test]
+    fn test_sample() {
+        let tracker = ProcessNetworkTracker::new();
+        for traffic in tracker.sample().values() {
+            assert!(traffic.connection_count > 0);
+            assert!(traffic.bytes_sent_per_sec >= 0.0);
+        }
+    }
+}
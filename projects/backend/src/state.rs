@@ -5,7 +5,16 @@
 
 use std::sync::Mutex;
 use std::time::{Duration, Instant};
-use sysinfo::{MemoryRefreshKind, ProcessRefreshKind, System, Users};
+use sysinfo::{MemoryRefreshKind, Pid, Process, ProcessRefreshKind, System, Users};
+
+/// The processes of `sys` without their threads. sysinfo lists each thread as a child
+/// process carrying its owner's memory and I/O counters, so anything that counts or
+/// totals processes would see every process once per thread.
+pub fn thread_groups(sys: &System) -> impl Iterator<Item = (&Pid, &Process)> {
+    sys.processes()
+        .iter()
+        .filter(|(_, process)| process.thread_kind().is_none())
+}
 
 /// Cached system information state
 pub struct SysInfoState {
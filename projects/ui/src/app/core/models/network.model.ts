@@ -14,7 +14,7 @@ export interface NetworkAdapter {
   dnsConfig: DnsConfig;
 }
 
-export type AdapterType = 'Ethernet' | 'WiFi' | 'Virtual' | 'Loopback' | 'Unknown';
+export type AdapterType = 'Ethernet' | 'WiFi' | 'Virtual' | 'Loopback' | 'Tunnel' | 'Unknown';
 
 export type AdapterStatus = 'Up' | 'Down' | 'Disconnected' | 'Unknown';
 
@@ -26,6 +26,8 @@ export interface Ipv4Config {
   dhcpServer: string | null;
   leaseObtained: string | null;
   leaseExpires: string | null;
+  /** Seconds until the DHCP lease expires; negative once it has expired */
+  leaseExpiresInSecs: number | null;
 }
 
 export interface Ipv6Config {
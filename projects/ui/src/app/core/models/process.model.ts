@@ -14,6 +14,27 @@ export interface ProcessInfo {
   diskWriteBytes: number;
   exePath: string | null;
   iconBase64: string | null;
+  /** Content hash of the icon, resolved once with `get_icon` */
+  iconHash: string | null;
+  /** Cgroup path, preferring the unified hierarchy (Linux) */
+  cgroup: string | null;
+  /** Systemd service or scope the process runs in */
+  systemdUnit: string | null;
+  /** Innermost systemd slice (system.slice, user-1000.slice, ...) */
+  systemdSlice: string | null;
+  /** Container the process runs in, detected from its cgroup path */
+  container: ContainerInfo | null;
+}
+
+/** Container runtime, as told from the cgroup a container's processes run in */
+export type ContainerRuntime = 'docker' | 'podman' | 'containerd' | 'criO' | 'kubernetes';
+
+export interface ContainerInfo {
+  /** Full 64-character container ID */
+  id: string;
+  runtime: ContainerRuntime;
+  /** UID of the Kubernetes pod the container belongs to */
+  podUid: string | null;
 }
 
 /** Summary of system processes */
@@ -31,8 +31,12 @@ export interface Partition {
 
 export interface Volume {
   driveLetter: string | null;
+  mountPoint: string;
+  device: string | null;
   label: string;
+  uuid: string | null;
   fileSystem: string;
+  mountOptions: string[];
   totalBytes: number;
   freeBytes: number;
   usedBytes: number;
@@ -40,6 +44,7 @@ export interface Volume {
   volumeSerial: string;
   isCompressed: boolean;
   isEncrypted: boolean;
+  isReadOnly: boolean;
   isSystem: boolean;
   isBoot: boolean;
 }
@@ -80,7 +85,10 @@ export interface NetworkDrive {
   uncPath: string;
   server: string;
   shareName: string;
-  status: 'Connected' | 'Disconnected' | 'Unknown';
+  mountPoint: string;
+  fileSystem: string;
+  protocol: 'SMB' | 'NFS' | 'SSHFS' | 'Other';
+  status: 'Connected' | 'Disconnected' | 'Unresponsive' | 'Unknown';
   totalBytes: number | null;
   freeBytes: number | null;
   usedBytes: number | null;